@@ -1,10 +1,14 @@
-use crate::parser::ProgramInfo;
+use crate::{output::OutputFormat, parser::ProgramInfo};
+use chrono_tz::Tz;
 use clap::{Arg, ArgGroup, Command};
 use clockwork_client::{
     automation::state::{InstructionData, Trigger},
     webhook::state::HttpMethod,
 };
-use solana_sdk::{pubkey::Pubkey, signature::Keypair};
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signature},
+};
 
 #[derive(Debug, PartialEq)]
 pub enum CliCommand {
@@ -15,20 +19,33 @@ pub enum CliCommand {
     },
 
     // Config commands
-    ConfigGet,
+    ConfigAcceptAdmin,
+    ConfigCancelAdmin,
+    ConfigGet {
+        output: OutputFormat,
+        watch: bool,
+    },
+    ConfigMigrate,
+    ConfigProposeAdmin {
+        pending_admin: Pubkey,
+    },
     ConfigSet {
-        admin: Option<Pubkey>,
+        creation_fee: Option<u64>,
         epoch_automation: Option<Pubkey>,
         hasher_automation: Option<Pubkey>,
+        token_stake_rate: Option<u64>,
+        treasury: Option<Pubkey>,
     },
 
     // Crontab
     Crontab {
         schedule: String,
+        timezone: Option<Tz>,
     },
 
     // Delegation
     DelegationCreate {
+        native: bool,
         worker_id: u64,
     },
     DelegationDeposit {
@@ -37,14 +54,36 @@ pub enum CliCommand {
         worker_id: u64,
     },
     DelegationGet {
+        delegation_id: u64,
+        output: OutputFormat,
+        worker_id: u64,
+    },
+    DelegationList {
+        worker_id: u64,
+    },
+    DelegationMigrate {
         delegation_id: u64,
         worker_id: u64,
     },
+    DelegationMerge {
+        from_id: u64,
+        into_id: u64,
+        worker_id: u64,
+    },
     DelegationWithdraw {
         amount: u64,
         delegation_id: u64,
         worker_id: u64,
     },
+    DelegationWithdrawClaim {
+        delegation_id: u64,
+        worker_id: u64,
+    },
+    DelegationWithdrawRequest {
+        amount: u64,
+        delegation_id: u64,
+        worker_id: u64,
+    },
 
     ExplorerGetAutomation {
         id: Option<String>,
@@ -62,29 +101,93 @@ pub enum CliCommand {
         program_infos: Vec<ProgramInfo>,
     },
 
+    // Network commands
+    NetworkPipelineStatus,
+    NetworkStats,
+
+    // Program commands
+    ProgramPause {
+        program_id: Pubkey,
+    },
+    ProgramResume {
+        program_id: Pubkey,
+    },
+
+    // Profile commands
+    ProfileGet {
+        name: Option<String>,
+    },
+    ProfileList,
+    ProfileSet {
+        name: String,
+        json_rpc_url: Option<String>,
+        keypair_path: Option<String>,
+        commitment: Option<String>,
+        default: bool,
+    },
+
     // Pool commands
+    PoolCreate {},
     PoolGet {
         id: u64,
+        output: OutputFormat,
+    },
+    PoolList {
+        offset: u64,
+        limit: Option<u64>,
+        min_size: Option<usize>,
     },
-    PoolList {},
     PoolUpdate {
         id: u64,
         size: usize,
     },
 
     // Automation commands
+    AutomationCostReport {
+        authority: Option<Pubkey>,
+        since: Option<u64>,
+    },
     AutomationCrateInfo,
+    AutomationList {
+        authority: Option<Pubkey>,
+    },
+    AutomationClone {
+        source_id: String,
+        new_id: String,
+        kickoff_instruction: Option<InstructionData>,
+        rate_limit: Option<u64>,
+        schedule: Option<String>,
+    },
     AutomationCreate {
         id: String,
+        description: Option<String>,
         kickoff_instruction: InstructionData,
+        max_executions: Option<u64>,
         trigger: Trigger,
     },
     AutomationDelete {
         id: String,
     },
+    AutomationEstimate {
+        cron: Option<String>,
+        executions: Option<u64>,
+        horizon: i64,
+        kickoff_instruction: InstructionData,
+        worker_id: u64,
+    },
     AutomationGet {
         id: Option<String>,
         address: Option<Pubkey>,
+        output: OutputFormat,
+    },
+    AutomationInstructionSchema,
+    AutomationLogs {
+        id: String,
+        limit: usize,
+        failed_only: bool,
+    },
+    AutomationMigrate {
+        id: String,
     },
     AutomationPause {
         id: String,
@@ -92,59 +195,175 @@ pub enum CliCommand {
     AutomationResume {
         id: String,
     },
+    AutomationReplay {
+        signature: Signature,
+        rpc_url: Option<String>,
+    },
     AutomationReset {
         id: String,
     },
+    AutomationSimulate {
+        id: String,
+        kickoff_instruction: InstructionData,
+        worker_id: u64,
+    },
     AutomationUpdate {
         id: String,
+        description: Option<String>,
+        lifetime_spend_cap: Option<Option<u64>>,
+        max_chain_depth: Option<u64>,
+        max_executions: Option<Option<u64>>,
+        max_retries: Option<u32>,
         rate_limit: Option<u64>,
         schedule: Option<String>,
     },
 
     // Registry
-    RegistryGet,
-    RegistryUnlock,
+    RegistryGet {
+        watch: bool,
+    },
+    RegistryUnlock {
+        force: bool,
+    },
+
+    // Snapshot commands
+    SnapshotDiff {
+        epoch_a: u64,
+        epoch_b: u64,
+    },
 
     // Http
     WebhookRequestNew {
         api: Pubkey,
+        backoff_seconds: u64,
+        body: Vec<u8>,
+        headers: Vec<(String, String)>,
         id: String,
+        max_retries: u64,
         method: HttpMethod,
         route: String,
     },
 
     // Worker commands
+    WorkerAddresses {
+        id: u64,
+        mint: Option<Pubkey>,
+        epoch: Option<u64>,
+    },
     WorkerCreate {
         signatory: Keypair,
     },
     WorkerGet {
         id: u64,
+        output: OutputFormat,
+    },
+    WorkerList {
+        in_pool: bool,
+    },
+    WorkerMigrate {
+        id: u64,
     },
     WorkerUpdate {
         id: u64,
+        commission: Option<u64>,
+        commission_recipient: Option<Pubkey>,
         signatory: Option<Keypair>,
+        force: bool,
     },
 }
 
+/// The `--output` arg of the commands that print an account.
+fn output_arg() -> Arg<'static> {
+    Arg::new("output")
+        .long("output")
+        .short('o')
+        .value_name("FORMAT")
+        .takes_value(true)
+        .possible_values(&["display", "json"])
+        .default_value("display")
+        .help("The format to print the account in")
+}
+
 pub fn app() -> Command<'static> {
     Command::new("Clockwork")
         .bin_name("clockwork")
         .about("An automation engine for the Solana blockchain")
         .version(version!())
         .arg_required_else_help(true)
+        .arg(
+            Arg::new("profile")
+                .long("profile")
+                .value_name("NAME")
+                .takes_value(true)
+                .global(true)
+                .help("The profile to take the url, keypair, and commitment from"),
+        )
+        .arg(
+            Arg::new("json_rpc_url")
+                .long("json_rpc_url")
+                .value_name("URL")
+                .takes_value(true)
+                .global(true)
+                .help("The RPC url to connect to, overriding the profile"),
+        )
+        .arg(
+            Arg::new("keypair")
+                .long("keypair")
+                .value_name("KEYPAIR")
+                .takes_value(true)
+                .global(true)
+                .help("The filepath of the keypair to sign with, overriding the profile"),
+        )
         .subcommand(
             Command::new("config")
                 .about("Manage the Clockwork network config")
                 .arg_required_else_help(true)
-                .subcommand(Command::new("get").about("Get a config value"))
+                .subcommand(
+                    Command::new("accept-admin")
+                        .about("Accept a pending transfer of the config admin to the signer"),
+                )
+                .subcommand(
+                    Command::new("cancel-admin")
+                        .about("Cancel a pending transfer of the config admin"),
+                )
+                .subcommand(
+                    Command::new("get")
+                        .about("Get a config value")
+                        .arg(output_arg().conflicts_with("watch"))
+                        .arg(
+                            Arg::new("watch")
+                                .long("watch")
+                                .short('w')
+                                .takes_value(false)
+                                .help("Watch the config and print a diff whenever it changes"),
+                        ),
+                )
+                .subcommand(
+                    Command::new("migrate")
+                        .about("Grow a config in the legacy layout to the current layout"),
+                )
+                .subcommand(
+                    Command::new("propose-admin")
+                        .about("Propose a new config admin")
+                        .arg_required_else_help(true)
+                        .arg(
+                            Arg::new("address")
+                                .index(1)
+                                .value_name("ADDRESS")
+                                .takes_value(true)
+                                .required(true)
+                                .help("The address that must accept the transfer to become admin"),
+                        ),
+                )
                 .subcommand(
                     Command::new("set")
                         .about("Set a config value")
                         .arg(
-                            Arg::new("admin")
-                                .long("admin")
-                                .value_name("ADDRESS")
-                                .takes_value(true),
+                            Arg::new("creation_fee")
+                                .long("creation_fee")
+                                .value_name("LAMPORTS")
+                                .takes_value(true)
+                                .help("The fee charged to create an automation"),
                         )
                         .arg(
                             Arg::new("epoch_automation")
@@ -158,9 +377,29 @@ pub fn app() -> Command<'static> {
                                 .value_name("ADDRESS")
                                 .takes_value(true),
                         )
+                        .arg(
+                            Arg::new("token_stake_rate")
+                                .long("token_stake_rate")
+                                .value_name("LAMPORTS")
+                                .takes_value(true)
+                                .help("The lamports-equivalent stake weight of one base unit of CLOCK"),
+                        )
+                        .arg(
+                            Arg::new("treasury")
+                                .long("treasury")
+                                .value_name("ADDRESS")
+                                .takes_value(true)
+                                .help("The account to collect automation creation fees"),
+                        )
                         .group(
                             ArgGroup::new("config_settings")
-                                .args(&["admin", "epoch_automation", "hasher_automation"])
+                                .args(&[
+                                    "creation_fee",
+                                    "epoch_automation",
+                                    "hasher_automation",
+                                    "token_stake_rate",
+                                    "treasury",
+                                ])
                                 .multiple(true),
                         ),
                 ),
@@ -175,6 +414,14 @@ pub fn app() -> Command<'static> {
                         .takes_value(true)
                         .required(true)
                         .help("The schedule to generate a cron table for"),
+                )
+                .arg(
+                    Arg::new("timezone")
+                        .long("timezone")
+                        .value_name("TZ")
+                        .takes_value(true)
+                        .required(false)
+                        .help("The IANA timezone to show the firing times in, e.g. America/New_York (defaults to UTC)"),
                 ),
         )
         .subcommand(
@@ -184,6 +431,12 @@ pub fn app() -> Command<'static> {
                     Command::new("create")
                         .about("Create a new delegation")
                         .arg_required_else_help(true)
+                        .arg(
+                            Arg::new("native")
+                                .long("native")
+                                .takes_value(false)
+                                .help("Stake SOL with the delegation instead of CLOCK"),
+                        )
                         .arg(
                             Arg::new("worker_id")
                                 .long("worker_id")
@@ -195,7 +448,7 @@ pub fn app() -> Command<'static> {
                 )
                 .subcommand(
                     Command::new("deposit")
-                        .about("Deposit CLOCK to a delegation account")
+                        .about("Deposit CLOCK, or SOL for a native delegation, to a delegation account")
                         .arg_required_else_help(true)
                         .arg(
                             Arg::new("amount")
@@ -234,6 +487,7 @@ pub fn app() -> Command<'static> {
                                 .required(false)
                                 .help("The ID of the delegation"),
                         )
+                        .arg(output_arg())
                         .arg(
                             Arg::new("worker_id")
                                 .long("worker_id")
@@ -243,9 +497,70 @@ pub fn app() -> Command<'static> {
                                 .help("The ID of the worker"),
                         ),
                 )
+                .subcommand(
+                    Command::new("list")
+                        .about("List every delegation to a worker")
+                        .arg_required_else_help(true)
+                        .arg(
+                            Arg::new("worker_id")
+                                .long("worker_id")
+                                .short('w')
+                                .takes_value(true)
+                                .required(true)
+                                .help("The ID of the worker"),
+                        ),
+                )
+                .subcommand(
+                    Command::new("migrate")
+                        .about("Migrate a delegation account to the current account layout")
+                        .arg_required_else_help(true)
+                        .arg(
+                            Arg::new("delegation_id")
+                                .long("delegation_id")
+                                .short('i')
+                                .takes_value(true)
+                                .required(true)
+                                .help("The ID of the delegation to migrate"),
+                        )
+                        .arg(
+                            Arg::new("worker_id")
+                                .long("worker_id")
+                                .short('w')
+                                .takes_value(true)
+                                .required(true)
+                                .help("The ID of the worker"),
+                        ),
+                )
+                .subcommand(
+                    Command::new("merge")
+                        .about("Merge a delegation into another delegation to the same worker")
+                        .arg_required_else_help(true)
+                        .arg(
+                            Arg::new("from_id")
+                                .long("from-id")
+                                .takes_value(true)
+                                .required(true)
+                                .help("The ID of the delegation to merge and close"),
+                        )
+                        .arg(
+                            Arg::new("into_id")
+                                .long("into-id")
+                                .takes_value(true)
+                                .required(true)
+                                .help("The ID of the delegation to merge into"),
+                        )
+                        .arg(
+                            Arg::new("worker_id")
+                                .long("worker_id")
+                                .short('w')
+                                .takes_value(true)
+                                .required(true)
+                                .help("The ID of the worker"),
+                        ),
+                )
                 .subcommand(
                     Command::new("withdraw")
-                        .about("Withdraw CLOCK from a delegation account")
+                        .about("Withdraw deposited CLOCK that has not yet been staked from a delegation account")
                         .arg_required_else_help(true)
                         .arg(
                             Arg::new("amount")
@@ -271,6 +586,56 @@ pub fn app() -> Command<'static> {
                                 .required(false)
                                 .help("The ID of the worker"),
                         ),
+                )
+                .subcommand(
+                    Command::new("withdraw-claim")
+                        .about("Claim a delegation's staked CLOCK once its withdrawal has finished unbonding")
+                        .arg_required_else_help(true)
+                        .arg(
+                            Arg::new("delegation_id")
+                                .long("delegation_id")
+                                .short('i')
+                                .takes_value(true)
+                                .required(true)
+                                .help("The ID of the delegation to claim from"),
+                        )
+                        .arg(
+                            Arg::new("worker_id")
+                                .long("worker_id")
+                                .short('w')
+                                .takes_value(true)
+                                .required(true)
+                                .help("The ID of the worker"),
+                        ),
+                )
+                .subcommand(
+                    Command::new("withdraw-request")
+                        .about("Request to withdraw staked CLOCK, which unbonds over the following epochs")
+                        .arg_required_else_help(true)
+                        .arg(
+                            Arg::new("amount")
+                                .long("amount")
+                                .short('a')
+                                .takes_value(true)
+                                .required(true)
+                                .help("The number of staked tokens to withdraw"),
+                        )
+                        .arg(
+                            Arg::new("delegation_id")
+                                .long("delegation_id")
+                                .short('i')
+                                .takes_value(true)
+                                .required(true)
+                                .help("The ID of the delegation to withdraw from"),
+                        )
+                        .arg(
+                            Arg::new("worker_id")
+                                .long("worker_id")
+                                .short('w')
+                                .takes_value(true)
+                                .required(true)
+                                .help("The ID of the worker"),
+                        ),
                 ),
         )
         .subcommand(
@@ -347,9 +712,22 @@ pub fn app() -> Command<'static> {
                     .help("URL for Solana's JSON RPC or moniker (or their first letter): [mainnet-beta, testnet, devnet, localhost]")
                 )
         )
+        .subcommand(
+            Command::new("network")
+                .about("Inspect the Clockwork network")
+                .arg_required_else_help(true)
+                .subcommand(
+                    Command::new("pipeline-status")
+                        .about("Report which stage of the epoch pipeline the network is in, and whether it is stuck"),
+                )
+                .subcommand(
+                    Command::new("stats").about("Print aggregate statistics of the network"),
+                ),
+        )
         .subcommand(
             Command::new("pool")
                 .about("Manage the Clockwork network worker pools")
+                .subcommand(Command::new("create").about("Create a new pool at the next id"))
                 .subcommand(
                     Command::new("get")
                         .about("Get a pool")
@@ -360,9 +738,34 @@ pub fn app() -> Command<'static> {
                                 .takes_value(true)
                                 .required(false)
                                 .help("The ID of the pool to lookup"),
+                        )
+                        .arg(output_arg()),
+                )
+                .subcommand(
+                    Command::new("list")
+                        .about("List the pools")
+                        .arg(
+                            Arg::new("limit")
+                                .long("limit")
+                                .takes_value(true)
+                                .required(false)
+                                .help("The max number of pools to list"),
+                        )
+                        .arg(
+                            Arg::new("offset")
+                                .long("offset")
+                                .takes_value(true)
+                                .required(false)
+                                .help("The number of pools to skip (defaults to 0)"),
+                        )
+                        .arg(
+                            Arg::new("min_size")
+                                .long("min-size")
+                                .takes_value(true)
+                                .required(false)
+                                .help("Only list pools with at least this many worker slots"),
                         ),
                 )
-                .subcommand(Command::new("list").about("List the pools"))
                 .subcommand(
                     Command::new("update")
                         .about("Update a pool")
@@ -388,22 +791,59 @@ pub fn app() -> Command<'static> {
             Command::new("automation")
                 .about("Manage your transaction automations")
                 .arg_required_else_help(true)
+                .subcommand(
+                    Command::new("cost-report")
+                        .about("Summarize the lamports spent by an authority's automations")
+                        .arg(
+                            Arg::new("authority")
+                                .long("authority")
+                                .value_name("ADDRESS")
+                                .takes_value(true)
+                                .required(false)
+                                .help("The authority of the automations (defaults to the payer)"),
+                        )
+                        .arg(
+                            Arg::new("since")
+                                .long("since")
+                                .value_name("SLOT")
+                                .takes_value(true)
+                                .required(false)
+                                .help("Only count transactions at or after this slot (defaults to the most recent transactions)"),
+                        ),
+                )
                 .subcommand(
                     Command::new("crate-info")
                         .about("Crate Information")
                 )
                 .subcommand(
-                    Command::new("create")
-                        .about("Create an new automation")
+                    Command::new("list")
+                        .about("List the automations owned by an authority")
+                        .arg(
+                            Arg::new("authority")
+                                .long("authority")
+                                .value_name("ADDRESS")
+                                .takes_value(true)
+                                .required(false)
+                                .help("The authority of the automations (defaults to the payer)"),
+                        ),
+                )
+                .subcommand(
+                    Command::new("clone")
+                        .about("Create a copy of an automation under a new id")
                         .arg_required_else_help(true)
                         .arg(
-                            Arg::new("id")
-                                .long("id")
-                                .short('i')
-                                .value_name("ID")
+                            Arg::new("source_id")
+                                .index(1)
                                 .takes_value(true)
                                 .required(true)
-                                .help("The ID of the automation to be created"),
+                                .help("The id of the automation to copy"),
+                        )
+                        .arg(
+                            Arg::new("new_id")
+                                .index(2)
+                                .takes_value(true)
+                                .required(true)
+                                .help("The id of the automation to be created"),
                         )
                         .arg(
                             Arg::new("kickoff_instruction")
@@ -411,25 +851,150 @@ pub fn app() -> Command<'static> {
                                 .short('k')
                                 .value_name("FILEPATH")
                                 .takes_value(true)
-                                .required(true)
-                                .help("Filepath to a description of the kickoff instruction"),
+                                .required(false)
+                                .help("Filepath to a description of a kickoff instruction to replace the source's instructions"),
                         )
                         .arg(
-                            Arg::new("account")
-                                .long("account")
-                                .short('a')
-                                .value_name("ADDRESS")
+                            Arg::new("rate_limit")
+                                .long("rate_limit")
+                                .short('r')
                                 .takes_value(true)
-                                .help("An account-based trigger"),
+                                .required(false)
+                                .help(
+                                    "The maximum number of instructions the copy can execute per slot",
+                                ),
                         )
                         .arg(
-                            Arg::new("cron")
-                                .long("cron")
-                                .short('c')
-                                .value_name("SCHEDULE")
+                            Arg::new("schedule")
+                                .long("schedule")
+                                .short('s')
+                                .takes_value(true)
+                                .required(false)
+                                .help("A cron schedule to trigger the copy on"),
+                        ),
+                )
+                .subcommand(
+                    Command::new("create")
+                        .about("Create an new automation")
+                        .arg_required_else_help(true)
+                        .arg(
+                            Arg::new("id")
+                                .long("id")
+                                .short('i')
+                                .value_name("ID")
+                                .takes_value(true)
+                                .required(true)
+                                .help("The ID of the automation to be created"),
+                        )
+                        .arg(
+                            Arg::new("description")
+                                .long("description")
+                                .short('d')
+                                .value_name("TEXT")
+                                .takes_value(true)
+                                .required(false)
+                                .help("A description of what the automation does"),
+                        )
+                        .arg(
+                            Arg::new("kickoff_instruction")
+                                .long("kickoff_instruction")
+                                .short('k')
+                                .value_name("FILEPATH")
+                                .takes_value(true)
+                                .required(true)
+                                .help("Filepath to a description of the kickoff instruction"),
+                        )
+                        .arg(
+                            Arg::new("max_executions")
+                                .long("max-executions")
+                                .value_name("COUNT")
+                                .takes_value(true)
+                                .required(false)
+                                .help("The maximum number of times this automation may execute before it is paused"),
+                        )
+                        .arg(
+                            Arg::new("account")
+                                .long("account")
+                                .short('a')
+                                .value_name("ADDRESS")
+                                .takes_value(true)
+                                .help("An account-based trigger"),
+                        )
+                        .arg(
+                            Arg::new("account_seeds")
+                                .long("account_seeds")
+                                .value_name("SEED")
+                                .takes_value(true)
+                                .multiple_values(true)
+                                .requires("account_program")
+                                .help(
+                                    "An account-based trigger on the PDA derived from these seeds \
+                                    (each formatted as string:<VALUE>, pubkey:<ADDRESS>, u64:<NUMBER>, or hex:<BYTES>)",
+                                ),
+                        )
+                        .arg(
+                            Arg::new("account_program")
+                                .long("account_program")
+                                .value_name("PROGRAM_ID")
+                                .takes_value(true)
+                                .requires("account_seeds")
+                                .help("The program ID to derive the account trigger's PDA from"),
+                        )
+                        .arg(
+                            Arg::new("account_offset")
+                                .long("account-offset")
+                                .value_name("BYTES")
+                                .takes_value(true)
+                                .requires("account_trigger")
+                                .help("The byte offset of the account data to monitor (defaults to 0)"),
+                        )
+                        .arg(
+                            Arg::new("account_size")
+                                .long("account-size")
+                                .value_name("BYTES")
+                                .takes_value(true)
+                                .requires("account_trigger")
+                                .help(
+                                    "The number of bytes of account data to monitor, at most 1024 \
+                                    (defaults to the predicate's length, or 32)",
+                                ),
+                        )
+                        .arg(
+                            Arg::new("account_predicate")
+                                .long("account-predicate")
+                                .value_name("EXPR")
+                                .takes_value(true)
+                                .multiple_values(true)
+                                .requires("account_trigger")
+                                .help(
+                                    "Only fire the account trigger when the monitored bytes match \
+                                    (each formatted as equals:<HEX> or changed_from:<HEX>)",
+                                ),
+                        )
+                        .arg(
+                            Arg::new("cron")
+                                .long("cron")
+                                .short('c')
+                                .value_name("SCHEDULE")
                                 .takes_value(true)
                                 .help("A cron-based trigger"),
                         )
+                        .arg(
+                            Arg::new("epoch")
+                                .long("epoch")
+                                .takes_value(false)
+                                .help("An epoch-based trigger"),
+                        )
+                        .arg(
+                            Arg::new("epoch_filter")
+                                .long("epoch_filter")
+                                .value_name("FILTER")
+                                .takes_value(true)
+                                .help(
+                                    "Only fire a cron or slot trigger in matching epochs \
+                                    (formatted as even, odd, or every:<N>)",
+                                ),
+                        )
                         .arg(
                             Arg::new("immediate")
                                 .long("immediate")
@@ -437,11 +1002,27 @@ pub fn app() -> Command<'static> {
                                 .takes_value(false)
                                 .help("An immediate trigger"),
                         )
+                        .arg(
+                            Arg::new("slot")
+                                .long("slot")
+                                .value_name("SLOT")
+                                .takes_value(true)
+                                .help("A slot-based trigger"),
+                        )
+                        .arg(
+                            Arg::new("timestamp")
+                                .long("timestamp")
+                                .value_name("UNIX_TIMESTAMP")
+                                .takes_value(true)
+                                .allow_hyphen_values(true)
+                                .help("A one-time trigger at a unix timestamp, after which the automation is paused"),
+                        )
                         .group(
                             ArgGroup::new("trigger")
-                                .args(&["account", "cron", "immediate"])
+                                .args(&["account", "account_seeds", "cron", "epoch", "immediate", "slot", "timestamp"])
                                 .required(true),
-                        ),
+                        )
+                        .group(ArgGroup::new("account_trigger").args(&["account", "account_seeds"])),
                 )
                 .subcommand(
                     Command::new("delete")
@@ -455,6 +1036,58 @@ pub fn app() -> Command<'static> {
                             .help("The id of the automation to delete"),
                     ),
                 )
+                .subcommand(
+                    Command::new("estimate")
+                        .about("Estimate the lamports an automation needs to be funded with")
+                        .arg_required_else_help(true)
+                        .arg(
+                            Arg::new("kickoff_instruction")
+                                .long("kickoff_instruction")
+                                .short('k')
+                                .value_name("FILEPATH")
+                                .takes_value(true)
+                                .required(true)
+                                .help("Filepath to a description of the kickoff instruction"),
+                        )
+                        .arg(
+                            Arg::new("cron")
+                                .long("cron")
+                                .short('c')
+                                .value_name("SCHEDULE")
+                                .takes_value(true)
+                                .help("The cron schedule the automation will be triggered on"),
+                        )
+                        .arg(
+                            Arg::new("executions")
+                                .long("executions")
+                                .short('e')
+                                .value_name("COUNT")
+                                .takes_value(true)
+                                .help("The number of executions to fund, for triggers without a schedule"),
+                        )
+                        .group(
+                            ArgGroup::new("firings")
+                                .args(&["cron", "executions"])
+                                .required(true),
+                        )
+                        .arg(
+                            Arg::new("horizon")
+                                .long("horizon")
+                                .value_name("SECONDS")
+                                .takes_value(true)
+                                .default_value("2592000")
+                                .help("How far ahead to count the cron schedule's firings (defaults to 30 days)"),
+                        )
+                        .arg(
+                            Arg::new("worker_id")
+                                .long("worker_id")
+                                .short('w')
+                                .value_name("WORKER_ID")
+                                .takes_value(true)
+                                .default_value("0")
+                                .help("The ID of the worker to simulate the exec as"),
+                        ),
+                )
                 .subcommand(
                     Command::new("get")
                         .about("Lookup an automation")
@@ -474,6 +1107,49 @@ pub fn app() -> Command<'static> {
                                 .takes_value(true)
                                 .help("The address of the automation to lookup"),
                         )
+                        .arg(output_arg())
+                )
+                .subcommand(
+                    Command::new("instruction-schema")
+                        .about("Print the JSON schema of the kickoff instruction file format"),
+                )
+                .subcommand(
+                    Command::new("logs")
+                        .about("Print the status and logs of an automation's recent transactions")
+                        .arg_required_else_help(true)
+                        .arg(
+                            Arg::new("id")
+                                .index(1)
+                                .takes_value(true)
+                                .required(true)
+                                .help("The id of the automation"),
+                        )
+                        .arg(
+                            Arg::new("limit")
+                                .long("limit")
+                                .short('l')
+                                .takes_value(true)
+                                .default_value("10")
+                                .help("The max number of transactions to print"),
+                        )
+                        .arg(
+                            Arg::new("failed_only")
+                                .long("failed_only")
+                                .takes_value(false)
+                                .help("Only print transactions that failed"),
+                        ),
+                )
+                .subcommand(
+                    Command::new("migrate")
+                        .about("Migrate an automation account to the current account layout")
+                        .arg_required_else_help(true)
+                        .arg(
+                            Arg::new("id")
+                                .index(1)
+                                .takes_value(true)
+                                .required(true)
+                                .help("The id of the automation to migrate"),
+                        ),
                 )
                 .subcommand(
                     Command::new("pause")
@@ -496,6 +1172,27 @@ pub fn app() -> Command<'static> {
                             .help("The id of the automation to resume"),
                     ),
                 )
+                .subcommand(
+                    Command::new("replay")
+                        .about("Re-simulate a historical automation transaction and print its logs")
+                        .arg_required_else_help(true)
+                        .arg(
+                            Arg::new("signature")
+                                .index(1)
+                                .takes_value(true)
+                                .required(true)
+                                .help("The signature of the transaction to replay"),
+                        )
+                        .arg(
+                            Arg::new("rpc_url")
+                                .long("rpc_url")
+                                .short('u')
+                                .value_name("URL")
+                                .takes_value(true)
+                                .required(false)
+                                .help("An archival RPC endpoint to fetch the transaction from"),
+                        ),
+                )
                 .subcommand(
                     Command::new("reset").about("Reset an automation").arg(
                         Arg::new("id")
@@ -505,6 +1202,38 @@ pub fn app() -> Command<'static> {
                             .help("The id of the automation to stop"),
                     ),
                 )
+                .subcommand(
+                    Command::new("simulate")
+                        .about("Simulate an automation's first exec without creating it")
+                        .arg_required_else_help(true)
+                        .arg(
+                            Arg::new("kickoff_instruction")
+                                .long("kickoff_instruction")
+                                .short('k')
+                                .value_name("FILEPATH")
+                                .takes_value(true)
+                                .required(true)
+                                .help("Filepath to a description of the kickoff instruction"),
+                        )
+                        .arg(
+                            Arg::new("id")
+                                .long("id")
+                                .short('i')
+                                .value_name("ID")
+                                .takes_value(true)
+                                .default_value("simulation")
+                                .help("The ID to simulate the automation under"),
+                        )
+                        .arg(
+                            Arg::new("worker_id")
+                                .long("worker_id")
+                                .short('w')
+                                .value_name("WORKER_ID")
+                                .takes_value(true)
+                                .default_value("0")
+                                .help("The ID of the worker to simulate the exec as"),
+                        ),
+                )
                 .subcommand(
                     Command::new("update")
                         .about("Update a property of an automation")
@@ -516,6 +1245,63 @@ pub fn app() -> Command<'static> {
                                 .required(false)
                                 .help("The id of the automation to lookup"),
                         )
+                        .arg(
+                            Arg::new("description")
+                                .long("description")
+                                .short('d')
+                                .value_name("TEXT")
+                                .takes_value(true)
+                                .required(false)
+                                .help("A description of what the automation does"),
+                        )
+                        .arg(
+                            Arg::new("lifetime_spend_cap")
+                                .long("lifetime_spend_cap")
+                                .value_name("LAMPORTS")
+                                .takes_value(true)
+                                .required(false)
+                                .help(
+                                    "The maximum number of lamports this automation can spend over its lifetime",
+                                ),
+                        )
+                        .arg(
+                            Arg::new("clear_lifetime_spend_cap")
+                                .long("clear_lifetime_spend_cap")
+                                .takes_value(false)
+                                .conflicts_with("lifetime_spend_cap")
+                                .help("Remove the automation's lifetime spend cap"),
+                        )
+                        .arg(
+                            Arg::new("max_chain_depth")
+                                .long("max_chain_depth")
+                                .value_name("COUNT")
+                                .takes_value(true)
+                                .required(false)
+                                .help("The maximum number of next instructions this automation's programs may chain per kickoff"),
+                        )
+                        .arg(
+                            Arg::new("max_executions")
+                                .long("max-executions")
+                                .value_name("COUNT")
+                                .takes_value(true)
+                                .required(false)
+                                .help("The maximum number of times this automation may execute before it is paused"),
+                        )
+                        .arg(
+                            Arg::new("clear_max_executions")
+                                .long("clear-max-executions")
+                                .takes_value(false)
+                                .conflicts_with("max_executions")
+                                .help("Remove the automation's maximum number of executions"),
+                        )
+                        .arg(
+                            Arg::new("max_retries")
+                                .long("max-retries")
+                                .value_name("COUNT")
+                                .takes_value(true)
+                                .required(false)
+                                .help("The number of failed simulations after which workers stop retrying this automation"),
+                        )
                         .arg(
                             Arg::new("rate_limit")
                                 .long("rate_limit")
@@ -536,18 +1322,156 @@ pub fn app() -> Command<'static> {
                         ),
                 ),
         )
+        .subcommand(
+            Command::new("profile")
+                .about("Manage named sets of connection defaults")
+                .arg_required_else_help(true)
+                .subcommand(
+                    Command::new("get").about("Get a profile").arg(
+                        Arg::new("name")
+                            .index(1)
+                            .takes_value(true)
+                            .required(false)
+                            .help("The name of the profile, defaults to the default profile"),
+                    ),
+                )
+                .subcommand(Command::new("list").about("List all profiles"))
+                .subcommand(
+                    Command::new("set")
+                        .about("Create or update a profile from the --json_rpc_url and --keypair flags")
+                        .arg_required_else_help(true)
+                        .arg(
+                            Arg::new("name")
+                                .index(1)
+                                .takes_value(true)
+                                .required(true)
+                                .help("The name of the profile"),
+                        )
+                        .arg(
+                            Arg::new("commitment")
+                                .long("commitment")
+                                .value_name("COMMITMENT")
+                                .takes_value(true)
+                                .possible_values(&["processed", "confirmed", "finalized"])
+                                .help("The commitment level to read and confirm at"),
+                        )
+                        .arg(
+                            Arg::new("default")
+                                .long("default")
+                                .takes_value(false)
+                                .help("Use this profile when no profile is passed"),
+                        ),
+                ),
+        )
+        .subcommand(
+            Command::new("program")
+                .about("Pause or resume automations targeting a program networkwide")
+                .arg_required_else_help(true)
+                .subcommand(
+                    Command::new("pause")
+                        .about("Stop all automations from invoking a program")
+                        .arg_required_else_help(true)
+                        .arg(
+                            Arg::new("program_id")
+                                .index(1)
+                                .value_name("PROGRAM_ID")
+                                .takes_value(true)
+                                .required(true)
+                                .help("The program to pause"),
+                        ),
+                )
+                .subcommand(
+                    Command::new("resume")
+                        .about("Allow automations to invoke a paused program again")
+                        .arg_required_else_help(true)
+                        .arg(
+                            Arg::new("program_id")
+                                .index(1)
+                                .value_name("PROGRAM_ID")
+                                .takes_value(true)
+                                .required(true)
+                                .help("The program to resume"),
+                        ),
+                ),
+        )
         .subcommand(
             Command::new("registry")
                 .about("Manage the Clockwork network registry")
                 .arg_required_else_help(true)
-                .subcommand(Command::new("get").about("Lookup the registry"))
-                .subcommand(Command::new("unlock").about("Manually unlock the registry")),
+                .subcommand(
+                    Command::new("get").about("Lookup the registry").arg(
+                        Arg::new("watch")
+                            .long("watch")
+                            .short('w')
+                            .takes_value(false)
+                            .help("Watch the registry and print its epoch, lock, and worker count whenever they change"),
+                    ),
+                )
+                .subcommand(
+                    Command::new("unlock")
+                        .about("Manually unlock the registry after the epoch jobs holding it have stalled")
+                        .arg(
+                            Arg::new("force")
+                                .long("force")
+                                .takes_value(false)
+                                .help("Unlock the registry even if the epoch jobs still appear to be making progress"),
+                        ),
+                ),
+        )
+        .subcommand(
+            Command::new("snapshot")
+                .about("Inspect the Clockwork network stake snapshots")
+                .arg_required_else_help(true)
+                .subcommand(
+                    Command::new("diff")
+                        .about("Compare the delegated stake of each worker between two epochs")
+                        .arg_required_else_help(true)
+                        .arg(
+                            Arg::new("epoch_a")
+                                .index(1)
+                                .takes_value(true)
+                                .required(true)
+                                .help("The epoch of the first snapshot"),
+                        )
+                        .arg(
+                            Arg::new("epoch_b")
+                                .index(2)
+                                .takes_value(true)
+                                .required(true)
+                                .help("The epoch of the second snapshot"),
+                        ),
+                ),
         )
-        .subcommand(Command::new("snapshot").about("Lookup the current Clockwork network registry"))
         .subcommand(
             Command::new("worker")
                 .about("Manage your workers")
                 .arg_required_else_help(true)
+                .subcommand(
+                    Command::new("addresses")
+                        .about("Derive the addresses of a worker's accounts")
+                        .arg(
+                            Arg::new("id")
+                                .index(1)
+                                .takes_value(true)
+                                .required(true)
+                                .help("The ID of the worker"),
+                        )
+                        .arg(
+                            Arg::new("mint")
+                                .long("mint")
+                                .value_name("ADDRESS")
+                                .takes_value(true)
+                                .required(false)
+                                .help("The stake mint (defaults to the network config's mint)"),
+                        )
+                        .arg(
+                            Arg::new("epoch")
+                                .long("epoch")
+                                .takes_value(true)
+                                .required(false)
+                                .help("The epoch of the snapshot (defaults to the current epoch)"),
+                        ),
+                )
                 .subcommand(
                     Command::new("create")
                         .about("Register a new worker with the Clockwork network")
@@ -568,6 +1492,29 @@ pub fn app() -> Command<'static> {
                                 .takes_value(true)
                                 .required(true)
                                 .help("The ID of the worker to lookup"),
+                        )
+                        .arg(output_arg()),
+                )
+                .subcommand(
+                    Command::new("list")
+                        .about("List the workers registered on the Clockwork network")
+                        .arg(
+                            Arg::new("in_pool")
+                                .long("in-pool")
+                                .takes_value(false)
+                                .required(false)
+                                .help("Only list the workers in pool 0"),
+                        ),
+                )
+                .subcommand(
+                    Command::new("migrate")
+                        .about("Migrate a worker account to the current account layout")
+                        .arg(
+                            Arg::new("id")
+                                .index(1)
+                                .takes_value(true)
+                                .required(true)
+                                .help("The ID of the worker to migrate"),
                         ),
                 )
                 .subcommand(
@@ -580,6 +1527,22 @@ pub fn app() -> Command<'static> {
                                 .required(true)
                                 .help("The ID of the worker to edit"),
                         )
+                        .arg(
+                            Arg::new("commission")
+                                .long("commission")
+                                .value_name("PERCENT")
+                                .takes_value(true)
+                                .required(false)
+                                .help("The percentage of fees the worker keeps, effective from the next epoch"),
+                        )
+                        .arg(
+                            Arg::new("commission_recipient")
+                                .long("commission_recipient")
+                                .value_name("ADDRESS")
+                                .takes_value(true)
+                                .required(false)
+                                .help("The account to pay the worker's commission out to"),
+                        )
                         .arg(
                             Arg::new("signatory_keypair")
                                 .long("signatory_keypair")
@@ -587,6 +1550,12 @@ pub fn app() -> Command<'static> {
                                 .takes_value(true)
                                 .required(false)
                                 .help("Filepath to the worker's new signatory keypair"),
+                        )
+                        .arg(
+                            Arg::new("force")
+                                .long("force")
+                                .takes_value(false)
+                                .help("Change the signatory even if the worker is currently in a pool"),
                         ),
                 ),
         )
@@ -1,7 +1,9 @@
-use std::time::Duration;
+use std::{str::FromStr, time::Duration};
 
 use solana_sdk::commitment_config::CommitmentConfig;
 
+use crate::{errors::CliError, profile::Profiles};
+
 pub const DEFAULT_RPC_TIMEOUT_SECONDS: Duration = Duration::from_secs(30);
 pub const DEFAULT_CONFIRM_TX_TIMEOUT_SECONDS: Duration = Duration::from_secs(5);
 
@@ -12,21 +14,55 @@ pub struct CliConfig {
     pub keypair_path: String,
     pub rpc_timeout: Duration,
     pub commitment: CommitmentConfig,
+    /// The commitment the RPC client reads and confirms transactions at.
+    pub client_commitment: CommitmentConfig,
     pub confirm_transaction_initial_timeout: Duration,
 }
 
+/// Connection settings passed explicitly on the command line.
+#[derive(Debug, Default)]
+pub struct CliOverrides {
+    pub profile: Option<String>,
+    pub json_rpc_url: Option<String>,
+    pub keypair_path: Option<String>,
+}
+
 impl CliConfig {
-    pub fn load() -> Self {
+    /// Load the config, taking each setting from the command line flags, then the active profile, then the Solana CLI config.
+    pub fn load(overrides: CliOverrides) -> Result<Self, CliError> {
         let config_file = solana_cli_config::CONFIG_FILE.as_ref().unwrap().as_str();
         let solana_config = solana_cli_config::Config::load(config_file).unwrap();
+        let profile = Profiles::load()?
+            .resolve(overrides.profile)?
+            .unwrap_or_default();
+
+        // If the url is overridden, leave the websocket url empty so it is computed from the new url.
+        let (json_rpc_url, websocket_url) =
+            match overrides.json_rpc_url.or(profile.json_rpc_url) {
+                None => (solana_config.json_rpc_url, solana_config.websocket_url),
+                Some(json_rpc_url) => (json_rpc_url, String::new()),
+            };
+        let keypair_path = overrides
+            .keypair_path
+            .or(profile.keypair_path)
+            .unwrap_or(solana_config.keypair_path);
+        let (commitment, client_commitment) = match profile.commitment {
+            None => (CommitmentConfig::confirmed(), CommitmentConfig::processed()),
+            Some(commitment) => {
+                let commitment = CommitmentConfig::from_str(&commitment)
+                    .map_err(|_err| CliError::BadParameter("commitment".into()))?;
+                (commitment, commitment)
+            }
+        };
 
-        CliConfig {
-            json_rpc_url: solana_config.json_rpc_url,
-            websocket_url: solana_config.websocket_url,
-            keypair_path: solana_config.keypair_path,
+        Ok(CliConfig {
+            json_rpc_url,
+            websocket_url,
+            keypair_path,
             rpc_timeout: DEFAULT_RPC_TIMEOUT_SECONDS,
-            commitment: CommitmentConfig::confirmed(),
+            commitment,
+            client_commitment,
             confirm_transaction_initial_timeout: DEFAULT_CONFIRM_TX_TIMEOUT_SECONDS,
-        }
+        })
     }
 }
@@ -22,6 +22,12 @@ pub enum CliError {
     InvalidAddress,
     #[error("Program file does not exist")]
     InvalidProgramFile,
+    #[error("Profile not found: {0}")]
+    ProfileNotFound(String),
+    #[error("Profiles could not be read or written: {0}")]
+    ProfilesNotReadable(String),
+    #[error("Transaction not found: {0}")]
+    TransactionNotFound(String),
     #[error("No default signer found in {0}, \
      run `solana-keygen new`, or `solana config set —keypair <FILEPATH>`")]
     KeypairNotFound(String),
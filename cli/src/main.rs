@@ -4,8 +4,10 @@ extern crate version;
 mod cli;
 mod config;
 mod errors;
+mod output;
 mod parser;
 mod processor;
+mod profile;
 
 use cli::app;
 use errors::CliError;
@@ -0,0 +1,330 @@
+use {
+    crate::errors::CliError,
+    clockwork_client::{
+        automation::state::{
+            Automation, ClockData, EpochFilter, ExecContext, ExecMode, InstructionData, Trigger,
+            TriggerContext,
+        },
+        network::state::{Config, Delegation, Pool, Worker},
+    },
+    serde_json::{json, Value},
+    solana_sdk::pubkey::Pubkey,
+    std::str::FromStr,
+};
+
+/// The format a `get` command prints the fetched account in.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OutputFormat {
+    /// A human-readable dump.
+    Display,
+    /// A JSON object, for scripts.
+    Json,
+}
+
+impl FromStr for OutputFormat {
+    type Err = CliError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "display" => Ok(OutputFormat::Display),
+            "json" => Ok(OutputFormat::Json),
+            _ => Err(CliError::BadParameter("output".into())),
+        }
+    }
+}
+
+pub fn print_json(value: &Value) {
+    println!("{}", serde_json::to_string_pretty(value).unwrap());
+}
+
+pub fn config_json(address: Pubkey, config: &Config) -> Value {
+    json!({
+        "address": address.to_string(),
+        "admin": config.admin.to_string(),
+        "creation_fee": config.creation_fee,
+        "epoch_automation": config.epoch_automation.to_string(),
+        "hasher_automation": config.hasher_automation.to_string(),
+        "mint": config.mint.to_string(),
+        "pending_admin": config.pending_admin.map(|pubkey| pubkey.to_string()),
+        "token_stake_rate": config.token_stake_rate,
+        "treasury": config.treasury.to_string(),
+    })
+}
+
+pub fn automation_json(address: Pubkey, automation: &Automation) -> Value {
+    json!({
+        "address": address.to_string(),
+        "authority": automation.authority.to_string(),
+        "bump": automation.bump,
+        "chain_depth": automation.chain_depth,
+        "created_at": clock_json(&automation.created_at),
+        "description": automation.description,
+        "exec_context": automation.exec_context.as_ref().map(exec_context_json),
+        "exec_mode": exec_mode_json(&automation.exec_mode),
+        "execution_count": automation.execution_count,
+        "fee": automation.fee,
+        "id": String::from_utf8_lossy(&automation.id),
+        "instructions": automation
+            .instructions
+            .iter()
+            .map(instruction_json)
+            .collect::<Vec<Value>>(),
+        "lifetime_spend_cap": automation.lifetime_spend_cap,
+        "lifetime_spent": automation.lifetime_spent,
+        "max_chain_depth": automation.max_chain_depth,
+        "max_executions": automation.max_executions,
+        "max_simulation_failures": automation.max_simulation_failures,
+        "name": automation.name,
+        "next_instruction": automation.next_instruction.as_ref().map(instruction_json),
+        "paused": automation.paused,
+        "rate_limit": automation.rate_limit,
+        "trigger": trigger_json(&automation.trigger),
+    })
+}
+
+pub fn worker_json(address: Pubkey, worker: &Worker) -> Value {
+    json!({
+        "address": address.to_string(),
+        "authority": worker.authority.to_string(),
+        "commission_balance": worker.commission_balance,
+        "commission_rate": worker.commission_rate,
+        "commission_recipient": worker.commission_recipient.map(|pubkey| pubkey.to_string()),
+        "id": worker.id,
+        "pending_commission_epoch": worker.pending_commission_epoch,
+        "pending_commission_rate": worker.pending_commission_rate,
+        "signatory": worker.signatory.to_string(),
+        "total_delegations": worker.total_delegations,
+    })
+}
+
+pub fn pool_json(address: Pubkey, pool: &Pool) -> Value {
+    json!({
+        "address": address.to_string(),
+        "id": pool.id,
+        "size": pool.size,
+        "workers": pool
+            .workers
+            .iter()
+            .map(|worker| worker.to_string())
+            .collect::<Vec<String>>(),
+    })
+}
+
+pub fn delegation_json(address: Pubkey, delegation: &Delegation) -> Value {
+    json!({
+        "address": address.to_string(),
+        "authority": delegation.authority.to_string(),
+        "id": delegation.id,
+        "pending_withdrawal": delegation.pending_withdrawal,
+        "stake_amount": delegation.stake_amount,
+        "stake_kind": format!("{:?}", delegation.stake_kind),
+        "unbonding_amount": delegation.unbonding_amount,
+        "withdrawal_epoch": delegation.withdrawal_epoch,
+        "worker": delegation.worker.to_string(),
+        "yield_balance": delegation.yield_balance,
+    })
+}
+
+fn clock_json(clock: &ClockData) -> Value {
+    json!({
+        "slot": clock.slot,
+        "epoch": clock.epoch,
+        "unix_timestamp": clock.unix_timestamp,
+    })
+}
+
+fn exec_context_json(exec_context: &ExecContext) -> Value {
+    let trigger_context = match exec_context.trigger_context {
+        TriggerContext::Account { data_hash } => {
+            json!({ "type": "Account", "data_hash": data_hash })
+        }
+        TriggerContext::Cron { started_at } => json!({ "type": "Cron", "started_at": started_at }),
+        TriggerContext::Immediate => json!({ "type": "Immediate" }),
+        TriggerContext::Slot { started_at } => json!({ "type": "Slot", "started_at": started_at }),
+        TriggerContext::Epoch { started_at } => {
+            json!({ "type": "Epoch", "started_at": started_at })
+        }
+        TriggerContext::Timestamp { started_at } => {
+            json!({ "type": "Timestamp", "started_at": started_at })
+        }
+    };
+    json!({
+        "exec_index": exec_context.exec_index,
+        "execs_since_reimbursement": exec_context.execs_since_reimbursement,
+        "execs_since_slot": exec_context.execs_since_slot,
+        "last_exec_at": exec_context.last_exec_at,
+        "trigger_context": trigger_context,
+    })
+}
+
+fn exec_mode_json(exec_mode: &ExecMode) -> Value {
+    match exec_mode {
+        ExecMode::Worker => json!({ "type": "Worker" }),
+        ExecMode::Relayer { relayer_authority } => json!({
+            "type": "Relayer",
+            "relayer_authority": relayer_authority.to_string(),
+        }),
+    }
+}
+
+fn instruction_json(instruction: &InstructionData) -> Value {
+    json!({
+        "program_id": instruction.program_id.to_string(),
+        "accounts": instruction
+            .accounts
+            .iter()
+            .map(|account| {
+                json!({
+                    "pubkey": account.pubkey.to_string(),
+                    "is_signer": account.is_signer,
+                    "is_writable": account.is_writable,
+                })
+            })
+            .collect::<Vec<Value>>(),
+        "data": instruction.data,
+    })
+}
+
+fn trigger_json(trigger: &Trigger) -> Value {
+    match trigger {
+        Trigger::Account {
+            address,
+            offset,
+            size,
+        } => json!({
+            "type": "Account",
+            "address": address.to_string(),
+            "offset": offset,
+            "size": size,
+        }),
+        Trigger::Cron {
+            schedule,
+            skippable,
+        } => json!({
+            "type": "Cron",
+            "schedule": schedule,
+            "skippable": skippable,
+        }),
+        Trigger::Immediate => json!({ "type": "Immediate" }),
+        Trigger::Slot { target_slot } => json!({ "type": "Slot", "target_slot": target_slot }),
+        Trigger::Epoch { skippable } => json!({ "type": "Epoch", "skippable": skippable }),
+        Trigger::Timestamp { unix_ts } => json!({ "type": "Timestamp", "unix_ts": unix_ts }),
+        Trigger::AccountValue {
+            address,
+            offset,
+            size,
+            equals,
+            changed_from,
+        } => json!({
+            "type": "AccountValue",
+            "address": address.to_string(),
+            "offset": offset,
+            "size": size,
+            "equals": equals,
+            "changed_from": changed_from,
+        }),
+        Trigger::EpochFilteredCron {
+            schedule,
+            skippable,
+            epoch_filter,
+        } => json!({
+            "type": "EpochFilteredCron",
+            "schedule": schedule,
+            "skippable": skippable,
+            "epoch_filter": epoch_filter_json(epoch_filter),
+        }),
+        Trigger::EpochFilteredSlot {
+            target_slot,
+            epoch_filter,
+        } => json!({
+            "type": "EpochFilteredSlot",
+            "target_slot": target_slot,
+            "epoch_filter": epoch_filter_json(epoch_filter),
+        }),
+    }
+}
+
+fn epoch_filter_json(epoch_filter: &EpochFilter) -> Value {
+    match epoch_filter {
+        EpochFilter::Even => json!({ "type": "Even" }),
+        EpochFilter::Odd => json!({ "type": "Odd" }),
+        EpochFilter::Every(n) => json!({ "type": "Every", "n": n }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_output_format() {
+        assert_eq!(OutputFormat::from_str("display").unwrap(), OutputFormat::Display);
+        assert_eq!(OutputFormat::from_str("json").unwrap(), OutputFormat::Json);
+        assert!(OutputFormat::from_str("yaml").is_err());
+    }
+
+    #[test]
+    fn test_pool_json() {
+        let worker = Pubkey::new_unique();
+        let address = Pool::pubkey(0);
+        let value = pool_json(
+            address,
+            &Pool {
+                id: 0,
+                size: 2,
+                workers: vec![worker].into(),
+            },
+        );
+        assert_eq!(value["address"], address.to_string());
+        assert_eq!(value["size"], 2);
+        assert_eq!(value["workers"][0], worker.to_string());
+    }
+
+    #[test]
+    fn test_automation_json_shows_description() {
+        let address = Pubkey::new_unique();
+        let automation = Automation {
+            authority: Pubkey::new_unique(),
+            bump: 255,
+            chain_depth: 0,
+            created_at: ClockData {
+                slot: 0,
+                epoch: 0,
+                unix_timestamp: 0,
+            },
+            description: "Cranks the orderbook".into(),
+            exec_context: None,
+            exec_mode: ExecMode::Worker,
+            execution_count: 0,
+            fee: 1_000,
+            id: b"crank".to_vec(),
+            instructions: vec![],
+            lifetime_spend_cap: None,
+            lifetime_spent: 0,
+            max_chain_depth: None,
+            max_executions: None,
+            max_simulation_failures: None,
+            name: String::new(),
+            next_instruction: None,
+            paused: false,
+            rate_limit: u64::MAX,
+            trigger: Trigger::Immediate,
+        };
+        let value = automation_json(address, &automation);
+        assert_eq!(value["address"], address.to_string());
+        assert_eq!(value["id"], "crank");
+        assert_eq!(value["description"], "Cranks the orderbook");
+    }
+
+    #[test]
+    fn test_trigger_json() {
+        let value = trigger_json(&Trigger::EpochFilteredCron {
+            schedule: "0 0 * * * * *".into(),
+            skippable: true,
+            epoch_filter: EpochFilter::Every(3),
+        });
+        assert_eq!(value["type"], "EpochFilteredCron");
+        assert_eq!(value["schedule"], "0 0 * * * * *");
+        assert_eq!(value["epoch_filter"]["n"], 3);
+    }
+}
@@ -1,13 +1,17 @@
-use crate::{cli::CliCommand, errors::CliError};
+use crate::{cli::CliCommand, config::CliOverrides, errors::CliError, output::OutputFormat};
+use chrono_tz::Tz;
 use clap::ArgMatches;
+use clockwork_cron::Schedule;
 use clockwork_client::{
-    automation::state::{AccountMetaData, InstructionData, Trigger},
+    automation::state::{
+        AccountMetaData, EpochFilter, InstructionData, Trigger, TRIGGER_ACCOUNT_MAX_SIZE,
+    },
     webhook::state::HttpMethod,
 };
 use serde::{Deserialize as JsonDeserialize, Serialize as JsonSerialize};
 use solana_sdk::{
     pubkey::Pubkey,
-    signature::{read_keypair_file, Keypair},
+    signature::{read_keypair_file, Keypair, Signature},
     signer::Signer,
 };
 use std::{convert::TryFrom, fs, path::PathBuf, str::FromStr};
@@ -24,9 +28,13 @@ impl TryFrom<&ArgMatches> for CliCommand {
             Some(("explorer", matches)) => parse_explorer_command(matches),
             Some(("initialize", matches)) => parse_initialize_command(matches),
             Some(("localnet", matches)) => parse_bpf_command(matches),
+            Some(("network", matches)) => parse_network_command(matches),
             Some(("pool", matches)) => parse_pool_command(matches),
+            Some(("profile", matches)) => parse_profile_command(matches),
+            Some(("program", matches)) => parse_program_command(matches),
             Some(("automation", matches)) => parse_automation_command(matches),
             Some(("registry", matches)) => parse_registry_command(matches),
+            Some(("snapshot", matches)) => parse_snapshot_command(matches),
             Some(("webhook", matches)) => parse_webhook_command(matches),
             Some(("worker", matches)) => parse_worker_command(matches),
             _ => Err(CliError::CommandNotRecognized(
@@ -36,6 +44,16 @@ impl TryFrom<&ArgMatches> for CliCommand {
     }
 }
 
+impl From<&ArgMatches> for CliOverrides {
+    fn from(matches: &ArgMatches) -> Self {
+        CliOverrides {
+            profile: parse_string("profile", matches).ok(),
+            json_rpc_url: parse_string("json_rpc_url", matches).ok(),
+            keypair_path: parse_string("keypair", matches).ok(),
+        }
+    }
+}
+
 // Command parsers
 fn parse_bpf_command(matches: &ArgMatches) -> Result<CliCommand, CliError> {
     let mut program_infos = Vec::<ProgramInfo>::new();
@@ -102,11 +120,22 @@ fn parse_api_command(matches: &ArgMatches) -> Result<CliCommand, CliError> {
 
 fn parse_config_command(matches: &ArgMatches) -> Result<CliCommand, CliError> {
     match matches.subcommand() {
-        Some(("get", _)) => Ok(CliCommand::ConfigGet {}),
+        Some(("accept-admin", _)) => Ok(CliCommand::ConfigAcceptAdmin),
+        Some(("cancel-admin", _)) => Ok(CliCommand::ConfigCancelAdmin),
+        Some(("get", matches)) => Ok(CliCommand::ConfigGet {
+            output: parse_output_format(matches)?,
+            watch: matches.is_present("watch"),
+        }),
+        Some(("migrate", _)) => Ok(CliCommand::ConfigMigrate),
+        Some(("propose-admin", matches)) => Ok(CliCommand::ConfigProposeAdmin {
+            pending_admin: parse_pubkey("address", matches)?,
+        }),
         Some(("set", matches)) => Ok(CliCommand::ConfigSet {
-            admin: parse_pubkey("admin", matches).ok(),
+            creation_fee: parse_u64("creation_fee", matches).ok(),
             epoch_automation: parse_pubkey("epoch_automation", matches).ok(),
             hasher_automation: parse_pubkey("hasher_automation", matches).ok(),
+            token_stake_rate: parse_u64("token_stake_rate", matches).ok(),
+            treasury: parse_pubkey("treasury", matches).ok(),
         }),
         _ => Err(CliError::CommandNotRecognized(
             matches.subcommand().unwrap().0.into(),
@@ -117,12 +146,22 @@ fn parse_config_command(matches: &ArgMatches) -> Result<CliCommand, CliError> {
 fn parse_crontab_command(matches: &ArgMatches) -> Result<CliCommand, CliError> {
     Ok(CliCommand::Crontab {
         schedule: parse_string("schedule", matches)?,
+        timezone: match matches.value_of("timezone") {
+            None => None,
+            Some(timezone) => Some(Tz::from_str(timezone).map_err(|_err| {
+                CliError::BadParameter(format!(
+                    "unknown timezone \"{}\", expected an IANA timezone name such as America/New_York",
+                    timezone
+                ))
+            })?),
+        },
     })
 }
 
 fn parse_delegation_command(matches: &ArgMatches) -> Result<CliCommand, CliError> {
     match matches.subcommand() {
         Some(("create", matches)) => Ok(CliCommand::DelegationCreate {
+            native: matches.is_present("native"),
             worker_id: parse_u64("worker_id", matches)?,
         }),
         Some(("deposit", matches)) => Ok(CliCommand::DelegationDeposit {
@@ -132,6 +171,19 @@ fn parse_delegation_command(matches: &ArgMatches) -> Result<CliCommand, CliError
         }),
         Some(("get", matches)) => Ok(CliCommand::DelegationGet {
             delegation_id: parse_u64("delegation_id", matches)?,
+            output: parse_output_format(matches)?,
+            worker_id: parse_u64("worker_id", matches)?,
+        }),
+        Some(("list", matches)) => Ok(CliCommand::DelegationList {
+            worker_id: parse_u64("worker_id", matches)?,
+        }),
+        Some(("migrate", matches)) => Ok(CliCommand::DelegationMigrate {
+            delegation_id: parse_u64("delegation_id", matches)?,
+            worker_id: parse_u64("worker_id", matches)?,
+        }),
+        Some(("merge", matches)) => Ok(CliCommand::DelegationMerge {
+            from_id: parse_u64("from_id", matches)?,
+            into_id: parse_u64("into_id", matches)?,
             worker_id: parse_u64("worker_id", matches)?,
         }),
         Some(("withdraw", matches)) => Ok(CliCommand::DelegationWithdraw {
@@ -139,6 +191,15 @@ fn parse_delegation_command(matches: &ArgMatches) -> Result<CliCommand, CliError
             delegation_id: parse_u64("delegation_id", matches)?,
             worker_id: parse_u64("worker_id", matches)?,
         }),
+        Some(("withdraw-claim", matches)) => Ok(CliCommand::DelegationWithdrawClaim {
+            delegation_id: parse_u64("delegation_id", matches)?,
+            worker_id: parse_u64("worker_id", matches)?,
+        }),
+        Some(("withdraw-request", matches)) => Ok(CliCommand::DelegationWithdrawRequest {
+            amount: parse_u64("amount", matches)?,
+            delegation_id: parse_u64("delegation_id", matches)?,
+            worker_id: parse_u64("worker_id", matches)?,
+        }),
         _ => Err(CliError::CommandNotRecognized(
             matches.subcommand().unwrap().0.into(),
         )),
@@ -165,14 +226,20 @@ fn parse_initialize_command(matches: &ArgMatches) -> Result<CliCommand, CliError
 
 fn parse_pool_command(matches: &ArgMatches) -> Result<CliCommand, CliError> {
     match matches.subcommand() {
+        Some(("create", _)) => Ok(CliCommand::PoolCreate {}),
         Some(("get", matches)) => Ok(CliCommand::PoolGet {
             id: parse_u64("id", matches)?,
+            output: parse_output_format(matches)?,
         }),
         Some(("update", matches)) => Ok(CliCommand::PoolUpdate {
             id: parse_u64("id", matches)?,
             size: parse_usize("size", matches)?,
         }),
-        Some(("list", _)) => Ok(CliCommand::PoolList {}),
+        Some(("list", matches)) => Ok(CliCommand::PoolList {
+            offset: parse_u64("offset", matches).unwrap_or(0),
+            limit: parse_u64("limit", matches).ok(),
+            min_size: parse_usize("min_size", matches).ok(),
+        }),
         _ => Err(CliError::CommandNotRecognized(
             matches.subcommand().unwrap().0.into(),
         )),
@@ -181,18 +248,57 @@ fn parse_pool_command(matches: &ArgMatches) -> Result<CliCommand, CliError> {
 
 fn parse_automation_command(matches: &ArgMatches) -> Result<CliCommand, CliError> {
     match matches.subcommand() {
+        Some(("cost-report", matches)) => Ok(CliCommand::AutomationCostReport {
+            authority: parse_pubkey("authority", matches).ok(),
+            since: parse_u64("since", matches).ok(),
+        }),
         Some(("crate-info", _)) => Ok(CliCommand::AutomationCrateInfo {}),
+        Some(("clone", matches)) => Ok(CliCommand::AutomationClone {
+            source_id: parse_string("source_id", matches)?,
+            new_id: parse_string("new_id", matches)?,
+            kickoff_instruction: matches
+                .is_present("kickoff_instruction")
+                .then(|| parse_instruction_file("kickoff_instruction", matches))
+                .transpose()?,
+            rate_limit: parse_u64("rate_limit", matches).ok(),
+            schedule: matches
+                .is_present("schedule")
+                .then(|| parse_cron_schedule("schedule", matches))
+                .transpose()?,
+        }),
+        Some(("instruction-schema", _)) => Ok(CliCommand::AutomationInstructionSchema),
         Some(("create", matches)) => Ok(CliCommand::AutomationCreate {
             id: parse_string("id", matches)?,
+            description: parse_string("description", matches).ok(),
             kickoff_instruction: parse_instruction_file("kickoff_instruction", matches)?,
+            max_executions: parse_u64("max_executions", matches).ok(),
             trigger: parse_trigger(matches)?,
         }),
         Some(("delete", matches)) => Ok(CliCommand::AutomationDelete {
             id: parse_string("id", matches)?,
         }),
+        Some(("estimate", matches)) => Ok(CliCommand::AutomationEstimate {
+            cron: parse_string("cron", matches).ok(),
+            executions: parse_u64("executions", matches).ok(),
+            horizon: parse_i64("horizon", matches)?,
+            kickoff_instruction: parse_instruction_file("kickoff_instruction", matches)?,
+            worker_id: parse_u64("worker_id", matches)?,
+        }),
         Some(("get", matches)) => Ok(CliCommand::AutomationGet {
             id: parse_string("id", matches).ok(),
             address: parse_pubkey("address", matches).ok(),
+            output: parse_output_format(matches)?,
+        }),
+        Some(("list", matches)) => Ok(CliCommand::AutomationList {
+            authority: parse_pubkey("authority", matches).ok(),
+        }),
+        Some(("logs", matches)) => Ok(CliCommand::AutomationLogs {
+            id: parse_string("id", matches)?,
+            limit: parse_usize("limit", matches)?,
+            failed_only: matches.is_present("failed_only"),
+        }),
+        Some(("migrate", matches)) => Ok(CliCommand::AutomationMigrate {
+            id: parse_string("id", matches)?,
         }),
         Some(("pause", matches)) => Ok(CliCommand::AutomationPause {
             id: parse_string("id", matches)?,
@@ -200,13 +306,79 @@ fn parse_automation_command(matches: &ArgMatches) -> Result<CliCommand, CliError
         Some(("resume", matches)) => Ok(CliCommand::AutomationResume {
             id: parse_string("id", matches)?,
         }),
+        Some(("replay", matches)) => Ok(CliCommand::AutomationReplay {
+            signature: parse_signature("signature", matches)?,
+            rpc_url: parse_string("rpc_url", matches).ok(),
+        }),
         Some(("reset", matches)) => Ok(CliCommand::AutomationReset {
             id: parse_string("id", matches)?,
         }),
+        Some(("simulate", matches)) => Ok(CliCommand::AutomationSimulate {
+            id: parse_string("id", matches)?,
+            kickoff_instruction: parse_instruction_file("kickoff_instruction", matches)?,
+            worker_id: parse_u64("worker_id", matches)?,
+        }),
         Some(("update", matches)) => Ok(CliCommand::AutomationUpdate {
             id: parse_string("id", matches)?,
+            description: parse_string("description", matches).ok(),
+            lifetime_spend_cap: match matches.is_present("clear_lifetime_spend_cap") {
+                true => Some(None),
+                false => parse_u64("lifetime_spend_cap", matches).ok().map(Some),
+            },
+            max_chain_depth: parse_u64("max_chain_depth", matches).ok(),
+            max_executions: match matches.is_present("clear_max_executions") {
+                true => Some(None),
+                false => parse_u64("max_executions", matches).ok().map(Some),
+            },
+            max_retries: parse_u32("max_retries", matches).ok(),
             rate_limit: parse_u64("rate_limit", matches).ok(),
-            schedule: parse_string("schedule", matches).ok(),
+            schedule: matches
+                .is_present("schedule")
+                .then(|| parse_cron_schedule("schedule", matches))
+                .transpose()?,
+        }),
+        _ => Err(CliError::CommandNotRecognized(
+            matches.subcommand().unwrap().0.into(),
+        )),
+    }
+}
+
+fn parse_network_command(matches: &ArgMatches) -> Result<CliCommand, CliError> {
+    match matches.subcommand() {
+        Some(("pipeline-status", _)) => Ok(CliCommand::NetworkPipelineStatus {}),
+        Some(("stats", _)) => Ok(CliCommand::NetworkStats {}),
+        _ => Err(CliError::CommandNotRecognized(
+            matches.subcommand().unwrap().0.into(),
+        )),
+    }
+}
+
+fn parse_profile_command(matches: &ArgMatches) -> Result<CliCommand, CliError> {
+    match matches.subcommand() {
+        Some(("get", matches)) => Ok(CliCommand::ProfileGet {
+            name: parse_string("name", matches).ok(),
+        }),
+        Some(("list", _matches)) => Ok(CliCommand::ProfileList),
+        Some(("set", matches)) => Ok(CliCommand::ProfileSet {
+            name: parse_string("name", matches)?,
+            json_rpc_url: parse_string("json_rpc_url", matches).ok(),
+            keypair_path: parse_string("keypair", matches).ok(),
+            commitment: parse_string("commitment", matches).ok(),
+            default: matches.is_present("default"),
+        }),
+        _ => Err(CliError::CommandNotRecognized(
+            matches.subcommand().unwrap().0.into(),
+        )),
+    }
+}
+
+fn parse_program_command(matches: &ArgMatches) -> Result<CliCommand, CliError> {
+    match matches.subcommand() {
+        Some(("pause", matches)) => Ok(CliCommand::ProgramPause {
+            program_id: parse_pubkey("program_id", matches)?,
+        }),
+        Some(("resume", matches)) => Ok(CliCommand::ProgramResume {
+            program_id: parse_pubkey("program_id", matches)?,
         }),
         _ => Err(CliError::CommandNotRecognized(
             matches.subcommand().unwrap().0.into(),
@@ -216,8 +388,24 @@ fn parse_automation_command(matches: &ArgMatches) -> Result<CliCommand, CliError
 
 fn parse_registry_command(matches: &ArgMatches) -> Result<CliCommand, CliError> {
     match matches.subcommand() {
-        Some(("get", _)) => Ok(CliCommand::RegistryGet {}),
-        Some(("unlock", _)) => Ok(CliCommand::RegistryUnlock {}),
+        Some(("get", matches)) => Ok(CliCommand::RegistryGet {
+            watch: matches.is_present("watch"),
+        }),
+        Some(("unlock", matches)) => Ok(CliCommand::RegistryUnlock {
+            force: matches.is_present("force"),
+        }),
+        _ => Err(CliError::CommandNotRecognized(
+            matches.subcommand().unwrap().0.into(),
+        )),
+    }
+}
+
+fn parse_snapshot_command(matches: &ArgMatches) -> Result<CliCommand, CliError> {
+    match matches.subcommand() {
+        Some(("diff", matches)) => Ok(CliCommand::SnapshotDiff {
+            epoch_a: parse_u64("epoch_a", matches)?,
+            epoch_b: parse_u64("epoch_b", matches)?,
+        }),
         _ => Err(CliError::CommandNotRecognized(
             matches.subcommand().unwrap().0.into(),
         )),
@@ -227,7 +415,18 @@ fn parse_registry_command(matches: &ArgMatches) -> Result<CliCommand, CliError>
 fn parse_webhook_command(matches: &ArgMatches) -> Result<CliCommand, CliError> {
     Ok(CliCommand::WebhookRequestNew {
         api: parse_pubkey("api", matches)?,
+        backoff_seconds: parse_u64("backoff_seconds", matches).unwrap_or(1),
+        body: parse_string("body", matches)
+            .map(|body| body.into_bytes())
+            .unwrap_or_default(),
+        headers: matches
+            .values_of("header")
+            .into_iter()
+            .flatten()
+            .map(parse_http_header)
+            .collect::<Result<Vec<(String, String)>, CliError>>()?,
         id: parse_string("id", matches)?,
+        max_retries: parse_u64("max_retries", matches).unwrap_or(0),
         method: parse_http_method("method", matches)?,
         route: parse_string("route", matches)?,
     })
@@ -235,15 +434,30 @@ fn parse_webhook_command(matches: &ArgMatches) -> Result<CliCommand, CliError> {
 
 fn parse_worker_command(matches: &ArgMatches) -> Result<CliCommand, CliError> {
     match matches.subcommand() {
+        Some(("addresses", matches)) => Ok(CliCommand::WorkerAddresses {
+            id: parse_u64("id", matches)?,
+            mint: parse_pubkey("mint", matches).ok(),
+            epoch: parse_u64("epoch", matches).ok(),
+        }),
         Some(("create", matches)) => Ok(CliCommand::WorkerCreate {
             signatory: parse_keypair_file("signatory_keypair", matches)?,
         }),
         Some(("get", matches)) => Ok(CliCommand::WorkerGet {
             id: parse_u64("id", matches)?,
+            output: parse_output_format(matches)?,
+        }),
+        Some(("list", matches)) => Ok(CliCommand::WorkerList {
+            in_pool: matches.is_present("in_pool"),
+        }),
+        Some(("migrate", matches)) => Ok(CliCommand::WorkerMigrate {
+            id: parse_u64("id", matches)?,
         }),
         Some(("update", matches)) => Ok(CliCommand::WorkerUpdate {
             id: parse_u64("id", matches)?,
+            commission: parse_u64("commission", matches).ok(),
+            commission_recipient: parse_pubkey("commission_recipient", matches).ok(),
             signatory: parse_keypair_file("signatory_keypair", matches).ok(),
+            force: matches.is_present("force"),
         }),
         _ => Err(CliError::CommandNotRecognized(
             matches.subcommand().unwrap().0.into(),
@@ -254,24 +468,165 @@ fn parse_worker_command(matches: &ArgMatches) -> Result<CliCommand, CliError> {
 // Arg parsers
 
 fn parse_trigger(matches: &ArgMatches) -> Result<Trigger, CliError> {
+    let trigger = parse_trigger_variant(matches)?;
+    if !trigger.is_valid() {
+        return Err(CliError::BadParameter("trigger".into()));
+    }
+    Ok(trigger)
+}
+
+/// Builds an account trigger, with value conditions if any predicate was given.
+fn account_trigger(
+    address: Pubkey,
+    offset: u64,
+    size: u64,
+    equals: Option<Vec<u8>>,
+    changed_from: Option<Vec<u8>>,
+) -> Trigger {
+    if equals.is_none() && changed_from.is_none() {
+        Trigger::Account {
+            address,
+            offset,
+            size,
+        }
+    } else {
+        Trigger::AccountValue {
+            address,
+            offset,
+            size,
+            equals,
+            changed_from,
+        }
+    }
+}
+
+fn parse_trigger_variant(matches: &ArgMatches) -> Result<Trigger, CliError> {
     if matches.is_present("account") {
-        return Ok(Trigger::Account {
-            address: parse_pubkey("address", matches)?,
-            offset: 0, // TODO
-            size: 32,  // TODO
-        });
+        let (offset, size, equals, changed_from) = parse_account_trigger_slice(matches)?;
+        let address = parse_pubkey("account", matches)?;
+        return Ok(account_trigger(address, offset, size, equals, changed_from));
+    } else if matches.is_present("account_seeds") {
+        let program_id = parse_pubkey("account_program", matches)?;
+        let seeds = matches
+            .values_of("account_seeds")
+            .ok_or(CliError::BadParameter("account_seeds".into()))?
+            .map(parse_seed)
+            .collect::<Result<Vec<Vec<u8>>, CliError>>()?;
+        let (offset, size, equals, changed_from) = parse_account_trigger_slice(matches)?;
+        let seeds = seeds.iter().map(|seed| seed.as_slice()).collect::<Vec<&[u8]>>();
+        let address = Pubkey::find_program_address(&seeds, &program_id).0;
+        return Ok(account_trigger(address, offset, size, equals, changed_from));
     } else if matches.is_present("cron") {
-        return Ok(Trigger::Cron {
-            schedule: parse_string("cron", matches)?,
-            skippable: true,
+        let schedule = parse_cron_schedule("cron", matches)?;
+        return Ok(match parse_epoch_filter(matches)? {
+            None => Trigger::Cron {
+                schedule,
+                skippable: true,
+            },
+            Some(epoch_filter) => Trigger::EpochFilteredCron {
+                schedule,
+                skippable: true,
+                epoch_filter,
+            },
         });
+    } else if matches.is_present("epoch") {
+        return Ok(Trigger::Epoch { skippable: true });
     } else if matches.is_present("immediate") {
         return Ok(Trigger::Immediate);
+    } else if matches.is_present("slot") {
+        let target_slot = parse_u64("slot", matches)?;
+        return Ok(match parse_epoch_filter(matches)? {
+            None => Trigger::Slot { target_slot },
+            Some(epoch_filter) => Trigger::EpochFilteredSlot {
+                target_slot,
+                epoch_filter,
+            },
+        });
+    } else if matches.is_present("timestamp") {
+        return Ok(Trigger::Timestamp {
+            unix_ts: parse_i64("timestamp", matches)?,
+        });
     }
 
     Err(CliError::BadParameter("trigger".into()))
 }
 
+/// Parses the monitored slice of an account trigger: its offset, size, and predicates.
+/// The size defaults to the length of the predicate values, or 32 bytes if there are none.
+fn parse_account_trigger_slice(
+    matches: &ArgMatches,
+) -> Result<(u64, u64, Option<Vec<u8>>, Option<Vec<u8>>), CliError> {
+    let offset = match matches.is_present("account_offset") {
+        true => parse_u64("account_offset", matches)?,
+        false => 0,
+    };
+    let mut equals = None;
+    let mut changed_from = None;
+    for expr in matches.values_of("account_predicate").into_iter().flatten() {
+        let bad_predicate = || CliError::BadParameter(format!("account_predicate: {}", expr));
+        let (target, value) = match expr.split_once(':') {
+            Some(("equals", value)) => (&mut equals, value),
+            Some(("changed_from", value)) => (&mut changed_from, value),
+            _ => return Err(bad_predicate()),
+        };
+        if target.is_some() {
+            return Err(bad_predicate());
+        }
+        *target = Some(parse_hex(value).ok_or_else(bad_predicate)?);
+    }
+    let predicate_len = equals.iter().chain(changed_from.iter()).map(|v| v.len() as u64).next();
+    let size = match matches.is_present("account_size") {
+        true => parse_u64("account_size", matches)?,
+        false => predicate_len.unwrap_or(32),
+    };
+    if size == 0 || size > TRIGGER_ACCOUNT_MAX_SIZE {
+        return Err(CliError::BadParameter(format!(
+            "account_size: must be between 1 and {} bytes",
+            TRIGGER_ACCOUNT_MAX_SIZE
+        )));
+    }
+    if equals.iter().chain(changed_from.iter()).any(|v| v.len() as u64 != size) {
+        return Err(CliError::BadParameter(format!(
+            "account_predicate: values must be {} bytes long",
+            size
+        )));
+    }
+    Ok((offset, size, equals, changed_from))
+}
+
+fn parse_seed(seed: &str) -> Result<Vec<u8>, CliError> {
+    let bad_seed = || CliError::BadParameter(format!("account_seeds: {}", seed));
+    match seed.split_once(':') {
+        Some(("string", value)) => Ok(value.as_bytes().to_vec()),
+        Some(("pubkey", value)) => Ok(Pubkey::from_str(value)
+            .map_err(|_err| bad_seed())?
+            .to_bytes()
+            .to_vec()),
+        Some(("u64", value)) => Ok(value
+            .parse::<u64>()
+            .map_err(|_err| bad_seed())?
+            .to_be_bytes()
+            .to_vec()),
+        Some(("hex", value)) => parse_hex(value).ok_or_else(bad_seed),
+        _ => Err(bad_seed()),
+    }
+}
+
+fn parse_hex(value: &str) -> Option<Vec<u8>> {
+    let value = value.strip_prefix("0x").unwrap_or(value);
+    if value.len() % 2 != 0 {
+        return None;
+    }
+    (0..value.len())
+        .step_by(2)
+        .map(|i| {
+            value
+                .get(i..i + 2)
+                .and_then(|byte| u8::from_str_radix(byte, 16).ok())
+        })
+        .collect()
+}
+
 fn parse_instruction_file(arg: &str, matches: &ArgMatches) -> Result<InstructionData, CliError> {
     let filepath = parse_string(arg, matches)?;
     let text = fs::read_to_string(filepath).map_err(|_err| CliError::BadParameter(arg.into()))?;
@@ -285,16 +640,61 @@ fn parse_keypair_file(arg: &str, matches: &ArgMatches) -> Result<Keypair, CliErr
         .map_err(|_err| CliError::BadParameter(arg.into()))?)
 }
 
+/// Parses a header formatted as `<NAME>: <VALUE>`.
+fn parse_http_header(header: &str) -> Result<(String, String), CliError> {
+    match header.split_once(':') {
+        Some((name, value)) if !name.trim().is_empty() => {
+            Ok((name.trim().to_string(), value.trim().to_string()))
+        }
+        _ => Err(CliError::BadParameter(format!("header: {}", header))),
+    }
+}
+
 fn parse_http_method(arg: &str, matches: &ArgMatches) -> Result<HttpMethod, CliError> {
     Ok(HttpMethod::from_str(parse_string(arg, matches)?.as_str())
         .map_err(|_err| CliError::BadParameter(arg.into()))?)
 }
 
+fn parse_epoch_filter(matches: &ArgMatches) -> Result<Option<EpochFilter>, CliError> {
+    let value = match matches.value_of("epoch_filter") {
+        None => return Ok(None),
+        Some(value) => value,
+    };
+    let epoch_filter = match value {
+        "even" => EpochFilter::Even,
+        "odd" => EpochFilter::Odd,
+        _ => value
+            .strip_prefix("every:")
+            .and_then(|n| n.parse::<u64>().ok())
+            .filter(|n| *n > 0)
+            .map(EpochFilter::Every)
+            .ok_or(CliError::BadParameter("epoch_filter".into()))?,
+    };
+    Ok(Some(epoch_filter))
+}
+
 fn parse_pubkey(arg: &str, matches: &ArgMatches) -> Result<Pubkey, CliError> {
     Ok(Pubkey::from_str(parse_string(arg, matches)?.as_str())
         .map_err(|_err| CliError::BadParameter(arg.into()))?)
 }
 
+fn parse_signature(arg: &str, matches: &ArgMatches) -> Result<Signature, CliError> {
+    Ok(Signature::from_str(parse_string(arg, matches)?.as_str())
+        .map_err(|_err| CliError::BadParameter(arg.into()))?)
+}
+
+fn parse_cron_schedule(arg: &str, matches: &ArgMatches) -> Result<String, CliError> {
+    let schedule = parse_string(arg, matches)?;
+    Schedule::from_str(&schedule).map_err(|err| {
+        CliError::BadParameter(format!("invalid cron schedule \"{}\": {}", schedule, err))
+    })?;
+    Ok(schedule)
+}
+
+fn parse_output_format(matches: &ArgMatches) -> Result<OutputFormat, CliError> {
+    OutputFormat::from_str(parse_string("output", matches)?.as_str())
+}
+
 fn parse_string(arg: &str, matches: &ArgMatches) -> Result<String, CliError> {
     Ok(matches
         .value_of(arg)
@@ -309,6 +709,19 @@ pub fn _parse_i64(arg: &str, matches: &ArgMatches) -> Result<i64, CliError> {
         .unwrap())
 }
 
+pub fn parse_i64(arg: &str, matches: &ArgMatches) -> Result<i64, CliError> {
+    parse_string(arg, matches)?
+        .parse::<i64>()
+        .map_err(|_err| CliError::BadParameter(arg.into()))
+}
+
+pub fn parse_u32(arg: &str, matches: &ArgMatches) -> Result<u32, CliError> {
+    Ok(parse_string(arg, matches)?
+        .parse::<u32>()
+        .map_err(|_err| CliError::BadParameter(arg.into()))
+        .unwrap())
+}
+
 pub fn parse_u64(arg: &str, matches: &ArgMatches) -> Result<u64, CliError> {
     Ok(parse_string(arg, matches)?
         .parse::<u64>()
@@ -349,6 +762,56 @@ impl TryFrom<&JsonInstructionData> for InstructionData {
     }
 }
 
+/// The JSON schema of the instruction description file accepted by `automation create`.
+pub fn instruction_schema() -> serde_json::Value {
+    serde_json::json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "title": "JsonInstructionData",
+        "description": "An instruction for an automation to execute",
+        "type": "object",
+        "required": ["program_id", "accounts", "data"],
+        "properties": {
+            "program_id": {
+                "description": "The base58 address of the program to invoke",
+                "type": "string",
+                "pattern": "^[1-9A-HJ-NP-Za-km-z]{32,44}$"
+            },
+            "accounts": {
+                "description": "The accounts required by the instruction",
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "required": ["pubkey", "is_signer", "is_writable"],
+                    "properties": {
+                        "pubkey": {
+                            "description": "The base58 address of the account",
+                            "type": "string",
+                            "pattern": "^[1-9A-HJ-NP-Za-km-z]{32,44}$"
+                        },
+                        "is_signer": {
+                            "description": "True if the instruction requires a signature from this account",
+                            "type": "boolean"
+                        },
+                        "is_writable": {
+                            "description": "True if the account data may be mutated by the instruction",
+                            "type": "boolean"
+                        }
+                    }
+                }
+            },
+            "data": {
+                "description": "The instruction data as a list of bytes",
+                "type": "array",
+                "items": {
+                    "type": "integer",
+                    "minimum": 0,
+                    "maximum": 255
+                }
+            }
+        }
+    })
+}
+
 // pub fn _parse_instruction(filepath: &String) -> Result<Instruction, CliError> {
 //     let text =
 //         fs::read_to_string(filepath).map_err(|_err| CliError::BadParameter("filepath".into()))?;
@@ -382,3 +845,338 @@ pub struct ProgramInfo {
     pub program_id: Pubkey,
     pub program_path: PathBuf,
 }
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::cli::app};
+
+    fn parse_create_trigger(args: &[&str]) -> Result<Trigger, CliError> {
+        let mut argv = vec![
+            "clockwork",
+            "automation",
+            "create",
+            "--id",
+            "test",
+            "--kickoff_instruction",
+            "ix.json",
+        ];
+        argv.extend_from_slice(args);
+        let matches = app().try_get_matches_from(argv).unwrap();
+        let (_, matches) = matches.subcommand().unwrap();
+        let (_, matches) = matches.subcommand().unwrap();
+        parse_trigger(matches)
+    }
+
+    #[test]
+    fn test_parse_full_account_trigger() {
+        let address = Pubkey::new_unique();
+        let trigger = parse_create_trigger(&[
+            "--account",
+            &address.to_string(),
+            "--account-offset",
+            "8",
+            "--account-size",
+            "2",
+            "--account-predicate",
+            "equals:0x0102",
+            "changed_from:0000",
+        ])
+        .unwrap();
+        assert_eq!(
+            trigger,
+            Trigger::AccountValue {
+                address,
+                offset: 8,
+                size: 2,
+                equals: Some(vec![1, 2]),
+                changed_from: Some(vec![0, 0]),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_account_trigger_defaults() {
+        let address = Pubkey::new_unique();
+        let trigger = parse_create_trigger(&["--account", &address.to_string()]).unwrap();
+        assert_eq!(
+            trigger,
+            Trigger::Account {
+                address,
+                offset: 0,
+                size: 32,
+            }
+        );
+
+        // The size defaults to the predicate's length.
+        let trigger = parse_create_trigger(&[
+            "--account",
+            &address.to_string(),
+            "--account-predicate",
+            "equals:ff",
+        ])
+        .unwrap();
+        assert!(matches!(trigger, Trigger::AccountValue { size: 1, .. }));
+    }
+
+    #[test]
+    fn test_parse_timestamp_trigger() {
+        let trigger = parse_create_trigger(&["--timestamp", "1700000000"]).unwrap();
+        assert_eq!(trigger, Trigger::Timestamp { unix_ts: 1700000000 });
+        assert!(parse_create_trigger(&["--timestamp", "tomorrow"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_account_trigger_rejects_invalid_specs() {
+        let address = Pubkey::new_unique().to_string();
+        let address = address.as_str();
+        for args in [
+            vec!["--account", address, "--account-size", "1025"],
+            vec!["--account", address, "--account-size", "0"],
+            vec!["--account", address, "--account-size", "4", "--account-predicate", "equals:01"],
+            vec!["--account", address, "--account-predicate", "equals:01", "equals:02"],
+            vec!["--account", address, "--account-predicate", "matches:01"],
+            vec!["--account", address, "--account-predicate", "equals:0"],
+        ] {
+            assert!(parse_create_trigger(&args).is_err(), "{:?}", args);
+        }
+
+        // The slice options only apply to account triggers.
+        let argv = [
+            "clockwork",
+            "automation",
+            "create",
+            "--id",
+            "test",
+            "--kickoff_instruction",
+            "ix.json",
+            "--immediate",
+            "--account-size",
+            "8",
+        ];
+        assert!(app().try_get_matches_from(argv).is_err());
+    }
+
+    #[test]
+    fn test_parse_cron_trigger_validates_schedule() {
+        assert_eq!(
+            parse_create_trigger(&["--cron", "0 */5 * * * * *"]).unwrap(),
+            Trigger::Cron {
+                schedule: "0 */5 * * * * *".into(),
+                skippable: true,
+            }
+        );
+        assert!(matches!(
+            parse_create_trigger(&["--cron", "every five minutes"]),
+            Err(CliError::BadParameter(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_epoch_filtered_triggers() {
+        assert_eq!(
+            parse_create_trigger(&["--cron", "0 */5 * * * * *", "--epoch_filter", "even"]).unwrap(),
+            Trigger::EpochFilteredCron {
+                schedule: "0 */5 * * * * *".into(),
+                skippable: true,
+                epoch_filter: EpochFilter::Even,
+            }
+        );
+        assert_eq!(
+            parse_create_trigger(&["--slot", "1000", "--epoch_filter", "every:3"]).unwrap(),
+            Trigger::EpochFilteredSlot {
+                target_slot: 1_000,
+                epoch_filter: EpochFilter::Every(3),
+            }
+        );
+        assert_eq!(
+            parse_create_trigger(&["--slot", "1000"]).unwrap(),
+            Trigger::Slot { target_slot: 1_000 }
+        );
+        assert!(parse_create_trigger(&["--slot", "1000", "--epoch_filter", "every:0"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_crontab_timezone() {
+        let parse = |argv: &[&str]| {
+            let matches = app().try_get_matches_from(argv).unwrap();
+            CliCommand::try_from(&matches)
+        };
+        assert_eq!(
+            parse(&["clockwork", "crontab", "0 0 9 * * * *"]).unwrap(),
+            CliCommand::Crontab {
+                schedule: "0 0 9 * * * *".into(),
+                timezone: None,
+            }
+        );
+        assert_eq!(
+            parse(&["clockwork", "crontab", "0 0 9 * * * *", "--timezone", "America/New_York"])
+                .unwrap(),
+            CliCommand::Crontab {
+                schedule: "0 0 9 * * * *".into(),
+                timezone: Some(Tz::America__New_York),
+            }
+        );
+        assert!(
+            parse(&["clockwork", "crontab", "0 0 9 * * * *", "--timezone", "Mars/Olympus"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_parse_delegation_create_native() {
+        let parse = |argv: &[&str]| {
+            let matches = app().try_get_matches_from(argv).unwrap();
+            CliCommand::try_from(&matches).unwrap()
+        };
+        assert_eq!(
+            parse(&["clockwork", "delegation", "create", "--worker_id", "2"]),
+            CliCommand::DelegationCreate {
+                native: false,
+                worker_id: 2,
+            }
+        );
+        assert_eq!(
+            parse(&["clockwork", "delegation", "create", "--worker_id", "2", "--native"]),
+            CliCommand::DelegationCreate {
+                native: true,
+                worker_id: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_output_format() {
+        let parse = |argv: &[&str]| {
+            let matches = app().try_get_matches_from(argv)?;
+            Ok::<_, clap::Error>(CliCommand::try_from(&matches).unwrap())
+        };
+        assert_eq!(
+            parse(&["clockwork", "pool", "get", "0"]).unwrap(),
+            CliCommand::PoolGet {
+                id: 0,
+                output: OutputFormat::Display,
+            }
+        );
+        assert_eq!(
+            parse(&["clockwork", "pool", "get", "0", "--output", "json"]).unwrap(),
+            CliCommand::PoolGet {
+                id: 0,
+                output: OutputFormat::Json,
+            }
+        );
+        assert!(parse(&["clockwork", "pool", "get", "0", "--output", "yaml"]).is_err());
+        assert!(parse(&["clockwork", "config", "get", "--watch", "--output", "json"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_registry_get_watch() {
+        let parse = |argv: &[&str]| {
+            let matches = app().try_get_matches_from(argv).unwrap();
+            CliCommand::try_from(&matches).unwrap()
+        };
+        assert_eq!(
+            parse(&["clockwork", "registry", "get"]),
+            CliCommand::RegistryGet { watch: false }
+        );
+        assert_eq!(
+            parse(&["clockwork", "registry", "get", "--watch"]),
+            CliCommand::RegistryGet { watch: true }
+        );
+    }
+
+    #[test]
+    fn test_parse_automation_clone() {
+        let parse = |argv: &[&str]| {
+            let matches = app().try_get_matches_from(argv).unwrap();
+            CliCommand::try_from(&matches)
+        };
+        assert_eq!(
+            parse(&["clockwork", "automation", "clone", "crank", "crank-2"]).unwrap(),
+            CliCommand::AutomationClone {
+                source_id: "crank".into(),
+                new_id: "crank-2".into(),
+                kickoff_instruction: None,
+                rate_limit: None,
+                schedule: None,
+            }
+        );
+        assert_eq!(
+            parse(&[
+                "clockwork",
+                "automation",
+                "clone",
+                "crank",
+                "crank-2",
+                "--rate_limit",
+                "5",
+                "--schedule",
+                "0 */5 * * * * *",
+            ])
+            .unwrap(),
+            CliCommand::AutomationClone {
+                source_id: "crank".into(),
+                new_id: "crank-2".into(),
+                kickoff_instruction: None,
+                rate_limit: Some(5),
+                schedule: Some("0 */5 * * * * *".into()),
+            }
+        );
+        assert!(parse(&[
+            "clockwork",
+            "automation",
+            "clone",
+            "crank",
+            "crank-2",
+            "--schedule",
+            "every five minutes",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_parse_automation_update_lifetime_spend_cap() {
+        let parse_cap = |args: &[&str]| {
+            let mut argv = vec!["clockwork", "automation", "update", "crank"];
+            argv.extend_from_slice(args);
+            let matches = app().try_get_matches_from(argv).unwrap();
+            match CliCommand::try_from(&matches).unwrap() {
+                CliCommand::AutomationUpdate {
+                    lifetime_spend_cap, ..
+                } => lifetime_spend_cap,
+                command => panic!("unexpected command: {:?}", command),
+            }
+        };
+        assert_eq!(parse_cap(&[]), None);
+        assert_eq!(
+            parse_cap(&["--lifetime_spend_cap", "1000000"]),
+            Some(Some(1_000_000))
+        );
+        assert_eq!(parse_cap(&["--clear_lifetime_spend_cap"]), Some(None));
+        assert!(app()
+            .try_get_matches_from([
+                "clockwork",
+                "automation",
+                "update",
+                "crank",
+                "--lifetime_spend_cap",
+                "1000000",
+                "--clear_lifetime_spend_cap",
+            ])
+            .is_err());
+    }
+
+    #[test]
+    fn test_parse_automation_update_max_executions() {
+        let parse_max_executions = |args: &[&str]| {
+            let mut argv = vec!["clockwork", "automation", "update", "crank"];
+            argv.extend_from_slice(args);
+            let matches = app().try_get_matches_from(argv).unwrap();
+            match CliCommand::try_from(&matches).unwrap() {
+                CliCommand::AutomationUpdate { max_executions, .. } => max_executions,
+                command => panic!("unexpected command: {:?}", command),
+            }
+        };
+        assert_eq!(parse_max_executions(&[]), None);
+        assert_eq!(parse_max_executions(&["--max-executions", "10"]), Some(Some(10)));
+        assert_eq!(parse_max_executions(&["--clear-max-executions"]), Some(None));
+    }
+}
@@ -1,11 +1,27 @@
 use {
-    crate::errors::CliError,
+    crate::{
+        errors::CliError,
+        output::{automation_json, print_json, OutputFormat},
+    },
+    chrono::{DateTime, NaiveDateTime, Utc},
     clockwork_client::{
-        automation::state::{Automation, AutomationSettings, InstructionData, Trigger},
-        Client,
+        automation::{
+            state::{Automation, AutomationSettings, InstructionData, Trigger},
+            minimum_exec_balance, AutomationOverrides, AutomationSchedule,
+            AUTOMATION_AUTHORITY_OFFSET, FUNDING_MARGIN_PERCENT,
+        },
+        network::state::Config,
+        Client, MAX_PAGE_SIZE,
     },
     clockwork_utils::CrateInfo,
-    solana_sdk::pubkey::Pubkey,
+    solana_client::{
+        rpc_client::{GetConfirmedSignaturesForAddress2Config, RpcClient},
+        rpc_config::{RpcSimulateTransactionConfig, RpcTransactionConfig},
+        rpc_filter::{Memcmp, RpcFilterType},
+    },
+    solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Signature},
+    solana_transaction_status::{EncodedConfirmedTransactionWithStatusMeta, UiTransactionEncoding},
+    std::str::FromStr,
 };
 
 pub fn crate_info(client: &Client) -> Result<(), CliError> {
@@ -15,12 +31,26 @@ pub fn crate_info(client: &Client) -> Result<(), CliError> {
     Ok(())
 }
 
+pub fn instruction_schema() -> Result<(), CliError> {
+    let schema = crate::parser::instruction_schema();
+    println!("{}", serde_json::to_string_pretty(&schema).unwrap());
+    Ok(())
+}
+
 pub fn create(
     client: &Client,
     id: String,
+    description: Option<String>,
     instructions: Vec<InstructionData>,
+    max_executions: Option<u64>,
     trigger: Trigger,
 ) -> Result<(), CliError> {
+    // Get the network config to look up the creation fee.
+    let config = client
+        .get::<Config>(&Config::pubkey())
+        .map_err(|_err| CliError::AccountNotFound(Config::pubkey().to_string()))?;
+    println!("Creation fee: {} lamports", config.creation_fee);
+
     let automation_pubkey = Automation::pubkey(client.payer_pubkey(), id.clone().into_bytes());
     let ix = clockwork_client::automation::instruction::automation_create(
         0,
@@ -29,10 +59,90 @@ pub fn create(
         instructions,
         client.payer_pubkey(),
         automation_pubkey,
+        config.treasury,
         trigger,
     );
-    client.send_and_confirm(&[ix], &[client.payer()]).unwrap();
-    get(client, automation_pubkey)?;
+
+    // Set the description and max executions in the same transaction, since they are not arguments of the create instruction.
+    let mut ixs = vec![ix];
+    if description.is_some() || max_executions.is_some() {
+        ixs.push(clockwork_client::automation::instruction::automation_update(
+            client.payer_pubkey(),
+            automation_pubkey,
+            AutomationSettings {
+                description,
+                exec_mode: None,
+                fee: None,
+                instructions: None,
+                lifetime_spend_cap: None,
+                max_chain_depth: None,
+                max_executions: max_executions.map(Some),
+                max_simulation_failures: None,
+                name: None,
+                rate_limit: None,
+                trigger: None,
+            },
+        ));
+    }
+    client.submit_batch(ixs, client.payer()).unwrap();
+    get(client, automation_pubkey, OutputFormat::Display)?;
+    Ok(())
+}
+
+pub fn clone(
+    client: &Client,
+    source_id: String,
+    new_id: String,
+    kickoff_instruction: Option<InstructionData>,
+    rate_limit: Option<u64>,
+    schedule: Option<String>,
+) -> Result<(), CliError> {
+    // A new schedule keeps the source's other cron settings, if it was triggered by cron.
+    let source_pubkey = Automation::pubkey(client.payer_pubkey(), source_id.clone().into_bytes());
+    let trigger = match schedule {
+        None => None,
+        Some(schedule) => {
+            let source = client
+                .get::<Automation>(&source_pubkey)
+                .map_err(|_err| CliError::AccountNotFound(source_pubkey.to_string()))?;
+            Some(match source.trigger {
+                Trigger::Cron { skippable, .. } => Trigger::Cron {
+                    schedule,
+                    skippable,
+                },
+                Trigger::EpochFilteredCron {
+                    skippable,
+                    epoch_filter,
+                    ..
+                } => Trigger::EpochFilteredCron {
+                    schedule,
+                    skippable,
+                    epoch_filter,
+                },
+                _ => Trigger::Cron {
+                    schedule,
+                    skippable: true,
+                },
+            })
+        }
+    };
+
+    let automation_pubkey = Automation::pubkey(client.payer_pubkey(), new_id.clone().into_bytes());
+    let ixs = client
+        .clone_automation(
+            source_id.into_bytes(),
+            new_id.into_bytes(),
+            AutomationOverrides {
+                kickoff_instruction,
+                rate_limit,
+                trigger,
+            },
+        )
+        .map_err(|err| CliError::BadClient(err.to_string()))?;
+    client
+        .submit_batch(ixs, client.payer())
+        .map_err(|err| CliError::BadClient(err.to_string()))?;
+    get(client, automation_pubkey, OutputFormat::Display)?;
     Ok(())
 }
 
@@ -47,11 +157,180 @@ pub fn delete(client: &Client, id: String) -> Result<(), CliError> {
     Ok(())
 }
 
-pub fn get(client: &Client, address: Pubkey) -> Result<(), CliError> {
+pub fn estimate(
+    client: &Client,
+    cron: Option<String>,
+    executions: Option<u64>,
+    horizon: i64,
+    kickoff_instruction: InstructionData,
+    worker_id: u64,
+) -> Result<(), CliError> {
+    let trigger = match cron {
+        Some(schedule) => Trigger::Cron {
+            schedule,
+            skippable: true,
+        },
+        None => Trigger::Immediate,
+    };
+    let estimate = client
+        .estimate_automation_funding(kickoff_instruction, &trigger, horizon, worker_id)
+        .map_err(|err| CliError::BadClient(err.to_string()))?;
+    let executions = executions
+        .or(estimate.executions)
+        .ok_or(CliError::BadParameter("cron".into()))?;
+    println!("Compute units: {:?}", estimate.units_consumed);
+    println!("Cost per execution: {} lamports", estimate.cost_per_execution);
+    println!("Executions: {}", executions);
+    println!(
+        "Recommended funding: {} lamports (includes a {}% margin)",
+        estimate.recommended_lamports_for(executions),
+        FUNDING_MARGIN_PERCENT
+    );
+    Ok(())
+}
+
+pub fn list(client: &Client, authority: Option<Pubkey>) -> Result<(), CliError> {
+    let authority = authority.unwrap_or(client.payer_pubkey());
+    let mut automations = client
+        .get_automations_by_authority(authority)
+        .map_err(|err| CliError::BadClient(err.to_string()))?;
+    automations.sort_by(|(_, a), (_, b)| a.id.cmp(&b.id));
+    for (automation_pubkey, automation) in automations.iter() {
+        println!(
+            "{} {} {:?}{}",
+            automation_pubkey,
+            String::from_utf8_lossy(&automation.id),
+            automation.trigger,
+            if automation.paused { " (paused)" } else { "" }
+        );
+    }
+    println!("{} automations", automations.len());
+    Ok(())
+}
+
+pub fn get(client: &Client, address: Pubkey, output: OutputFormat) -> Result<(), CliError> {
     let automation = client
         .get::<Automation>(&address)
         .map_err(|_err| CliError::AccountDataNotParsable(address.to_string()))?;
+    if output.eq(&OutputFormat::Json) {
+        print_json(&automation_json(address, &automation));
+        return Ok(());
+    }
     println!("Address: {}\n{:#?}", address, automation);
+    if !automation.description.is_empty() {
+        println!("Description: {}", automation.description);
+    }
+
+    // Accounts created before the creation clock was recorded have a zeroed timestamp.
+    match (automation.created_at.unix_timestamp, client.get_clock()) {
+        (0, _) | (_, Err(_)) => println!("Created: unknown"),
+        (created_at, Ok(clock)) => println!(
+            "Created: slot {} ({} ago)",
+            automation.created_at.slot,
+            format_age(clock.unix_timestamp.saturating_sub(created_at))
+        ),
+    }
+    if let Ok(clock) = client.get_clock() {
+        if let Some(next_execution_time) = automation.next_execution_time(clock.unix_timestamp) {
+            let datetime = DateTime::<Utc>::from_utc(
+                NaiveDateTime::from_timestamp(next_execution_time, 0),
+                Utc,
+            );
+            let delta = next_execution_time.saturating_sub(clock.unix_timestamp);
+            println!(
+                "Next execution: {} ({}){}",
+                datetime.to_rfc3339(),
+                if delta >= 0 {
+                    format!("in {}", format_age(delta))
+                } else {
+                    format!("{} ago", format_age(delta.saturating_neg()))
+                },
+                if automation.paused { ", paused" } else { "" }
+            );
+        }
+    }
+    if let Trigger::Account { address, .. } | Trigger::AccountValue { address, .. } =
+        automation.trigger
+    {
+        if client.get_account(&address).is_err() {
+            println!(
+                "Monitored account: {} (closed, the trigger will not fire until it is recreated)",
+                address
+            );
+        }
+    }
+    match automation.lifetime_spend_cap {
+        None => println!("Lifetime spent: {} lamports", automation.lifetime_spent),
+        Some(lifetime_spend_cap) => println!(
+            "Lifetime spent: {} / {} lamports",
+            automation.lifetime_spent, lifetime_spend_cap
+        ),
+    }
+    if let Ok(account) = client.get_account(&address) {
+        println!("Balance: {} lamports", account.lamports);
+
+        // Workers hold back an automation that cannot pay for its next exec until it is topped up.
+        if let Ok(rent_exempt_minimum) =
+            client.get_minimum_balance_for_rent_exemption(account.data.len())
+        {
+            let minimum_balance = minimum_exec_balance(automation.fee, rent_exempt_minimum);
+            if account.lamports < minimum_balance {
+                println!(
+                    "Funding: paused until topped up to at least {} lamports",
+                    minimum_balance
+                );
+            }
+        }
+    }
+    match automation.max_executions {
+        None => println!("Executions: {}", automation.execution_count),
+        Some(max_executions) => println!(
+            "Executions: {} / {}",
+            automation.execution_count, max_executions
+        ),
+    }
+    Ok(())
+}
+
+/// Formats a duration in seconds as its largest whole unit, e.g. "3 days".
+fn format_age(seconds: i64) -> String {
+    let (value, unit) = match seconds.max(0) {
+        s if s >= 86_400 => (s / 86_400, "day"),
+        s if s >= 3_600 => (s / 3_600, "hour"),
+        s if s >= 60 => (s / 60, "minute"),
+        s => (s, "second"),
+    };
+    match value {
+        1 => format!("1 {}", unit),
+        _ => format!("{} {}s", value, unit),
+    }
+}
+
+pub fn logs(client: &Client, id: String, limit: usize, failed_only: bool) -> Result<(), CliError> {
+    let automation_pubkey = Automation::pubkey(client.payer_pubkey(), id.into_bytes());
+    let transactions = client
+        .get_automation_transactions(&automation_pubkey, limit, failed_only)
+        .map_err(|err| CliError::BadClient(err.to_string()))?;
+    for tx in transactions {
+        println!("Signature: {}", tx.signature);
+        println!("Slot: {}", tx.slot);
+        match tx.err {
+            None => println!("Status: Ok"),
+            Some(err) => println!("Status: {:?}", err),
+        }
+        println!("Logs: {:#?}\n", tx.logs);
+    }
+    Ok(())
+}
+
+pub fn migrate(client: &Client, id: String) -> Result<(), CliError> {
+    let automation_pubkey = Automation::pubkey(client.payer_pubkey(), id.into_bytes());
+    let ix = clockwork_client::automation::instruction::automation_migrate(
+        client.payer_pubkey(),
+        automation_pubkey,
+    );
+    client.send_and_confirm(&[ix], &[client.payer()]).unwrap();
+    get(client, automation_pubkey, OutputFormat::Display)?;
     Ok(())
 }
 
@@ -62,7 +341,7 @@ pub fn pause(client: &Client, id: String) -> Result<(), CliError> {
         automation_pubkey,
     );
     client.send_and_confirm(&[ix], &[client.payer()]).unwrap();
-    get(client, automation_pubkey)?;
+    get(client, automation_pubkey, OutputFormat::Display)?;
     Ok(())
 }
 
@@ -73,10 +352,183 @@ pub fn resume(client: &Client, id: String) -> Result<(), CliError> {
         automation_pubkey,
     );
     client.send_and_confirm(&[ix], &[client.payer()]).unwrap();
-    get(client, automation_pubkey)?;
+    get(client, automation_pubkey, OutputFormat::Display)?;
+    Ok(())
+}
+
+pub fn replay(
+    client: &Client,
+    signature: Signature,
+    rpc_url: Option<String>,
+) -> Result<(), CliError> {
+    // Fetch the transaction, optionally from an archival RPC endpoint.
+    let archival_client = rpc_url.map(RpcClient::new);
+    let rpc_client: &RpcClient = match archival_client.as_ref() {
+        Some(archival_client) => archival_client,
+        None => client,
+    };
+    let confirmed_tx = rpc_client
+        .get_transaction_with_config(
+            &signature,
+            RpcTransactionConfig {
+                encoding: Some(UiTransactionEncoding::Base64),
+                commitment: Some(CommitmentConfig::confirmed()),
+                max_supported_transaction_version: Some(0),
+            },
+        )
+        .map_err(|_err| CliError::TransactionNotFound(signature.to_string()))?;
+    let tx = confirmed_tx
+        .transaction
+        .transaction
+        .decode()
+        .ok_or(CliError::TransactionNotFound(signature.to_string()))?;
+
+    // Print the original execution's outcome.
+    println!("Slot: {}", confirmed_tx.slot);
+    if let Some(meta) = confirmed_tx.transaction.meta {
+        let logs: Option<Vec<String>> = meta.log_messages.into();
+        println!("Original status: {:?}", meta.status);
+        println!("Original logs: {:#?}", logs.unwrap_or_default());
+    }
+
+    // Re-simulate the transaction.
+    // RPC nodes only simulate against their latest state, so the account state at the original slot is not reproduced.
+    println!(
+        "Simulating against the cluster's latest state (account state at slot {} is not available)",
+        confirmed_tx.slot
+    );
+    let response = client
+        .simulate_transaction_with_config(
+            &tx,
+            RpcSimulateTransactionConfig {
+                sig_verify: false,
+                replace_recent_blockhash: true,
+                commitment: Some(CommitmentConfig::processed()),
+                ..RpcSimulateTransactionConfig::default()
+            },
+        )
+        .map_err(|err| CliError::FailedTransaction(err.to_string()))?;
+    println!("Replay error: {:?}", response.value.err);
+    println!("Replay logs: {:#?}", response.value.logs.unwrap_or_default());
+    println!("Compute units: {:?}", response.value.units_consumed);
     Ok(())
 }
 
+pub fn cost_report(
+    client: &Client,
+    authority: Option<Pubkey>,
+    since: Option<u64>,
+) -> Result<(), CliError> {
+    // Page through the automations owned by the authority rather than loading them all at once.
+    let authority = authority.unwrap_or(client.payer_pubkey());
+    let pages = client
+        .get_program_accounts_paged::<Automation>(
+            &clockwork_client::automation::ID,
+            vec![RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+                AUTOMATION_AUTHORITY_OFFSET,
+                authority.to_bytes().to_vec(),
+            ))],
+            MAX_PAGE_SIZE,
+        )
+        .map_err(|err| CliError::BadClient(err.to_string()))?;
+
+    // Sum the lamports each automation spent across its transactions in the window.
+    let mut total_lamports = 0;
+    for page in pages {
+        let page = page.map_err(|err| CliError::BadClient(err.to_string()))?;
+        for (automation_pubkey, automation) in page {
+            let mut tx_count = 0;
+            let mut lamports = 0;
+            for signature in get_signatures_since(client, &automation_pubkey, since)? {
+                let tx = client
+                    .get_transaction_with_config(
+                        &signature,
+                        RpcTransactionConfig {
+                            encoding: Some(UiTransactionEncoding::Base64),
+                            commitment: Some(CommitmentConfig::confirmed()),
+                            max_supported_transaction_version: Some(0),
+                        },
+                    )
+                    .map_err(|_err| CliError::TransactionNotFound(signature.to_string()))?;
+                tx_count += 1;
+                lamports += lamports_spent(&tx, &automation_pubkey);
+            }
+            total_lamports += lamports;
+            println!(
+                "{} id: {} description: {} transactions: {} lamports: {}",
+                automation_pubkey,
+                String::from_utf8_lossy(&automation.id),
+                automation.description,
+                tx_count,
+                lamports
+            );
+        }
+    }
+    match since {
+        Some(since) => println!("Total since slot {}: {} lamports", since, total_lamports),
+        None => println!("Total: {} lamports", total_lamports),
+    }
+    Ok(())
+}
+
+/// Fetch the signatures of successful transactions involving the address, newest first.
+/// Pages back to the given slot, or returns only the most recent page if no slot is given.
+fn get_signatures_since(
+    client: &Client,
+    address: &Pubkey,
+    since: Option<u64>,
+) -> Result<Vec<Signature>, CliError> {
+    let mut signatures = vec![];
+    let mut before = None;
+    loop {
+        let page = client
+            .get_signatures_for_address_with_config(
+                address,
+                GetConfirmedSignaturesForAddress2Config {
+                    before,
+                    until: None,
+                    limit: None,
+                    commitment: Some(CommitmentConfig::confirmed()),
+                },
+            )
+            .map_err(|err| CliError::BadClient(err.to_string()))?;
+        let last_signature = match page.last() {
+            None => break,
+            Some(status) => Signature::from_str(&status.signature).ok(),
+        };
+        let reached_since = since.map_or(true, |since| page.iter().any(|status| status.slot < since));
+        signatures.extend(
+            page.iter()
+                .filter(|status| status.err.is_none())
+                .filter(|status| since.map_or(true, |since| status.slot >= since))
+                .filter_map(|status| Signature::from_str(&status.signature).ok()),
+        );
+        if reached_since || last_signature.is_none() {
+            break;
+        }
+        before = last_signature;
+    }
+    Ok(signatures)
+}
+
+/// The lamports the account lost over the course of the transaction.
+fn lamports_spent(tx: &EncodedConfirmedTransactionWithStatusMeta, account: &Pubkey) -> u64 {
+    let account_index = tx.transaction.transaction.decode().and_then(|decoded_tx| {
+        decoded_tx
+            .message
+            .static_account_keys()
+            .iter()
+            .position(|key| key.eq(account))
+    });
+    match (account_index, tx.transaction.meta.as_ref()) {
+        (Some(i), Some(meta)) => match (meta.pre_balances.get(i), meta.post_balances.get(i)) {
+            (Some(pre), Some(post)) => pre.saturating_sub(*post),
+            _ => 0,
+        },
+        _ => 0,
+    }
+}
+
 pub fn reset(client: &Client, id: String) -> Result<(), CliError> {
     let automation_pubkey = Automation::pubkey(client.payer_pubkey(), id.into_bytes());
     let ix = clockwork_client::automation::instruction::automation_reset(
@@ -84,13 +536,36 @@ pub fn reset(client: &Client, id: String) -> Result<(), CliError> {
         automation_pubkey,
     );
     client.send_and_confirm(&[ix], &[client.payer()]).unwrap();
-    get(client, automation_pubkey)?;
+    get(client, automation_pubkey, OutputFormat::Display)?;
+    Ok(())
+}
+
+pub fn simulate(
+    client: &Client,
+    id: String,
+    kickoff_instruction: InstructionData,
+    worker_id: u64,
+) -> Result<(), CliError> {
+    let simulation = client
+        .simulate_automation(id.into_bytes(), kickoff_instruction, worker_id)
+        .map_err(|err| CliError::BadClient(err.to_string()))?;
+    match simulation.err {
+        None => println!("Status: Ok"),
+        Some(err) => println!("Status: {:?}", err),
+    }
+    println!("Compute units: {:?}", simulation.units_consumed);
+    println!("Logs: {:#?}", simulation.logs);
     Ok(())
 }
 
 pub fn update(
     client: &Client,
     id: String,
+    description: Option<String>,
+    lifetime_spend_cap: Option<Option<u64>>,
+    max_chain_depth: Option<u64>,
+    max_executions: Option<Option<u64>>,
+    max_retries: Option<u32>,
     rate_limit: Option<u64>,
     schedule: Option<String>,
 ) -> Result<(), CliError> {
@@ -104,8 +579,14 @@ pub fn update(
         None
     };
     let settings = AutomationSettings {
+        description,
+        exec_mode: None,
         fee: None,
         instructions: None,
+        lifetime_spend_cap,
+        max_chain_depth,
+        max_executions,
+        max_simulation_failures: max_retries,
         name: None,
         rate_limit,
         trigger,
@@ -116,7 +597,7 @@ pub fn update(
         settings,
     );
     client.send_and_confirm(&[ix], &[client.payer()]).unwrap();
-    get(client, automation_pubkey)?;
+    get(client, automation_pubkey, OutputFormat::Display)?;
     Ok(())
 }
 
@@ -1,25 +1,155 @@
 use {
-    crate::errors::CliError,
+    crate::{
+        config::CliConfig,
+        errors::CliError,
+        output::{config_json, print_json, OutputFormat},
+    },
     clockwork_client::{
         network::state::{Config, ConfigSettings},
         Client,
     },
+    solana_account_decoder::UiAccountEncoding,
+    solana_client::{pubsub_client::PubsubClient, rpc_config::RpcAccountInfoConfig},
     solana_sdk::pubkey::Pubkey,
 };
 
-pub fn get(client: &Client) -> Result<(), CliError> {
+pub fn get(client: &Client, output: OutputFormat) -> Result<(), CliError> {
     let config = client
         .get::<Config>(&Config::pubkey())
         .map_err(|_err| CliError::AccountNotFound(Config::pubkey().to_string()))?;
-    println!("{:#?}", config);
+    match output {
+        OutputFormat::Display => println!("{:#?}", config),
+        OutputFormat::Json => print_json(&config_json(Config::pubkey(), &config)),
+    }
+    Ok(())
+}
+
+pub fn watch(client: &Client, cli_config: CliConfig) -> Result<(), CliError> {
+    // Print the current config as the baseline for diffs.
+    let config_pubkey = Config::pubkey();
+    let mut prev = client
+        .get::<Config>(&config_pubkey)
+        .map_err(|_err| CliError::AccountNotFound(config_pubkey.to_string()))?;
+    println!("{:#?}", prev);
+
+    // Subscribe to changes of the config account.
+    let websocket_url = match cli_config.websocket_url.is_empty() {
+        true => solana_cli_config::Config::compute_websocket_url(&cli_config.json_rpc_url),
+        false => cli_config.websocket_url,
+    };
+    let (_subscription, receiver) = PubsubClient::account_subscribe(
+        &websocket_url,
+        &config_pubkey,
+        Some(RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            commitment: Some(cli_config.commitment),
+            ..RpcAccountInfoConfig::default()
+        }),
+    )
+    .map_err(|err| CliError::BadClient(err.to_string()))?;
+    println!("Watching {} for changes...", config_pubkey);
+
+    // Print the changed fields on every update.
+    for response in receiver {
+        let next = match response.value.data.decode().map(Config::try_from) {
+            Some(Ok(config)) => config,
+            _ => return Err(CliError::AccountDataNotParsable(config_pubkey.to_string())),
+        };
+        let changes = diff(&prev, &next);
+        if !changes.is_empty() {
+            println!("Config changed at slot {}:", response.context.slot);
+            for change in changes {
+                println!("  {}", change);
+            }
+        }
+        prev = next;
+    }
+
+    Ok(())
+}
+
+fn diff(prev: &Config, next: &Config) -> Vec<String> {
+    let mut changes = vec![];
+    if prev.admin.ne(&next.admin) {
+        changes.push(format!("admin: {} -> {}", prev.admin, next.admin));
+    }
+    if prev.creation_fee.ne(&next.creation_fee) {
+        changes.push(format!(
+            "creation_fee: {} -> {}",
+            prev.creation_fee, next.creation_fee
+        ));
+    }
+    if prev.epoch_automation.ne(&next.epoch_automation) {
+        changes.push(format!(
+            "epoch_automation: {} -> {}",
+            prev.epoch_automation, next.epoch_automation
+        ));
+    }
+    if prev.hasher_automation.ne(&next.hasher_automation) {
+        changes.push(format!(
+            "hasher_automation: {} -> {}",
+            prev.hasher_automation, next.hasher_automation
+        ));
+    }
+    if prev.mint.ne(&next.mint) {
+        changes.push(format!("mint: {} -> {}", prev.mint, next.mint));
+    }
+    if prev.pending_admin.ne(&next.pending_admin) {
+        changes.push(format!(
+            "pending_admin: {:?} -> {:?}",
+            prev.pending_admin, next.pending_admin
+        ));
+    }
+    if prev.token_stake_rate.ne(&next.token_stake_rate) {
+        changes.push(format!(
+            "token_stake_rate: {} -> {}",
+            prev.token_stake_rate, next.token_stake_rate
+        ));
+    }
+    if prev.treasury.ne(&next.treasury) {
+        changes.push(format!("treasury: {} -> {}", prev.treasury, next.treasury));
+    }
+    changes
+}
+
+pub fn migrate(client: &Client) -> Result<(), CliError> {
+    let ix = clockwork_client::network::instruction::config_migrate(client.payer_pubkey());
+    client.send_and_confirm(&[ix], &[client.payer()]).unwrap();
+    get(client, OutputFormat::Display)?;
+    Ok(())
+}
+
+pub fn propose_admin(client: &Client, pending_admin: Pubkey) -> Result<(), CliError> {
+    let ix = clockwork_client::network::instruction::config_propose_admin(
+        client.payer_pubkey(),
+        pending_admin,
+    );
+    client.send_and_confirm(&[ix], &[client.payer()]).unwrap();
+    get(client, OutputFormat::Display)?;
+    Ok(())
+}
+
+pub fn accept_admin(client: &Client) -> Result<(), CliError> {
+    let ix = clockwork_client::network::instruction::config_accept_admin(client.payer_pubkey());
+    client.send_and_confirm(&[ix], &[client.payer()]).unwrap();
+    get(client, OutputFormat::Display)?;
+    Ok(())
+}
+
+pub fn cancel_admin(client: &Client) -> Result<(), CliError> {
+    let ix = clockwork_client::network::instruction::config_cancel_admin(client.payer_pubkey());
+    client.send_and_confirm(&[ix], &[client.payer()]).unwrap();
+    get(client, OutputFormat::Display)?;
     Ok(())
 }
 
 pub fn set(
     client: &Client,
-    admin: Option<Pubkey>,
+    creation_fee: Option<u64>,
     epoch_automation: Option<Pubkey>,
     hasher_automation: Option<Pubkey>,
+    token_stake_rate: Option<u64>,
+    treasury: Option<Pubkey>,
 ) -> Result<(), CliError> {
     // Get the current config.
     let config = client
@@ -28,15 +158,17 @@ pub fn set(
 
     // Build new config. settings
     let settings = ConfigSettings {
-        admin: admin.unwrap_or(config.admin),
+        creation_fee: creation_fee.unwrap_or(config.creation_fee),
         epoch_automation: epoch_automation.unwrap_or(config.epoch_automation),
         hasher_automation: hasher_automation.unwrap_or(config.hasher_automation),
         mint: config.mint,
+        token_stake_rate: token_stake_rate.unwrap_or(config.token_stake_rate),
+        treasury: treasury.unwrap_or(config.treasury),
     };
 
     // Submit tx
     let ix = clockwork_client::network::instruction::config_update(client.payer_pubkey(), settings);
     client.send_and_confirm(&[ix], &[client.payer()]).unwrap();
-    get(client)?;
+    get(client, OutputFormat::Display)?;
     Ok(())
 }
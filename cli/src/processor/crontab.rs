@@ -1,21 +1,26 @@
 use {
     crate::errors::CliError,
     chrono::{DateTime, NaiveDateTime, Utc},
+    chrono_tz::Tz,
     clockwork_client::Client,
     clockwork_cron::Schedule,
     std::str::FromStr,
 };
 
-pub fn get(client: &Client, schedule: String) -> Result<(), CliError> {
+pub fn get(client: &Client, schedule: String, timezone: Option<Tz>) -> Result<(), CliError> {
     let clock = client.get_clock().unwrap();
     let schedule = Schedule::from_str(schedule.as_str()).unwrap();
 
+    // Schedules are always evaluated in UTC on chain; a timezone only changes how the firings are shown.
     let mut i = 0;
     for t in schedule.after(&DateTime::<Utc>::from_utc(
         NaiveDateTime::from_timestamp(clock.unix_timestamp, 0),
         Utc,
     )) {
-        println!("{:#?}", t);
+        match timezone {
+            None => println!("{:#?}", t),
+            Some(tz) => println!("{:#?}", t.with_timezone(&tz)),
+        }
         i += 1;
         if i > 8 {
             break;
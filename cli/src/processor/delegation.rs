@@ -1,12 +1,21 @@
 use spl_associated_token_account::get_associated_token_address;
 
 use {
-    crate::errors::CliError,
-    clockwork_client::network::state::{Config, Delegation, Worker},
+    crate::{
+        errors::CliError,
+        output::{delegation_json, print_json, OutputFormat},
+    },
+    clockwork_client::network::state::{
+        Config, Delegation, Pool, Registry, Snapshot, SnapshotFrame, StakeKind, Worker,
+        DELEGATION_UNBONDING_EPOCHS,
+    },
     clockwork_client::Client,
+    solana_sdk::{instruction::Instruction, pubkey::Pubkey, system_instruction},
+    spl_associated_token_account::instruction::create_associated_token_account_idempotent,
+    spl_token::native_mint,
 };
 
-pub fn create(client: &Client, worker_id: u64) -> Result<(), CliError> {
+pub fn create(client: &Client, native: bool, worker_id: u64) -> Result<(), CliError> {
     // Get config data
     let config_pubkey = Config::pubkey();
     let config_data = client
@@ -24,11 +33,17 @@ pub fn create(client: &Client, worker_id: u64) -> Result<(), CliError> {
         .map_err(|_err| CliError::AccountDataNotParsable(worker_pubkey.to_string()))?;
 
     // Build ix
+    let stake_kind = if native {
+        StakeKind::Native
+    } else {
+        StakeKind::Token
+    };
     let delegation_pubkey = Delegation::pubkey(worker_pubkey, worker.total_delegations);
     let ix = clockwork_client::network::instruction::delegation_create(
         client.payer_pubkey(),
         delegation_pubkey,
-        config.mint,
+        stake_kind.mint(&config),
+        stake_kind,
         worker_pubkey,
     );
     client.send_and_confirm(&[ix], &[client.payer()]).unwrap();
@@ -52,18 +67,78 @@ pub fn deposit(
 
     // TODO Map the amount using the mint's decimals.
 
-    // Build ix
+    // Build ixs, wrapping the SOL to deposit into a native delegation.
     let worker_pubkey = Worker::pubkey(worker_id);
     let delegation_pubkey = Delegation::pubkey(worker_pubkey, delegation_id);
-    let ix = clockwork_client::network::instruction::delegation_deposit(
+    let stake_kind = get_stake_kind(client, delegation_pubkey)?;
+    let mut ixs = match stake_kind {
+        StakeKind::Token => vec![],
+        StakeKind::Native => wrap_sol(client.payer_pubkey(), amount)?,
+    };
+    ixs.push(clockwork_client::network::instruction::delegation_deposit(
         amount,
         client.payer_pubkey(),
         delegation_pubkey,
-        config.mint,
+        stake_kind.mint(&config),
+    ));
+    client.send_and_confirm(&ixs, &[client.payer()]).unwrap();
+
+    Ok(())
+}
+
+pub fn migrate(client: &Client, delegation_id: u64, worker_id: u64) -> Result<(), CliError> {
+    let delegation_pubkey = Delegation::pubkey(Worker::pubkey(worker_id), delegation_id);
+    let ix = clockwork_client::network::instruction::delegation_migrate(
+        client.payer_pubkey(),
+        delegation_pubkey,
     );
     client.send_and_confirm(&[ix], &[client.payer()]).unwrap();
+    get(client, delegation_id, OutputFormat::Display, worker_id)
+}
 
-    Ok(())
+pub fn merge(
+    client: &Client,
+    from_id: u64,
+    into_id: u64,
+    worker_id: u64,
+) -> Result<(), CliError> {
+    // Get config and registry data
+    let config_pubkey = Config::pubkey();
+    let config = client
+        .get::<Config>(&config_pubkey)
+        .map_err(|_err| CliError::AccountDataNotParsable(config_pubkey.to_string()))?;
+    let registry_pubkey = Registry::pubkey();
+    let registry = client
+        .get::<Registry>(&registry_pubkey)
+        .map_err(|_err| CliError::AccountDataNotParsable(registry_pubkey.to_string()))?;
+
+    // Build ix
+    let worker_pubkey = Worker::pubkey(worker_id);
+    let source_pubkey = Delegation::pubkey(worker_pubkey, from_id);
+    let stake_kind = get_stake_kind(client, source_pubkey)?;
+    let ix = clockwork_client::network::instruction::delegation_merge(
+        client.payer_pubkey(),
+        Delegation::pubkey(worker_pubkey, into_id),
+        stake_kind.mint(&config),
+        SnapshotFrame::pubkey(Snapshot::pubkey(registry.current_epoch), worker_id),
+        source_pubkey,
+        worker_pubkey,
+    );
+    client.send_and_confirm(&[ix], &[client.payer()]).unwrap();
+
+    // Report whether the source delegation could be closed.
+    if client.get_account_data(&source_pubkey).is_ok() {
+        println!(
+            "Delegation {} was merged into delegation {}, but remains open with an empty balance. Only a worker's newest delegation, created after the current snapshot, can be closed.",
+            from_id, into_id
+        );
+    } else {
+        println!(
+            "Delegation {} was merged into delegation {} and closed",
+            from_id, into_id
+        );
+    }
+    get(client, into_id, OutputFormat::Display, worker_id)
 }
 
 pub fn withdraw(
@@ -82,21 +157,109 @@ pub fn withdraw(
 
     // TODO Map the amount using the mint's decimals.
 
+    // Build ixs
+    let worker_pubkey = Worker::pubkey(worker_id);
+    let delegation_pubkey = Delegation::pubkey(worker_pubkey, delegation_id);
+    let stake_kind = get_stake_kind(client, delegation_pubkey)?;
+    let mut ixs = match stake_kind {
+        StakeKind::Token => vec![],
+        StakeKind::Native => vec![create_wrapped_sol_account(client.payer_pubkey())],
+    };
+    ixs.push(clockwork_client::network::instruction::delegation_withdraw(
+        amount,
+        client.payer_pubkey(),
+        delegation_pubkey,
+        stake_kind.mint(&config),
+    ));
+    client.send_and_confirm(&ixs, &[client.payer()]).unwrap();
+    print_wrapped_sol_notice(stake_kind);
+
+    Ok(())
+}
+
+pub fn withdraw_request(
+    client: &Client,
+    amount: u64,
+    delegation_id: u64,
+    worker_id: u64,
+) -> Result<(), CliError> {
     // Build ix
     let worker_pubkey = Worker::pubkey(worker_id);
     let delegation_pubkey = Delegation::pubkey(worker_pubkey, delegation_id);
-    let ix = clockwork_client::network::instruction::delegation_withdraw(
+    let ix = clockwork_client::network::instruction::delegation_withdraw_request(
         amount,
         client.payer_pubkey(),
         delegation_pubkey,
-        config.mint,
     );
     client.send_and_confirm(&[ix], &[client.payer()]).unwrap();
+    get(client, delegation_id, OutputFormat::Display, worker_id)
+}
 
+pub fn withdraw_claim(client: &Client, delegation_id: u64, worker_id: u64) -> Result<(), CliError> {
+    // Get config data
+    let config_pubkey = Config::pubkey();
+    let config = client
+        .get::<Config>(&config_pubkey)
+        .map_err(|_err| CliError::AccountDataNotParsable(config_pubkey.to_string()))?;
+
+    // Build ixs
+    let worker_pubkey = Worker::pubkey(worker_id);
+    let delegation_pubkey = Delegation::pubkey(worker_pubkey, delegation_id);
+    let stake_kind = get_stake_kind(client, delegation_pubkey)?;
+    let mut ixs = match stake_kind {
+        StakeKind::Token => vec![],
+        StakeKind::Native => vec![create_wrapped_sol_account(client.payer_pubkey())],
+    };
+    ixs.push(
+        clockwork_client::network::instruction::delegation_withdraw_claim(
+            client.payer_pubkey(),
+            delegation_pubkey,
+            stake_kind.mint(&config),
+        ),
+    );
+    client.send_and_confirm(&ixs, &[client.payer()]).unwrap();
+    print_wrapped_sol_notice(stake_kind);
+    get(client, delegation_id, OutputFormat::Display, worker_id)
+}
+
+pub fn list(client: &Client, worker_id: u64) -> Result<(), CliError> {
+    let delegations = client
+        .get_delegations_by_worker(worker_id)
+        .map_err(|err| CliError::BadClient(err.to_string()))?;
+
+    println!(
+        "{:<6} {:<44} {:<8} {:<14} {:<18} {}",
+        "ID", "Authority", "Kind", "Stake", "Pending withdrawal", "Unbonding"
+    );
+    for (_delegation_pubkey, delegation) in delegations {
+        let unbonding = if delegation.unbonding_amount.gt(&0) {
+            format!(
+                "{} (claimable from epoch {})",
+                delegation.unbonding_amount,
+                delegation.withdrawal_epoch + DELEGATION_UNBONDING_EPOCHS
+            )
+        } else {
+            "0".into()
+        };
+        println!(
+            "{:<6} {:<44} {:<8} {:<14} {:<18} {}",
+            delegation.id,
+            delegation.authority.to_string(),
+            format!("{:?}", delegation.stake_kind),
+            delegation.stake_amount,
+            delegation.pending_withdrawal,
+            unbonding
+        );
+    }
     Ok(())
 }
 
-pub fn get(client: &Client, delegation_id: u64, worker_id: u64) -> Result<(), CliError> {
+pub fn get(
+    client: &Client,
+    delegation_id: u64,
+    output: OutputFormat,
+    worker_id: u64,
+) -> Result<(), CliError> {
     // Get config account
     let config_pubkey = Config::pubkey();
     let config_data = client
@@ -115,15 +278,104 @@ pub fn get(client: &Client, delegation_id: u64, worker_id: u64) -> Result<(), Cl
         .map_err(|_err| CliError::AccountDataNotParsable(delegation_pubkey.to_string()))?;
 
     // Get the delegation's token account.
-    let delegation_tokens_pubkey = get_associated_token_address(&delegation_pubkey, &config.mint);
+    let delegation_tokens_pubkey =
+        get_associated_token_address(&delegation_pubkey, &delegation.stake_kind.mint(&config));
     let token_balance = client
         .get_token_account_balance(&delegation_tokens_pubkey)
         .map_err(|_err| CliError::AccountDataNotParsable(delegation_pubkey.to_string()))?;
 
+    if output.eq(&OutputFormat::Json) {
+        let mut value = delegation_json(delegation_pubkey, &delegation);
+        value["liquid_balance"] = token_balance.ui_amount_string.into();
+        print_json(&value);
+        return Ok(());
+    }
+
     println!(
         "Address: {}\n{:#?}\nLiquid balance: {}",
         delegation_pubkey, delegation, token_balance.ui_amount_string
     );
 
+    // Report the progress of an outstanding withdrawal.
+    if delegation.pending_withdrawal.gt(&0) {
+        println!(
+            "Withdrawal: {} tokens will be unstaked at the end of epoch {}",
+            delegation.pending_withdrawal, delegation.withdrawal_epoch
+        );
+    } else if delegation.unbonding_amount.gt(&0) {
+        println!(
+            "Withdrawal: {} tokens unbonding, claimable from epoch {}",
+            delegation.unbonding_amount,
+            delegation.withdrawal_epoch + DELEGATION_UNBONDING_EPOCHS
+        );
+    }
+
+    // Warn if the stake is delegated to a worker that is no longer participating in the network.
+    if delegation.stake_amount.gt(&0) && !is_worker_active(client, worker_id)? {
+        println!(
+            "Warning: worker {} is not in any pool or in the current snapshot. This delegation's stake appears stranded and is not earning fees. Consider withdrawing and delegating to an active worker.",
+            worker_id
+        );
+    }
+
     Ok(())
 }
+
+/// Returns true if the worker is a member of a pool or has a frame in the current epoch's snapshot.
+fn is_worker_active(client: &Client, worker_id: u64) -> Result<bool, CliError> {
+    let registry_pubkey = Registry::pubkey();
+    let registry = client
+        .get::<Registry>(&registry_pubkey)
+        .map_err(|_err| CliError::AccountDataNotParsable(registry_pubkey.to_string()))?;
+
+    // Check if the worker is in any pool.
+    let worker_pubkey = Worker::pubkey(worker_id);
+    for pool_id in 0..registry.total_pools {
+        if let Ok(pool) = client.get::<Pool>(&Pool::pubkey(pool_id)) {
+            if pool.workers.contains(&worker_pubkey) {
+                return Ok(true);
+            }
+        }
+    }
+
+    // Check if the worker has stake in the current snapshot.
+    let snapshot_pubkey = Snapshot::pubkey(registry.current_epoch);
+    let snapshot_frame_pubkey = SnapshotFrame::pubkey(snapshot_pubkey, worker_id);
+    Ok(client
+        .get::<SnapshotFrame>(&snapshot_frame_pubkey)
+        .map_or(false, |snapshot_frame| {
+            snapshot_frame.worker.eq(&worker_pubkey) && snapshot_frame.stake_amount.gt(&0)
+        }))
+}
+
+fn get_stake_kind(client: &Client, delegation_pubkey: Pubkey) -> Result<StakeKind, CliError> {
+    client
+        .get::<Delegation>(&delegation_pubkey)
+        .map(|delegation| delegation.stake_kind)
+        .map_err(|_err| CliError::AccountDataNotParsable(delegation_pubkey.to_string()))
+}
+
+fn create_wrapped_sol_account(authority: Pubkey) -> Instruction {
+    create_associated_token_account_idempotent(
+        &authority,
+        &authority,
+        &native_mint::id(),
+        &spl_token::id(),
+    )
+}
+
+fn wrap_sol(authority: Pubkey, amount: u64) -> Result<Vec<Instruction>, CliError> {
+    let wrapped_sol_pubkey = get_associated_token_address(&authority, &native_mint::id());
+    Ok(vec![
+        create_wrapped_sol_account(authority),
+        system_instruction::transfer(&authority, &wrapped_sol_pubkey, amount),
+        spl_token::instruction::sync_native(&spl_token::id(), &wrapped_sol_pubkey)
+            .map_err(|err| CliError::BadClient(err.to_string()))?,
+    ])
+}
+
+fn print_wrapped_sol_notice(stake_kind: StakeKind) {
+    if stake_kind.eq(&StakeKind::Native) {
+        println!("Withdrawn SOL is held as wrapped SOL in your associated token account. Unwrap it with `spl-token unwrap`.");
+    }
+}
@@ -176,6 +176,7 @@ fn create_automations(client: &Client, mint_pubkey: Pubkey) -> Result<()> {
         ],
         client.payer_pubkey(),
         epoch_automation_pubkey,
+        client.payer_pubkey(),
         Trigger::Cron {
             schedule: "0 * * * * * *".into(),
             skippable: true,
@@ -195,6 +196,7 @@ fn create_automations(client: &Client, mint_pubkey: Pubkey) -> Result<()> {
         ],
         client.payer_pubkey(),
         hasher_automation_pubkey,
+        client.payer_pubkey(),
         Trigger::Cron {
             schedule: "*/15 * * * * * *".into(),
             skippable: true,
@@ -205,10 +207,12 @@ fn create_automations(client: &Client, mint_pubkey: Pubkey) -> Result<()> {
     let ix_c = clockwork_client::network::instruction::config_update(
         client.payer_pubkey(),
         ConfigSettings {
-            admin: client.payer_pubkey(),
+            creation_fee: 0,
             epoch_automation: epoch_automation_pubkey,
             hasher_automation: hasher_automation_pubkey,
             mint: mint_pubkey,
+            token_stake_rate: 1,
+            treasury: client.payer_pubkey(),
         },
     );
 
@@ -5,9 +5,13 @@ mod delegation;
 mod explorer;
 mod initialize;
 mod localnet;
+mod network;
 mod pool;
 mod process;
+mod profile;
+mod program;
 mod registry;
+mod snapshot;
 mod automation;
 mod webhook;
 mod worker;
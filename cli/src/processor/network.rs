@@ -0,0 +1,137 @@
+use {
+    crate::errors::CliError,
+    anchor_lang::AccountDeserialize,
+    clockwork_client::{
+        automation::state::Automation,
+        network::{
+            state::{Fee, Registry, Snapshot, Worker},
+            PipelineStage, REGISTRY_LOCK_STALL_THRESHOLD,
+        },
+        Client, MAX_PAGE_SIZE,
+    },
+    solana_sdk::{account::Account, pubkey::Pubkey},
+};
+
+/// The max number of accounts that may be requested in a single `getMultipleAccounts` call.
+const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+pub fn stats(client: &Client) -> Result<(), CliError> {
+    // Get registry
+    let registry_pubkey = Registry::pubkey();
+    let registry = client
+        .get::<Registry>(&registry_pubkey)
+        .map_err(|_err| CliError::AccountDataNotParsable(registry_pubkey.to_string()))?;
+
+    // Get the current snapshot for the total delegated stake.
+    let snapshot_pubkey = Snapshot::pubkey(registry.current_epoch);
+    let snapshot = client
+        .get::<Snapshot>(&snapshot_pubkey)
+        .map_err(|_err| CliError::AccountDataNotParsable(snapshot_pubkey.to_string()))?;
+
+    // Batch fetch the workers and their fee accounts.
+    let worker_pubkeys = (0..registry.total_workers)
+        .map(|id| Worker::pubkey(id))
+        .collect::<Vec<Pubkey>>();
+    let fee_pubkeys = worker_pubkeys
+        .iter()
+        .map(|worker_pubkey| Fee::pubkey(*worker_pubkey))
+        .collect::<Vec<Pubkey>>();
+    let workers = get_multiple::<Worker>(client, &worker_pubkeys)?;
+    let fees = get_multiple::<Fee>(client, &fee_pubkeys)?;
+    let total_commission_balance: u64 = workers
+        .iter()
+        .map(|worker| worker.commission_balance)
+        .sum();
+    let total_distributable_fees: u64 = fees.iter().map(|fee| fee.distributable_balance).sum();
+
+    // Count the active automations, a page at a time.
+    let mut total_automations = 0;
+    let mut active_automations = 0;
+    for page in client
+        .get_program_accounts_paged::<Automation>(
+            &clockwork_client::automation::ID,
+            vec![],
+            MAX_PAGE_SIZE,
+        )
+        .map_err(|err| CliError::BadClient(err.to_string()))?
+    {
+        let page = page.map_err(|err| CliError::BadClient(err.to_string()))?;
+        total_automations += page.len();
+        active_automations += page
+            .iter()
+            .filter(|(_pubkey, automation)| !automation.paused)
+            .count();
+    }
+
+    println!(
+        "Epoch: {}\nWorkers: {}\nPools: {}\nDelegated stake: {}\nAutomations: {} ({} active)\nFees distributable this epoch: {}\nUnclaimed commissions: {}",
+        registry.current_epoch,
+        registry.total_workers,
+        registry.total_pools,
+        snapshot.total_stake,
+        total_automations,
+        active_automations,
+        total_distributable_fees,
+        total_commission_balance,
+    );
+    Ok(())
+}
+
+pub fn pipeline_status(client: &Client) -> Result<(), CliError> {
+    let status = client
+        .get_pipeline_status()
+        .map_err(|err| CliError::BadClient(err.to_string()))?;
+
+    let stage = match status.frame_progress {
+        Some((frame, total_frames)) => format!(
+            "{:?} (frame {} of {})",
+            status.stage,
+            frame + 1,
+            total_frames
+        ),
+        None => format!("{:?}", status.stage),
+    };
+    let last_exec = |last_exec_at: Option<u64>| {
+        last_exec_at.map_or("never".to_string(), |slot| format!("slot {}", slot))
+    };
+    println!(
+        "Epoch: {}\nRegistry lock: {:?}\nStage: {}\nEpoch automation last executed: {} ({} slots ago)\nHasher automation last executed: {}",
+        status.epoch,
+        status.lock_reason,
+        stage,
+        last_exec(status.last_exec_at),
+        status.idle_slots,
+        last_exec(status.hasher_last_exec_at),
+    );
+    if status.is_stuck {
+        println!(
+            "Status: stuck, the epoch automation has not executed in over {} slots",
+            REGISTRY_LOCK_STALL_THRESHOLD
+        );
+    } else if status.stage == PipelineStage::Idle {
+        println!("Status: idle, waiting for the next epoch");
+    } else {
+        println!("Status: progressing");
+    }
+    Ok(())
+}
+
+fn get_multiple<T: AccountDeserialize>(
+    client: &Client,
+    pubkeys: &[Pubkey],
+) -> Result<Vec<T>, CliError> {
+    let mut accounts = vec![];
+    for chunk in pubkeys.chunks(MAX_MULTIPLE_ACCOUNTS) {
+        let chunk_accounts: Vec<Option<Account>> = client
+            .get_multiple_accounts(chunk)
+            .map_err(|err| CliError::BadClient(err.to_string()))?;
+        for (pubkey, account) in chunk.iter().zip(chunk_accounts) {
+            let account = account.ok_or(CliError::AccountNotFound(pubkey.to_string()))?;
+            accounts.push(
+                T::try_deserialize(&mut account.data.as_slice())
+                    .map_err(|_err| CliError::AccountDataNotParsable(pubkey.to_string()))?,
+            );
+        }
+    }
+    Ok(accounts)
+}
@@ -1,36 +1,63 @@
 use clockwork_client::network::state::PoolSettings;
 
 use {
-    crate::errors::CliError,
+    crate::{
+        errors::CliError,
+        output::{pool_json, print_json, OutputFormat},
+    },
     clockwork_client::{
-        network::state::{Pool, Registry},
+        network::{
+            state::{Pool, Registry},
+            PoolFilter,
+        },
         Client,
     },
 };
 
-pub fn get(client: &Client, id: u64) -> Result<(), CliError> {
+pub fn create(client: &Client) -> Result<(), CliError> {
+    // The new pool is initialized at the registry's next pool id.
+    let registry_pubkey = Registry::pubkey();
+    let registry = client
+        .get::<Registry>(&registry_pubkey)
+        .map_err(|_err| CliError::AccountDataNotParsable(registry_pubkey.to_string()))?;
+    let ix = clockwork_client::network::instruction::pool_create(
+        client.payer_pubkey(),
+        client.payer_pubkey(),
+        Pool::pubkey(registry.total_pools),
+    );
+    client.send_and_confirm(&[ix], &[client.payer()]).unwrap();
+    get(client, registry.total_pools, OutputFormat::Display)?;
+    Ok(())
+}
+
+pub fn get(client: &Client, id: u64, output: OutputFormat) -> Result<(), CliError> {
     let pool_pubkey = Pool::pubkey(id);
     let pool = client
         .get::<Pool>(&pool_pubkey)
         .map_err(|_err| CliError::AccountDataNotParsable(pool_pubkey.to_string()))?;
-    println!("{:#?}", pool);
+    match output {
+        OutputFormat::Display => println!("{:#?}", pool),
+        OutputFormat::Json => print_json(&pool_json(pool_pubkey, &pool)),
+    }
     Ok(())
 }
 
-pub fn list(client: &Client) -> Result<(), CliError> {
-    let registry_pubkey = Registry::pubkey();
-    let registry = client
-        .get::<Registry>(&registry_pubkey)
-        .map_err(|_err| CliError::AccountDataNotParsable(registry_pubkey.to_string()))?;
-
-    for pool_id in 0..registry.total_pools {
-        let pool_pubkey = Pool::pubkey(pool_id);
-        let pool = client
-            .get::<Pool>(&pool_pubkey)
-            .map_err(|_err| CliError::AccountDataNotParsable(pool_pubkey.to_string()))?;
+pub fn list(
+    client: &Client,
+    offset: u64,
+    limit: Option<u64>,
+    min_size: Option<usize>,
+) -> Result<(), CliError> {
+    let pools = client
+        .get_pools(PoolFilter {
+            offset,
+            limit,
+            min_size,
+        })
+        .map_err(|err| CliError::BadClient(err.to_string()))?;
+    for (_pool_pubkey, pool) in pools {
         println!("{:#?}", pool);
     }
-
     Ok(())
 }
 
@@ -43,6 +70,6 @@ pub fn update(client: &Client, id: u64, size: usize) -> Result<(), CliError> {
         PoolSettings { size },
     );
     client.send_and_confirm(&[ix], &[client.payer()]).unwrap();
-    get(client, id)?;
+    get(client, id, OutputFormat::Display)?;
     Ok(())
 }
@@ -1,5 +1,7 @@
 use crate::{
-    cli::CliCommand, config::CliConfig, errors::CliError,
+    cli::CliCommand,
+    config::{CliConfig, CliOverrides},
+    errors::CliError,
     processor::automation::parse_pubkey_from_id_or_address,
 };
 use anyhow::Result;
@@ -11,7 +13,7 @@ pub fn process(matches: &ArgMatches) -> Result<(), CliError> {
     // Parse command and config
     let command = CliCommand::try_from(matches)?;
 
-    match command {
+    match &command {
         // Set solana config if using localnet command
         CliCommand::Localnet {
             clone_addresses: _,
@@ -21,16 +23,38 @@ pub fn process(matches: &ArgMatches) -> Result<(), CliError> {
             // TODO Verify the Solana CLI version is compatable with this build.
             set_solana_config().map_err(|err| CliError::FailedLocalnet(err.to_string()))?
         }
+        // Profile commands manage the config itself, so process them before loading it.
+        CliCommand::ProfileGet { name } => return super::profile::get(name.clone()),
+        CliCommand::ProfileList => return super::profile::list(),
+        CliCommand::ProfileSet {
+            name,
+            json_rpc_url,
+            keypair_path,
+            commitment,
+            default,
+        } => {
+            return super::profile::set(
+                name.clone(),
+                json_rpc_url.clone(),
+                keypair_path.clone(),
+                commitment.clone(),
+                *default,
+            )
+        }
         _ => {}
     }
 
-    let config = CliConfig::load();
+    let config = CliConfig::load(CliOverrides::from(matches))?;
 
     // Build the RPC client
     let payer = read_keypair_file(&config.keypair_path)
         .map_err(|_| CliError::KeypairNotFound(config.keypair_path.clone()))?;
 
-    let client = Client::new(payer, config.json_rpc_url.clone());
+    let client = Client::new_with_commitment(
+        payer,
+        config.json_rpc_url.clone(),
+        config.client_commitment,
+    );
 
     // Process the command
     match command {
@@ -38,14 +62,36 @@ pub fn process(matches: &ArgMatches) -> Result<(), CliError> {
             ack_authority,
             base_url,
         } => super::api::api_new(&client, ack_authority, base_url),
-        CliCommand::ConfigGet => super::config::get(&client),
+        CliCommand::ConfigAcceptAdmin => super::config::accept_admin(&client),
+        CliCommand::ConfigCancelAdmin => super::config::cancel_admin(&client),
+        CliCommand::ConfigGet { output, watch } => match watch {
+            true => super::config::watch(&client, config),
+            false => super::config::get(&client, output),
+        },
+        CliCommand::ConfigMigrate => super::config::migrate(&client),
+        CliCommand::ConfigProposeAdmin { pending_admin } => {
+            super::config::propose_admin(&client, pending_admin)
+        }
         CliCommand::ConfigSet {
-            admin,
+            creation_fee,
+            epoch_automation,
+            hasher_automation,
+            token_stake_rate,
+            treasury,
+        } => super::config::set(
+            &client,
+            creation_fee,
             epoch_automation,
             hasher_automation,
-        } => super::config::set(&client, admin, epoch_automation, hasher_automation),
-        CliCommand::Crontab { schedule } => super::crontab::get(&client, schedule),
-        CliCommand::DelegationCreate { worker_id } => super::delegation::create(&client, worker_id),
+            token_stake_rate,
+            treasury,
+        ),
+        CliCommand::Crontab { schedule, timezone } => {
+            super::crontab::get(&client, schedule, timezone)
+        }
+        CliCommand::DelegationCreate { native, worker_id } => {
+            super::delegation::create(&client, native, worker_id)
+        }
         CliCommand::DelegationDeposit {
             amount,
             delegation_id,
@@ -53,13 +99,33 @@ pub fn process(matches: &ArgMatches) -> Result<(), CliError> {
         } => super::delegation::deposit(&client, amount, delegation_id, worker_id),
         CliCommand::DelegationGet {
             delegation_id,
+            output,
+            worker_id,
+        } => super::delegation::get(&client, delegation_id, output, worker_id),
+        CliCommand::DelegationList { worker_id } => super::delegation::list(&client, worker_id),
+        CliCommand::DelegationMigrate {
+            delegation_id,
+            worker_id,
+        } => super::delegation::migrate(&client, delegation_id, worker_id),
+        CliCommand::DelegationMerge {
+            from_id,
+            into_id,
             worker_id,
-        } => super::delegation::get(&client, delegation_id, worker_id),
+        } => super::delegation::merge(&client, from_id, into_id, worker_id),
         CliCommand::DelegationWithdraw {
             amount,
             delegation_id,
             worker_id,
         } => super::delegation::withdraw(&client, amount, delegation_id, worker_id),
+        CliCommand::DelegationWithdrawClaim {
+            delegation_id,
+            worker_id,
+        } => super::delegation::withdraw_claim(&client, delegation_id, worker_id),
+        CliCommand::DelegationWithdrawRequest {
+            amount,
+            delegation_id,
+            worker_id,
+        } => super::delegation::withdraw_request(&client, amount, delegation_id, worker_id),
         CliCommand::ExplorerGetAutomation { id, address } => {
             let pubkey = parse_pubkey_from_id_or_address(client.payer_pubkey(), id, address)?;
             super::explorer::automation_url(pubkey, config)
@@ -70,39 +136,164 @@ pub fn process(matches: &ArgMatches) -> Result<(), CliError> {
             network_url,
             program_infos,
         } => super::localnet::start(&client, clone_addresses, network_url, program_infos),
-        CliCommand::PoolGet { id } => super::pool::get(&client, id),
-        CliCommand::PoolList {} => super::pool::list(&client),
+        CliCommand::NetworkPipelineStatus => super::network::pipeline_status(&client),
+        CliCommand::NetworkStats => super::network::stats(&client),
+        CliCommand::PoolCreate {} => super::pool::create(&client),
+        CliCommand::PoolGet { id, output } => super::pool::get(&client, id, output),
+        CliCommand::PoolList {
+            offset,
+            limit,
+            min_size,
+        } => super::pool::list(&client, offset, limit, min_size),
         CliCommand::PoolUpdate { id, size } => super::pool::update(&client, id, size),
+        CliCommand::AutomationCostReport { authority, since } => {
+            super::automation::cost_report(&client, authority, since)
+        }
         CliCommand::AutomationCrateInfo {} => super::automation::crate_info(&client),
+        CliCommand::AutomationClone {
+            source_id,
+            new_id,
+            kickoff_instruction,
+            rate_limit,
+            schedule,
+        } => super::automation::clone(
+            &client,
+            source_id,
+            new_id,
+            kickoff_instruction,
+            rate_limit,
+            schedule,
+        ),
         CliCommand::AutomationCreate {
             id,
+            description,
             kickoff_instruction,
+            max_executions,
+            trigger,
+        } => super::automation::create(
+            &client,
+            id,
+            description,
+            vec![kickoff_instruction],
+            max_executions,
             trigger,
-        } => super::automation::create(&client, id, vec![kickoff_instruction], trigger),
+        ),
+        CliCommand::AutomationEstimate {
+            cron,
+            executions,
+            horizon,
+            kickoff_instruction,
+            worker_id,
+        } => super::automation::estimate(
+            &client,
+            cron,
+            executions,
+            horizon,
+            kickoff_instruction,
+            worker_id,
+        ),
+        CliCommand::AutomationInstructionSchema => super::automation::instruction_schema(),
+        CliCommand::AutomationList { authority } => super::automation::list(&client, authority),
+        CliCommand::AutomationLogs {
+            id,
+            limit,
+            failed_only,
+        } => super::automation::logs(&client, id, limit, failed_only),
         CliCommand::AutomationDelete { id } => super::automation::delete(&client, id),
+        CliCommand::AutomationMigrate { id } => super::automation::migrate(&client, id),
         CliCommand::AutomationPause { id } => super::automation::pause(&client, id),
         CliCommand::AutomationResume { id } => super::automation::resume(&client, id),
+        CliCommand::AutomationReplay { signature, rpc_url } => {
+            super::automation::replay(&client, signature, rpc_url)
+        }
         CliCommand::AutomationReset { id } => super::automation::reset(&client, id),
-        CliCommand::AutomationGet { id, address } => {
+        CliCommand::AutomationSimulate {
+            id,
+            kickoff_instruction,
+            worker_id,
+        } => super::automation::simulate(&client, id, kickoff_instruction, worker_id),
+        CliCommand::AutomationGet {
+            id,
+            address,
+            output,
+        } => {
             let pubkey = parse_pubkey_from_id_or_address(client.payer_pubkey(), id, address)?;
-            super::automation::get(&client, pubkey)
+            super::automation::get(&client, pubkey, output)
         }
         CliCommand::AutomationUpdate {
             id,
+            description,
+            lifetime_spend_cap,
+            max_chain_depth,
+            max_executions,
+            max_retries,
             rate_limit,
             schedule,
-        } => super::automation::update(&client, id, rate_limit, schedule),
-        CliCommand::RegistryGet => super::registry::get(&client),
-        CliCommand::RegistryUnlock => super::registry::unlock(&client),
+        } => super::automation::update(
+            &client,
+            id,
+            description,
+            lifetime_spend_cap,
+            max_chain_depth,
+            max_executions,
+            max_retries,
+            rate_limit,
+            schedule,
+        ),
+        CliCommand::ProfileGet { .. } | CliCommand::ProfileList | CliCommand::ProfileSet { .. } => {
+            unreachable!("profile commands are processed before the config is loaded")
+        }
+        CliCommand::ProgramPause { program_id } => super::program::pause(&client, program_id),
+        CliCommand::ProgramResume { program_id } => super::program::resume(&client, program_id),
+        CliCommand::RegistryGet { watch } => match watch {
+            true => super::registry::watch(&client, config),
+            false => super::registry::get(&client),
+        },
+        CliCommand::RegistryUnlock { force } => super::registry::unlock(&client, force),
+        CliCommand::SnapshotDiff { epoch_a, epoch_b } => {
+            super::snapshot::diff(&client, epoch_a, epoch_b)
+        }
         CliCommand::WebhookRequestNew {
             api,
+            backoff_seconds,
+            body,
+            headers,
+            id,
+            max_retries,
+            method,
+            route,
+        } => super::webhook::request_new(
+            &client,
+            api,
+            backoff_seconds,
+            body,
+            headers,
             id,
+            max_retries,
             method,
             route,
-        } => super::webhook::request_new(&client, api, id, method, route),
+        ),
+        CliCommand::WorkerAddresses { id, mint, epoch } => {
+            super::worker::addresses(&client, id, mint, epoch)
+        }
         CliCommand::WorkerCreate { signatory } => super::worker::create(&client, signatory, false),
-        CliCommand::WorkerGet { id } => super::worker::get(&client, id),
-        CliCommand::WorkerUpdate { id, signatory } => super::worker::update(&client, id, signatory),
+        CliCommand::WorkerGet { id, output } => super::worker::get(&client, id, output),
+        CliCommand::WorkerList { in_pool } => super::worker::list(&client, in_pool),
+        CliCommand::WorkerMigrate { id } => super::worker::migrate(&client, id),
+        CliCommand::WorkerUpdate {
+            id,
+            commission,
+            commission_recipient,
+            signatory,
+            force,
+        } => super::worker::update(
+            &client,
+            id,
+            commission,
+            commission_recipient,
+            signatory,
+            force,
+        ),
     }
 }
 
@@ -0,0 +1,53 @@
+use crate::{errors::CliError, profile::Profiles};
+
+pub fn get(name: Option<String>) -> Result<(), CliError> {
+    let profiles = Profiles::load()?;
+    let name = name
+        .or_else(|| profiles.default_profile.clone())
+        .ok_or(CliError::BadParameter("name".into()))?;
+    let profile = profiles
+        .profiles
+        .get(&name)
+        .ok_or(CliError::ProfileNotFound(name.clone()))?;
+    println!("Name: {}\n{:#?}", name, profile);
+    Ok(())
+}
+
+pub fn list() -> Result<(), CliError> {
+    let profiles = Profiles::load()?;
+    for name in profiles.profiles.keys() {
+        match profiles.default_profile.as_ref() == Some(name) {
+            true => println!("{} (default)", name),
+            false => println!("{}", name),
+        }
+    }
+    Ok(())
+}
+
+pub fn set(
+    name: String,
+    json_rpc_url: Option<String>,
+    keypair_path: Option<String>,
+    commitment: Option<String>,
+    default: bool,
+) -> Result<(), CliError> {
+    let mut profiles = Profiles::load()?;
+
+    // Only overwrite the settings that were passed.
+    let profile = profiles.profiles.entry(name.clone()).or_default();
+    if json_rpc_url.is_some() {
+        profile.json_rpc_url = json_rpc_url;
+    }
+    if keypair_path.is_some() {
+        profile.keypair_path = keypair_path;
+    }
+    if commitment.is_some() {
+        profile.commitment = commitment;
+    }
+    if default {
+        profiles.default_profile = Some(name.clone());
+    }
+
+    profiles.save()?;
+    get(Some(name))
+}
@@ -0,0 +1,28 @@
+use {
+    crate::errors::CliError,
+    clockwork_client::{network::state::ProgramStatus, Client},
+    solana_sdk::pubkey::Pubkey,
+};
+
+pub fn get(client: &Client, program_id: Pubkey) -> Result<(), CliError> {
+    let program_status_pubkey = ProgramStatus::pubkey(program_id);
+    let program_status = client
+        .get::<ProgramStatus>(&program_status_pubkey)
+        .map_err(|_err| CliError::AccountDataNotParsable(program_status_pubkey.to_string()))?;
+    println!("{:#?}", program_status);
+    Ok(())
+}
+
+pub fn pause(client: &Client, program_id: Pubkey) -> Result<(), CliError> {
+    let ix = clockwork_client::network::instruction::program_pause(client.payer_pubkey(), program_id);
+    client.send_and_confirm(&[ix], &[client.payer()]).unwrap();
+    get(client, program_id)?;
+    Ok(())
+}
+
+pub fn resume(client: &Client, program_id: Pubkey) -> Result<(), CliError> {
+    let ix = clockwork_client::network::instruction::program_resume(client.payer_pubkey(), program_id);
+    client.send_and_confirm(&[ix], &[client.payer()]).unwrap();
+    get(client, program_id)?;
+    Ok(())
+}
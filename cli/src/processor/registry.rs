@@ -1,9 +1,14 @@
 use {
-    crate::errors::CliError,
+    crate::{config::CliConfig, errors::CliError},
     clockwork_client::{
-        network::state::{Registry, Snapshot},
+        network::{
+            state::{Registry, Snapshot},
+            RegistryLockStatus, REGISTRY_LOCK_STALL_THRESHOLD,
+        },
         Client,
     },
+    solana_account_decoder::UiAccountEncoding,
+    solana_client::{pubsub_client::PubsubClient, rpc_config::RpcAccountInfoConfig},
 };
 
 pub fn get(client: &Client) -> Result<(), CliError> {
@@ -22,7 +27,76 @@ pub fn get(client: &Client) -> Result<(), CliError> {
     Ok(())
 }
 
-pub fn unlock(client: &Client) -> Result<(), CliError> {
+pub fn watch(client: &Client, cli_config: CliConfig) -> Result<(), CliError> {
+    // Print the current registry as the baseline.
+    let registry_pubkey = Registry::pubkey();
+    let mut prev = client
+        .get::<Registry>(&registry_pubkey)
+        .map_err(|_err| CliError::AccountDataNotParsable(registry_pubkey.to_string()))?;
+    println!("{}", summary(&prev));
+
+    // Subscribe to changes of the registry account.
+    let websocket_url = match cli_config.websocket_url.is_empty() {
+        true => solana_cli_config::Config::compute_websocket_url(&cli_config.json_rpc_url),
+        false => cli_config.websocket_url,
+    };
+    let (_subscription, receiver) = PubsubClient::account_subscribe(
+        &websocket_url,
+        &registry_pubkey,
+        Some(RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            commitment: Some(cli_config.commitment),
+            ..RpcAccountInfoConfig::default()
+        }),
+    )
+    .map_err(|err| CliError::BadClient(err.to_string()))?;
+    println!("Watching {} for changes...", registry_pubkey);
+
+    // Re-render the summary whenever it changes. Nonce updates alone are not shown.
+    for response in receiver {
+        let next = match response.value.data.decode().map(Registry::try_from) {
+            Some(Ok(registry)) => registry,
+            _ => return Err(CliError::AccountDataNotParsable(registry_pubkey.to_string())),
+        };
+        if summary(&next).ne(&summary(&prev)) {
+            println!("Slot {}: {}", response.context.slot, summary(&next));
+        }
+        prev = next;
+    }
+
+    Ok(())
+}
+
+fn summary(registry: &Registry) -> String {
+    format!(
+        "current_epoch: {} locked: {} ({:?}) total_workers: {}",
+        registry.current_epoch,
+        registry.is_locked(),
+        registry.lock_reason,
+        registry.total_workers
+    )
+}
+
+pub fn unlock(client: &Client, force: bool) -> Result<(), CliError> {
+    // Only release a lock the epoch jobs have stopped making progress on, unless forced.
+    let status = client
+        .get_registry_lock_status()
+        .map_err(|err| CliError::BadClient(err.to_string()))?;
+    match status {
+        RegistryLockStatus::Unlocked => {
+            println!("The registry is not locked");
+            return Ok(());
+        }
+        RegistryLockStatus::Held { reason, idle_slots } if !force => {
+            return Err(CliError::BadParameter(format!(
+                "The registry is locked for {:?} and the epoch automation executed {} slots ago. \
+                Wait until it has been idle for more than {} slots, or pass --force",
+                reason, idle_slots, REGISTRY_LOCK_STALL_THRESHOLD
+            )));
+        }
+        _ => {}
+    }
+
     let ix = clockwork_client::network::instruction::registry_unlock(client.payer_pubkey());
     client.send_and_confirm(&[ix], &[client.payer()]).unwrap();
     get(client)?;
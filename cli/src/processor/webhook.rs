@@ -6,7 +6,11 @@ use crate::errors::CliError;
 pub fn request_new(
     _client: &Client,
     _api: Pubkey,
+    _backoff_seconds: u64,
+    _body: Vec<u8>,
+    _headers: Vec<(String, String)>,
     _id: String,
+    _max_retries: u64,
     _method: HttpMethod,
     _route: String,
 ) -> Result<(), CliError> {
@@ -14,8 +18,12 @@ pub fn request_new(
 
     // let ix = clockwork_client::webhook::instruction::request_new(
     //     api,
+    //     backoff_seconds,
+    //     body,
     //     client.payer_pubkey(),
+    //     headers,
     //     id,
+    //     max_retries,
     //     method,
     //     client.payer_pubkey(),
     //     route,
@@ -1,13 +1,52 @@
-use clockwork_client::network::state::{Penalty, WorkerSettings};
+use clockwork_client::network::state::{Penalty, Pool, WorkerSettings};
 
 use {
-    crate::errors::CliError,
+    crate::{
+        errors::CliError,
+        output::{print_json, worker_json, OutputFormat},
+    },
     clockwork_client::network::state::{Config, Fee, Registry, Snapshot, SnapshotFrame, Worker},
     clockwork_client::Client,
-    solana_sdk::signature::{Keypair, Signer},
+    solana_sdk::{
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+    },
 };
 
-pub fn get(client: &Client, id: u64) -> Result<(), CliError> {
+pub fn addresses(
+    client: &Client,
+    id: u64,
+    mint: Option<Pubkey>,
+    epoch: Option<u64>,
+) -> Result<(), CliError> {
+    // Only look up the mint if it was not provided.
+    let mint = match mint {
+        Some(mint) => mint,
+        None => {
+            client
+                .get::<Config>(&Config::pubkey())
+                .map_err(|_err| CliError::AccountNotFound(Config::pubkey().to_string()))?
+                .mint
+        }
+    };
+
+    // Only look up the current epoch if it was not provided.
+    let epoch = match epoch {
+        Some(epoch) => epoch,
+        None => {
+            client
+                .get::<Registry>(&Registry::pubkey())
+                .map_err(|_err| CliError::AccountNotFound(Registry::pubkey().to_string()))?
+                .current_epoch
+        }
+    };
+
+    let addresses = clockwork_client::network::worker_addresses(id, mint, epoch);
+    println!("{:#?}", addresses);
+    Ok(())
+}
+
+pub fn get(client: &Client, id: u64, output: OutputFormat) -> Result<(), CliError> {
     let worker_pubkey = Worker::pubkey(id);
     let worker = client
         .get::<Worker>(&worker_pubkey)
@@ -35,6 +74,16 @@ pub fn get(client: &Client, id: u64) -> Result<(), CliError> {
     let penalty_balance = client.get_balance(&penalty_pubkey).unwrap();
     let penalty_total = penalty_balance - penalty_min_rent;
 
+    if output.eq(&OutputFormat::Json) {
+        let mut value = worker_json(worker_pubkey, &worker);
+        value["fee_account"] = fee_pubkey.to_string().into();
+        value["fees"] = fees_total.into();
+        value["penalty_account"] = penalty_pubkey.to_string().into();
+        value["penalty"] = penalty_total.into();
+        print_json(&value);
+        return Ok(());
+    }
+
     println!(
         "Address: {}\nFees: {}\nFee account: {}\nPenalty: {}\nPenalty account: {}\n{:#?}",
         worker_pubkey, fees_total, fee_pubkey, penalty_total, penalty_pubkey, worker
@@ -63,6 +112,37 @@ pub fn get(client: &Client, id: u64) -> Result<(), CliError> {
     Ok(())
 }
 
+pub fn list(client: &Client, in_pool: bool) -> Result<(), CliError> {
+    let workers = client
+        .get_workers()
+        .map_err(|err| CliError::BadClient(err.to_string()))?;
+    let pool_pubkey = Pool::pubkey(0);
+    let pool = client
+        .get::<Pool>(&pool_pubkey)
+        .map_err(|_err| CliError::AccountDataNotParsable(pool_pubkey.to_string()))?;
+    let workers = if in_pool {
+        clockwork_client::network::workers_in_pool(workers, &pool)
+    } else {
+        workers
+    };
+
+    println!(
+        "{:<6} {:<44} {:<10} {:<11} {}",
+        "ID", "Authority", "Commission", "Delegations", "In pool"
+    );
+    for (worker_pubkey, worker) in workers {
+        println!(
+            "{:<6} {:<44} {:<10} {:<11} {}",
+            worker.id,
+            worker.authority.to_string(),
+            format!("{}%", worker.commission_rate),
+            worker.total_delegations,
+            pool.workers.contains(&worker_pubkey)
+        );
+    }
+    Ok(())
+}
+
 pub fn create(client: &Client, signatory: Keypair, silent: bool) -> Result<(), CliError> {
     // Get config data
     let config_pubkey = Config::pubkey();
@@ -93,29 +173,108 @@ pub fn create(client: &Client, signatory: Keypair, silent: bool) -> Result<(), C
         .send_and_confirm(&[ix], &[client.payer(), &signatory])
         .unwrap();
     if !silent {
-        get(client, worker_id)?;
+        get(client, worker_id, OutputFormat::Display)?;
     }
     Ok(())
 }
 
-pub fn update(client: &Client, id: u64, signatory: Option<Keypair>) -> Result<(), CliError> {
+pub fn migrate(client: &Client, id: u64) -> Result<(), CliError> {
+    let ix = clockwork_client::network::instruction::worker_migrate(
+        client.payer_pubkey(),
+        Worker::pubkey(id),
+    );
+    client.send_and_confirm(&[ix], &[client.payer()]).unwrap();
+    get(client, id, OutputFormat::Display)
+}
+
+pub fn update(
+    client: &Client,
+    id: u64,
+    commission: Option<u64>,
+    commission_recipient: Option<Pubkey>,
+    signatory: Option<Keypair>,
+    force: bool,
+) -> Result<(), CliError> {
+    if commission.map_or(false, |commission| commission > 100) {
+        return Err(CliError::BadParameter("commission".into()));
+    }
+
     // Derive worker keypair.
     let worker_pubkey = Worker::pubkey(id);
     let worker = client
         .get::<Worker>(&worker_pubkey)
         .map_err(|_err| CliError::AccountDataNotParsable(worker_pubkey.to_string()))?;
 
+    // Only look up the worker's pools if its signatory is changing.
+    let new_signatory = signatory.as_ref().map(|v| v.pubkey());
+    if new_signatory.map_or(false, |new_signatory| new_signatory.ne(&worker.signatory)) {
+        let pool_ids = client
+            .get_worker_pool_ids(worker_pubkey)
+            .map_err(|err| CliError::BadClient(err.to_string()))?;
+        check_signatory_change(&pool_ids, force)?;
+    }
+
     // Build and submit tx.
     let settings = WorkerSettings {
-        commission_rate: 0,
+        commission_rate: worker.commission_rate,
         signatory: signatory.map_or(worker.signatory, |v| v.pubkey()),
+        commission_recipient: commission_recipient.or(worker.commission_recipient),
     };
-    let ix = clockwork_client::network::instruction::worker_update(
+    let mut ixs = vec![clockwork_client::network::instruction::worker_update(
         client.payer_pubkey(),
         settings,
         worker_pubkey,
+    )];
+
+    // Commission rate changes are staged and take effect from the next epoch's fee distribution.
+    if let Some(commission) = commission {
+        ixs.push(clockwork_client::network::instruction::worker_commission_update(
+            client.payer_pubkey(),
+            commission,
+            worker_pubkey,
+        ));
+    }
+    client.send_and_confirm(&ixs, &[client.payer()]).unwrap();
+    get(client, worker.id, OutputFormat::Display)?;
+    Ok(())
+}
+
+/// Rejects a signatory change while the worker holds a slot in any pool, unless forced.
+///
+/// Workers sign their pool rotations and exec transactions with the signatory. A node still running with
+/// the old signatory keypair fails those signature checks once the change lands, so the worker stops
+/// executing and is eventually rotated out of its pools. Operators should wait to be rotated out, or
+/// restart the node with the new keypair right after a forced change.
+fn check_signatory_change(pool_ids: &[u64], force: bool) -> Result<(), CliError> {
+    if pool_ids.is_empty() {
+        return Ok(());
+    }
+    if !force {
+        return Err(CliError::BadParameter(format!(
+            "signatory: the worker is in pools {:?}. Changing its signatory will fail its transactions \
+            until the node runs with the new keypair. Rotate out of the pools first, or pass --force",
+            pool_ids
+        )));
+    }
+    println!(
+        "Warning: changing the signatory of a worker in pools {:?}. Restart the node with the new keypair",
+        pool_ids
     );
-    client.send_and_confirm(&[ix], &[client.payer()]).unwrap();
-    get(client, worker.id)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signatory_change_in_pool_is_flagged() {
+        assert!(check_signatory_change(&[0, 3], false).is_err());
+        assert!(check_signatory_change(&[0, 3], true).is_ok());
+    }
+
+    #[test]
+    fn test_signatory_change_outside_pools_is_allowed() {
+        assert!(check_signatory_change(&[], false).is_ok());
+    }
+}
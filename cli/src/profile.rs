@@ -0,0 +1,72 @@
+use std::{collections::BTreeMap, fs::File, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::CliError;
+
+/// A named set of defaults for connecting to a cluster.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Profile {
+    pub json_rpc_url: Option<String>,
+    pub keypair_path: Option<String>,
+    pub commitment: Option<String>,
+}
+
+/// The profiles stored in the user's Clockwork config directory.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct Profiles {
+    /// The profile to use when no profile is passed on the command line.
+    pub default_profile: Option<String>,
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+impl Profiles {
+    /// The path of the profiles file.
+    pub fn path() -> Result<PathBuf, CliError> {
+        dirs_next::home_dir()
+            .map(|mut path| {
+                path.extend(&[".config", "solana", "clockwork", "profiles.yml"]);
+                path
+            })
+            .ok_or(CliError::ProfilesNotReadable(
+                "home directory not found".into(),
+            ))
+    }
+
+    /// Read the profiles file, or return an empty set of profiles if it does not exist yet.
+    pub fn load() -> Result<Self, CliError> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(Profiles::default());
+        }
+        let file =
+            File::open(&path).map_err(|err| CliError::ProfilesNotReadable(err.to_string()))?;
+        serde_yaml::from_reader(file).map_err(|err| CliError::ProfilesNotReadable(err.to_string()))
+    }
+
+    /// Write the profiles file, creating the config directory if needed.
+    pub fn save(&self) -> Result<(), CliError> {
+        let path = Self::path()?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .map_err(|err| CliError::ProfilesNotReadable(err.to_string()))?;
+        }
+        let file =
+            File::create(&path).map_err(|err| CliError::ProfilesNotReadable(err.to_string()))?;
+        serde_yaml::to_writer(file, self).map_err(|err| CliError::ProfilesNotReadable(err.to_string()))
+    }
+
+    /// Get a profile by name, or the default profile if no name is given.
+    /// Naming a profile that does not exist is an error, while having no default profile is not.
+    pub fn resolve(&self, name: Option<String>) -> Result<Option<Profile>, CliError> {
+        match name.or_else(|| self.default_profile.clone()) {
+            None => Ok(None),
+            Some(name) => self
+                .profiles
+                .get(&name)
+                .cloned()
+                .map(Some)
+                .ok_or(CliError::ProfileNotFound(name)),
+        }
+    }
+}
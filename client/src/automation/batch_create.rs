@@ -0,0 +1,144 @@
+use {
+    super::AutomationBuilderError,
+    crate::{
+        batch::{transaction_size, TRANSACTION_COMPUTE_UNIT_LIMIT},
+        Client, ClientResult,
+    },
+    clockwork_automation_program::state::{
+        AutomationCreateParams, InstructionData as ClockworkInstructionData, Trigger,
+        MAX_AUTOMATION_BATCH_SIZE,
+    },
+    clockwork_network_program::state::Config,
+    solana_sdk::{
+        compute_budget::ComputeBudgetInstruction,
+        instruction::Instruction,
+        packet::PACKET_DATA_SIZE,
+        pubkey::Pubkey,
+        signature::{Keypair, Signature, Signer},
+    },
+};
+
+impl Client {
+    /// Creates an automation for each id, kickoff instruction, and trigger, funding each with `amount` lamports.
+    /// The automations are created in as few transactions as fit the size limit, with the payer as their authority.
+    pub fn create_automations(
+        &self,
+        automations: Vec<(Vec<u8>, ClockworkInstructionData, Trigger)>,
+        amount: u64,
+        payer: &Keypair,
+    ) -> ClientResult<Vec<Signature>> {
+        let treasury = self.get::<Config>(&Config::pubkey())?.treasury;
+        let instructions = build_automation_create_batches(
+            payer.pubkey(),
+            automations,
+            amount,
+            payer.pubkey(),
+            treasury,
+        )?;
+        self.submit_batch(instructions, payer)
+    }
+}
+
+/// Emits `automation_create_batch` instructions that create the automations in order.
+/// The automations are split so each instruction fits in a transaction alongside a compute unit limit
+/// instruction. An automation too large to fit is still given its own instruction, so the failure
+/// surfaces when it is sent. Both the authority and the payer must sign each transaction.
+pub fn build_automation_create_batches(
+    authority: Pubkey,
+    automations: Vec<(Vec<u8>, ClockworkInstructionData, Trigger)>,
+    amount: u64,
+    payer: Pubkey,
+    treasury: Pubkey,
+) -> Result<Vec<Instruction>, AutomationBuilderError> {
+    let compute_budget_ix =
+        ComputeBudgetInstruction::set_compute_unit_limit(TRANSACTION_COMPUTE_UNIT_LIMIT);
+    let batch_ix = |batch: Vec<AutomationCreateParams>| {
+        super::instruction::automation_create_batch(authority, batch, payer, treasury)
+    };
+
+    let mut batches = vec![];
+    let mut batch: Vec<AutomationCreateParams> = vec![];
+    for (id, kickoff_instruction, trigger) in automations {
+        if !trigger.is_valid() {
+            return Err(AutomationBuilderError::InvalidTrigger);
+        }
+        batch.push(AutomationCreateParams {
+            amount,
+            id,
+            instructions: vec![kickoff_instruction],
+            trigger,
+        });
+        let fits = batch.len() <= MAX_AUTOMATION_BATCH_SIZE
+            && transaction_size(&[compute_budget_ix.clone(), batch_ix(batch.clone())], &payer)
+                <= PACKET_DATA_SIZE;
+        if !fits && batch.len() > 1 {
+            let params = batch.pop().unwrap();
+            batches.push(batch);
+            batch = vec![params];
+        }
+    }
+    if !batch.is_empty() {
+        batches.push(batch);
+    }
+    Ok(batches.into_iter().map(batch_ix).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn automations(count: usize) -> Vec<(Vec<u8>, ClockworkInstructionData, Trigger)> {
+        (0..count)
+            .map(|i| {
+                (
+                    format!("user-{}", i).into_bytes(),
+                    ClockworkInstructionData {
+                        program_id: Pubkey::new_unique(),
+                        accounts: vec![],
+                        data: vec![0; 32],
+                    },
+                    Trigger::Cron {
+                        schedule: "0 0 * * * * *".into(),
+                        skippable: true,
+                    },
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_batches_fit_in_transactions() {
+        let authority = Pubkey::new_unique();
+        let treasury = Pubkey::new_unique();
+        let ixs =
+            build_automation_create_batches(authority, automations(100), 0, authority, treasury)
+                .unwrap();
+        assert!(ixs.len() > 1);
+
+        let compute_budget_ix =
+            ComputeBudgetInstruction::set_compute_unit_limit(TRANSACTION_COMPUTE_UNIT_LIMIT);
+        let mut created = 0;
+        for ix in ixs {
+            let size = transaction_size(&[compute_budget_ix.clone(), ix.clone()], &authority);
+            assert!(size <= PACKET_DATA_SIZE);
+            // The fixed accounts are followed by one automation account per automation.
+            created += ix.accounts.len() - 5;
+        }
+        assert_eq!(created, 100);
+    }
+
+    #[test]
+    fn test_batches_reject_invalid_trigger() {
+        let authority = Pubkey::new_unique();
+        let mut automations = automations(2);
+        automations[1].2 = Trigger::Account {
+            address: Pubkey::new_unique(),
+            offset: 0,
+            size: 0,
+        };
+        assert_eq!(
+            build_automation_create_batches(authority, automations, 0, authority, authority),
+            Err(AutomationBuilderError::InvalidTrigger)
+        );
+    }
+}
@@ -0,0 +1,152 @@
+use {
+    anchor_lang::solana_program::{instruction::Instruction, pubkey::Pubkey},
+    clockwork_automation_program::state::{
+        Automation, AutomationSettings, InstructionData as ClockworkInstructionData, Trigger,
+    },
+    thiserror::Error,
+};
+
+#[derive(Debug, Error, PartialEq)]
+pub enum AutomationBuilderError {
+    #[error("An automation id is required")]
+    MissingId,
+
+    #[error("A kickoff instruction is required")]
+    MissingKickoffInstruction,
+
+    #[error("A network treasury is required")]
+    MissingTreasury,
+
+    #[error("A trigger is required")]
+    MissingTrigger,
+
+    #[error("The trigger's parameters are out of bounds")]
+    InvalidTrigger,
+}
+
+/// Accumulates the properties of a new automation and emits the instructions to create it.
+#[derive(Clone, Debug)]
+pub struct AutomationBuilder {
+    amount: u64,
+    authority: Pubkey,
+    description: Option<String>,
+    id: Option<Vec<u8>>,
+    kickoff_instruction: Option<ClockworkInstructionData>,
+    payer: Pubkey,
+    rate_limit: Option<u64>,
+    treasury: Option<Pubkey>,
+    trigger: Option<Trigger>,
+}
+
+impl AutomationBuilder {
+    /// Start building an automation owned by the authority, who also pays for it unless another payer is set.
+    pub fn new(authority: Pubkey) -> Self {
+        Self {
+            amount: 0,
+            authority,
+            description: None,
+            id: None,
+            kickoff_instruction: None,
+            payer: authority,
+            rate_limit: None,
+            treasury: None,
+            trigger: None,
+        }
+    }
+
+    /// The number of lamports to fund the automation with.
+    pub fn amount(mut self, amount: u64) -> Self {
+        self.amount = amount;
+        self
+    }
+
+    /// A free-text description of what the automation does.
+    pub fn description<T: Into<String>>(mut self, description: T) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn id<T: Into<Vec<u8>>>(mut self, id: T) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    pub fn kickoff_instruction<T: Into<ClockworkInstructionData>>(mut self, instruction: T) -> Self {
+        self.kickoff_instruction = Some(instruction.into());
+        self
+    }
+
+    pub fn payer(mut self, payer: Pubkey) -> Self {
+        self.payer = payer;
+        self
+    }
+
+    /// The maximum number of execs allowed per slot.
+    pub fn rate_limit(mut self, rate_limit: u64) -> Self {
+        self.rate_limit = Some(rate_limit);
+        self
+    }
+
+    /// The network treasury that collects the creation fee, as set in the network config.
+    pub fn treasury(mut self, treasury: Pubkey) -> Self {
+        self.treasury = Some(treasury);
+        self
+    }
+
+    pub fn trigger(mut self, trigger: Trigger) -> Self {
+        self.trigger = Some(trigger);
+        self
+    }
+
+    /// The address of the automation being built.
+    pub fn pubkey(&self) -> Result<Pubkey, AutomationBuilderError> {
+        let id = self.id.clone().ok_or(AutomationBuilderError::MissingId)?;
+        Ok(Automation::pubkey(self.authority, id))
+    }
+
+    /// Emit the `automation_create` instruction, followed by an `automation_update` instruction if a description or rate limit was set.
+    /// Both the authority and the payer must sign the transaction.
+    pub fn build(self) -> Result<Vec<Instruction>, AutomationBuilderError> {
+        let automation = self.pubkey()?;
+        let id = self.id.ok_or(AutomationBuilderError::MissingId)?;
+        let kickoff_instruction = self
+            .kickoff_instruction
+            .ok_or(AutomationBuilderError::MissingKickoffInstruction)?;
+        let trigger = self.trigger.ok_or(AutomationBuilderError::MissingTrigger)?;
+        if !trigger.is_valid() {
+            return Err(AutomationBuilderError::InvalidTrigger);
+        }
+        let treasury = self.treasury.ok_or(AutomationBuilderError::MissingTreasury)?;
+
+        let mut instructions = vec![super::instruction::automation_create(
+            self.amount,
+            self.authority,
+            id,
+            vec![kickoff_instruction],
+            self.payer,
+            automation,
+            treasury,
+            trigger,
+        )];
+        if self.description.is_some() || self.rate_limit.is_some() {
+            instructions.push(super::instruction::automation_update(
+                self.authority,
+                automation,
+                AutomationSettings {
+                    description: self.description,
+                    exec_mode: None,
+                    fee: None,
+                    instructions: None,
+                    lifetime_spend_cap: None,
+                    max_chain_depth: None,
+                    max_executions: None,
+                    max_simulation_failures: None,
+                    name: None,
+                    rate_limit: self.rate_limit,
+                    trigger: None,
+                },
+            ));
+        }
+        Ok(instructions)
+    }
+}
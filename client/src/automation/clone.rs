@@ -0,0 +1,229 @@
+use {
+    super::AutomationBuilderError,
+    crate::{Client, ClientResult},
+    anchor_lang::solana_program::{instruction::Instruction, pubkey::Pubkey},
+    clockwork_automation_program::state::{
+        Automation, AutomationSettings, InstructionData as ClockworkInstructionData, Trigger,
+    },
+    clockwork_network_program::state::Config,
+};
+
+/// The properties of a cloned automation that differ from its source.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AutomationOverrides {
+    /// Replaces the source's instructions with a single kickoff instruction.
+    pub kickoff_instruction: Option<ClockworkInstructionData>,
+    pub rate_limit: Option<u64>,
+    pub trigger: Option<Trigger>,
+}
+
+impl Client {
+    /// Emits the instructions to copy the payer's automation under a new id, with the overrides applied.
+    /// The payer pays for the copy and must sign the transaction.
+    pub fn clone_automation(
+        &self,
+        source_id: Vec<u8>,
+        new_id: Vec<u8>,
+        overrides: AutomationOverrides,
+    ) -> ClientResult<Vec<Instruction>> {
+        let source = self.get::<Automation>(&Automation::pubkey(self.payer_pubkey(), source_id))?;
+        let treasury = self.get::<Config>(&Config::pubkey())?.treasury;
+        Ok(build_automation_clone(
+            &source,
+            new_id,
+            overrides,
+            self.payer_pubkey(),
+            treasury,
+        )?)
+    }
+}
+
+/// Emits the `automation_create` instruction for a copy of the source automation under the new id, owned by the
+/// same authority, followed by an `automation_update` instruction carrying over the source's settings.
+/// The copy starts unpaused, with no execution history.
+pub fn build_automation_clone(
+    source: &Automation,
+    new_id: Vec<u8>,
+    overrides: AutomationOverrides,
+    payer: Pubkey,
+    treasury: Pubkey,
+) -> Result<Vec<Instruction>, AutomationBuilderError> {
+    let trigger = overrides.trigger.unwrap_or_else(|| source.trigger.clone());
+    if !trigger.is_valid() {
+        return Err(AutomationBuilderError::InvalidTrigger);
+    }
+    let instructions = match overrides.kickoff_instruction {
+        Some(kickoff_instruction) => vec![kickoff_instruction],
+        None => source.instructions.clone(),
+    };
+
+    let automation = Automation::pubkey(source.authority, new_id.clone());
+    Ok(vec![
+        super::instruction::automation_create(
+            0,
+            source.authority,
+            new_id,
+            instructions,
+            payer,
+            automation,
+            treasury,
+            trigger,
+        ),
+        super::instruction::automation_update(
+            source.authority,
+            automation,
+            AutomationSettings {
+                description: Some(source.description.clone()),
+                exec_mode: Some(source.exec_mode),
+                fee: Some(source.fee),
+                instructions: None,
+                lifetime_spend_cap: Some(source.lifetime_spend_cap),
+                max_chain_depth: source.max_chain_depth,
+                max_executions: Some(source.max_executions),
+                max_simulation_failures: source.max_simulation_failures,
+                name: Some(source.name.clone()),
+                rate_limit: Some(overrides.rate_limit.unwrap_or(source.rate_limit)),
+                trigger: None,
+            },
+        ),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        anchor_lang::AnchorDeserialize,
+        clockwork_automation_program::state::{ClockData, ExecMode},
+    };
+
+    fn source() -> Automation {
+        Automation {
+            authority: Pubkey::new_unique(),
+            bump: 255,
+            chain_depth: 0,
+            created_at: ClockData {
+                slot: 100,
+                epoch: 1,
+                unix_timestamp: 1_672_531_000,
+            },
+            description: "hourly crank".into(),
+            exec_context: None,
+            exec_mode: ExecMode::Worker,
+            execution_count: 12,
+            fee: 2_000,
+            id: b"crank".to_vec(),
+            instructions: vec![ClockworkInstructionData {
+                program_id: Pubkey::new_unique(),
+                accounts: vec![],
+                data: vec![1, 2, 3],
+            }],
+            lifetime_spend_cap: Some(1_000_000),
+            lifetime_spent: 24_000,
+            max_chain_depth: Some(8),
+            max_executions: None,
+            max_simulation_failures: Some(3),
+            name: "crank".into(),
+            next_instruction: None,
+            paused: true,
+            rate_limit: 4,
+            trigger: Trigger::Cron {
+                schedule: "0 0 * * * * *".into(),
+                skippable: true,
+            },
+        }
+    }
+
+    fn update_settings(ix: &Instruction) -> AutomationSettings {
+        clockwork_automation_program::instruction::AutomationUpdate::try_from_slice(&ix.data[8..])
+            .unwrap()
+            .settings
+    }
+
+    #[test]
+    fn test_clone_copies_settings() {
+        let source = source();
+        let payer = Pubkey::new_unique();
+        let ixs = build_automation_clone(
+            &source,
+            b"crank-2".to_vec(),
+            AutomationOverrides::default(),
+            payer,
+            Pubkey::new_unique(),
+        )
+        .unwrap();
+        assert_eq!(ixs.len(), 2);
+
+        let create = clockwork_automation_program::instruction::AutomationCreate::try_from_slice(
+            &ixs[0].data[8..],
+        )
+        .unwrap();
+        assert_eq!(create.id, b"crank-2".to_vec());
+        assert_eq!(create.instructions, source.instructions);
+        assert_eq!(create.trigger, source.trigger);
+        assert_eq!(
+            ixs[0].accounts[5].pubkey,
+            Automation::pubkey(source.authority, b"crank-2".to_vec())
+        );
+
+        let settings = update_settings(&ixs[1]);
+        assert_eq!(settings.description, Some(source.description));
+        assert_eq!(settings.fee, Some(2_000));
+        assert_eq!(settings.lifetime_spend_cap, Some(Some(1_000_000)));
+        assert_eq!(settings.max_chain_depth, Some(8));
+        assert_eq!(settings.max_simulation_failures, Some(3));
+        assert_eq!(settings.rate_limit, Some(4));
+    }
+
+    #[test]
+    fn test_clone_applies_overrides() {
+        let source = source();
+        let kickoff_instruction = ClockworkInstructionData {
+            program_id: Pubkey::new_unique(),
+            accounts: vec![],
+            data: vec![4, 5, 6],
+        };
+        let trigger = Trigger::Slot { target_slot: 1_000 };
+        let ixs = build_automation_clone(
+            &source,
+            b"crank-2".to_vec(),
+            AutomationOverrides {
+                kickoff_instruction: Some(kickoff_instruction.clone()),
+                rate_limit: Some(10),
+                trigger: Some(trigger.clone()),
+            },
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        )
+        .unwrap();
+
+        let create = clockwork_automation_program::instruction::AutomationCreate::try_from_slice(
+            &ixs[0].data[8..],
+        )
+        .unwrap();
+        assert_eq!(create.instructions, vec![kickoff_instruction]);
+        assert_eq!(create.trigger, trigger);
+        assert_eq!(update_settings(&ixs[1]).rate_limit, Some(10));
+    }
+
+    #[test]
+    fn test_clone_rejects_invalid_trigger() {
+        assert_eq!(
+            build_automation_clone(
+                &source(),
+                b"crank-2".to_vec(),
+                AutomationOverrides {
+                    trigger: Some(Trigger::Account {
+                        address: Pubkey::new_unique(),
+                        offset: 0,
+                        size: 0,
+                    }),
+                    ..AutomationOverrides::default()
+                },
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+            ),
+            Err(AutomationBuilderError::InvalidTrigger)
+        );
+    }
+}
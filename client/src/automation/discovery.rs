@@ -0,0 +1,31 @@
+use {
+    crate::{Client, ClientResult, MAX_PAGE_SIZE},
+    clockwork_automation_program::state::Automation,
+    solana_client::rpc_filter::{Memcmp, RpcFilterType},
+    solana_sdk::pubkey::Pubkey,
+};
+
+/// The offset of the authority in an automation account's data, after the 8-byte discriminator.
+pub const AUTOMATION_AUTHORITY_OFFSET: usize = 8;
+
+impl Client {
+    /// Lists every automation owned by the authority.
+    /// The automations are matched on chain by their authority, then fetched and decoded a page at a time.
+    pub fn get_automations_by_authority(
+        &self,
+        authority: Pubkey,
+    ) -> ClientResult<Vec<(Pubkey, Automation)>> {
+        let mut automations = vec![];
+        for page in self.get_program_accounts_paged::<Automation>(
+            &clockwork_automation_program::ID,
+            vec![RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+                AUTOMATION_AUTHORITY_OFFSET,
+                authority.to_bytes().to_vec(),
+            ))],
+            MAX_PAGE_SIZE,
+        )? {
+            automations.extend(page?);
+        }
+        Ok(automations)
+    }
+}
@@ -0,0 +1,176 @@
+use {
+    crate::{Client, ClientError, ClientResult},
+    chrono::{DateTime, NaiveDateTime, Utc},
+    clockwork_automation_program::state::{InstructionData as ClockworkInstructionData, Trigger},
+    clockwork_cron::Schedule,
+    clockwork_network_program::state::{Pool, Worker},
+    std::str::FromStr,
+};
+
+/// The id the automation is simulated under when estimating its funding.
+const FUNDING_ESTIMATE_ID: &[u8] = b"funding-estimate";
+
+/// The percentage added on top of the estimated cost, to absorb executions that cost more than the simulated one.
+pub const FUNDING_MARGIN_PERCENT: u64 = 20;
+
+/// The lamports the automation program reimburses a worker for a transaction's base fee.
+pub const TRANSACTION_BASE_FEE_REIMBURSEMENT: u64 = 5_000;
+
+/// The most firings counted for a cron schedule, so a per-second schedule over a long horizon stays cheap to count.
+const MAX_COUNTED_EXECUTIONS: usize = 1_000_000;
+
+/// The estimated cost of running an automation over a horizon.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FundingEstimate {
+    /// The lamports one execution costs the automation: the worker fee, the transaction fee reimbursement,
+    /// and the lamports the instruction spent from the `PAYER_PUBKEY` signatory, which the automation reimburses.
+    pub cost_per_execution: u64,
+    /// The number of times the trigger is expected to fire over the horizon, if it can be predicted.
+    pub executions: Option<u64>,
+    /// The compute units the simulated execution consumed.
+    pub units_consumed: Option<u64>,
+}
+
+impl FundingEstimate {
+    /// The lamports to fund the automation with for the given number of executions, including the safety margin.
+    pub fn recommended_lamports_for(&self, executions: u64) -> u64 {
+        let cost = self.cost_per_execution.saturating_mul(executions);
+        cost.saturating_add(cost.saturating_mul(FUNDING_MARGIN_PERCENT) / 100)
+    }
+
+    /// The lamports to fund the automation with over the horizon, if its number of executions can be predicted.
+    pub fn recommended_lamports(&self) -> Option<u64> {
+        self.executions
+            .map(|executions| self.recommended_lamports_for(executions))
+    }
+}
+
+impl Client {
+    /// Estimates the lamports an automation needs to run its kickoff instruction for the given number of seconds.
+    /// One execution is simulated to measure its cost, and the trigger decides how many times it will be paid.
+    pub fn estimate_automation_funding(
+        &self,
+        kickoff_instruction: ClockworkInstructionData,
+        trigger: &Trigger,
+        horizon_seconds: i64,
+        worker_id: u64,
+    ) -> ClientResult<FundingEstimate> {
+        let simulation = self.simulate_automation(
+            FUNDING_ESTIMATE_ID.to_vec(),
+            kickoff_instruction,
+            worker_id,
+        )?;
+        if let Some(err) = simulation.err {
+            return Err(ClientError::SimulationFailed(err));
+        }
+        let automation = simulation
+            .automation
+            .ok_or(ClientError::DeserializationError)?;
+
+        // The simulated exec only pays the worker fee if the worker is in the pool, but a live one will.
+        let pool = self.get::<Pool>(&Pool::pubkey(0))?;
+        let mut cost_per_execution = automation.lifetime_spent;
+        if !pool.workers.contains(&Worker::pubkey(worker_id)) {
+            cost_per_execution = cost_per_execution.saturating_add(automation.fee);
+        }
+
+        let now = self.get_clock()?.unix_timestamp;
+        Ok(FundingEstimate {
+            cost_per_execution,
+            executions: expected_executions(trigger, now, horizon_seconds),
+            units_consumed: simulation.units_consumed,
+        })
+    }
+}
+
+/// Returns the balance an automation with the given worker fee needs to pay for an exec without dropping below its
+/// rent-exempt minimum. Any lamports its instructions spend from the `PAYER_PUBKEY` signatory come on top of this.
+pub fn minimum_exec_balance(fee: u64, rent_exempt_minimum: u64) -> u64 {
+    rent_exempt_minimum
+        .saturating_add(fee)
+        .saturating_add(TRANSACTION_BASE_FEE_REIMBURSEMENT)
+}
+
+/// Returns the number of times the trigger is expected to fire within the horizon from `now`.
+/// Triggers that depend on account changes or epoch boundaries cannot be predicted and return `None`.
+/// Epoch filters are ignored, so a filtered cron schedule is overestimated rather than underfunded.
+pub fn expected_executions(trigger: &Trigger, now: i64, horizon_seconds: i64) -> Option<u64> {
+    match trigger {
+        Trigger::Account { .. } | Trigger::AccountValue { .. } | Trigger::Epoch { .. } => None,
+        Trigger::Immediate
+        | Trigger::Slot { .. }
+        | Trigger::EpochFilteredSlot { .. }
+        | Trigger::Timestamp { .. } => Some(1),
+        Trigger::Cron { schedule, .. } | Trigger::EpochFilteredCron { schedule, .. } => {
+            let schedule = Schedule::from_str(schedule).ok()?;
+            let end = now.saturating_add(horizon_seconds);
+            let count = schedule
+                .after(&DateTime::<Utc>::from_utc(
+                    NaiveDateTime::from_timestamp(now, 0),
+                    Utc,
+                ))
+                .take(MAX_COUNTED_EXECUTIONS)
+                .take_while(|datetime| datetime.timestamp() <= end)
+                .count();
+            Some(count as u64)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cron(schedule: &str) -> Trigger {
+        Trigger::Cron {
+            schedule: schedule.into(),
+            skippable: true,
+        }
+    }
+
+    #[test]
+    fn test_expected_executions_counts_cron_firings() {
+        // 2023-01-01 00:00:00 UTC
+        let now = 1_672_531_200;
+        assert_eq!(expected_executions(&cron("0 * * * * * *"), now, 3_600), Some(60));
+        assert_eq!(expected_executions(&cron("0 0 * * * * *"), now, 86_400), Some(24));
+        assert_eq!(expected_executions(&cron("not a schedule"), now, 3_600), None);
+    }
+
+    #[test]
+    fn test_expected_executions_of_other_triggers() {
+        assert_eq!(expected_executions(&Trigger::Immediate, 0, 3_600), Some(1));
+        assert_eq!(
+            expected_executions(&Trigger::Timestamp { unix_ts: 60 }, 0, 3_600),
+            Some(1)
+        );
+        assert_eq!(
+            expected_executions(&Trigger::Epoch { skippable: true }, 0, 3_600),
+            None
+        );
+    }
+
+    #[test]
+    fn test_recommended_lamports_include_margin() {
+        let estimate = FundingEstimate {
+            cost_per_execution: 6_000,
+            executions: Some(10),
+            units_consumed: None,
+        };
+        assert_eq!(estimate.recommended_lamports(), Some(72_000));
+        assert_eq!(
+            FundingEstimate {
+                executions: None,
+                ..estimate
+            }
+            .recommended_lamports(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_minimum_exec_balance() {
+        assert_eq!(minimum_exec_balance(1_000, 2_000_000), 2_006_000);
+        assert_eq!(minimum_exec_balance(u64::MAX, 2_000_000), u64::MAX);
+    }
+}
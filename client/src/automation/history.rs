@@ -0,0 +1,82 @@
+use {
+    crate::{Client, ClientResult},
+    anchor_lang::solana_program::pubkey::Pubkey,
+    solana_client::{
+        rpc_client::GetConfirmedSignaturesForAddress2Config, rpc_config::RpcTransactionConfig,
+    },
+    solana_sdk::{commitment_config::CommitmentConfig, signature::Signature, transaction::TransactionError},
+    std::str::FromStr,
+};
+
+/// A confirmed transaction that touched an automation.
+#[derive(Clone, Debug)]
+pub struct AutomationTransaction {
+    pub signature: Signature,
+    pub slot: u64,
+    /// The error the transaction failed with, if any.
+    pub err: Option<TransactionError>,
+    pub logs: Vec<String>,
+}
+
+impl Client {
+    /// Walks the automation's signature history from newest to oldest and fetches the logs of up to `limit` transactions.
+    /// Only transactions that landed on chain are found. Failed simulations are never recorded.
+    pub fn get_automation_transactions(
+        &self,
+        automation: &Pubkey,
+        limit: usize,
+        failed_only: bool,
+    ) -> ClientResult<Vec<AutomationTransaction>> {
+        let mut transactions = vec![];
+        let mut before = None;
+        while transactions.len() < limit {
+            let page = self.get_signatures_for_address_with_config(
+                automation,
+                GetConfirmedSignaturesForAddress2Config {
+                    before,
+                    until: None,
+                    limit: None,
+                    commitment: Some(CommitmentConfig::confirmed()),
+                },
+            )?;
+            before = match page.last() {
+                None => break,
+                Some(status) => Signature::from_str(&status.signature).ok(),
+            };
+            for status in page {
+                if transactions.len() >= limit {
+                    break;
+                }
+                if failed_only && status.err.is_none() {
+                    continue;
+                }
+                let signature = match Signature::from_str(&status.signature) {
+                    Err(_) => continue,
+                    Ok(signature) => signature,
+                };
+                let tx = self.get_transaction_with_config(
+                    &signature,
+                    RpcTransactionConfig {
+                        commitment: Some(CommitmentConfig::confirmed()),
+                        max_supported_transaction_version: Some(0),
+                        ..RpcTransactionConfig::default()
+                    },
+                )?;
+                let logs: Option<Vec<String>> = tx
+                    .transaction
+                    .meta
+                    .and_then(|meta| meta.log_messages.into());
+                transactions.push(AutomationTransaction {
+                    signature,
+                    slot: status.slot,
+                    err: status.err,
+                    logs: logs.unwrap_or_default(),
+                });
+            }
+            if before.is_none() {
+                break;
+            }
+        }
+        Ok(transactions)
+    }
+}
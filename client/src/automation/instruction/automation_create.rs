@@ -8,6 +8,7 @@ use {
         InstructionData,
     },
     clockwork_automation_program::state::{InstructionData as ClockworkInstructionData, Trigger},
+    clockwork_network_program::state::Config,
 };
 
 pub fn automation_create(
@@ -17,14 +18,17 @@ pub fn automation_create(
     instructions: Vec<ClockworkInstructionData>,
     payer: Pubkey,
     automation: Pubkey,
+    treasury: Pubkey,
     trigger: Trigger,
 ) -> Instruction {
     Instruction {
         program_id: clockwork_automation_program::ID,
         accounts: vec![
             AccountMeta::new_readonly(authority, true),
+            AccountMeta::new_readonly(Config::pubkey(), false),
             AccountMeta::new(payer, true),
             AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(treasury, false),
             AccountMeta::new(automation, false),
         ],
         data: clockwork_automation_program::instruction::AutomationCreate {
@@ -0,0 +1,38 @@
+use {
+    anchor_lang::{
+        solana_program::{
+            instruction::{AccountMeta, Instruction},
+            pubkey::Pubkey,
+            system_program,
+        },
+        InstructionData,
+    },
+    clockwork_automation_program::state::{Automation, AutomationCreateParams},
+    clockwork_network_program::state::Config,
+};
+
+pub fn automation_create_batch(
+    authority: Pubkey,
+    automations: Vec<AutomationCreateParams>,
+    payer: Pubkey,
+    treasury: Pubkey,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(authority, true),
+        AccountMeta::new_readonly(Config::pubkey(), false),
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(system_program::ID, false),
+        AccountMeta::new(treasury, false),
+    ];
+    accounts.extend(
+        automations
+            .iter()
+            .map(|params| AccountMeta::new(Automation::pubkey(authority, params.id.clone()), false)),
+    );
+    Instruction {
+        program_id: clockwork_automation_program::ID,
+        accounts,
+        data: clockwork_automation_program::instruction::AutomationCreateBatch { automations }
+            .data(),
+    }
+}
@@ -5,16 +5,23 @@ use anchor_lang::{
     },
     InstructionData,
 };
-use clockwork_network_program::state::{Fee, Pool};
+use clockwork_network_program::state::{Fee, ProgramStatus};
 
-pub fn automation_exec(signatory: Pubkey, automation: Pubkey, worker: Pubkey) -> Instruction {
+pub fn automation_exec(
+    signatory: Pubkey,
+    automation: Pubkey,
+    pool: Pubkey,
+    program_id: Pubkey,
+    worker: Pubkey,
+) -> Instruction {
     Instruction {
         program_id: clockwork_automation_program::ID,
         accounts: vec![
             AccountMeta::new(Fee::pubkey(worker), false),
-            AccountMeta::new_readonly(Pool::pubkey(0), false),
+            AccountMeta::new_readonly(pool, false),
             AccountMeta::new(signatory, true),
             AccountMeta::new(automation, false),
+            AccountMeta::new_readonly(ProgramStatus::pubkey(program_id), false),
             AccountMeta::new_readonly(worker, false),
         ],
         data: clockwork_automation_program::instruction::AutomationExec {}.data(),
@@ -0,0 +1,20 @@
+use anchor_lang::{
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+        system_program,
+    },
+    InstructionData,
+};
+
+pub fn automation_migrate(payer: Pubkey, automation: Pubkey) -> Instruction {
+    Instruction {
+        program_id: clockwork_automation_program::ID,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(automation, false),
+        ],
+        data: clockwork_automation_program::instruction::AutomationMigrate {}.data(),
+    }
+}
@@ -1,7 +1,9 @@
 mod automation_create;
+mod automation_create_batch;
 mod automation_delete;
 mod automation_exec;
 mod automation_kickoff;
+mod automation_migrate;
 mod automation_pause;
 mod automation_reset;
 mod automation_resume;
@@ -9,9 +11,11 @@ mod automation_update;
 mod get_crate_info;
 
 pub use automation_create::*;
+pub use automation_create_batch::*;
 pub use automation_delete::*;
 pub use automation_exec::*;
 pub use automation_kickoff::*;
+pub use automation_migrate::*;
 pub use automation_pause::*;
 pub use automation_reset::*;
 pub use automation_resume::*;
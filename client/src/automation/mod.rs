@@ -1,5 +1,22 @@
 pub mod instruction;
 
+mod batch_create;
+mod builder;
+mod clone;
+mod discovery;
+mod funding;
+mod history;
+mod schedule;
+mod simulation;
+
+pub use batch_create::*;
+pub use builder::*;
+pub use clone::*;
+pub use discovery::*;
+pub use funding::*;
+pub use history::*;
+pub use schedule::*;
+pub use simulation::*;
 pub use clockwork_automation_program::errors;
 pub use clockwork_automation_program::state;
 pub use clockwork_automation_program::ID;
@@ -0,0 +1,111 @@
+use {
+    chrono::{DateTime, NaiveDateTime, Utc},
+    clockwork_automation_program::state::{Automation, Trigger},
+    clockwork_cron::Schedule,
+    std::str::FromStr,
+};
+
+/// Predicts when an automation's trigger will next fire.
+pub trait AutomationSchedule {
+    /// Returns the unix timestamp the automation's trigger next fires at, after the given time.
+    /// Cron schedules fire at their next moment, timestamp triggers at their timestamp, and immediate triggers
+    /// fired when the automation was created. Triggers that wait on accounts, slots, or epochs cannot be
+    /// predicted in time and return `None`.
+    fn next_execution_time(&self, after: i64) -> Option<i64>;
+}
+
+impl AutomationSchedule for Automation {
+    fn next_execution_time(&self, after: i64) -> Option<i64> {
+        match &self.trigger {
+            Trigger::Account { .. }
+            | Trigger::AccountValue { .. }
+            | Trigger::Epoch { .. }
+            | Trigger::Slot { .. }
+            | Trigger::EpochFilteredSlot { .. } => None,
+            Trigger::Immediate => Some(self.created_at.unix_timestamp),
+            Trigger::Timestamp { unix_ts } => Some(*unix_ts),
+            Trigger::Cron { schedule, .. } | Trigger::EpochFilteredCron { schedule, .. } => {
+                Schedule::from_str(schedule)
+                    .ok()?
+                    .next_after(&DateTime::<Utc>::from_utc(
+                        NaiveDateTime::from_timestamp(after, 0),
+                        Utc,
+                    ))
+                    .take()
+                    .map(|datetime| datetime.timestamp())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        clockwork_automation_program::state::{ClockData, ExecMode},
+        solana_sdk::pubkey::Pubkey,
+    };
+
+    fn automation(trigger: Trigger) -> Automation {
+        Automation {
+            authority: Pubkey::new_unique(),
+            bump: 255,
+            chain_depth: 0,
+            created_at: ClockData {
+                slot: 100,
+                epoch: 1,
+                unix_timestamp: 1_672_531_000,
+            },
+            description: String::new(),
+            exec_context: None,
+            exec_mode: ExecMode::Worker,
+            execution_count: 0,
+            fee: 1_000,
+            id: b"schedule".to_vec(),
+            instructions: vec![],
+            lifetime_spend_cap: None,
+            lifetime_spent: 0,
+            max_chain_depth: None,
+            max_executions: None,
+            max_simulation_failures: None,
+            name: String::new(),
+            next_instruction: None,
+            paused: false,
+            rate_limit: u64::MAX,
+            trigger,
+        }
+    }
+
+    #[test]
+    fn test_next_execution_time_of_cron() {
+        // 2023-01-01 00:00:00 UTC
+        let now = 1_672_531_200;
+        let automation = automation(Trigger::Cron {
+            schedule: "0 0 * * * * *".into(),
+            skippable: true,
+        });
+        assert_eq!(automation.next_execution_time(now), Some(now + 3_600));
+        assert_eq!(automation.next_execution_time(now + 1), Some(now + 3_600));
+    }
+
+    #[test]
+    fn test_next_execution_time_of_other_triggers() {
+        assert_eq!(
+            automation(Trigger::Immediate).next_execution_time(1_672_531_200),
+            Some(1_672_531_000)
+        );
+        assert_eq!(
+            automation(Trigger::Timestamp { unix_ts: 1_700_000_000 }).next_execution_time(0),
+            Some(1_700_000_000)
+        );
+        assert_eq!(
+            automation(Trigger::Account {
+                address: Pubkey::new_unique(),
+                offset: 0,
+                size: 32,
+            })
+            .next_execution_time(0),
+            None
+        );
+    }
+}
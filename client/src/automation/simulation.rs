@@ -0,0 +1,117 @@
+use {
+    crate::{Client, ClientResult},
+    anchor_lang::{
+        solana_program::instruction::{AccountMeta, Instruction},
+        AccountDeserialize,
+    },
+    clockwork_automation_program::state::{
+        Automation, InstructionData as ClockworkInstructionData, Trigger,
+    },
+    clockwork_network_program::state::{Config, Pool, Worker},
+    solana_account_decoder::UiAccountEncoding,
+    solana_client::rpc_config::{RpcSimulateTransactionAccountsConfig, RpcSimulateTransactionConfig},
+    solana_sdk::{
+        account::Account, commitment_config::CommitmentConfig,
+        compute_budget::ComputeBudgetInstruction,
+        transaction::{Transaction, TransactionError},
+    },
+};
+
+/// Max compute units that may be used by a transaction.
+const TRANSACTION_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+
+/// The lamports to fund the simulated automation with, enough to cover an exec's fee and reimbursement.
+const SIMULATION_FUNDING_LAMPORTS: u64 = 1_000_000;
+
+/// The outcome of simulating an automation's first exec.
+#[derive(Clone, Debug)]
+pub struct AutomationSimulation {
+    /// The simulated automation's state after the exec, if the simulation succeeded.
+    pub automation: Option<Automation>,
+    /// The error the transaction failed with, if any.
+    pub err: Option<TransactionError>,
+    pub logs: Vec<String>,
+    pub units_consumed: Option<u64>,
+}
+
+impl Client {
+    /// Simulates creating an automation and running its kickoff instruction through an exec, the way a worker would.
+    /// The automation is created with an immediate trigger in the same transaction, so nothing needs to exist on chain
+    /// beyond the network config and the worker. Nothing is committed and no lamports are spent.
+    pub fn simulate_automation(
+        &self,
+        id: Vec<u8>,
+        kickoff_instruction: ClockworkInstructionData,
+        worker_id: u64,
+    ) -> ClientResult<AutomationSimulation> {
+        let config = self.get::<Config>(&Config::pubkey())?;
+        let automation_pubkey = Automation::pubkey(self.payer_pubkey(), id.clone());
+        let worker_pubkey = Worker::pubkey(worker_id);
+
+        // Build the exec instruction as the plugin would, injecting the target program and its accounts.
+        let mut exec_ix = crate::automation::instruction::automation_exec(
+            self.payer_pubkey(),
+            automation_pubkey,
+            Pool::pubkey(0),
+            kickoff_instruction.program_id,
+            worker_pubkey,
+        );
+        exec_ix.accounts.push(AccountMeta::new_readonly(
+            kickoff_instruction.program_id,
+            false,
+        ));
+        for acc in kickoff_instruction.exec_accounts(self.payer_pubkey()) {
+            exec_ix.accounts.push(match acc.is_writable {
+                true => AccountMeta::new(acc.pubkey, false),
+                false => AccountMeta::new_readonly(acc.pubkey, false),
+            })
+        }
+
+        let ixs: Vec<Instruction> = vec![
+            ComputeBudgetInstruction::set_compute_unit_limit(TRANSACTION_COMPUTE_UNIT_LIMIT),
+            crate::automation::instruction::automation_create(
+                SIMULATION_FUNDING_LAMPORTS,
+                self.payer_pubkey(),
+                id,
+                vec![kickoff_instruction],
+                self.payer_pubkey(),
+                automation_pubkey,
+                config.treasury,
+                Trigger::Immediate,
+            ),
+            crate::automation::instruction::automation_kickoff(
+                self.payer_pubkey(),
+                automation_pubkey,
+                worker_pubkey,
+            ),
+            exec_ix,
+        ];
+        let mut tx = Transaction::new_with_payer(&ixs, Some(&self.payer_pubkey()));
+        tx.sign(&[self.payer()], self.latest_blockhash()?);
+        let response = self.client.simulate_transaction_with_config(
+            &tx,
+            RpcSimulateTransactionConfig {
+                replace_recent_blockhash: true,
+                commitment: Some(CommitmentConfig::processed()),
+                accounts: Some(RpcSimulateTransactionAccountsConfig {
+                    encoding: Some(UiAccountEncoding::Base64),
+                    addresses: vec![automation_pubkey.to_string()],
+                }),
+                ..RpcSimulateTransactionConfig::default()
+            },
+        )?;
+        let automation = response
+            .value
+            .accounts
+            .and_then(|accounts| accounts.into_iter().next().flatten())
+            .and_then(|ui_account| ui_account.decode::<Account>())
+            .and_then(|account| Automation::try_deserialize(&mut account.data.as_slice()).ok());
+        Ok(AutomationSimulation {
+            automation,
+            err: response.value.err,
+            logs: response.value.logs.unwrap_or_default(),
+            units_consumed: response.value.units_consumed,
+        })
+    }
+}
+
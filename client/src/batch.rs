@@ -0,0 +1,139 @@
+use {
+    crate::{Client, ClientError, ClientResult},
+    solana_sdk::{
+        compute_budget::ComputeBudgetInstruction,
+        instruction::Instruction,
+        packet::PACKET_DATA_SIZE,
+        pubkey::Pubkey,
+        signature::{Keypair, Signature, Signer},
+        transaction::Transaction,
+    },
+};
+
+/// Max compute units that may be used by a transaction.
+pub(crate) const TRANSACTION_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+
+/// Number of times to send a transaction of a batch before giving up on it.
+const MAX_SEND_ATTEMPTS: usize = 3;
+
+impl Client {
+    /// Packs the instructions into as few transactions as fit the size limit, then sends and confirms them in order.
+    ///
+    /// Each transaction requests the max compute unit limit so several instructions can share it. Transactions
+    /// are retried with a fresh blockhash if they fail to land, but not if they fail on chain. Solana has no
+    /// cross-transaction rollback, so a failure stops the batch and reports the signatures that already landed.
+    /// The payer must be the only signer the instructions require.
+    pub fn submit_batch(
+        &self,
+        instructions: Vec<Instruction>,
+        payer: &Keypair,
+    ) -> ClientResult<Vec<Signature>> {
+        let mut signatures = vec![];
+        for ixs in pack_instructions(instructions, &payer.pubkey()) {
+            match self.send_and_confirm_with_retry(&ixs, payer) {
+                Ok(signature) => signatures.push(signature),
+                Err(err) => {
+                    return Err(ClientError::BatchFailed {
+                        signatures,
+                        source: Box::new(err),
+                    })
+                }
+            }
+        }
+        Ok(signatures)
+    }
+
+    fn send_and_confirm_with_retry(
+        &self,
+        ixs: &[Instruction],
+        payer: &Keypair,
+    ) -> ClientResult<Signature> {
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            let mut tx = Transaction::new_with_payer(ixs, Some(&payer.pubkey()));
+            tx.sign(&[payer], self.latest_blockhash()?);
+            match self.send_and_confirm_transaction(&tx) {
+                Ok(signature) => return Ok(signature),
+                // Transactions that executed and failed would fail again, so only retry those that did not land.
+                Err(err) if err.get_transaction_error().is_none() && attempts < MAX_SEND_ATTEMPTS => {}
+                Err(err) => return Err(err.into()),
+            }
+        }
+    }
+}
+
+/// Greedily groups the instructions, in order, into transactions that fit within the packet size limit.
+/// Each group starts with a compute unit limit instruction. An instruction too large to fit alongside it
+/// is still given its own group, so the failure surfaces when it is sent.
+pub fn pack_instructions(instructions: Vec<Instruction>, payer: &Pubkey) -> Vec<Vec<Instruction>> {
+    let compute_budget_ix =
+        ComputeBudgetInstruction::set_compute_unit_limit(TRANSACTION_COMPUTE_UNIT_LIMIT);
+    let mut groups = vec![];
+    let mut group = vec![compute_budget_ix.clone()];
+    for ix in instructions {
+        group.push(ix);
+        if group.len() > 2 && transaction_size(&group, payer) > PACKET_DATA_SIZE {
+            let ix = group.pop().unwrap();
+            groups.push(group);
+            group = vec![compute_budget_ix.clone(), ix];
+        }
+    }
+    if group.len() > 1 {
+        groups.push(group);
+    }
+    groups
+}
+
+/// The serialized size of a signed transaction of the instructions.
+pub(crate) fn transaction_size(ixs: &[Instruction], payer: &Pubkey) -> usize {
+    let tx = Transaction::new_with_payer(ixs, Some(payer));
+    bincode::serialized_size(&tx).unwrap_or(u64::MAX) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::{instruction::AccountMeta, system_instruction};
+
+    #[test]
+    fn test_pack_instructions_in_order() {
+        let payer = Pubkey::new_unique();
+        let ixs = (0..5)
+            .map(|i| system_instruction::transfer(&payer, &Pubkey::new_unique(), i))
+            .collect::<Vec<Instruction>>();
+        let groups = pack_instructions(ixs.clone(), &payer);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0][1..], ixs[..]);
+    }
+
+    #[test]
+    fn test_pack_instructions_respects_size_limit() {
+        let payer = Pubkey::new_unique();
+        let program_id = Pubkey::new_unique();
+        let ixs = (0..6)
+            .map(|_| {
+                Instruction::new_with_bytes(
+                    program_id,
+                    &[0; 400],
+                    vec![AccountMeta::new(Pubkey::new_unique(), false)],
+                )
+            })
+            .collect::<Vec<Instruction>>();
+        let groups = pack_instructions(ixs.clone(), &payer);
+        assert!(groups.len() > 1);
+        for group in groups.iter() {
+            assert!(transaction_size(group, &payer) <= PACKET_DATA_SIZE);
+        }
+        let packed = groups
+            .into_iter()
+            .flat_map(|group| group.into_iter().skip(1))
+            .collect::<Vec<Instruction>>();
+        assert_eq!(packed, ixs);
+    }
+
+    #[test]
+    fn test_pack_instructions_empty() {
+        assert!(pack_instructions(vec![], &Pubkey::new_unique()).is_empty());
+    }
+}
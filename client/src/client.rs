@@ -1,3 +1,4 @@
+use crate::automation::AutomationBuilderError;
 use anchor_lang::{prelude::Clock, AccountDeserialize};
 use anchor_spl::token::{
     spl_token::{self, state::Account as TokenAccount},
@@ -5,8 +6,8 @@ use anchor_spl::token::{
 };
 use clockwork_utils::ProgramLogsDeserializable;
 use solana_client::{
-    client_error, rpc_client::RpcClient, rpc_config::RpcSendTransactionConfig,
-    rpc_response::RpcSimulateTransactionResult,
+    client_error, pubsub_client::PubsubClientError, rpc_client::RpcClient,
+    rpc_config::RpcSendTransactionConfig, rpc_response::RpcSimulateTransactionResult,
 };
 use solana_sdk::{
     commitment_config::CommitmentConfig,
@@ -18,7 +19,7 @@ use solana_sdk::{
     signature::{Keypair, Signature, Signer},
     signers::Signers,
     system_instruction,
-    transaction::Transaction,
+    transaction::{Transaction, TransactionError},
 };
 use std::{
     fmt::Debug,
@@ -37,6 +38,21 @@ pub enum ClientError {
 
     #[error("Failed to deserialize account data")]
     DeserializationError,
+
+    #[error("Batch failed after {} confirmed transactions: {source}", signatures.len())]
+    BatchFailed {
+        signatures: Vec<Signature>,
+        source: Box<ClientError>,
+    },
+
+    #[error("Simulation failed: {0}")]
+    SimulationFailed(TransactionError),
+
+    #[error(transparent)]
+    Builder(#[from] AutomationBuilderError),
+
+    #[error(transparent)]
+    Pubsub(#[from] PubsubClientError),
 }
 
 pub type ClientResult<T> = Result<T, ClientError>;
@@ -52,6 +68,11 @@ impl Client {
         Self { client, payer }
     }
 
+    pub fn new_with_commitment(payer: Keypair, url: String, commitment: CommitmentConfig) -> Self {
+        let client = RpcClient::new_with_commitment::<String>(url, commitment);
+        Self { client, payer }
+    }
+
     pub fn get<T: AccountDeserialize>(&self, pubkey: &Pubkey) -> ClientResult<T> {
         let data = self.client.get_account_data(pubkey)?;
         T::try_deserialize(&mut data.as_slice()).map_err(|_| ClientError::DeserializationError)
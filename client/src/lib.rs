@@ -2,5 +2,9 @@ pub mod network;
 pub mod automation;
 pub mod webhook;
 
+mod batch;
 mod client;
+mod program_accounts;
+pub use batch::*;
 pub use client::{Client, ClientError, ClientResult, SplToken};
+pub use program_accounts::*;
@@ -0,0 +1,70 @@
+use {
+    anchor_lang::solana_program::pubkey::Pubkey,
+    anchor_spl::associated_token::get_associated_token_address,
+    clockwork_network_program::state::*,
+};
+
+/// The full set of addresses derived for a worker deployment.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WorkerAddresses {
+    pub fee: Pubkey,
+    pub penalty: Pubkey,
+    pub snapshot: Pubkey,
+    pub snapshot_frame: Pubkey,
+    pub stake_tokens: Pubkey,
+    pub worker: Pubkey,
+}
+
+/// Derives all addresses relevant to a worker for the given mint and epoch. Does not make any network requests.
+pub fn worker_addresses(worker_id: u64, mint: Pubkey, epoch: u64) -> WorkerAddresses {
+    let worker = Worker::pubkey(worker_id);
+    let snapshot = Snapshot::pubkey(epoch);
+    WorkerAddresses {
+        fee: Fee::pubkey(worker),
+        penalty: Penalty::pubkey(worker),
+        snapshot,
+        snapshot_frame: SnapshotFrame::pubkey(snapshot, worker_id),
+        stake_tokens: get_associated_token_address(&worker, &mint),
+        worker,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_worker_addresses() {
+        let mint = Pubkey::new_unique();
+        let addresses = worker_addresses(3, mint, 7);
+
+        let worker = Worker::pubkey(3);
+        let snapshot = Snapshot::pubkey(7);
+        assert_eq!(
+            addresses,
+            WorkerAddresses {
+                fee: Fee::pubkey(worker),
+                penalty: Penalty::pubkey(worker),
+                snapshot,
+                snapshot_frame: SnapshotFrame::pubkey(snapshot, 3),
+                stake_tokens: get_associated_token_address(&worker, &mint),
+                worker,
+            }
+        );
+        assert_eq!(
+            addresses.worker,
+            Pubkey::find_program_address(
+                &[SEED_WORKER, 3u64.to_be_bytes().as_ref()],
+                &clockwork_network_program::ID
+            )
+            .0
+        );
+
+        // The snapshot addresses follow the epoch, the worker's own addresses do not.
+        let next_epoch = worker_addresses(3, mint, 8);
+        assert_eq!(next_epoch.fee, addresses.fee);
+        assert_eq!(next_epoch.worker, addresses.worker);
+        assert_ne!(next_epoch.snapshot, addresses.snapshot);
+        assert_ne!(next_epoch.snapshot_frame, addresses.snapshot_frame);
+    }
+}
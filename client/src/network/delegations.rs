@@ -0,0 +1,59 @@
+use {
+    crate::{Client, ClientResult, MAX_PAGE_SIZE},
+    anchor_lang::solana_program::pubkey::Pubkey,
+    clockwork_network_program::state::*,
+    solana_client::rpc_filter::{Memcmp, RpcFilterType},
+};
+
+/// The offset of the worker in a delegation account's data, after the discriminator, authority, id, and stake amount.
+pub const DELEGATION_WORKER_OFFSET: usize = 8 + 32 + 8 + 8;
+
+impl Client {
+    /// Lists every delegation to the worker, ordered by id.
+    /// The delegations are matched on chain by their worker, then fetched and decoded a page at a time.
+    pub fn get_delegations_by_worker(
+        &self,
+        worker_id: u64,
+    ) -> ClientResult<Vec<(Pubkey, Delegation)>> {
+        let mut delegations = vec![];
+        for page in self.get_program_accounts_paged::<Delegation>(
+            &clockwork_network_program::ID,
+            vec![RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+                DELEGATION_WORKER_OFFSET,
+                Worker::pubkey(worker_id).to_bytes().to_vec(),
+            ))],
+            MAX_PAGE_SIZE,
+        )? {
+            delegations.extend(page?);
+        }
+        delegations.sort_by_key(|(_, delegation)| delegation.id);
+        Ok(delegations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, anchor_lang::AccountSerialize};
+
+    #[test]
+    fn test_delegation_worker_offset() {
+        let worker = Worker::pubkey(7);
+        let delegation = Delegation {
+            authority: Pubkey::new_unique(),
+            id: 3,
+            stake_amount: 1_000,
+            worker,
+            yield_balance: 0,
+            pending_withdrawal: 0,
+            unbonding_amount: 0,
+            withdrawal_epoch: 0,
+            stake_kind: StakeKind::Token,
+        };
+        let mut data = vec![];
+        delegation.try_serialize(&mut data).unwrap();
+        assert_eq!(
+            &data[DELEGATION_WORKER_OFFSET..DELEGATION_WORKER_OFFSET + 32],
+            worker.as_ref()
+        );
+    }
+}
@@ -0,0 +1,21 @@
+use {
+    anchor_lang::{
+        solana_program::{
+            instruction::{AccountMeta, Instruction},
+            pubkey::Pubkey,
+        },
+        InstructionData,
+    },
+    clockwork_network_program::state::*,
+};
+
+pub fn config_accept_admin(pending_admin: Pubkey) -> Instruction {
+    Instruction {
+        program_id: clockwork_network_program::ID,
+        accounts: vec![
+            AccountMeta::new(Config::pubkey(), false),
+            AccountMeta::new_readonly(pending_admin, true),
+        ],
+        data: clockwork_network_program::instruction::ConfigAcceptAdmin {}.data(),
+    }
+}
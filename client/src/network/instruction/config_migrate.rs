@@ -0,0 +1,23 @@
+use {
+    anchor_lang::{
+        solana_program::{
+            instruction::{AccountMeta, Instruction},
+            pubkey::Pubkey,
+            system_program,
+        },
+        InstructionData,
+    },
+    clockwork_network_program::state::*,
+};
+
+pub fn config_migrate(admin: Pubkey) -> Instruction {
+    Instruction {
+        program_id: clockwork_network_program::ID,
+        accounts: vec![
+            AccountMeta::new(admin, true),
+            AccountMeta::new(Config::pubkey(), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: clockwork_network_program::instruction::ConfigMigrate {}.data(),
+    }
+}
@@ -0,0 +1,21 @@
+use {
+    anchor_lang::{
+        solana_program::{
+            instruction::{AccountMeta, Instruction},
+            pubkey::Pubkey,
+        },
+        InstructionData,
+    },
+    clockwork_network_program::state::*,
+};
+
+pub fn config_propose_admin(admin: Pubkey, pending_admin: Pubkey) -> Instruction {
+    Instruction {
+        program_id: clockwork_network_program::ID,
+        accounts: vec![
+            AccountMeta::new_readonly(admin, true),
+            AccountMeta::new(Config::pubkey(), false),
+        ],
+        data: clockwork_network_program::instruction::ConfigProposeAdmin { pending_admin }.data(),
+    }
+}
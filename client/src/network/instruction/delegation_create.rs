@@ -7,6 +7,7 @@ use {
         },
         InstructionData,
     },
+    anchor_spl::token::spl_token::native_mint,
     clockwork_network_program::state::*,
     spl_associated_token_account::get_associated_token_address,
 };
@@ -15,6 +16,7 @@ pub fn delegation_create(
     authority: Pubkey,
     delegation: Pubkey,
     mint: Pubkey,
+    stake_kind: StakeKind,
     worker: Pubkey,
 ) -> Instruction {
     Instruction {
@@ -30,7 +32,8 @@ pub fn delegation_create(
             AccountMeta::new_readonly(system_program::ID, false),
             AccountMeta::new_readonly(anchor_spl::token::ID, false),
             AccountMeta::new(worker, false),
+            AccountMeta::new(get_associated_token_address(&worker, &native_mint::ID), false),
         ],
-        data: clockwork_network_program::instruction::DelegationCreate {}.data(),
+        data: clockwork_network_program::instruction::DelegationCreate { stake_kind }.data(),
     }
 }
@@ -0,0 +1,37 @@
+use {
+    anchor_lang::{
+        solana_program::{
+            instruction::{AccountMeta, Instruction},
+            pubkey::Pubkey,
+        },
+        InstructionData,
+    },
+    clockwork_network_program::state::*,
+    spl_associated_token_account::get_associated_token_address,
+};
+
+pub fn delegation_merge(
+    authority: Pubkey,
+    destination: Pubkey,
+    mint: Pubkey,
+    snapshot_frame: Pubkey,
+    source: Pubkey,
+    worker: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: clockwork_network_program::ID,
+        accounts: vec![
+            AccountMeta::new(authority, true),
+            AccountMeta::new_readonly(Config::pubkey(), false),
+            AccountMeta::new(destination, false),
+            AccountMeta::new(get_associated_token_address(&destination, &mint), false),
+            AccountMeta::new_readonly(Registry::pubkey(), false),
+            AccountMeta::new_readonly(snapshot_frame, false),
+            AccountMeta::new(source, false),
+            AccountMeta::new(get_associated_token_address(&source, &mint), false),
+            AccountMeta::new_readonly(anchor_spl::token::ID, false),
+            AccountMeta::new(worker, false),
+        ],
+        data: clockwork_network_program::instruction::DelegationMerge {}.data(),
+    }
+}
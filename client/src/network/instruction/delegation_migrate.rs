@@ -0,0 +1,20 @@
+use anchor_lang::{
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+        system_program,
+    },
+    InstructionData,
+};
+
+pub fn delegation_migrate(payer: Pubkey, delegation: Pubkey) -> Instruction {
+    Instruction {
+        program_id: clockwork_network_program::ID,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(delegation, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: clockwork_network_program::instruction::DelegationMigrate {}.data(),
+    }
+}
@@ -0,0 +1,22 @@
+use {
+    anchor_lang::{
+        solana_program::{
+            instruction::{AccountMeta, Instruction},
+            pubkey::Pubkey,
+        },
+        InstructionData,
+    },
+    clockwork_network_program::state::*,
+};
+
+pub fn delegation_withdraw_request(amount: u64, authority: Pubkey, delegation: Pubkey) -> Instruction {
+    Instruction {
+        program_id: clockwork_network_program::ID,
+        accounts: vec![
+            AccountMeta::new_readonly(authority, true),
+            AccountMeta::new(delegation, false),
+            AccountMeta::new_readonly(Registry::pubkey(), false),
+        ],
+        data: clockwork_network_program::instruction::DelegationWithdrawRequest { amount }.data(),
+    }
+}
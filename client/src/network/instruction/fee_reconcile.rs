@@ -0,0 +1,24 @@
+use {
+    anchor_lang::{
+        solana_program::{
+            instruction::{AccountMeta, Instruction},
+            pubkey::Pubkey,
+        },
+        InstructionData,
+    },
+    clockwork_network_program::state::*,
+};
+
+pub fn fee_reconcile(admin: Pubkey, worker: Pubkey) -> Instruction {
+    Instruction {
+        program_id: clockwork_network_program::ID,
+        accounts: vec![
+            AccountMeta::new_readonly(admin, true),
+            AccountMeta::new_readonly(Config::pubkey(), false),
+            AccountMeta::new(Fee::pubkey(worker), false),
+            AccountMeta::new_readonly(Registry::pubkey(), false),
+            AccountMeta::new_readonly(worker, false),
+        ],
+        data: clockwork_network_program::instruction::FeeReconcile {}.data(),
+    }
+}
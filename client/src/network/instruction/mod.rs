@@ -1,25 +1,53 @@
+mod config_accept_admin;
+mod config_cancel_admin;
+mod config_migrate;
+mod config_propose_admin;
 mod config_update;
 mod delegation_create;
 mod delegation_deposit;
+mod delegation_merge;
+mod delegation_migrate;
 mod delegation_withdraw;
+mod delegation_withdraw_claim;
+mod delegation_withdraw_request;
+mod fee_reconcile;
 mod initialize;
 mod pool_create;
 mod pool_rotate;
 mod pool_update;
+mod program_pause;
+mod program_resume;
 mod registry_nonce_hash;
 mod registry_unlock;
+mod unstake_migrate;
+mod worker_commission_update;
 mod worker_create;
+mod worker_migrate;
 mod worker_update;
 
+pub use config_accept_admin::*;
+pub use config_cancel_admin::*;
+pub use config_migrate::*;
+pub use config_propose_admin::*;
 pub use config_update::*;
 pub use delegation_create::*;
 pub use delegation_deposit::*;
+pub use delegation_merge::*;
+pub use delegation_migrate::*;
 pub use delegation_withdraw::*;
+pub use delegation_withdraw_claim::*;
+pub use delegation_withdraw_request::*;
+pub use fee_reconcile::*;
 pub use initialize::*;
 pub use pool_create::*;
 pub use pool_rotate::*;
 pub use pool_update::*;
+pub use program_pause::*;
+pub use program_resume::*;
 pub use registry_nonce_hash::*;
 pub use registry_unlock::*;
+pub use unstake_migrate::*;
+pub use worker_commission_update::*;
 pub use worker_create::*;
+pub use worker_migrate::*;
 pub use worker_update::*;
@@ -0,0 +1,27 @@
+use {
+    anchor_lang::{
+        solana_program::{
+            bpf_loader_upgradeable,
+            instruction::{AccountMeta, Instruction},
+            pubkey::Pubkey,
+        },
+        InstructionData,
+    },
+    clockwork_network_program::state::*,
+};
+
+pub fn program_resume(authority: Pubkey, program_id: Pubkey) -> Instruction {
+    // The program's data account lets its upgrade authority act in place of the config admin.
+    let program_data =
+        Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::ID).0;
+    Instruction {
+        program_id: clockwork_network_program::ID,
+        accounts: vec![
+            AccountMeta::new_readonly(authority, true),
+            AccountMeta::new_readonly(Config::pubkey(), false),
+            AccountMeta::new(ProgramStatus::pubkey(program_id), false),
+            AccountMeta::new_readonly(program_data, false),
+        ],
+        data: clockwork_network_program::instruction::ProgramResume { program_id }.data(),
+    }
+}
@@ -0,0 +1,20 @@
+use anchor_lang::{
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+        system_program,
+    },
+    InstructionData,
+};
+
+pub fn unstake_migrate(payer: Pubkey, unstake: Pubkey) -> Instruction {
+    Instruction {
+        program_id: clockwork_network_program::ID,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(unstake, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: clockwork_network_program::instruction::UnstakeMigrate {}.data(),
+    }
+}
@@ -0,0 +1,25 @@
+use anchor_lang::{
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+    },
+    InstructionData,
+};
+use clockwork_network_program::state::*;
+
+pub fn worker_commission_update(
+    authority: Pubkey,
+    commission_rate: u64,
+    worker: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: clockwork_network_program::ID,
+        accounts: vec![
+            AccountMeta::new_readonly(authority, true),
+            AccountMeta::new_readonly(Registry::pubkey(), false),
+            AccountMeta::new(worker, false),
+        ],
+        data: clockwork_network_program::instruction::WorkerCommissionUpdate { commission_rate }
+            .data(),
+    }
+}
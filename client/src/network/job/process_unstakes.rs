@@ -14,7 +14,7 @@ pub fn process_unstakes(automation: Pubkey) -> Instruction {
         program_id: clockwork_network_program::ID,
         accounts: vec![
             AccountMeta::new_readonly(Config::pubkey(), false),
-            AccountMeta::new_readonly(Registry::pubkey(), false),
+            AccountMeta::new(Registry::pubkey(), false),
             AccountMeta::new_readonly(automation, true),
         ],
         data: clockwork_network_program::instruction::ProcessUnstakesJob {}.data(),
@@ -1,5 +1,24 @@
 pub mod instruction;
 pub mod job;
 
+mod addresses;
+mod delegations;
+mod pipeline;
+mod pool_membership;
+mod pools;
+mod registry_lock;
+mod snapshot_verification;
+mod snapshots;
+mod workers;
+
+pub use addresses::*;
+pub use delegations::*;
+pub use pipeline::*;
+pub use pool_membership::*;
+pub use pools::*;
+pub use registry_lock::*;
+pub use snapshot_verification::*;
+pub use snapshots::*;
+pub use workers::*;
 pub use clockwork_network_program::state;
 pub use clockwork_network_program::ID;
@@ -0,0 +1,274 @@
+use {
+    super::REGISTRY_LOCK_STALL_THRESHOLD,
+    crate::{Client, ClientResult},
+    anchor_lang::{solana_program::pubkey::Pubkey, InstructionData as _},
+    clockwork_automation_program::state::Automation,
+    clockwork_network_program::{instruction as jobs, state::*},
+};
+
+/// The stage of the epoch pipeline the epoch automation is working through.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PipelineStage {
+    /// The epoch automation is waiting for its next kickoff.
+    Idle,
+    DistributingFees,
+    ProcessingUnstakes,
+    StakingDelegations,
+    TakingSnapshot,
+    IncrementingEpoch,
+    DeletingSnapshot,
+    /// The epoch automation is running an instruction that is not one of the network's jobs.
+    Unknown,
+}
+
+impl PipelineStage {
+    /// Returns the epoch of the snapshot whose frames the stage walks through, if any.
+    pub fn snapshot_epoch(&self, current_epoch: u64) -> Option<u64> {
+        match self {
+            PipelineStage::DistributingFees => Some(current_epoch),
+            PipelineStage::TakingSnapshot => current_epoch.checked_add(1),
+            _ => None,
+        }
+    }
+}
+
+/// The health of the epoch pipeline: which stage the current epoch is in and whether it has stopped progressing.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PipelineStatus {
+    pub epoch: u64,
+    pub lock_reason: RegistryLockReason,
+    pub stage: PipelineStage,
+    /// The id of the snapshot frame the stage will process next, out of the snapshot's total frames.
+    pub frame_progress: Option<(u64, u64)>,
+    /// The slot the epoch automation last executed at, if it ever has.
+    pub last_exec_at: Option<u64>,
+    /// The slot the hasher automation last executed at, if it ever has.
+    pub hasher_last_exec_at: Option<u64>,
+    /// The number of slots since the epoch automation last executed.
+    pub idle_slots: u64,
+    /// Whether the pipeline is partway through a stage but has not progressed within the stall threshold.
+    pub is_stuck: bool,
+}
+
+impl Client {
+    /// Fetches the registry, the network's automations, and the snapshot being worked on, and reports the
+    /// pipeline's health.
+    pub fn get_pipeline_status(&self) -> ClientResult<PipelineStatus> {
+        let config = self.get::<Config>(&Config::pubkey())?;
+        let registry = self.get::<Registry>(&Registry::pubkey())?;
+        let epoch_automation = self.get::<Automation>(&config.epoch_automation)?;
+        let hasher_last_exec_at = self
+            .get::<Automation>(&config.hasher_automation)?
+            .exec_context
+            .map(|exec_context| exec_context.last_exec_at);
+        let snapshot = pipeline_stage(&epoch_automation)
+            .snapshot_epoch(registry.current_epoch)
+            .and_then(|epoch| {
+                let snapshot_pubkey = Snapshot::pubkey(epoch);
+                self.get::<Snapshot>(&snapshot_pubkey)
+                    .ok()
+                    .map(|snapshot| (snapshot_pubkey, snapshot))
+            });
+        let slot = self.client.get_slot()?;
+        Ok(pipeline_status(
+            &registry,
+            &epoch_automation,
+            hasher_last_exec_at,
+            snapshot,
+            slot,
+        ))
+    }
+}
+
+/// Returns the stage of the job the epoch automation is running, identified by the instruction its chain started with.
+pub fn pipeline_stage(epoch_automation: &Automation) -> PipelineStage {
+    let exec_context = match (epoch_automation.exec_context, &epoch_automation.next_instruction) {
+        (Some(exec_context), Some(_)) => exec_context,
+        _ => return PipelineStage::Idle,
+    };
+    let job = match epoch_automation
+        .instructions
+        .get(exec_context.exec_index as usize)
+    {
+        None => return PipelineStage::Unknown,
+        Some(job) => job,
+    };
+    [
+        (jobs::DistributeFeesJob {}.data(), PipelineStage::DistributingFees),
+        (jobs::ProcessUnstakesJob {}.data(), PipelineStage::ProcessingUnstakes),
+        (jobs::StakeDelegationsJob {}.data(), PipelineStage::StakingDelegations),
+        (jobs::TakeSnapshotJob {}.data(), PipelineStage::TakingSnapshot),
+        (jobs::IncrementEpoch {}.data(), PipelineStage::IncrementingEpoch),
+        (jobs::DeleteSnapshotJob {}.data(), PipelineStage::DeletingSnapshot),
+    ]
+    .into_iter()
+    .find(|(data, _)| job.program_id.eq(&clockwork_network_program::ID) && job.data.eq(data))
+    .map_or(PipelineStage::Unknown, |(_, stage)| stage)
+}
+
+/// Returns the pipeline's health at the given slot.
+/// The snapshot is the one the current stage walks through, used to report how many of its frames are done.
+pub fn pipeline_status(
+    registry: &Registry,
+    epoch_automation: &Automation,
+    hasher_last_exec_at: Option<u64>,
+    snapshot: Option<(Pubkey, Snapshot)>,
+    slot: u64,
+) -> PipelineStatus {
+    let stage = pipeline_stage(epoch_automation);
+    let last_exec_at = epoch_automation
+        .exec_context
+        .map(|exec_context| exec_context.last_exec_at);
+    let idle_slots = slot.saturating_sub(last_exec_at.unwrap_or(0));
+    let frame_progress = snapshot.and_then(|(snapshot_pubkey, snapshot)| {
+        let next_instruction = epoch_automation.next_instruction.as_ref()?;
+        (0..snapshot.total_frames)
+            .find(|id| {
+                let frame_pubkey = SnapshotFrame::pubkey(snapshot_pubkey, *id);
+                next_instruction
+                    .accounts
+                    .iter()
+                    .any(|account| account.pubkey.eq(&frame_pubkey))
+            })
+            .map(|id| (id, snapshot.total_frames))
+    });
+    PipelineStatus {
+        epoch: registry.current_epoch,
+        lock_reason: registry.lock_reason,
+        stage,
+        frame_progress,
+        last_exec_at,
+        hasher_last_exec_at,
+        idle_slots,
+        is_stuck: (registry.is_locked() || stage != PipelineStage::Idle)
+            && idle_slots > REGISTRY_LOCK_STALL_THRESHOLD,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        clockwork_automation_program::state::{
+            AccountMetaData, ClockData, ExecContext, ExecMode, InstructionData, Trigger,
+            TriggerContext,
+        },
+    };
+
+    fn epoch_automation(exec_index: u64, next_instruction: Option<InstructionData>) -> Automation {
+        let automation_pubkey = Pubkey::new_unique();
+        Automation {
+            authority: Pubkey::new_unique(),
+            bump: 255,
+            chain_depth: 0,
+            created_at: ClockData {
+                slot: 0,
+                epoch: 0,
+                unix_timestamp: 0,
+            },
+            description: String::new(),
+            exec_context: Some(ExecContext {
+                exec_index,
+                execs_since_reimbursement: 0,
+                execs_since_slot: 0,
+                last_exec_at: 10_000,
+                trigger_context: TriggerContext::Cron { started_at: 0 },
+            }),
+            exec_mode: ExecMode::Worker,
+            execution_count: 0,
+            fee: 1_000,
+            id: b"clockwork.network.epoch".to_vec(),
+            instructions: vec![
+                crate::network::job::distribute_fees(automation_pubkey).into(),
+                crate::network::job::process_unstakes(automation_pubkey).into(),
+                crate::network::job::stake_delegations(automation_pubkey).into(),
+                crate::network::job::take_snapshot(automation_pubkey).into(),
+                crate::network::job::increment_epoch(automation_pubkey).into(),
+                crate::network::job::delete_snapshot(automation_pubkey).into(),
+            ],
+            lifetime_spend_cap: None,
+            lifetime_spent: 0,
+            max_chain_depth: None,
+            max_executions: None,
+            max_simulation_failures: None,
+            name: String::new(),
+            next_instruction,
+            paused: false,
+            rate_limit: u64::MAX,
+            trigger: Trigger::Cron {
+                schedule: "0 * * * * * *".into(),
+                skippable: true,
+            },
+        }
+    }
+
+    fn registry(lock_reason: RegistryLockReason) -> Registry {
+        Registry {
+            current_epoch: 7,
+            lock_reason,
+            nonce: 0,
+            total_pools: 1,
+            total_unstakes: 0,
+            total_workers: 3,
+        }
+    }
+
+    #[test]
+    fn test_idle_pipeline_is_not_stuck() {
+        let status = pipeline_status(
+            &registry(RegistryLockReason::Unlocked),
+            &epoch_automation(5, None),
+            Some(10_000),
+            None,
+            1_000_000,
+        );
+        assert_eq!(status.stage, PipelineStage::Idle);
+        assert!(!status.is_stuck);
+    }
+
+    #[test]
+    fn test_stall_at_distribution_stage() {
+        let snapshot_pubkey = Snapshot::pubkey(7);
+        let snapshot = Snapshot {
+            id: 7,
+            total_frames: 3,
+            total_stake: 300,
+        };
+
+        // The epoch automation is about to distribute the fees of the second worker's frame.
+        let process_frame = InstructionData {
+            program_id: clockwork_network_program::ID,
+            accounts: vec![
+                AccountMetaData::new_readonly(Registry::pubkey(), false),
+                AccountMetaData::new_readonly(snapshot_pubkey, false),
+                AccountMetaData::new_readonly(SnapshotFrame::pubkey(snapshot_pubkey, 1), false),
+            ],
+            data: vec![],
+        };
+        let automation = epoch_automation(0, Some(process_frame));
+        let reason = RegistryLockReason::Distributing;
+
+        let status = pipeline_status(
+            &registry(reason),
+            &automation,
+            Some(10_000),
+            Some((snapshot_pubkey, snapshot.clone())),
+            10_000 + 40,
+        );
+        assert_eq!(status.stage, PipelineStage::DistributingFees);
+        assert_eq!(status.frame_progress, Some((1, 3)));
+        assert!(!status.is_stuck);
+
+        let status = pipeline_status(
+            &registry(reason),
+            &automation,
+            Some(10_000),
+            Some((snapshot_pubkey, snapshot)),
+            10_001 + REGISTRY_LOCK_STALL_THRESHOLD,
+        );
+        assert_eq!(status.stage, PipelineStage::DistributingFees);
+        assert_eq!(status.lock_reason, reason);
+        assert_eq!(status.idle_slots, REGISTRY_LOCK_STALL_THRESHOLD + 1);
+        assert!(status.is_stuck);
+    }
+}
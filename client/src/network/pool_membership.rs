@@ -0,0 +1,114 @@
+use {
+    crate::{Client, ClientError, ClientResult},
+    anchor_lang::solana_program::pubkey::Pubkey,
+    clockwork_network_program::state::Pool,
+    solana_account_decoder::UiAccountEncoding,
+    solana_client::{pubsub_client::PubsubClient, rpc_config::RpcAccountInfoConfig},
+};
+
+/// A change in a pool's membership between two observed states of the pool.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PoolMembershipChange {
+    /// The worker rotated into the pool at the position.
+    Joined { worker: Pubkey, position: usize },
+    /// The worker was rotated or drained out of the pool.
+    Left { worker: Pubkey },
+}
+
+impl Client {
+    /// Watches the pool account over the websocket and calls `on_change` with the slot and the membership
+    /// changes of every update that adds or removes workers. The current state of the pool is the baseline.
+    /// Blocks until the subscription is closed.
+    pub fn watch_pool_membership<F>(
+        &self,
+        websocket_url: &str,
+        pool_id: u64,
+        mut on_change: F,
+    ) -> ClientResult<()>
+    where
+        F: FnMut(u64, Vec<PoolMembershipChange>),
+    {
+        let pool_pubkey = Pool::pubkey(pool_id);
+        let mut prev = self.get::<Pool>(&pool_pubkey)?;
+        let (_subscription, receiver) = PubsubClient::account_subscribe(
+            websocket_url,
+            &pool_pubkey,
+            Some(RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                commitment: Some(self.commitment()),
+                ..RpcAccountInfoConfig::default()
+            }),
+        )?;
+        for response in receiver {
+            let next = match response.value.data.decode().map(Pool::try_from) {
+                Some(Ok(pool)) => pool,
+                _ => return Err(ClientError::DeserializationError),
+            };
+            let changes = pool_membership_changes(&prev, &next);
+            if !changes.is_empty() {
+                on_change(response.context.slot, changes);
+            }
+            prev = next;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the workers that left the pool, followed by the workers that joined it, in pool order.
+pub fn pool_membership_changes(prev: &Pool, next: &Pool) -> Vec<PoolMembershipChange> {
+    let left = prev
+        .workers
+        .iter()
+        .filter(|worker| !next.workers.contains(worker))
+        .map(|worker| PoolMembershipChange::Left { worker: *worker });
+    let joined = next
+        .workers
+        .iter()
+        .enumerate()
+        .filter(|(_, worker)| !prev.workers.contains(worker))
+        .map(|(position, worker)| PoolMembershipChange::Joined {
+            worker: *worker,
+            position,
+        });
+    left.chain(joined).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, std::collections::VecDeque};
+
+    fn pool(workers: Vec<Pubkey>) -> Pool {
+        Pool {
+            id: 0,
+            size: 2,
+            workers: VecDeque::from(workers),
+        }
+    }
+
+    #[test]
+    fn test_pool_membership_changes() {
+        let a = Pubkey::new_unique();
+        let b = Pubkey::new_unique();
+        let c = Pubkey::new_unique();
+
+        // A rotation pushes the new worker to the back and drains the oldest from the front.
+        assert_eq!(
+            pool_membership_changes(&pool(vec![a, b]), &pool(vec![b, c])),
+            vec![
+                PoolMembershipChange::Left { worker: a },
+                PoolMembershipChange::Joined {
+                    worker: c,
+                    position: 1,
+                },
+            ]
+        );
+        assert_eq!(
+            pool_membership_changes(&pool(vec![]), &pool(vec![a])),
+            vec![PoolMembershipChange::Joined {
+                worker: a,
+                position: 0,
+            }]
+        );
+        assert!(pool_membership_changes(&pool(vec![a, b]), &pool(vec![a, b])).is_empty());
+    }
+}
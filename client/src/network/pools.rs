@@ -0,0 +1,111 @@
+use {
+    crate::{Client, ClientError, ClientResult, MAX_PAGE_SIZE},
+    anchor_lang::{solana_program::pubkey::Pubkey, AccountDeserialize},
+    clockwork_network_program::state::*,
+};
+
+/// Which pools to list, and which page of them.
+#[derive(Clone, Debug, Default)]
+pub struct PoolFilter {
+    /// The number of matching pools to skip, in id order.
+    pub offset: u64,
+    /// The max number of pools to return, if any.
+    pub limit: Option<u64>,
+    /// Only return pools with at least this many worker slots.
+    pub min_size: Option<usize>,
+}
+
+impl Client {
+    /// Lists the pools matching the filter, ordered by id.
+    ///
+    /// Pools are addressed by contiguous id, so a page of unfiltered pools is fetched directly by address.
+    /// An account's data size grows with its pool size, but RPC filters only match sizes exactly,
+    /// so the size filter lists every pool account and is applied client side.
+    pub fn get_pools(&self, filter: PoolFilter) -> ClientResult<Vec<(Pubkey, Pool)>> {
+        let limit = filter.limit.unwrap_or(u64::MAX);
+        match filter.min_size {
+            None => {
+                let registry = self.get::<Registry>(&Registry::pubkey())?;
+                let end = registry
+                    .total_pools
+                    .min(filter.offset.saturating_add(limit));
+                let pool_pubkeys = (filter.offset..end)
+                    .map(Pool::pubkey)
+                    .collect::<Vec<Pubkey>>();
+                let mut pools = vec![];
+                for chunk in pool_pubkeys.chunks(MAX_PAGE_SIZE) {
+                    for (pubkey, account) in chunk.iter().zip(self.get_multiple_accounts(chunk)?) {
+                        if let Some(account) = account {
+                            pools.push((
+                                *pubkey,
+                                Pool::try_deserialize(&mut account.data.as_slice())
+                                    .map_err(|_| ClientError::DeserializationError)?,
+                            ));
+                        }
+                    }
+                }
+                Ok(pools)
+            }
+            Some(min_size) => {
+                let mut pools = vec![];
+                for page in self.get_program_accounts_paged::<Pool>(
+                    &clockwork_network_program::ID,
+                    vec![],
+                    MAX_PAGE_SIZE,
+                )? {
+                    pools.extend(page?.into_iter().filter(|(_, pool)| pool.size >= min_size));
+                }
+                pools.sort_by_key(|(_, pool)| pool.id);
+                Ok(pools
+                    .into_iter()
+                    .skip(filter.offset as usize)
+                    .take(limit.min(usize::MAX as u64) as usize)
+                    .collect())
+            }
+        }
+    }
+
+    /// Returns the ids of the pools the worker currently holds a slot in.
+    pub fn get_worker_pool_ids(&self, worker: Pubkey) -> ClientResult<Vec<u64>> {
+        let pools = self.get_pools(PoolFilter::default())?;
+        Ok(pools_with_worker(&pools, worker))
+    }
+}
+
+/// Returns the ids of the pools that contain the worker.
+pub fn pools_with_worker(pools: &[(Pubkey, Pool)], worker: Pubkey) -> Vec<u64> {
+    pools
+        .iter()
+        .filter(|(_, pool)| pool.workers.contains(&worker))
+        .map(|(_, pool)| pool.id)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, std::collections::VecDeque};
+
+    fn pool(id: u64, workers: Vec<Pubkey>) -> (Pubkey, Pool) {
+        (
+            Pool::pubkey(id),
+            Pool {
+                id,
+                size: 2,
+                workers: VecDeque::from(workers),
+            },
+        )
+    }
+
+    #[test]
+    fn test_pools_with_worker() {
+        let worker = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        let pools = vec![
+            pool(0, vec![other, worker]),
+            pool(1, vec![other]),
+            pool(2, vec![worker]),
+        ];
+        assert_eq!(pools_with_worker(&pools, worker), vec![0, 2]);
+        assert!(pools_with_worker(&pools, Pubkey::new_unique()).is_empty());
+    }
+}
@@ -0,0 +1,133 @@
+use {
+    crate::{Client, ClientResult},
+    clockwork_automation_program::state::Automation,
+    clockwork_network_program::state::*,
+};
+
+/// Number of slots the registry may stay locked without the epoch automation executing before the lock is
+/// considered stuck (roughly 10 minutes).
+pub const REGISTRY_LOCK_STALL_THRESHOLD: u64 = 1_500;
+
+/// The health of the registry lock, judged by the progress of the epoch automation that holds it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RegistryLockStatus {
+    /// The registry is unlocked.
+    Unlocked,
+    /// The registry is locked and the epoch jobs are still making progress.
+    Held {
+        reason: RegistryLockReason,
+        idle_slots: u64,
+    },
+    /// The registry is locked but the epoch automation has not executed within the stall threshold.
+    /// The job holding the lock has likely died, and the admin may unlock the registry.
+    Stuck {
+        reason: RegistryLockReason,
+        idle_slots: u64,
+    },
+}
+
+impl RegistryLockStatus {
+    pub fn is_stuck(&self) -> bool {
+        matches!(self, RegistryLockStatus::Stuck { .. })
+    }
+}
+
+impl Client {
+    /// Fetches the registry and the epoch automation, and reports whether the registry lock is stuck.
+    pub fn get_registry_lock_status(&self) -> ClientResult<RegistryLockStatus> {
+        let config = self.get::<Config>(&Config::pubkey())?;
+        let registry = self.get::<Registry>(&Registry::pubkey())?;
+        let epoch_automation = self.get::<Automation>(&config.epoch_automation)?;
+        let last_exec_at = epoch_automation
+            .exec_context
+            .map(|exec_context| exec_context.last_exec_at);
+        let slot = self.client.get_slot()?;
+        Ok(registry_lock_status(&registry, last_exec_at, slot))
+    }
+}
+
+/// Returns the health of the registry lock at the given slot.
+/// The epoch automation's last exec slot marks the latest progress of the jobs holding the lock.
+pub fn registry_lock_status(
+    registry: &Registry,
+    last_exec_at: Option<u64>,
+    slot: u64,
+) -> RegistryLockStatus {
+    if !registry.is_locked() {
+        return RegistryLockStatus::Unlocked;
+    }
+    let idle_slots = slot.saturating_sub(last_exec_at.unwrap_or(0));
+    if idle_slots > REGISTRY_LOCK_STALL_THRESHOLD {
+        RegistryLockStatus::Stuck {
+            reason: registry.lock_reason,
+            idle_slots,
+        }
+    } else {
+        RegistryLockStatus::Held {
+            reason: registry.lock_reason,
+            idle_slots,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registry(lock_reason: RegistryLockReason) -> Registry {
+        Registry {
+            current_epoch: 7,
+            lock_reason,
+            nonce: 0,
+            total_pools: 1,
+            total_unstakes: 0,
+            total_workers: 3,
+        }
+    }
+
+    #[test]
+    fn test_unlocked_registry_is_never_stuck() {
+        let status = registry_lock_status(&registry(RegistryLockReason::Unlocked), None, 1_000_000);
+        assert_eq!(status, RegistryLockStatus::Unlocked);
+    }
+
+    #[test]
+    fn test_lock_held_while_epoch_jobs_progress() {
+        let reason = RegistryLockReason::Distributing;
+        let status = registry_lock_status(&registry(reason), Some(10_000), 10_000 + 40);
+        assert_eq!(
+            status,
+            RegistryLockStatus::Held {
+                reason,
+                idle_slots: 40
+            }
+        );
+        let status = registry_lock_status(
+            &registry(reason),
+            Some(10_000),
+            10_000 + REGISTRY_LOCK_STALL_THRESHOLD,
+        );
+        assert!(!status.is_stuck());
+    }
+
+    #[test]
+    fn test_lock_stuck_after_epoch_jobs_stall() {
+        let reason = RegistryLockReason::Staking;
+        let status = registry_lock_status(
+            &registry(reason),
+            Some(10_000),
+            10_001 + REGISTRY_LOCK_STALL_THRESHOLD,
+        );
+        assert_eq!(
+            status,
+            RegistryLockStatus::Stuck {
+                reason,
+                idle_slots: REGISTRY_LOCK_STALL_THRESHOLD + 1
+            }
+        );
+
+        // An epoch automation that has never executed cannot be making progress.
+        let status = registry_lock_status(&registry(reason), None, 10_000);
+        assert!(status.is_stuck());
+    }
+}
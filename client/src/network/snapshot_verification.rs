@@ -0,0 +1,407 @@
+use {
+    super::snapshots::MAX_MULTIPLE_ACCOUNTS,
+    crate::{Client, ClientError, ClientResult},
+    anchor_lang::{solana_program::pubkey::Pubkey, AccountDeserialize},
+    clockwork_network_program::state::*,
+};
+
+/// A way in which a snapshot's account tree does not add up.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SnapshotDiscrepancy {
+    /// The snapshot's frame count does not match the registry's worker count.
+    FrameCountMismatch { expected: u64, actual: u64 },
+    /// The snapshot's total stake does not match the sum of its frames' stake.
+    TotalStakeMismatch { expected: u64, actual: u64 },
+    /// A frame within the snapshot's frame count does not exist.
+    MissingFrame { frame: u64 },
+    /// A frame is not for the worker with the same id.
+    FrameWorkerMismatch {
+        frame: u64,
+        expected: Pubkey,
+        actual: Pubkey,
+    },
+    /// A frame's stake offset does not match the sum of the stake of the frames before it.
+    FrameStakeOffsetMismatch { frame: u64, expected: u64, actual: u64 },
+    /// A frame's stake does not match the sum of its entries' stake.
+    FrameStakeMismatch { frame: u64, expected: u64, actual: u64 },
+    /// A frame's entry count does not match its worker's delegation count.
+    FrameEntryCountMismatch { frame: u64, expected: u64, actual: u64 },
+    /// The worker of a frame does not exist.
+    MissingWorker { frame: u64 },
+    /// An entry within a frame's entry count does not exist.
+    MissingEntry { frame: u64, entry: u64 },
+    /// An entry is not for the delegation with the same id.
+    EntryDelegationMismatch {
+        frame: u64,
+        entry: u64,
+        expected: Pubkey,
+        actual: Pubkey,
+    },
+    /// An entry's stake does not match its delegation's lamports-equivalent locked stake.
+    EntryStakeMismatch {
+        frame: u64,
+        entry: u64,
+        expected: u64,
+        actual: u64,
+    },
+    /// The delegation of an entry does not exist.
+    MissingDelegation { frame: u64, entry: u64 },
+}
+
+/// The result of checking a snapshot's frames and entries against each other and the network's accounts.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SnapshotReport {
+    pub epoch: u64,
+    pub snapshot: Pubkey,
+    pub total_frames: u64,
+    pub total_entries: u64,
+    pub total_stake: u64,
+    pub discrepancies: Vec<SnapshotDiscrepancy>,
+}
+
+impl SnapshotReport {
+    pub fn is_valid(&self) -> bool {
+        self.discrepancies.is_empty()
+    }
+}
+
+/// A snapshot frame with the accounts it is checked against.
+#[derive(Debug)]
+pub struct SnapshotFrameAccounts {
+    pub frame: SnapshotFrame,
+    /// The delegation count of the frame's worker, if the worker exists.
+    pub worker_total_delegations: Option<u64>,
+    /// The frame's entries by id, each with the lamports-equivalent locked stake of its delegation, if the
+    /// delegation exists.
+    pub entries: Vec<Option<(SnapshotEntry, Option<u64>)>>,
+}
+
+impl Client {
+    /// Fetches the snapshot for the given epoch along with all of its frames and entries, and checks that they
+    /// sum correctly and match the on-chain workers and delegations.
+    /// Workers, delegations, and stake change at each epoch boundary, so only the current epoch's snapshot is
+    /// expected to match them exactly.
+    pub fn verify_snapshot(&self, epoch: u64) -> ClientResult<SnapshotReport> {
+        let config = self.get::<Config>(&Config::pubkey())?;
+        let registry = self.get::<Registry>(&Registry::pubkey())?;
+        let snapshot_pubkey = Snapshot::pubkey(epoch);
+        let snapshot = self.get::<Snapshot>(&snapshot_pubkey)?;
+
+        let frame_pubkeys = (0..snapshot.total_frames)
+            .map(|id| SnapshotFrame::pubkey(snapshot_pubkey, id))
+            .collect::<Vec<Pubkey>>();
+        let worker_pubkeys = (0..snapshot.total_frames)
+            .map(Worker::pubkey)
+            .collect::<Vec<Pubkey>>();
+        let frames = self.get_multiple::<SnapshotFrame>(&frame_pubkeys)?;
+        let workers = self.get_multiple::<Worker>(&worker_pubkeys)?;
+
+        let mut frame_accounts = vec![];
+        for ((frame_pubkey, frame), worker) in frame_pubkeys.iter().zip(frames).zip(workers) {
+            let frame = match frame {
+                None => {
+                    frame_accounts.push(None);
+                    continue;
+                }
+                Some(frame) => frame,
+            };
+            let entry_pubkeys = (0..frame.total_entries)
+                .map(|id| SnapshotEntry::pubkey(*frame_pubkey, id))
+                .collect::<Vec<Pubkey>>();
+            let entries = self.get_multiple::<SnapshotEntry>(&entry_pubkeys)?;
+            let delegation_pubkeys = entries
+                .iter()
+                .flatten()
+                .map(|entry| entry.delegation)
+                .collect::<Vec<Pubkey>>();
+            let mut delegations = self
+                .get_multiple::<Delegation>(&delegation_pubkeys)?
+                .into_iter();
+            let entries = entries
+                .into_iter()
+                .map(|entry| {
+                    entry.map(|entry| {
+                        let stake_amount = delegations
+                            .next()
+                            .flatten()
+                            .map(|delegation| {
+                                config.stake_weight(delegation.stake_kind, delegation.stake_amount)
+                            });
+                        (entry, stake_amount)
+                    })
+                })
+                .collect();
+            frame_accounts.push(Some(SnapshotFrameAccounts {
+                frame,
+                worker_total_delegations: worker.map(|worker| worker.total_delegations),
+                entries,
+            }));
+        }
+
+        Ok(snapshot_report(
+            epoch,
+            snapshot_pubkey,
+            &snapshot,
+            registry.total_workers,
+            &frame_accounts,
+        ))
+    }
+
+    /// Batch fetches and deserializes accounts, returning `None` for those that do not exist.
+    fn get_multiple<T: AccountDeserialize>(
+        &self,
+        pubkeys: &[Pubkey],
+    ) -> ClientResult<Vec<Option<T>>> {
+        let mut accounts = vec![];
+        for chunk in pubkeys.chunks(MAX_MULTIPLE_ACCOUNTS) {
+            for account in self.get_multiple_accounts(chunk)? {
+                accounts.push(match account {
+                    None => None,
+                    Some(account) => Some(
+                        T::try_deserialize(&mut account.data.as_slice())
+                            .map_err(|_| ClientError::DeserializationError)?,
+                    ),
+                });
+            }
+        }
+        Ok(accounts)
+    }
+}
+
+/// Checks a snapshot's frames, given by id, and their entries against each other and the network's accounts.
+pub fn snapshot_report(
+    epoch: u64,
+    snapshot_pubkey: Pubkey,
+    snapshot: &Snapshot,
+    total_workers: u64,
+    frames: &[Option<SnapshotFrameAccounts>],
+) -> SnapshotReport {
+    let mut discrepancies = vec![];
+    if snapshot.total_frames.ne(&total_workers) {
+        discrepancies.push(SnapshotDiscrepancy::FrameCountMismatch {
+            expected: total_workers,
+            actual: snapshot.total_frames,
+        });
+    }
+
+    let mut stake_offset: u64 = 0;
+    let mut total_entries: u64 = 0;
+    for (frame_id, frame_accounts) in (0u64..).zip(frames) {
+        let SnapshotFrameAccounts {
+            frame,
+            worker_total_delegations,
+            entries,
+        } = match frame_accounts {
+            None => {
+                discrepancies.push(SnapshotDiscrepancy::MissingFrame { frame: frame_id });
+                continue;
+            }
+            Some(frame_accounts) => frame_accounts,
+        };
+        total_entries = total_entries.saturating_add(frame.total_entries);
+
+        // Check the frame's place in the snapshot.
+        let worker_pubkey = Worker::pubkey(frame_id);
+        if frame.worker.ne(&worker_pubkey) {
+            discrepancies.push(SnapshotDiscrepancy::FrameWorkerMismatch {
+                frame: frame_id,
+                expected: worker_pubkey,
+                actual: frame.worker,
+            });
+        }
+        if frame.stake_offset.ne(&stake_offset) {
+            discrepancies.push(SnapshotDiscrepancy::FrameStakeOffsetMismatch {
+                frame: frame_id,
+                expected: stake_offset,
+                actual: frame.stake_offset,
+            });
+        }
+        stake_offset = stake_offset.saturating_add(frame.stake_amount);
+        match worker_total_delegations {
+            None => discrepancies.push(SnapshotDiscrepancy::MissingWorker { frame: frame_id }),
+            Some(total_delegations) if total_delegations.ne(&frame.total_entries) => {
+                discrepancies.push(SnapshotDiscrepancy::FrameEntryCountMismatch {
+                    frame: frame_id,
+                    expected: *total_delegations,
+                    actual: frame.total_entries,
+                })
+            }
+            _ => {}
+        }
+
+        // Check the frame's entries.
+        let mut entries_stake: u64 = 0;
+        for (entry_id, entry) in (0u64..).zip(entries) {
+            let (entry, delegation_stake) = match entry {
+                None => {
+                    discrepancies.push(SnapshotDiscrepancy::MissingEntry {
+                        frame: frame_id,
+                        entry: entry_id,
+                    });
+                    continue;
+                }
+                Some(entry) => entry,
+            };
+            entries_stake = entries_stake.saturating_add(entry.stake_amount);
+            let delegation_pubkey = Delegation::pubkey(worker_pubkey, entry_id);
+            if entry.delegation.ne(&delegation_pubkey) {
+                discrepancies.push(SnapshotDiscrepancy::EntryDelegationMismatch {
+                    frame: frame_id,
+                    entry: entry_id,
+                    expected: delegation_pubkey,
+                    actual: entry.delegation,
+                });
+            }
+            match delegation_stake {
+                None => discrepancies.push(SnapshotDiscrepancy::MissingDelegation {
+                    frame: frame_id,
+                    entry: entry_id,
+                }),
+                Some(stake_amount) if stake_amount.ne(&entry.stake_amount) => {
+                    discrepancies.push(SnapshotDiscrepancy::EntryStakeMismatch {
+                        frame: frame_id,
+                        entry: entry_id,
+                        expected: *stake_amount,
+                        actual: entry.stake_amount,
+                    })
+                }
+                _ => {}
+            }
+        }
+        if entries_stake.ne(&frame.stake_amount) {
+            discrepancies.push(SnapshotDiscrepancy::FrameStakeMismatch {
+                frame: frame_id,
+                expected: entries_stake,
+                actual: frame.stake_amount,
+            });
+        }
+    }
+
+    if stake_offset.ne(&snapshot.total_stake) {
+        discrepancies.push(SnapshotDiscrepancy::TotalStakeMismatch {
+            expected: stake_offset,
+            actual: snapshot.total_stake,
+        });
+    }
+
+    SnapshotReport {
+        epoch,
+        snapshot: snapshot_pubkey,
+        total_frames: snapshot.total_frames,
+        total_entries,
+        total_stake: snapshot.total_stake,
+        discrepancies,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds the accounts of a frame whose entries and delegations hold the given stakes.
+    fn frame_accounts(id: u64, stake_offset: u64, stakes: &[u64]) -> SnapshotFrameAccounts {
+        let snapshot_pubkey = Snapshot::pubkey(7);
+        let frame_pubkey = SnapshotFrame::pubkey(snapshot_pubkey, id);
+        let worker_pubkey = Worker::pubkey(id);
+        SnapshotFrameAccounts {
+            frame: SnapshotFrame {
+                id,
+                snapshot: snapshot_pubkey,
+                stake_amount: stakes.iter().sum(),
+                stake_offset,
+                total_entries: stakes.len() as u64,
+                worker: worker_pubkey,
+            },
+            worker_total_delegations: Some(stakes.len() as u64),
+            entries: (0u64..)
+                .zip(stakes)
+                .map(|(entry_id, stake_amount)| {
+                    Some((
+                        SnapshotEntry {
+                            delegation: Delegation::pubkey(worker_pubkey, entry_id),
+                            id: entry_id,
+                            snapshot_frame: frame_pubkey,
+                            stake_amount: *stake_amount,
+                        },
+                        Some(*stake_amount),
+                    ))
+                })
+                .collect(),
+        }
+    }
+
+    fn snapshot(total_frames: u64, total_stake: u64) -> Snapshot {
+        Snapshot {
+            id: 7,
+            total_frames,
+            total_stake,
+        }
+    }
+
+    #[test]
+    fn test_consistent_snapshot_is_valid() {
+        let frames = vec![
+            Some(frame_accounts(0, 0, &[100, 50])),
+            Some(frame_accounts(1, 150, &[25])),
+        ];
+        let report = snapshot_report(7, Snapshot::pubkey(7), &snapshot(2, 175), 2, &frames);
+        assert!(report.is_valid(), "{:?}", report.discrepancies);
+        assert_eq!(report.total_entries, 3);
+    }
+
+    #[test]
+    fn test_stake_discrepancies_are_reported() {
+        let mut frame = frame_accounts(1, 150, &[25, 10]);
+        // The second delegation's stake changed after the entry was recorded.
+        frame.entries[1].as_mut().unwrap().1 = Some(20);
+        frame.frame.stake_amount = 40;
+        let frames = vec![Some(frame_accounts(0, 0, &[100, 50])), Some(frame)];
+        let report = snapshot_report(7, Snapshot::pubkey(7), &snapshot(2, 190), 2, &frames);
+        assert_eq!(
+            report.discrepancies,
+            vec![
+                SnapshotDiscrepancy::EntryStakeMismatch {
+                    frame: 1,
+                    entry: 1,
+                    expected: 20,
+                    actual: 10
+                },
+                SnapshotDiscrepancy::FrameStakeMismatch {
+                    frame: 1,
+                    expected: 35,
+                    actual: 40
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_missing_accounts_are_reported() {
+        let mut frame = frame_accounts(0, 0, &[100, 50]);
+        frame.entries[1] = None;
+        frame.worker_total_delegations = Some(3);
+        let frames = vec![Some(frame), None];
+        let report = snapshot_report(7, Snapshot::pubkey(7), &snapshot(2, 150), 3, &frames);
+        assert_eq!(
+            report.discrepancies,
+            vec![
+                SnapshotDiscrepancy::FrameCountMismatch {
+                    expected: 3,
+                    actual: 2
+                },
+                SnapshotDiscrepancy::FrameEntryCountMismatch {
+                    frame: 0,
+                    expected: 3,
+                    actual: 2
+                },
+                SnapshotDiscrepancy::MissingEntry { frame: 0, entry: 1 },
+                SnapshotDiscrepancy::FrameStakeMismatch {
+                    frame: 0,
+                    expected: 100,
+                    actual: 150
+                },
+                SnapshotDiscrepancy::MissingFrame { frame: 1 },
+            ]
+        );
+    }
+}
@@ -0,0 +1,71 @@
+use {
+    crate::{Client, ClientError, ClientResult},
+    anchor_lang::{solana_program::pubkey::Pubkey, AccountDeserialize},
+    clockwork_network_program::state::*,
+    std::collections::BTreeMap,
+};
+
+/// The max number of accounts that may be requested in a single `getMultipleAccounts` call.
+pub(super) const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+/// The change in a worker's delegated stake between two snapshots.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WorkerStakeDelta {
+    pub worker: Pubkey,
+    /// The worker's stake in the first snapshot, or `None` if the worker was not in it.
+    pub stake_a: Option<u64>,
+    /// The worker's stake in the second snapshot, or `None` if the worker was not in it.
+    pub stake_b: Option<u64>,
+    pub delta: i128,
+}
+
+impl Client {
+    /// Fetches the frames of the snapshots for both epochs and computes per-worker stake deltas.
+    pub fn diff_snapshots(&self, epoch_a: u64, epoch_b: u64) -> ClientResult<Vec<WorkerStakeDelta>> {
+        let frames_a = self.get_snapshot_frames(epoch_a)?;
+        let frames_b = self.get_snapshot_frames(epoch_b)?;
+        Ok(worker_stake_deltas(&frames_a, &frames_b))
+    }
+
+    /// Batch fetches all frames of the snapshot for the given epoch.
+    pub fn get_snapshot_frames(&self, epoch: u64) -> ClientResult<Vec<SnapshotFrame>> {
+        let snapshot_pubkey = Snapshot::pubkey(epoch);
+        let snapshot = self.get::<Snapshot>(&snapshot_pubkey)?;
+        let frame_pubkeys = (0..snapshot.total_frames)
+            .map(|id| SnapshotFrame::pubkey(snapshot_pubkey, id))
+            .collect::<Vec<Pubkey>>();
+        let mut frames = vec![];
+        for chunk in frame_pubkeys.chunks(MAX_MULTIPLE_ACCOUNTS) {
+            for account in self.get_multiple_accounts(chunk)?.into_iter().flatten() {
+                frames.push(
+                    SnapshotFrame::try_deserialize(&mut account.data.as_slice())
+                        .map_err(|_| ClientError::DeserializationError)?,
+                );
+            }
+        }
+        Ok(frames)
+    }
+}
+
+/// Computes the stake delta of every worker present in either set of frames, ordered by worker.
+pub fn worker_stake_deltas(
+    frames_a: &[SnapshotFrame],
+    frames_b: &[SnapshotFrame],
+) -> Vec<WorkerStakeDelta> {
+    let mut stakes: BTreeMap<Pubkey, (Option<u64>, Option<u64>)> = BTreeMap::new();
+    for frame in frames_a {
+        stakes.entry(frame.worker).or_default().0 = Some(frame.stake_amount);
+    }
+    for frame in frames_b {
+        stakes.entry(frame.worker).or_default().1 = Some(frame.stake_amount);
+    }
+    stakes
+        .into_iter()
+        .map(|(worker, (stake_a, stake_b))| WorkerStakeDelta {
+            worker,
+            stake_a,
+            stake_b,
+            delta: stake_b.unwrap_or(0) as i128 - stake_a.unwrap_or(0) as i128,
+        })
+        .collect()
+}
@@ -0,0 +1,65 @@
+use {
+    crate::{Client, ClientResult, MAX_PAGE_SIZE},
+    anchor_lang::solana_program::pubkey::Pubkey,
+    clockwork_network_program::state::*,
+};
+
+impl Client {
+    /// Lists every registered worker, ordered by id.
+    pub fn get_workers(&self) -> ClientResult<Vec<(Pubkey, Worker)>> {
+        let mut workers = vec![];
+        for page in self.get_program_accounts_paged::<Worker>(
+            &clockwork_network_program::ID,
+            vec![],
+            MAX_PAGE_SIZE,
+        )? {
+            workers.extend(page?);
+        }
+        workers.sort_by_key(|(_, worker)| worker.id);
+        Ok(workers)
+    }
+}
+
+/// Returns the workers that hold a slot in the pool, keeping their order.
+pub fn workers_in_pool(workers: Vec<(Pubkey, Worker)>, pool: &Pool) -> Vec<(Pubkey, Worker)> {
+    workers
+        .into_iter()
+        .filter(|(pubkey, _)| pool.workers.contains(pubkey))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, std::collections::VecDeque};
+
+    fn worker(id: u64) -> (Pubkey, Worker) {
+        (
+            Worker::pubkey(id),
+            Worker {
+                authority: Pubkey::new_unique(),
+                commission_balance: 0,
+                commission_rate: 10,
+                id,
+                signatory: Pubkey::new_unique(),
+                total_delegations: 0,
+                commission_recipient: None,
+                pending_commission_rate: None,
+                pending_commission_epoch: 0,
+            },
+        )
+    }
+
+    #[test]
+    fn test_workers_in_pool() {
+        let pool = Pool {
+            id: 0,
+            size: 2,
+            workers: VecDeque::from(vec![Worker::pubkey(2), Worker::pubkey(0)]),
+        };
+        let workers = workers_in_pool(vec![worker(0), worker(1), worker(2)], &pool);
+        assert_eq!(
+            workers.iter().map(|(_, worker)| worker.id).collect::<Vec<u64>>(),
+            vec![0, 2]
+        );
+    }
+}
@@ -0,0 +1,190 @@
+use {
+    crate::{Client, ClientError, ClientResult},
+    anchor_lang::{AccountDeserialize, Discriminator},
+    solana_account_decoder::{UiAccountEncoding, UiDataSliceConfig},
+    solana_client::{
+        rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+        rpc_filter::{Memcmp, RpcFilterType},
+    },
+    solana_sdk::{account::Account, pubkey::Pubkey},
+    std::{marker::PhantomData, vec::IntoIter},
+};
+
+/// The most accounts a single `getMultipleAccounts` request may ask for.
+pub const MAX_PAGE_SIZE: usize = 100;
+
+/// Iterates over the accounts of a single type owned by a program, one page at a time.
+///
+/// Only the addresses of the matching accounts are held in memory up front. Account data is
+/// fetched and decoded lazily as each page is consumed.
+pub struct ProgramAccountPages<'a, T> {
+    fetch: Box<dyn Fn(&[Pubkey]) -> ClientResult<Vec<Option<Account>>> + 'a>,
+    page_size: usize,
+    pubkeys: IntoIter<Pubkey>,
+    account_type: PhantomData<T>,
+}
+
+impl<'a, T: AccountDeserialize> Iterator for ProgramAccountPages<'a, T> {
+    type Item = ClientResult<Vec<(Pubkey, T)>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let page = self
+            .pubkeys
+            .by_ref()
+            .take(self.page_size)
+            .collect::<Vec<Pubkey>>();
+        if page.is_empty() {
+            return None;
+        }
+        Some(self.fetch_page(page))
+    }
+}
+
+impl<'a, T: AccountDeserialize> ProgramAccountPages<'a, T> {
+    /// Pages through the accounts at the addresses, fetching each page of accounts with `fetch`.
+    fn new<F>(pubkeys: Vec<Pubkey>, page_size: usize, fetch: F) -> Self
+    where
+        F: Fn(&[Pubkey]) -> ClientResult<Vec<Option<Account>>> + 'a,
+    {
+        ProgramAccountPages {
+            fetch: Box::new(fetch),
+            page_size: page_size.clamp(1, MAX_PAGE_SIZE),
+            pubkeys: pubkeys.into_iter(),
+            account_type: PhantomData,
+        }
+    }
+
+    fn fetch_page(&self, page: Vec<Pubkey>) -> ClientResult<Vec<(Pubkey, T)>> {
+        let accounts = (self.fetch)(&page)?;
+        let mut decoded = vec![];
+        // Accounts closed since the addresses were listed come back empty and are skipped.
+        for (pubkey, account) in page.into_iter().zip(accounts) {
+            if let Some(account) = account {
+                decoded.push((
+                    pubkey,
+                    T::try_deserialize(&mut account.data.as_slice())
+                        .map_err(|_| ClientError::DeserializationError)?,
+                ));
+            }
+        }
+        Ok(decoded)
+    }
+}
+
+impl Client {
+    /// Lists the accounts of type `T` owned by the program that match the filters, and returns an
+    /// iterator that fetches and decodes them in pages of at most `page_size` accounts.
+    /// The page size is clamped to `MAX_PAGE_SIZE`.
+    pub fn get_program_accounts_paged<T: AccountDeserialize + Discriminator>(
+        &self,
+        program_id: &Pubkey,
+        filters: Vec<RpcFilterType>,
+        page_size: usize,
+    ) -> ClientResult<ProgramAccountPages<T>> {
+        let mut filters = filters;
+        filters.insert(
+            0,
+            RpcFilterType::Memcmp(Memcmp::new_raw_bytes(0, T::discriminator().to_vec())),
+        );
+
+        // Ask for an empty slice of each account's data so only the addresses are returned.
+        let pubkeys = self
+            .get_program_accounts_with_config(
+                program_id,
+                RpcProgramAccountsConfig {
+                    filters: Some(filters),
+                    account_config: RpcAccountInfoConfig {
+                        encoding: Some(UiAccountEncoding::Base64),
+                        data_slice: Some(UiDataSliceConfig {
+                            offset: 0,
+                            length: 0,
+                        }),
+                        ..RpcAccountInfoConfig::default()
+                    },
+                    ..RpcProgramAccountsConfig::default()
+                },
+            )?
+            .into_iter()
+            .map(|(pubkey, _account)| pubkey)
+            .collect::<Vec<Pubkey>>();
+
+        Ok(ProgramAccountPages::new(pubkeys, page_size, move |page| {
+            Ok(self.get_multiple_accounts(page)?)
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*, anchor_lang::AccountSerialize, clockwork_network_program::state::Fee,
+        std::collections::HashMap,
+    };
+
+    fn fee_account(distributable_balance: u64) -> (Pubkey, Account) {
+        let worker = Pubkey::new_unique();
+        let mut data = vec![];
+        Fee {
+            distributable_balance,
+            worker,
+        }
+        .try_serialize(&mut data)
+        .unwrap();
+        (
+            Fee::pubkey(worker),
+            Account {
+                data,
+                owner: clockwork_network_program::ID,
+                ..Account::default()
+            },
+        )
+    }
+
+    #[test]
+    fn test_pages_yield_all_accounts_in_chunks() {
+        let accounts = (0..5).map(fee_account).collect::<Vec<(Pubkey, Account)>>();
+        let pubkeys = accounts.iter().map(|(pubkey, _)| *pubkey).collect::<Vec<Pubkey>>();
+        let store = accounts.into_iter().collect::<HashMap<Pubkey, Account>>();
+        let fetch =
+            |page: &[Pubkey]| Ok(page.iter().map(|pubkey| store.get(pubkey).cloned()).collect());
+
+        let pages = ProgramAccountPages::<Fee>::new(pubkeys.clone(), 2, fetch)
+            .collect::<ClientResult<Vec<Vec<(Pubkey, Fee)>>>>()
+            .unwrap();
+        assert_eq!(
+            pages.iter().map(|page| page.len()).collect::<Vec<usize>>(),
+            vec![2, 2, 1]
+        );
+
+        // The pages hold the same accounts as a full fetch, in the same order.
+        let paged = pages.into_iter().flatten().collect::<Vec<(Pubkey, Fee)>>();
+        assert_eq!(
+            paged.iter().map(|(pubkey, _)| *pubkey).collect::<Vec<Pubkey>>(),
+            pubkeys
+        );
+        for (i, (pubkey, fee)) in paged.iter().enumerate() {
+            assert_eq!(fee.distributable_balance, i as u64);
+            assert_eq!(Fee::pubkey(fee.worker), *pubkey);
+        }
+    }
+
+    #[test]
+    fn test_pages_skip_closed_accounts() {
+        let (open_pubkey, open_account) = fee_account(7);
+        let closed_pubkey = Pubkey::new_unique();
+        let fetch = |page: &[Pubkey]| {
+            Ok(page
+                .iter()
+                .map(|pubkey| (*pubkey == open_pubkey).then(|| open_account.clone()))
+                .collect())
+        };
+        let pages = ProgramAccountPages::<Fee>::new(vec![closed_pubkey, open_pubkey], 0, fetch)
+            .collect::<ClientResult<Vec<Vec<(Pubkey, Fee)>>>>()
+            .unwrap();
+
+        // A page size of zero is clamped to one account per page.
+        assert_eq!(pages.len(), 2);
+        assert!(pages[0].is_empty());
+        assert_eq!(pages[1][0].0, open_pubkey);
+    }
+}
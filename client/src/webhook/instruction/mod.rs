@@ -1,9 +1,11 @@
 mod api_new;
 mod initialize;
 mod request_ack;
+mod request_migrate;
 mod request_new;
 
 pub use api_new::*;
 pub use initialize::*;
 pub use request_ack::*;
+pub use request_migrate::*;
 pub use request_new::*;
@@ -2,13 +2,14 @@ use anchor_lang::{
     solana_program::{
         instruction::{AccountMeta, Instruction},
         pubkey::Pubkey,
-        system_program, sysvar,
+        system_program,
     },
     InstructionData,
 };
 
 pub fn request_ack(
     ack_authority: Pubkey,
+    api: Pubkey,
     caller: Pubkey,
     request: Pubkey,
     worker: Pubkey,
@@ -19,8 +20,8 @@ pub fn request_ack(
         program_id: clockwork_webhook_program::ID,
         accounts: vec![
             AccountMeta::new(ack_authority, true),
+            AccountMeta::new_readonly(api, false),
             AccountMeta::new(caller, false),
-            AccountMeta::new_readonly(sysvar::clock::ID, false),
             AccountMeta::new_readonly(config_pubkey, false),
             AccountMeta::new(fee_pubkey, false),
             AccountMeta::new(request, false),
@@ -0,0 +1,20 @@
+use anchor_lang::{
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+        system_program,
+    },
+    InstructionData,
+};
+
+pub fn request_migrate(payer: Pubkey, request: Pubkey) -> Instruction {
+    Instruction {
+        program_id: clockwork_webhook_program::ID,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(request, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: clockwork_webhook_program::instruction::RequestMigrate {}.data(),
+    }
+}
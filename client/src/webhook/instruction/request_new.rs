@@ -10,8 +10,12 @@ use clockwork_webhook_program::objects::HttpMethod;
 
 pub fn request_new(
     api: Pubkey,
+    backoff_seconds: u64,
+    body: Vec<u8>,
     caller: Pubkey,
+    headers: Vec<(String, String)>,
     id: String,
+    max_retries: u64,
     method: HttpMethod,
     payer: Pubkey,
     route: String,
@@ -32,6 +36,15 @@ pub fn request_new(
             AccountMeta::new(request_pubkey, false),
             AccountMeta::new_readonly(system_program::ID, false),
         ],
-        data: clockwork_webhook_program::instruction::RequestNew { id, method, route }.data(),
+        data: clockwork_webhook_program::instruction::RequestNew {
+            id,
+            method,
+            route,
+            max_retries,
+            backoff_seconds,
+            headers,
+            body,
+        }
+        .data(),
     }
 }
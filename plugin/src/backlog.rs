@@ -0,0 +1,129 @@
+use {
+    serde::{Deserialize, Serialize},
+    solana_program::pubkey::Pubkey,
+    std::collections::HashMap,
+};
+
+/// How the worker handles the missed cron moments of non-skippable automations it finds at startup.
+///
+/// Skippable automations are unaffected, since their kickoff already jumps ahead to the current moment on chain.
+/// Missed moments the worker does not replay are not dropped. The automation program still kicks off one missed
+/// moment at a time, so deferred automations work through their backlog at the schedule's own cadence.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StartupBacklogPolicy {
+    /// Replay every missed moment as fast as possible.
+    ReplayAll,
+    /// Replay up to this many missed moments per automation, then fall back to the schedule's cadence.
+    ReplayCapped(u64),
+    /// Replay no missed moments and wait for the schedule's next upcoming moment.
+    SkipToNow,
+}
+
+impl Default for StartupBacklogPolicy {
+    fn default() -> Self {
+        StartupBacklogPolicy::ReplayAll
+    }
+}
+
+impl StartupBacklogPolicy {
+    /// The number of missed moments an automation may replay, or none if unlimited.
+    pub fn replay_budget(&self) -> Option<u64> {
+        match self {
+            StartupBacklogPolicy::ReplayAll => None,
+            StartupBacklogPolicy::ReplayCapped(cap) => Some(*cap),
+            StartupBacklogPolicy::SkipToNow => Some(0),
+        }
+    }
+}
+
+/// Tracks how many more missed moments each backlogged automation found at startup may replay.
+#[derive(Debug, Default)]
+pub struct BacklogBudgets {
+    pub policy: StartupBacklogPolicy,
+    pub budgets: HashMap<Pubkey, u64>,
+}
+
+impl BacklogBudgets {
+    pub fn new(policy: StartupBacklogPolicy) -> Self {
+        Self {
+            policy,
+            budgets: HashMap::new(),
+        }
+    }
+
+    /// Returns true if the automation's next moment should be indexed as-is, or false if a missed moment
+    /// should be deferred to the schedule's next upcoming moment.
+    /// An automation's budget is set when it is found backlogged at startup and cleared once it catches up.
+    pub fn should_replay(
+        &mut self,
+        automation_pubkey: &Pubkey,
+        is_startup: bool,
+        is_backlogged: bool,
+    ) -> bool {
+        if !is_backlogged {
+            self.budgets.remove(automation_pubkey);
+            return true;
+        }
+        if is_startup {
+            if let Some(budget) = self.policy.replay_budget() {
+                self.budgets.insert(*automation_pubkey, budget);
+            }
+        }
+        match self.budgets.get_mut(automation_pubkey) {
+            None => true,
+            Some(0) => false,
+            Some(budget) => {
+                *budget -= 1;
+                true
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Counts how many moments of a synthetic backlog are replayed, given the automation is found at startup
+    /// and re-observed after each kickoff.
+    fn replayed_moments(policy: StartupBacklogPolicy, backlog: usize) -> usize {
+        let automation_pubkey = Pubkey::new_unique();
+        let mut backlog_budgets = BacklogBudgets::new(policy);
+        (0..backlog)
+            .filter(|i| backlog_budgets.should_replay(&automation_pubkey, *i == 0, true))
+            .count()
+    }
+
+    #[test]
+    fn test_replay_all() {
+        assert_eq!(replayed_moments(StartupBacklogPolicy::ReplayAll, 10), 10);
+    }
+
+    #[test]
+    fn test_replay_capped() {
+        assert_eq!(replayed_moments(StartupBacklogPolicy::ReplayCapped(3), 10), 3);
+        assert_eq!(replayed_moments(StartupBacklogPolicy::ReplayCapped(30), 10), 10);
+    }
+
+    #[test]
+    fn test_skip_to_now() {
+        assert_eq!(replayed_moments(StartupBacklogPolicy::SkipToNow, 10), 0);
+    }
+
+    #[test]
+    fn test_caught_up_clears_budget() {
+        let automation_pubkey = Pubkey::new_unique();
+        let mut backlog_budgets = BacklogBudgets::new(StartupBacklogPolicy::SkipToNow);
+        assert!(!backlog_budgets.should_replay(&automation_pubkey, true, true));
+        assert!(backlog_budgets.should_replay(&automation_pubkey, false, false));
+        assert!(backlog_budgets.budgets.is_empty());
+    }
+
+    #[test]
+    fn test_backlog_after_startup_is_unlimited() {
+        let automation_pubkey = Pubkey::new_unique();
+        let mut backlog_budgets = BacklogBudgets::new(StartupBacklogPolicy::SkipToNow);
+        assert!(backlog_budgets.should_replay(&automation_pubkey, false, true));
+    }
+}
@@ -0,0 +1,69 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// A change in whether the worker's keypair can afford to submit transactions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BalanceTransition {
+    Unchanged,
+    /// The balance fell below the minimum, so submissions are paused.
+    Paused,
+    /// The balance recovered to the minimum, so submissions resume.
+    Resumed,
+}
+
+/// Pauses new submissions while the keypair's balance is below the configured minimum,
+/// rather than letting every exec fail and count towards the automations' failure thresholds.
+#[derive(Debug, Default)]
+pub struct BalanceGuard {
+    paused: AtomicBool,
+}
+
+impl BalanceGuard {
+    /// Records the keypair's latest balance and returns whether that paused or resumed submissions.
+    /// A minimum of zero disables the guard.
+    pub fn observe(&self, balance: u64, min_balance: u64) -> BalanceTransition {
+        let is_low = balance < min_balance;
+        match (self.paused.swap(is_low, Ordering::Relaxed), is_low) {
+            (false, true) => BalanceTransition::Paused,
+            (true, false) => BalanceTransition::Resumed,
+            _ => BalanceTransition::Unchanged,
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MIN_BALANCE: u64 = 10_000_000;
+
+    #[test]
+    fn test_low_balance_pauses_submission() {
+        let guard = BalanceGuard::default();
+        assert_eq!(guard.observe(MIN_BALANCE, MIN_BALANCE), BalanceTransition::Unchanged);
+        assert!(!guard.is_paused());
+
+        assert_eq!(guard.observe(MIN_BALANCE - 1, MIN_BALANCE), BalanceTransition::Paused);
+        assert!(guard.is_paused());
+        assert_eq!(guard.observe(5_000, MIN_BALANCE), BalanceTransition::Unchanged);
+        assert!(guard.is_paused());
+    }
+
+    #[test]
+    fn test_recovered_balance_resumes_submission() {
+        let guard = BalanceGuard::default();
+        guard.observe(0, MIN_BALANCE);
+        assert_eq!(guard.observe(MIN_BALANCE * 2, MIN_BALANCE), BalanceTransition::Resumed);
+        assert!(!guard.is_paused());
+    }
+
+    #[test]
+    fn test_zero_minimum_never_pauses() {
+        let guard = BalanceGuard::default();
+        assert_eq!(guard.observe(0, 0), BalanceTransition::Unchanged);
+        assert!(!guard.is_paused());
+    }
+}
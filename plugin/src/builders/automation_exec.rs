@@ -1,10 +1,9 @@
 use std::sync::Arc;
 
 use clockwork_client::{
-    network::state::Worker,
+    network::state::{Pool, Worker},
     automation::state::{Automation, Trigger},
 };
-use clockwork_utils::automation::PAYER_PUBKEY;
 use log::info;
 use solana_account_decoder::UiAccountEncoding;
 use solana_client::{
@@ -12,6 +11,7 @@ use solana_client::{
     rpc_config::{RpcSimulateTransactionAccountsConfig, RpcSimulateTransactionConfig},
 };
 use solana_program::{
+    hash::Hash,
     instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
 };
@@ -30,34 +30,108 @@ static TRANSACTION_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
 /// The buffer amount to add to transactions' compute units in case on-chain PDA derivations take more CUs than used in simulation.
 static TRANSACTION_COMPUTE_UNIT_BUFFER: u32 = 1000;
 
+/// Builds and signs an exec transaction for the automation with the prefix instructions bundled at the front.
+/// If the bundle does not simulate successfully or does not fit in a transaction, the exec is rebuilt on its own,
+/// so the automation is not held responsible for a failing prefix. Also returns whether the prefix was bundled.
+#[allow(clippy::too_many_arguments)]
+pub async fn build_bundled_automation_exec_tx(
+    client: Arc<RpcClient>,
+    payer: &Keypair,
+    automation: Automation,
+    automation_pubkey: Pubkey,
+    pool_id: u64,
+    worker_id: u64,
+    instruction_limit: Option<usize>,
+    prefix_ixs: Vec<Instruction>,
+    compute_unit_estimate: Option<u64>,
+    compute_unit_price: Option<u64>,
+    blockhash: Hash,
+) -> Option<((Transaction, Option<u64>, bool), bool)> {
+    let mut attempts = vec![prefix_ixs];
+    if !attempts[0].is_empty() {
+        attempts.push(vec![]);
+    }
+    for prefix_ixs in attempts {
+        let bundled = !prefix_ixs.is_empty();
+        if let Some(result) = build_automation_exec_tx(
+            client.clone(),
+            payer,
+            automation.clone(),
+            automation_pubkey,
+            pool_id,
+            worker_id,
+            instruction_limit,
+            prefix_ixs,
+            compute_unit_estimate,
+            compute_unit_price,
+            blockhash,
+        )
+        .await
+        {
+            return Some((result, bundled));
+        }
+        if bundled {
+            info!(
+                "automation: {} status: unbundled reason: BundleFailed",
+                automation_pubkey
+            );
+        }
+    }
+    None
+}
+
+/// Builds and signs an exec transaction for the automation, packing as many exec steps as simulate successfully.
+/// Also returns the compute units consumed in simulation, and whether packing stopped at the automation's rate limit.
+#[allow(clippy::too_many_arguments)]
 pub async fn build_automation_exec_tx(
     client: Arc<RpcClient>,
     payer: &Keypair,
     automation: Automation,
     automation_pubkey: Pubkey,
+    pool_id: u64,
     worker_id: u64,
-) -> Option<Transaction> {
+    instruction_limit: Option<usize>,
+    prefix_ixs: Vec<Instruction>,
+    compute_unit_estimate: Option<u64>,
+    compute_unit_price: Option<u64>,
+    blockhash: Hash,
+) -> Option<(Transaction, Option<u64>, bool)> {
     // Grab the automation and relevant data.
     let now = std::time::Instant::now();
-    let blockhash = client.get_latest_blockhash().await.unwrap();
     let signatory_pubkey = payer.pubkey();
 
     // Build the first instruction of the transaction.
     let first_instruction = if automation.next_instruction.is_some() {
-        build_exec_ix(automation, signatory_pubkey, worker_id)
+        build_exec_ix(automation, signatory_pubkey, pool_id, worker_id)
     } else {
         build_kickoff_ix(automation, signatory_pubkey, worker_id)
     };
 
     // Simulate the transactino and pack as many instructions as possible until we hit mem/cpu limits.
     // TODO Migrate to versioned transactions.
-    let mut ixs: Vec<Instruction> = vec![
-        ComputeBudgetInstruction::set_compute_unit_limit(TRANSACTION_COMPUTE_UNIT_LIMIT),
-        first_instruction,
-    ];
+    // Any prefix instructions (e.g. a pool rotation) are placed between the compute budget and the exec instructions.
+    let mut ixs: Vec<Instruction> = vec![ComputeBudgetInstruction::set_compute_unit_limit(
+        TRANSACTION_COMPUTE_UNIT_LIMIT,
+    )];
+    if let Some(compute_unit_price) = compute_unit_price {
+        ixs.push(ComputeBudgetInstruction::set_compute_unit_price(
+            compute_unit_price,
+        ));
+    }
+    ixs.extend(prefix_ixs);
+    let exec_offset = ixs.len();
+    ixs.push(first_instruction);
     let mut successful_ixs: Vec<Instruction> = vec![];
     let mut units_consumed: Option<u64> = None;
-    loop {
+    let mut rate_limited = false;
+
+    // If the caller already has a compute unit estimate, skip simulation and submit the first instruction as-is.
+    if compute_unit_estimate.is_some() {
+        successful_ixs = ixs.clone();
+        units_consumed = compute_unit_estimate;
+    }
+
+    while compute_unit_estimate.is_none() {
         let mut sim_tx = Transaction::new_with_payer(&ixs, Some(&signatory_pubkey));
         sim_tx.sign(&[payer], blockhash);
 
@@ -104,6 +178,13 @@ pub async fn build_automation_exec_tx(
                 // Update flag tracking if at least one instruction succeed.
                 successful_ixs = ixs.clone();
 
+                // Exit early if the transaction has reached the step limit (excluding the compute budget and prefix instructions).
+                if let Some(instruction_limit) = instruction_limit {
+                    if ixs.len() - exec_offset >= instruction_limit {
+                        break;
+                    }
+                }
+
                 // Record the compute units consumed by the simulation.
                 if response.value.units_consumed.is_some() {
                     units_consumed = response.value.units_consumed;
@@ -121,10 +202,12 @@ pub async fn build_automation_exec_tx(
                                             ixs.push(build_exec_ix(
                                                 sim_automation,
                                                 signatory_pubkey,
+                                                pool_id,
                                                 worker_id,
                                             ));
                                         } else {
                                             // Exit early if the automation has reached its rate limit.
+                                            rate_limited = true;
                                             break;
                                         }
                                     }
@@ -160,14 +243,15 @@ pub async fn build_automation_exec_tx(
     let mut tx = Transaction::new_with_payer(&successful_ixs, Some(&signatory_pubkey));
     tx.sign(&[payer], blockhash);
     info!(
-        "automation: {:?} sim_duration: {:?} instruction_count: {:?} compute_units: {:?} tx_sig: {:?}",
+        "automation: {:?} sim_duration: {:?} simulated: {} instruction_count: {:?} compute_units: {:?} tx_sig: {:?}",
         automation_pubkey,
         now.elapsed(),
+        compute_unit_estimate.is_none(),
         successful_ixs.len(),
         units_consumed,
         tx.signatures[0]
     );
-    Some(tx)
+    Some((tx, units_consumed, rate_limited))
 }
 
 fn build_kickoff_ix(automation: Automation, signatory_pubkey: Pubkey, worker_id: u64) -> Instruction {
@@ -181,27 +265,42 @@ fn build_kickoff_ix(automation: Automation, signatory_pubkey: Pubkey, worker_id:
 
     // If the automation's trigger is account-based, inject the triggering account.
     match automation.trigger {
-        Trigger::Account {
-            address,
-            offset: _,
-            size: _,
-        } => kickoff_ix.accounts.push(AccountMeta {
-            pubkey: address,
-            is_signer: false,
-            is_writable: false,
-        }),
+        Trigger::Account { address, .. } | Trigger::AccountValue { address, .. } => {
+            kickoff_ix.accounts.push(AccountMeta {
+                pubkey: address,
+                is_signer: false,
+                is_writable: false,
+            })
+        }
         _ => {}
     }
 
     kickoff_ix
 }
 
-fn build_exec_ix(automation: Automation, signatory_pubkey: Pubkey, worker_id: u64) -> Instruction {
+/// Builds an exec instruction for the automation's next instruction.
+///
+/// Accounts of the next instruction are passed to the automation program as non-signers. The automation
+/// program invokes the next instruction with `invoke_signed` using the automation's PDA seeds, so a target
+/// program may require the automation account as a signer without the worker signing for it.
+fn build_exec_ix(
+    automation: Automation,
+    signatory_pubkey: Pubkey,
+    pool_id: u64,
+    worker_id: u64,
+) -> Instruction {
     // Build the instruction.
     let automation_pubkey = Automation::pubkey(automation.authority, automation.id);
+    let program_id = automation
+        .next_instruction
+        .as_ref()
+        .map(|next_instruction| next_instruction.program_id)
+        .unwrap_or_default();
     let mut exec_ix = clockwork_client::automation::instruction::automation_exec(
         signatory_pubkey,
         automation_pubkey,
+        Pool::pubkey(pool_id),
+        program_id,
         Worker::pubkey(worker_id),
     );
 
@@ -213,18 +312,154 @@ fn build_exec_ix(automation: Automation, signatory_pubkey: Pubkey, worker_id: u6
         ));
 
         // Inject the worker pubkey as the dynamic "payer" account.
-        for acc in next_instruction.clone().accounts {
-            let acc_pubkey = if acc.pubkey == PAYER_PUBKEY {
-                signatory_pubkey
-            } else {
-                acc.pubkey
-            };
+        // Signer flags are dropped here and restored in the inner instruction, where the automation PDA signs via CPI.
+        for acc in next_instruction.exec_accounts(signatory_pubkey) {
             exec_ix.accounts.push(match acc.is_writable {
-                true => AccountMeta::new(acc_pubkey, false),
-                false => AccountMeta::new_readonly(acc_pubkey, false),
+                true => AccountMeta::new(acc.pubkey, false),
+                false => AccountMeta::new_readonly(acc.pubkey, false),
             })
         }
     }
 
     exec_ix
 }
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        clockwork_client::automation::state::{ClockData, ExecMode},
+        tokio::runtime::Runtime,
+    };
+
+    fn automation() -> Automation {
+        Automation {
+            authority: Pubkey::new_unique(),
+            bump: 255,
+            chain_depth: 0,
+            created_at: ClockData {
+                slot: 0,
+                epoch: 0,
+                unix_timestamp: 0,
+            },
+            description: String::new(),
+            exec_context: None,
+            exec_mode: ExecMode::Worker,
+            execution_count: 0,
+            fee: 1_000,
+            id: b"crank".to_vec(),
+            instructions: vec![],
+            lifetime_spend_cap: None,
+            lifetime_spent: 0,
+            max_chain_depth: None,
+            max_executions: None,
+            max_simulation_failures: None,
+            name: String::new(),
+            next_instruction: None,
+            paused: false,
+            rate_limit: u64::MAX,
+            trigger: Trigger::Immediate,
+        }
+    }
+
+    fn build_bundled(
+        prefix_ixs: Vec<Instruction>,
+    ) -> Option<((Transaction, Option<u64>, bool), bool)> {
+        let automation = automation();
+        let automation_pubkey = Automation::pubkey(automation.authority, automation.id.clone());
+        Runtime::new().unwrap().block_on(build_bundled_automation_exec_tx(
+            Arc::new(RpcClient::new_mock("succeeds".into())),
+            &Keypair::new(),
+            automation,
+            automation_pubkey,
+            0,
+            0,
+            Some(1),
+            prefix_ixs,
+            None,
+            None,
+            Hash::new_unique(),
+        ))
+    }
+
+    fn program_ids(tx: &Transaction) -> Vec<Pubkey> {
+        tx.message
+            .instructions
+            .iter()
+            .map(|ix| *ix.program_id(&tx.message.account_keys))
+            .collect()
+    }
+
+    #[test]
+    fn test_bundles_prefix_before_exec() {
+        let rotation_ix = Instruction::new_with_bytes(Pubkey::new_unique(), &[1], vec![]);
+        let ((tx, _, _), bundled) = build_bundled(vec![rotation_ix.clone()]).unwrap();
+        assert!(bundled);
+        assert_eq!(
+            program_ids(&tx),
+            vec![
+                solana_sdk::compute_budget::id(),
+                rotation_ix.program_id,
+                clockwork_client::automation::ID,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unbundles_prefix_that_does_not_fit() {
+        let rotation_ix = Instruction::new_with_bytes(
+            Pubkey::new_unique(),
+            &[0; TRANSACTION_MESSAGE_SIZE_LIMIT],
+            vec![],
+        );
+        let ((tx, _, _), bundled) = build_bundled(vec![rotation_ix.clone()]).unwrap();
+        assert!(!bundled);
+        assert_eq!(
+            program_ids(&tx),
+            vec![solana_sdk::compute_budget::id(), clockwork_client::automation::ID]
+        );
+    }
+
+    fn build_with_estimate(
+        client: RpcClient,
+        compute_unit_estimate: Option<u64>,
+    ) -> Option<(Transaction, Option<u64>, bool)> {
+        let automation = automation();
+        let automation_pubkey = Automation::pubkey(automation.authority, automation.id.clone());
+        Runtime::new().unwrap().block_on(build_automation_exec_tx(
+            Arc::new(client),
+            &Keypair::new(),
+            automation,
+            automation_pubkey,
+            0,
+            0,
+            None,
+            vec![],
+            compute_unit_estimate,
+            None,
+            Hash::new_unique(),
+        ))
+    }
+
+    #[test]
+    fn test_skips_simulation_with_compute_unit_estimate() {
+        // Every RPC request fails, so the transaction can only be built without simulating it.
+        assert!(build_with_estimate(RpcClient::new_mock("fails".into()), None).is_none());
+
+        let (tx, units_consumed, rate_limited) =
+            build_with_estimate(RpcClient::new_mock("fails".into()), Some(50_000)).unwrap();
+        assert_eq!(units_consumed, Some(50_000));
+        assert!(!rate_limited);
+        assert_eq!(
+            program_ids(&tx),
+            vec![solana_sdk::compute_budget::id(), clockwork_client::automation::ID]
+        );
+
+        // The compute unit limit is the estimate plus the buffer.
+        let units_committed = 50_000 + TRANSACTION_COMPUTE_UNIT_BUFFER;
+        assert_eq!(
+            tx.message.instructions[0].data,
+            ComputeBudgetInstruction::set_compute_unit_limit(units_committed).data
+        );
+    }
+}
@@ -1,21 +1,37 @@
-use std::sync::Arc;
-
 use clockwork_client::network::state::{Pool, Registry, Snapshot, SnapshotFrame, Worker};
 use log::info;
-use solana_client::nonblocking::rpc_client::RpcClient;
-use solana_sdk::{signature::Keypair, signer::Signer, transaction::Transaction};
+use solana_program::{instruction::Instruction, pubkey::Pubkey};
 
 use crate::pool_position::PoolPosition;
 
-pub async fn build_pool_rotation_tx<'a>(
-    client: Arc<RpcClient>,
-    keypair: &Keypair,
+/// The reasons a worker may not attempt to rotate into the pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PoolRotationSkipReason {
+    /// The worker is already in the pool.
+    AlreadyInPool,
+    /// The registry nonce has not been initialized by the hasher automation.
+    NonceNotInitialized,
+    /// There is no snapshot for the current epoch.
+    NoSnapshot,
+    /// The worker has no snapshot frame for the current epoch.
+    NoSnapshotFrame,
+    /// The current snapshot has no stake.
+    NoSnapshotStake,
+    /// The worker has no delegated stake in the current snapshot.
+    NoWorkerStake,
+    /// The registry nonce does not fall within the worker's stake range.
+    RotationWindowClosed,
+}
+
+pub fn build_pool_rotation_ix(
+    signatory_pubkey: Pubkey,
     pool_position: PoolPosition,
     registry: Registry,
     snapshot: Snapshot,
     snapshot_frame: SnapshotFrame,
+    pool_id: u64,
     worker_id: u64,
-) -> Option<Transaction> {
+) -> Result<Instruction, PoolRotationSkipReason> {
     info!("nonce: {:?} total_stake: {:?} current_position: {:?} stake_offset: {:?} stake_amount: {:?}",
         registry.nonce.checked_rem(snapshot.total_stake),
         snapshot.total_stake,
@@ -26,22 +42,22 @@ pub async fn build_pool_rotation_tx<'a>(
 
     // Exit early if the rotator is not intialized
     if registry.nonce == 0 {
-        return None;
+        return Err(PoolRotationSkipReason::NonceNotInitialized);
     }
 
     // Exit early the snapshot has no stake
     if snapshot.total_stake == 0 {
-        return None;
+        return Err(PoolRotationSkipReason::NoSnapshotStake);
     }
 
     // Exit early if the worker is already in the pool.
     if pool_position.current_position.is_some() {
-        return None;
+        return Err(PoolRotationSkipReason::AlreadyInPool);
     }
 
     // Exit early if the snapshot frame is none or the worker has no delegated stake.
     if snapshot_frame.stake_amount.eq(&0) {
-        return None;
+        return Err(PoolRotationSkipReason::NoWorkerStake);
     }
 
     // Check if the rotation window is open for this worker.
@@ -57,21 +73,16 @@ pub async fn build_pool_rotation_tx<'a>(
         }
     };
     if !is_rotation_window_open {
-        return None;
+        return Err(PoolRotationSkipReason::RotationWindowClosed);
     }
 
-    // Build rotation instruction to rotate the worker into pool 0.
+    // Build rotation instruction to rotate the worker into the configured pool.
     let snapshot_pubkey = Snapshot::pubkey(snapshot.id);
-    let ix = clockwork_client::network::instruction::pool_rotate(
-        Pool::pubkey(0),
-        keypair.pubkey(),
+    Ok(clockwork_client::network::instruction::pool_rotate(
+        Pool::pubkey(pool_id),
+        signatory_pubkey,
         snapshot_pubkey,
         SnapshotFrame::pubkey(snapshot_pubkey, worker_id),
         Worker::pubkey(worker_id),
-    );
-
-    // Build and sign tx.
-    let mut tx = Transaction::new_with_payer(&[ix.clone()], Some(&keypair.pubkey()));
-    tx.sign(&[keypair], client.get_latest_blockhash().await.unwrap());
-    return Some(tx);
+    ))
 }
@@ -1,4 +1,7 @@
 use {
+    crate::{
+        backlog::StartupBacklogPolicy, maintenance::CronWindow, priority_fee::PriorityFee,
+    },
     serde::Deserialize,
     solana_geyser_plugin_interface::geyser_plugin_interface::{
         GeyserPluginError, Result as PluginResult,
@@ -6,32 +9,164 @@ use {
     std::{fs::File, path::Path},
 };
 
+static DEFAULT_COMPUTE_UNIT_REPORT_SIZE: usize = 10;
+static DEFAULT_MIN_KEYPAIR_BALANCE: u64 = 10_000_000;
+static DEFAULT_TRANSACTION_HISTORY_TTL: u64 = 300;
 static DEFAULT_TRANSACTION_TIMEOUT_THRESHOLD: u64 = 150;
 static DEFAULT_THREAD_COUNT: usize = 10;
+static DEFAULT_SIMULATION_FAILURE_COOLDOWN: u64 = 2;
+static DEFAULT_RETRY_JITTER_WINDOW: u64 = 2;
+static DEFAULT_RPC_URL: &str = "http://127.0.0.1:8899";
+static DEFAULT_WEBSOCKET_URL: &str = "ws://127.0.0.1:8900";
 
 /// Plugin config.
 #[derive(Clone, Debug, Deserialize)]
 pub struct PluginConfig {
+    /// Whether to bundle the pool rotation into the first exec transaction when rotating into the pool.
+    #[serde(default)]
+    pub bundle_pool_rotation: bool,
+    /// The number of most compute-intensive automations to report in the periodic compute unit log. Zero disables the report.
+    #[serde(default = "default_compute_unit_report_size")]
+    pub compute_unit_report_size: usize,
+    /// Where to bind a unix socket that streams the transaction executor's activity as JSON lines, if anywhere.
+    /// Each subscriber receives the exec attempts, successes, failures, drops, and pool rotations that happen after it connects.
+    #[serde(default)]
+    pub event_stream_path: Option<String>,
+    /// RPC endpoints to fall back to when the local RPC is unavailable.
+    #[serde(default)]
+    pub fallback_rpc_urls: Vec<String>,
     pub keypath: Option<String>,
+    /// Recurring windows during which the worker does not submit transactions, such as nightly maintenance.
+    /// In-flight transactions are still confirmed and requeued for retry during a window.
+    #[serde(default)]
+    pub maintenance_windows: Vec<CronWindow>,
+    /// The lamport balance below which the worker's keypair stops submitting new transactions, logging a critical warning.
+    /// Submissions resume once the keypair is topped back up. Zero disables the check.
+    #[serde(default = "default_min_keypair_balance")]
+    pub min_keypair_balance: u64,
+    /// The port to serve Prometheus metrics on, if any. The metrics are served on every interface.
+    #[serde(default)]
+    pub metrics_port: Option<u16>,
+    /// The id of the worker pool to rotate into and collect fees through.
+    /// Workers may be segmented into pools, e.g. by hardware or region, each created with `pool_create`.
+    #[serde(default)]
+    pub pool_id: u64,
+    /// The ids of the worker pools to take part in, if more than one. Overrides `pool_id` when set.
+    /// The worker rotates into each pool, and executes each automation through one of them per slot.
+    #[serde(default)]
+    pub pool_ids: Vec<u64>,
+    /// The compute unit price to attach to exec transactions so they are not dropped under congestion.
+    /// Workers pay the priority fee themselves and recover it from the automation fees their fee account collects.
+    #[serde(default)]
+    pub priority_fee: PriorityFee,
+    /// The max number of slots to randomly shift an automation's retry by after a simulation failure.
+    /// The shift is seeded from the worker id so workers retrying the same automation do not do so in lockstep.
+    #[serde(default = "default_retry_jitter_window")]
+    pub retry_jitter_window: u64,
+    /// The validator's RPC endpoint, used as the primary RPC client and by the TPU client.
+    #[serde(default = "default_rpc_url")]
+    pub rpc_url: String,
     pub sentry_url: Option<String>,
+    /// The minimum number of slots to wait after a simulation failure before simulating the automation again.
+    /// This is a floor under the exponential backoff, so a transient failure is not re-simulated on the very next slot.
+    #[serde(default = "default_simulation_failure_cooldown")]
+    pub simulation_failure_cooldown: u64,
+    /// Automations to submit without simulation once their compute units have been estimated.
+    /// This trades the safety of simulation for lower latency and must be opted into per automation.
+    #[serde(default)]
+    pub skip_simulation_automations: Vec<String>,
+    /// How to handle the missed cron moments of non-skippable automations found at startup.
+    /// Skippable automations take precedence over the policy, since they jump ahead to the current moment on chain.
+    #[serde(default)]
+    pub startup_backlog_policy: StartupBacklogPolicy,
+    /// Where to write a JSON dump of the plugin's runtime state for support requests.
+    /// A dump is written whenever a file named `<state_dump_path>.request` exists, after which the request file is removed.
+    #[serde(default)]
+    pub state_dump_path: Option<String>,
     pub thread_count: usize,
+    /// The max number of slots a sent transaction is tracked for before it is evicted and its automation requeued,
+    /// even if its status was never resolved. This is a safety net under the confirmation check.
+    #[serde(default = "default_transaction_history_ttl")]
+    pub transaction_history_ttl: u64,
     pub transaction_timeout_threshold: u64,
+    /// Where to persist the transaction executor's in-flight state so it survives a restart.
+    /// The state is written periodically and on unload, and reloaded when the first slot is processed.
+    #[serde(default)]
+    pub tx_state_path: Option<String>,
+    /// The validator's websocket endpoint, used by the TPU client to track the leader schedule.
+    #[serde(default = "default_websocket_url")]
+    pub websocket_url: String,
     pub worker_id: u64,
 }
 
 impl Default for PluginConfig {
     fn default() -> Self {
         Self {
+            bundle_pool_rotation: false,
+            compute_unit_report_size: DEFAULT_COMPUTE_UNIT_REPORT_SIZE,
+            event_stream_path: None,
+            fallback_rpc_urls: vec![],
             keypath: None,
+            maintenance_windows: vec![],
+            min_keypair_balance: DEFAULT_MIN_KEYPAIR_BALANCE,
+            metrics_port: None,
+            pool_id: 0,
+            pool_ids: vec![],
+            priority_fee: PriorityFee::default(),
+            retry_jitter_window: DEFAULT_RETRY_JITTER_WINDOW,
+            rpc_url: DEFAULT_RPC_URL.into(),
             sentry_url: None,
+            simulation_failure_cooldown: DEFAULT_SIMULATION_FAILURE_COOLDOWN,
+            skip_simulation_automations: vec![],
+            startup_backlog_policy: StartupBacklogPolicy::default(),
+            state_dump_path: None,
+            transaction_history_ttl: DEFAULT_TRANSACTION_HISTORY_TTL,
             transaction_timeout_threshold: DEFAULT_TRANSACTION_TIMEOUT_THRESHOLD,
             thread_count: DEFAULT_THREAD_COUNT,
+            tx_state_path: None,
+            websocket_url: DEFAULT_WEBSOCKET_URL.into(),
             worker_id: 0,
         }
     }
 }
 
+fn default_compute_unit_report_size() -> usize {
+    DEFAULT_COMPUTE_UNIT_REPORT_SIZE
+}
+
+fn default_min_keypair_balance() -> u64 {
+    DEFAULT_MIN_KEYPAIR_BALANCE
+}
+
+fn default_retry_jitter_window() -> u64 {
+    DEFAULT_RETRY_JITTER_WINDOW
+}
+
+fn default_rpc_url() -> String {
+    DEFAULT_RPC_URL.into()
+}
+
+fn default_simulation_failure_cooldown() -> u64 {
+    DEFAULT_SIMULATION_FAILURE_COOLDOWN
+}
+
+fn default_transaction_history_ttl() -> u64 {
+    DEFAULT_TRANSACTION_HISTORY_TTL
+}
+
+fn default_websocket_url() -> String {
+    DEFAULT_WEBSOCKET_URL.into()
+}
+
 impl PluginConfig {
+    /// The ids of the worker pools to take part in.
+    pub fn pool_ids(&self) -> Vec<u64> {
+        match self.pool_ids.is_empty() {
+            true => vec![self.pool_id],
+            false => self.pool_ids.clone(),
+        }
+    }
+
     /// Read plugin from JSON file.
     pub fn read_from<P: AsRef<Path>>(config_path: P) -> PluginResult<Self> {
         let file = File::open(config_path)?;
@@ -0,0 +1,176 @@
+use {
+    log::info,
+    serde::Serialize,
+    solana_program::pubkey::Pubkey,
+    std::sync::Arc,
+    tokio::{
+        io::AsyncWriteExt,
+        net::{UnixListener, UnixStream},
+        sync::broadcast::{self, error::RecvError, Receiver, Sender},
+    },
+};
+
+/// Number of events buffered per subscriber before a slow subscriber starts missing events.
+static EVENT_BUFFER_SIZE: usize = 1024;
+
+/// A structured record of the transaction executor's activity.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ExecEvent {
+    /// An exec transaction was sent to the leaders.
+    Attempted {
+        slot: u64,
+        automation: String,
+        signature: String,
+    },
+    /// A sent exec transaction was confirmed.
+    Succeeded {
+        slot: u64,
+        automation: String,
+        signature: String,
+    },
+    /// An exec transaction could not be built, sent, or landed.
+    Failed {
+        slot: u64,
+        automation: String,
+        reason: String,
+    },
+    /// An automation crossed the simulation failure threshold and is no longer tracked.
+    Dropped { slot: u64, automation: String },
+    /// An automation reached its rate limit, deferring its remaining execs to a later slot.
+    RateLimited {
+        slot: u64,
+        automation: String,
+        rate_limit: u64,
+    },
+    /// The worker attempted, or skipped, rotating into its pool.
+    Rotation {
+        slot: u64,
+        status: String,
+        reason: Option<String>,
+    },
+}
+
+impl ExecEvent {
+    pub fn failed(slot: u64, automation_pubkey: Pubkey, reason: impl ToString) -> Self {
+        ExecEvent::Failed {
+            slot,
+            automation: automation_pubkey.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+}
+
+/// Broadcasts exec events to the subscribers of a local unix socket, one JSON object per line.
+/// Events emitted while no one is subscribed are discarded.
+#[derive(Debug)]
+pub struct EventStream {
+    sender: Sender<ExecEvent>,
+}
+
+impl Default for EventStream {
+    fn default() -> Self {
+        let (sender, _receiver) = broadcast::channel(EVENT_BUFFER_SIZE);
+        Self { sender }
+    }
+}
+
+impl EventStream {
+    pub fn emit(&self, event: ExecEvent) {
+        self.sender.send(event).ok();
+    }
+
+    pub fn subscribe(&self) -> Receiver<ExecEvent> {
+        self.sender.subscribe()
+    }
+
+    /// Accepts subscribers on the unix socket at the path, replacing any stale socket left by a previous run.
+    pub async fn serve(self: Arc<Self>, path: String) -> std::io::Result<()> {
+        std::fs::remove_file(&path).ok();
+        let listener = UnixListener::bind(&path)?;
+        info!("event_stream: path: {} status: listening", path);
+        loop {
+            let (stream, _addr) = listener.accept().await?;
+            tokio::spawn(forward_events(stream, self.subscribe()));
+        }
+    }
+}
+
+/// Writes events to the subscriber until it disconnects.
+/// A subscriber that falls behind skips the events it missed rather than being disconnected.
+async fn forward_events(mut stream: UnixStream, mut receiver: Receiver<ExecEvent>) {
+    loop {
+        match receiver.recv().await {
+            Err(RecvError::Closed) => break,
+            Err(RecvError::Lagged(_count)) => continue,
+            Ok(event) => {
+                let mut line = serde_json::to_vec(&event).unwrap();
+                line.push(b'\n');
+                if stream.write_all(&line).await.is_err() {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        tokio::{
+            io::{AsyncBufReadExt, BufReader},
+            runtime::Runtime,
+            time::{sleep, Duration},
+        },
+    };
+
+    #[test]
+    fn test_subscriber_receives_exec_events() {
+        let path = std::env::temp_dir()
+            .join(format!("clockwork-events-{}.sock", std::process::id()))
+            .to_string_lossy()
+            .to_string();
+        Runtime::new().unwrap().block_on(async {
+            let event_stream = Arc::new(EventStream::default());
+            tokio::spawn(event_stream.clone().serve(path.clone()));
+
+            // Wait for the socket to be bound, then subscribe.
+            let mut stream = None;
+            for _ in 0..100 {
+                if let Ok(s) = UnixStream::connect(&path).await {
+                    stream = Some(s);
+                    break;
+                }
+                sleep(Duration::from_millis(10)).await;
+            }
+            let mut lines = BufReader::new(stream.unwrap()).lines();
+
+            // Emit events for a simulated exec, once the subscriber has been registered.
+            let automation_pubkey = Pubkey::new_unique();
+            while event_stream.sender.receiver_count() == 0 {
+                sleep(Duration::from_millis(10)).await;
+            }
+            event_stream.emit(ExecEvent::Attempted {
+                slot: 1,
+                automation: automation_pubkey.to_string(),
+                signature: "sig".into(),
+            });
+            event_stream.emit(ExecEvent::Succeeded {
+                slot: 12,
+                automation: automation_pubkey.to_string(),
+                signature: "sig".into(),
+            });
+
+            let attempted: serde_json::Value =
+                serde_json::from_str(&lines.next_line().await.unwrap().unwrap()).unwrap();
+            assert_eq!(attempted["event"], "attempted");
+            assert_eq!(attempted["automation"], automation_pubkey.to_string());
+            let succeeded: serde_json::Value =
+                serde_json::from_str(&lines.next_line().await.unwrap().unwrap()).unwrap();
+            assert_eq!(succeeded["event"], "succeeded");
+            assert_eq!(succeeded["slot"], 12);
+        });
+        std::fs::remove_file(&path).ok();
+    }
+}
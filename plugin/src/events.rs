@@ -1,6 +1,8 @@
 use anchor_lang::Discriminator;
 use bincode::deserialize;
-use clockwork_client::{automation::state::Automation, webhook::state::Request};
+use clockwork_client::{
+    automation::state::Automation, network::state::ProgramStatus, webhook::state::Request,
+};
 use log::info;
 use solana_geyser_plugin_interface::geyser_plugin_interface::{
     GeyserPluginError, ReplicaAccountInfo,
@@ -12,6 +14,7 @@ pub enum AccountUpdateEvent {
     Clock { clock: Clock },
     HttpRequest { request: Request },
     Automation { automation: Automation },
+    ProgramStatus { program_status: ProgramStatus },
 }
 
 impl TryFrom<ReplicaAccountInfo<'_>> for AccountUpdateEvent {
@@ -55,6 +58,20 @@ impl TryFrom<ReplicaAccountInfo<'_>> for AccountUpdateEvent {
             }
         }
 
+        // If the account is a program status of the network program, parse it.
+        if owner_pubkey.eq(&clockwork_client::network::ID) && account_info.data.len() > 8 {
+            let d = &account_info.data[..8];
+            if d.eq(&ProgramStatus::discriminator()) {
+                return Ok(AccountUpdateEvent::ProgramStatus {
+                    program_status: ProgramStatus::try_from(account_info.data.to_vec()).map_err(
+                        |_| GeyserPluginError::AccountsUpdateError {
+                            msg: "Failed to parse Clockwork program status account".into(),
+                        },
+                    )?,
+                });
+            }
+        }
+
         // If the account belongs to the webhook program, parse in
         if owner_pubkey.eq(&clockwork_client::webhook::ID) && account_info.data.len() > 8 {
             return Ok(AccountUpdateEvent::HttpRequest {
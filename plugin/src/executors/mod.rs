@@ -4,17 +4,27 @@ pub mod webhook;
 use std::{
     fmt::Debug,
     sync::{
-        atomic::{AtomicBool, Ordering},
-        Arc,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex, RwLock,
     },
+    time::Duration,
 };
 
 use anchor_lang::{prelude::Pubkey, AccountDeserialize};
 use async_trait::async_trait;
-use log::info;
+use clockwork_client::{
+    automation::state::Automation,
+    network::{
+        registry_lock_status,
+        state::{Config, Registry},
+        RegistryLockStatus,
+    },
+};
+use log::{error, info};
 use solana_client::{
     client_error::{ClientError, ClientErrorKind, Result as ClientResult},
     nonblocking::rpc_client::RpcClient,
+    rpc_request::RpcError,
 };
 use solana_geyser_plugin_interface::geyser_plugin_interface::Result as PluginResult;
 use solana_sdk::commitment_config::CommitmentConfig;
@@ -22,28 +32,208 @@ use tokio::runtime::Runtime;
 use tx::TxExecutor;
 use webhook::WebhookExecutor;
 
-use crate::{config::PluginConfig, observers::Observers};
+use crate::{
+    config::PluginConfig, event_stream::EventStream, observers::Observers, state_dump::StateDump,
+    tx_state::TxState,
+};
+
+/// Number of consecutive failed health checks of the primary RPC before falling back to another endpoint.
+static RPC_FALLBACK_THRESHOLD: u64 = 3;
+
+/// Number of slots the executor lock may be held before the watchdog considers the executor stalled.
+static WATCHDOG_TIMEOUT_THRESHOLD: u64 = 150;
+
+/// Number of slots between writes of the transaction executor's state to disk.
+static TX_STATE_PERSIST_PERIOD: u64 = 10;
+
+/// Number of slots between checks for a stuck registry lock.
+static REGISTRY_LOCK_CHECK_PERIOD: u64 = 100;
 
-static LOCAL_RPC_URL: &str = "http://127.0.0.1:8899";
+/// Max time to wait on shutdown for the sent transactions to land.
+static SHUTDOWN_DRAIN_TIMEOUT: Duration = Duration::from_secs(10);
 
 pub struct Executors {
-    pub tx: Arc<TxExecutor>,
+    pub config: PluginConfig,
+    pub tx: RwLock<Arc<TxExecutor>>,
+    pub event_stream: Arc<EventStream>,
     pub webhook: Arc<WebhookExecutor>,
-    pub client: Arc<RpcClient>,
-    pub lock: AtomicBool,
+    pub clients: Vec<Arc<RpcClient>>,
+    pub rpc_failover: RpcFailover,
+    pub lock: ExecutorLock,
+    /// Whether the transaction executor has yet to reload its persisted state.
+    pub tx_state_restore_pending: AtomicBool,
 }
 
 impl Executors {
     pub fn new(config: PluginConfig) -> Self {
+        let event_stream = Arc::new(EventStream::default());
         Executors {
-            tx: Arc::new(TxExecutor::new(config.clone())),
+            config: config.clone(),
+            tx: RwLock::new(Arc::new(TxExecutor::new(
+                config.clone(),
+                event_stream.clone(),
+            ))),
+            event_stream,
             webhook: Arc::new(WebhookExecutor::new(config.clone())),
-            client: Arc::new(RpcClient::new_with_commitment(
-                LOCAL_RPC_URL.into(),
-                CommitmentConfig::processed(),
-            )),
-            lock: AtomicBool::new(false),
+            clients: std::iter::once(config.rpc_url.clone())
+                .chain(config.fallback_rpc_urls.clone())
+                .map(|url| {
+                    Arc::new(RpcClient::new_with_commitment(
+                        url,
+                        CommitmentConfig::processed(),
+                    ))
+                })
+                .collect(),
+            rpc_failover: RpcFailover::default(),
+            lock: ExecutorLock::default(),
+            tx_state_restore_pending: AtomicBool::new(config.tx_state_path.is_some()),
+        }
+    }
+
+    /// Replaces the transaction executor with a fresh instance and releases the executor lock.
+    fn recover(&self, slot: u64) {
+        error!(
+            "watchdog: executor stalled at slot: {} last_processed_slot: {} status: reinitializing",
+            slot,
+            self.lock.last_processed_slot()
+        );
+        *self.tx.write().unwrap() = Arc::new(TxExecutor::new(
+            self.config.clone(),
+            self.event_stream.clone(),
+        ));
+        self.tx_state_restore_pending
+            .store(self.config.tx_state_path.is_some(), Ordering::Relaxed);
+        self.lock.recover(slot);
+    }
+
+    /// Writes a state dump if the operator has requested one by creating the request file next to the dump path.
+    async fn dump_state_if_requested(&self, slot: u64) {
+        let dump_path = match &self.config.state_dump_path {
+            None => return,
+            Some(dump_path) => dump_path,
+        };
+        let request_path = format!("{}.request", dump_path);
+        if !std::path::Path::new(&request_path).exists() {
+            return;
         }
+        let tx = self.tx.read().unwrap().clone();
+        match StateDump::new(&tx, slot).await.write_to(dump_path) {
+            Err(err) => error!(
+                "state_dump: slot: {} path: {} status: failed err: {}",
+                slot, dump_path, err
+            ),
+            Ok(()) => info!(
+                "state_dump: slot: {} path: {} status: written",
+                slot, dump_path
+            ),
+        }
+        std::fs::remove_file(&request_path).ok();
+    }
+
+    /// Writes the transaction executor's in-flight state to disk, if a path is configured.
+    pub async fn persist_tx_state(&self, slot: u64) {
+        let tx_state_path = match &self.config.tx_state_path {
+            None => return,
+            Some(tx_state_path) => tx_state_path,
+        };
+        let tx = self.tx.read().unwrap().clone();
+        if let Err(err) = TxState::new(&tx, slot).await.write_to(tx_state_path) {
+            error!(
+                "tx_state: slot: {} path: {} status: write_failed err: {}",
+                slot, tx_state_path, err
+            );
+        }
+    }
+
+    /// Drains the transaction executor and persists whatever is still in flight, before the plugin is unloaded.
+    pub async fn shutdown(&self) {
+        let slot = self.lock.last_processed_slot();
+        let tx = self.tx.read().unwrap().clone();
+        tx.shutdown(self.clients[0].clone(), SHUTDOWN_DRAIN_TIMEOUT)
+            .await;
+        self.persist_tx_state(slot).await;
+    }
+
+    /// Reloads the state persisted by a previous instance of the transaction executor, once.
+    async fn restore_tx_state_if_pending(&self, slot: u64) {
+        if !self.tx_state_restore_pending.swap(false, Ordering::Relaxed) {
+            return;
+        }
+        let tx_state_path = match &self.config.tx_state_path {
+            None => return,
+            Some(tx_state_path) => tx_state_path,
+        };
+        if !std::path::Path::new(tx_state_path).exists() {
+            return;
+        }
+        match TxState::read_from(tx_state_path) {
+            Err(err) => error!(
+                "tx_state: slot: {} path: {} status: read_failed err: {}",
+                slot, tx_state_path, err
+            ),
+            Ok(state) => {
+                let tx = self.tx.read().unwrap().clone();
+                tx.restore(state, slot).await;
+            }
+        }
+    }
+
+    /// Reports a registry lock that is no longer being released by the epoch jobs, so the admin can recover it.
+    async fn check_registry_lock(&self, client: &RpcClient, slot: u64) {
+        let registry = match client.get::<Registry>(&Registry::pubkey()).await {
+            Err(_err) => return,
+            Ok(registry) => registry,
+        };
+        if !registry.is_locked() {
+            return;
+        }
+        let last_exec_at = match client.get::<Config>(&Config::pubkey()).await {
+            Err(_err) => return,
+            Ok(config) => match client.get::<Automation>(&config.epoch_automation).await {
+                Err(_err) => return,
+                Ok(automation) => automation
+                    .exec_context
+                    .map(|exec_context| exec_context.last_exec_at),
+            },
+        };
+        if let RegistryLockStatus::Stuck { reason, idle_slots } =
+            registry_lock_status(&registry, last_exec_at, slot)
+        {
+            error!(
+                "registry_lock: slot: {} epoch: {} reason: {:?} idle_slots: {} status: stuck recovery: clockwork registry unlock",
+                slot, registry.current_epoch, reason, idle_slots
+            );
+        }
+    }
+
+    /// Returns a healthy RPC client, preferring the primary and rotating to the fallbacks after repeated primary failures.
+    async fn healthy_client(&self, slot: u64) -> Option<Arc<RpcClient>> {
+        let primary = self.clients[0].clone();
+        let primary_failures = match self
+            .rpc_failover
+            .observe_primary(primary.get_health().await.is_ok())
+        {
+            RpcRoute::Primary { recovered } => {
+                if recovered {
+                    info!("rpc: slot: {} status: recovered endpoint: {}", slot, primary.url());
+                }
+                return Some(primary);
+            }
+            RpcRoute::Wait => return None,
+            RpcRoute::Fallback { primary_failures } => primary_failures,
+        };
+        for fallback in self.clients.iter().skip(1) {
+            if fallback.get_health().await.is_ok() {
+                info!(
+                    "rpc: slot: {} status: fallback primary_failures: {} endpoint: {}",
+                    slot,
+                    primary_failures,
+                    fallback.url()
+                );
+                return Some(fallback.clone());
+            }
+        }
+        None
     }
 
     pub async fn process_slot(
@@ -55,49 +245,75 @@ impl Executors {
         info!("process_slot: {}", slot,);
         let now = std::time::Instant::now();
 
-        // Return early if node is not healthy.
-        if self.client.get_health().await.is_err() {
-            info!(
-                "processed_slot: {} duration: {:?} status: unhealthy",
-                slot,
-                now.elapsed()
-            );
-            return Ok(());
-        }
+        // Return early if neither the node nor any fallback endpoint is healthy.
+        let client = match self.healthy_client(slot).await {
+            None => {
+                info!(
+                    "processed_slot: {} duration: {:?} status: unhealthy",
+                    slot,
+                    now.elapsed()
+                );
+                return Ok(());
+            }
+            Some(client) => client,
+        };
 
-        // Acquire lock.
-        if self
-            .clone()
-            .lock
-            .compare_exchange(false, true, Ordering::Relaxed, Ordering::Relaxed)
-            .is_err()
-        {
-            info!(
-                "processed_slot: {} duration: {:?} status: locked",
-                slot,
-                now.elapsed()
-            );
-            return Ok(());
+        // Recover the executor if a previous slot's task has panicked or hung while holding the lock.
+        if self.lock.is_stalled(slot) {
+            self.recover(slot);
         }
 
+        // Acquire lock. It is released when the guard drops, including on the early returns below.
+        let guard = match self.lock.try_acquire(slot) {
+            None => {
+                info!(
+                    "processed_slot: {} duration: {:?} status: locked",
+                    slot,
+                    now.elapsed()
+                );
+                return Ok(());
+            }
+            Some(guard) => guard,
+        };
+
+        // Reload the transaction executor's state from before a restart.
+        self.restore_tx_state_if_pending(slot).await;
+
         // Process the slot on the observers.
         let executable_automations = observers.automation.clone().process_slot(slot).await?;
 
         // Process the slot in the transaction executor.
-        self.tx
+        let tx = self.tx.read().unwrap().clone();
+        tx.execute_txs(
+            client.clone(),
+            executable_automations,
+            slot,
+            runtime.clone(),
+        )
+        .await?;
+
+        // Send the pending webhook requests.
+        self.webhook
             .clone()
-            .execute_txs(
-                self.client.clone(),
-                executable_automations,
-                slot,
-                runtime.clone(),
-            )
+            .execute_requests(observers.clone(), runtime.clone())
             .await?;
 
-        // Release the lock.
-        self.clone()
-            .lock
-            .store(false, std::sync::atomic::Ordering::Relaxed);
+        // Periodically check that the epoch jobs have not left the registry locked.
+        if slot % REGISTRY_LOCK_CHECK_PERIOD == 0 {
+            self.check_registry_lock(&client, slot).await;
+        }
+
+        // Export the runtime state if an operator asked for it.
+        self.dump_state_if_requested(slot).await;
+
+        // Periodically persist the in-flight transactions.
+        if slot % TX_STATE_PERSIST_PERIOD == 0 {
+            self.persist_tx_state(slot).await;
+        }
+
+        // Record the completed slot and release the lock.
+        guard.complete(slot);
+        drop(guard);
         info!(
             "processed_slot: {} duration: {:?} status: processed",
             slot,
@@ -107,6 +323,123 @@ impl Executors {
     }
 }
 
+/// Where to send a slot's requests, given the latest health check of the primary RPC endpoint.
+#[derive(Debug, PartialEq)]
+pub enum RpcRoute {
+    /// Use the primary. It is recovered if it was failing over before this check.
+    Primary { recovered: bool },
+    /// The primary has not failed often enough yet to fall back. Skip the slot.
+    Wait,
+    /// The primary has failed repeatedly. Use the first healthy fallback.
+    Fallback { primary_failures: u64 },
+}
+
+/// Counts the consecutive failed health checks of the primary RPC endpoint.
+#[derive(Debug, Default)]
+pub struct RpcFailover {
+    primary_failures: AtomicU64,
+}
+
+impl RpcFailover {
+    /// Records a health check of the primary and returns the route for the slot. Only falls back
+    /// once the primary has failed several times in a row.
+    pub fn observe_primary(&self, healthy: bool) -> RpcRoute {
+        if healthy {
+            let primary_failures = self.primary_failures.swap(0, Ordering::Relaxed);
+            return RpcRoute::Primary {
+                recovered: primary_failures >= RPC_FALLBACK_THRESHOLD,
+            };
+        }
+        let primary_failures = self.primary_failures.fetch_add(1, Ordering::Relaxed) + 1;
+        match primary_failures < RPC_FALLBACK_THRESHOLD {
+            true => RpcRoute::Wait,
+            false => RpcRoute::Fallback { primary_failures },
+        }
+    }
+}
+
+/// The lock serializing slot processing. Each acquisition is stamped with a new generation, so a
+/// task the watchdog has recovered from can neither release the lock nor record a slot.
+#[derive(Debug, Default)]
+pub struct ExecutorLock {
+    state: Mutex<ExecutorLockState>,
+}
+
+#[derive(Debug, Default)]
+struct ExecutorLockState {
+    /// The generation of the task holding the lock, if any.
+    holder: Option<u64>,
+    /// The generation of the most recent acquisition.
+    generation: u64,
+    /// The last slot completed by a task holding the lock, or recovered from by the watchdog.
+    last_processed_slot: u64,
+}
+
+impl ExecutorLock {
+    /// Acquires the lock for the task processing the slot, if it is free.
+    pub fn try_acquire(&self, slot: u64) -> Option<ExecutorLockGuard> {
+        let mut state = self.state.lock().unwrap();
+        if state.holder.is_some() {
+            return None;
+        }
+        state.generation = state.generation.wrapping_add(1);
+        state.holder = Some(state.generation);
+
+        // Start the watchdog clock from the first slot this executor processes.
+        if state.last_processed_slot == 0 {
+            state.last_processed_slot = slot;
+        }
+        Some(ExecutorLockGuard {
+            lock: self,
+            generation: state.generation,
+        })
+    }
+
+    /// Returns true if the lock has been held without a slot completing for longer than the
+    /// watchdog threshold.
+    pub fn is_stalled(&self, slot: u64) -> bool {
+        let state = self.state.lock().unwrap();
+        state.holder.is_some()
+            && slot.saturating_sub(state.last_processed_slot) > WATCHDOG_TIMEOUT_THRESHOLD
+    }
+
+    /// Takes the lock from a stalled task and restarts the watchdog clock.
+    pub fn recover(&self, slot: u64) {
+        let mut state = self.state.lock().unwrap();
+        state.holder = None;
+        state.last_processed_slot = slot;
+    }
+
+    pub fn last_processed_slot(&self) -> u64 {
+        self.state.lock().unwrap().last_processed_slot
+    }
+}
+
+/// Holds the executor lock for one slot's task, releasing it on drop if it has not been recovered.
+pub struct ExecutorLockGuard<'a> {
+    lock: &'a ExecutorLock,
+    generation: u64,
+}
+
+impl ExecutorLockGuard<'_> {
+    /// Records the slot as processed, unless the watchdog has since taken the lock.
+    pub fn complete(&self, slot: u64) {
+        let mut state = self.lock.state.lock().unwrap();
+        if state.holder == Some(self.generation) {
+            state.last_processed_slot = slot;
+        }
+    }
+}
+
+impl Drop for ExecutorLockGuard<'_> {
+    fn drop(&mut self) {
+        let mut state = self.lock.state.lock().unwrap();
+        if state.holder == Some(self.generation) {
+            state.holder = None;
+        }
+    }
+}
+
 impl Debug for Executors {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "executors")
@@ -129,3 +462,89 @@ impl AccountGet for RpcClient {
         })
     }
 }
+
+/// Returns true if the client error was caused by the requested account not existing.
+pub fn is_account_not_found(err: &ClientError) -> bool {
+    match err.kind() {
+        ClientErrorKind::RpcError(RpcError::ForUser(msg)) => msg.starts_with("AccountNotFound"),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rpc_failover() {
+        let rpc_failover = RpcFailover::default();
+        assert_eq!(
+            rpc_failover.observe_primary(true),
+            RpcRoute::Primary { recovered: false }
+        );
+
+        // A failing primary is retried until it reaches the threshold, then falls back.
+        for _ in 1..RPC_FALLBACK_THRESHOLD {
+            assert_eq!(rpc_failover.observe_primary(false), RpcRoute::Wait);
+        }
+        assert_eq!(
+            rpc_failover.observe_primary(false),
+            RpcRoute::Fallback {
+                primary_failures: RPC_FALLBACK_THRESHOLD
+            }
+        );
+        assert_eq!(
+            rpc_failover.observe_primary(false),
+            RpcRoute::Fallback {
+                primary_failures: RPC_FALLBACK_THRESHOLD + 1
+            }
+        );
+
+        // Once the primary recovers, it is used again and must fail afresh before falling back.
+        assert_eq!(
+            rpc_failover.observe_primary(true),
+            RpcRoute::Primary { recovered: true }
+        );
+        assert_eq!(rpc_failover.observe_primary(false), RpcRoute::Wait);
+        assert_eq!(
+            rpc_failover.observe_primary(true),
+            RpcRoute::Primary { recovered: false }
+        );
+    }
+
+    #[test]
+    fn test_executor_lock_released_on_drop() {
+        let lock = ExecutorLock::default();
+        let guard = lock.try_acquire(100).unwrap();
+        assert!(lock.try_acquire(101).is_none());
+
+        // A task that returns early without completing its slot still releases the lock.
+        drop(guard);
+        assert!(lock.try_acquire(102).is_some());
+        assert_eq!(lock.last_processed_slot(), 100);
+    }
+
+    #[test]
+    fn test_executor_lock_recovers_stalled_slot() {
+        let lock = ExecutorLock::default();
+        let stale = lock.try_acquire(100).unwrap();
+        assert!(!lock.is_stalled(100 + WATCHDOG_TIMEOUT_THRESHOLD));
+        assert!(lock.is_stalled(101 + WATCHDOG_TIMEOUT_THRESHOLD));
+
+        // The watchdog takes the lock and the next slot's task acquires it.
+        let slot = 101 + WATCHDOG_TIMEOUT_THRESHOLD;
+        lock.recover(slot);
+        let current = lock.try_acquire(slot).unwrap();
+
+        // The stale task finishing can neither record its slot nor clear the new lock.
+        stale.complete(100);
+        drop(stale);
+        assert_eq!(lock.last_processed_slot(), slot);
+        assert!(lock.try_acquire(slot + 1).is_none());
+
+        current.complete(slot + 1);
+        drop(current);
+        assert_eq!(lock.last_processed_slot(), slot + 1);
+        assert!(lock.try_acquire(slot + 2).is_some());
+    }
+}
@@ -1,21 +1,30 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{hash_map::DefaultHasher, HashMap, HashSet},
     fmt::Debug,
+    hash::{Hash as _, Hasher},
     sync::{
-        atomic::{AtomicU64, Ordering},
+        atomic::{AtomicBool, AtomicU64, Ordering},
         Arc,
     },
+    time::{Duration, Instant},
 };
 
-use async_once::AsyncOnce;
 use bincode::serialize;
+use futures::{
+    stream::{FuturesUnordered, StreamExt},
+    Future,
+};
 use clockwork_client::{
     network::state::{Pool, Registry, Snapshot, SnapshotFrame, Worker},
-    automation::state::Automation,
+    automation::{
+        minimum_exec_balance,
+        state::{Automation, ExecContext, Trigger},
+    },
 };
-use lazy_static::lazy_static;
-use log::info;
+use anchor_lang::AnchorSerialize;
+use log::{error, info};
 use solana_client::{
+    client_error::ClientError,
     nonblocking::{rpc_client::RpcClient, tpu_client::TpuClient},
     rpc_config::RpcSimulateTransactionConfig,
     tpu_client::TpuClientConfig,
@@ -23,17 +32,35 @@ use solana_client::{
 use solana_geyser_plugin_interface::geyser_plugin_interface::{
     GeyserPluginError, Result as PluginResult,
 };
-use solana_program::{hash::Hash, message::Message, pubkey::Pubkey};
+use solana_program::{
+    hash::{hashv, Hash},
+    instruction::Instruction,
+    message::Message,
+    pubkey::Pubkey,
+};
 use solana_sdk::{
     commitment_config::CommitmentConfig,
     signature::{Keypair, Signature},
-    transaction::Transaction,
+    signer::Signer,
+    transaction::{Transaction, TransactionError},
+};
+use tokio::{
+    runtime::Runtime,
+    sync::{OnceCell, RwLock},
+    task::JoinError,
 };
-use tokio::{runtime::Runtime, sync::RwLock};
 
-use crate::{config::PluginConfig, pool_position::PoolPosition, utils::read_or_new_keypair};
+use crate::{
+    balance::{BalanceGuard, BalanceTransition},
+    builders::PoolRotationSkipReason,
+    config::PluginConfig,
+    event_stream::{EventStream, ExecEvent},
+    leader_schedule::{cached_schedule, LeaderSchedule},
+    maintenance::active_window, pool_position::{exec_pool_position, PoolPosition}, tx_state::TxState,
+    utils::read_or_new_keypair,
+};
 
-use super::AccountGet;
+use super::{is_account_not_found, AccountGet};
 
 /// Number of slots to wait before checking for a confirmed transaction.
 static TRANSACTION_CONFIRMATION_PERIOD: u64 = 10;
@@ -41,18 +68,61 @@ static TRANSACTION_CONFIRMATION_PERIOD: u64 = 10;
 /// Number of slots to wait before trying to execute a automation while not in the pool.
 static AUTOMATION_TIMEOUT_WINDOW: u64 = 8;
 
-/// Number of times to retry a automation simulation.
+/// Number of times to retry a automation simulation, unless the automation sets its own threshold.
 static MAX_AUTOMATION_SIMULATION_FAILURES: u32 = 5;
 
 /// The constant of the exponential backoff function.
 static EXPONENTIAL_BACKOFF_CONSTANT: u32 = 2;
 
+/// The longest exponential backoff between simulation retries, in slots.
+static MAX_BACKOFF_SLOTS: u64 = 1_024;
+
+/// Number of upcoming slots to look up leaders for before submitting transactions.
+pub static LEADER_LOOKAHEAD_SLOTS: u64 = 16;
+
+/// Number of slots a cached blockhash may be reused for when a fresh fetch fails.
+/// Blockhashes expire after 150 slots; this leaves headroom for the transaction to land.
+static BLOCKHASH_CACHE_VALIDITY: u64 = 100;
+
+/// Number of slots between reports of the most compute-intensive automations.
+static COMPUTE_UNIT_REPORT_PERIOD: u64 = 100;
+
+/// Time to wait between checks of the outstanding transactions while draining on shutdown.
+static SHUTDOWN_POLL_INTERVAL: Duration = Duration::from_millis(400);
+
 /// TxExecutor
 pub struct TxExecutor {
     pub config: PluginConfig,
     pub executable_automations: RwLock<HashMap<Pubkey, ExecutableAutomationMetadata>>,
     pub transaction_history: RwLock<HashMap<Pubkey, TransactionMetadata>>,
     pub dropped_automations: AtomicU64,
+    /// The number of exec transactions that were built and simulated successfully.
+    pub simulation_successes: AtomicU64,
+    /// The number of exec transactions that failed to build or simulate.
+    pub simulation_failures: AtomicU64,
+    /// The number of times an automation's execs were deferred to a later slot by its rate limit.
+    pub rate_limited_execs: AtomicU64,
+    pub trigger_metrics: TriggerMetrics,
+    /// The compute units consumed by the last simulation of each automation that skips simulation.
+    pub compute_unit_estimates: RwLock<HashMap<Pubkey, u64>>,
+    /// The compute units consumed by the last submitted exec transaction of each automation.
+    pub compute_unit_usage: RwLock<HashMap<Pubkey, u64>>,
+    /// The last successfully fetched blockhash and the slot it was fetched at.
+    pub cached_blockhash: RwLock<Option<(Hash, u64)>>,
+    /// The leader schedule of the current epoch, refreshed when the epoch changes.
+    pub leader_schedule: RwLock<Option<LeaderSchedule>>,
+    /// This worker's position in each of its delegate pools as of the last processed slot.
+    pub pool_positions: RwLock<Vec<PoolPosition>>,
+    /// The TPU client, connected to the configured RPC and websocket endpoints on first use.
+    pub tpu_client: OnceCell<TpuClient>,
+    /// The stream of exec activity published to local subscribers, shared across executor reinitializations.
+    pub event_stream: Arc<EventStream>,
+    /// Whether submissions are paused because the keypair's balance is below the configured minimum.
+    pub balance_guard: BalanceGuard,
+    /// Automations held back because they cannot pay for their exec, until they are topped up.
+    pub underfunded_automations: RwLock<HashSet<Pubkey>>,
+    /// Whether the executor is draining for shutdown and no longer accepts executable automations.
+    pub shutting_down: AtomicBool,
     pub keypair: Keypair,
 }
 
@@ -60,25 +130,186 @@ pub struct TxExecutor {
 pub struct ExecutableAutomationMetadata {
     pub due_slot: u64,
     pub simulation_failures: u32,
+    /// The max number of exec steps to pack into the next transaction, set when a multi-step transaction failed partway through.
+    pub instruction_limit: Option<usize>,
+    /// The slot of the most recent simulation failure.
+    pub last_simulation_failure_slot: Option<u64>,
+    /// The automation's own simulation failure threshold, recorded when it was last fetched.
+    pub max_simulation_failures: Option<u32>,
+    /// The slot the automation was last found at its rate limit in. It is not simulated again in that slot.
+    pub rate_limited_slot: Option<u64>,
+}
+
+impl ExecutableAutomationMetadata {
+    /// The metadata of an automation requeued at the slot after its transaction failed or expired.
+    /// It keeps the automation's own simulation failure threshold.
+    pub fn requeued(
+        slot: u64,
+        instruction_limit: Option<usize>,
+        max_simulation_failures: Option<u32>,
+    ) -> Self {
+        ExecutableAutomationMetadata {
+            due_slot: slot,
+            simulation_failures: 0,
+            instruction_limit,
+            last_simulation_failure_slot: None,
+            max_simulation_failures,
+            rate_limited_slot: None,
+        }
+    }
+
+    /// Whether the automation has failed more simulations than its threshold, or the global default if it sets none.
+    pub fn exceeds_simulation_failures(&self) -> bool {
+        self.simulation_failures
+            > self
+                .max_simulation_failures
+                .unwrap_or(MAX_AUTOMATION_SIMULATION_FAILURES)
+    }
+
+    /// The first slot the automation may be simulated at. This is the later of the jittered exponential backoff
+    /// from the due slot and the cooldown from the most recent simulation failure.
+    /// The backoff is capped at `MAX_BACKOFF_SLOTS`, however many simulations have failed.
+    pub fn retry_slot(&self, automation_pubkey: &Pubkey, config: &PluginConfig) -> u64 {
+        let backoff = EXPONENTIAL_BACKOFF_CONSTANT
+            .checked_pow(self.simulation_failures)
+            .map_or(MAX_BACKOFF_SLOTS, |backoff| (backoff as u64).min(MAX_BACKOFF_SLOTS));
+        let backoff_slot = self.due_slot.saturating_add(backoff) - 1;
+        let backoff_slot = if self.simulation_failures > 0 {
+            let jitter = retry_jitter(
+                config.worker_id,
+                automation_pubkey,
+                self.simulation_failures,
+                config.retry_jitter_window,
+            );
+            let jittered_slot = if jitter.is_negative() {
+                backoff_slot.saturating_sub(jitter.unsigned_abs())
+            } else {
+                backoff_slot.saturating_add(jitter as u64)
+            };
+            jittered_slot.max(self.due_slot)
+        } else {
+            backoff_slot
+        };
+        match self.last_simulation_failure_slot {
+            None => backoff_slot,
+            Some(failure_slot) => {
+                backoff_slot.max(failure_slot.saturating_add(config.simulation_failure_cooldown))
+            }
+        }
+    }
+}
+
+/// A pseudo-random offset in `[-jitter_window, jitter_window]` slots for an automation's retry.
+/// The offset is seeded from the worker id, so it is reproducible on a given worker but
+/// decorrelated across workers retrying the same automation.
+fn retry_jitter(
+    worker_id: u64,
+    automation_pubkey: &Pubkey,
+    simulation_failures: u32,
+    jitter_window: u64,
+) -> i64 {
+    if jitter_window == 0 {
+        return 0;
+    }
+    let mut hasher = DefaultHasher::new();
+    worker_id.hash(&mut hasher);
+    automation_pubkey.hash(&mut hasher);
+    simulation_failures.hash(&mut hasher);
+    let span = jitter_window.saturating_mul(2).saturating_add(1);
+    (hasher.finish() % span) as i64 - jitter_window as i64
+}
+
+/// Counts of executed automations broken down by trigger type.
+#[derive(Debug, Default)]
+pub struct TriggerMetrics {
+    pub account: AtomicU64,
+    pub cron: AtomicU64,
+    pub epoch: AtomicU64,
+    pub immediate: AtomicU64,
+    pub slot: AtomicU64,
+    pub timestamp: AtomicU64,
+}
+
+impl TriggerMetrics {
+    pub fn record(&self, trigger: &Trigger) {
+        let counter = match trigger {
+            Trigger::Account { .. } | Trigger::AccountValue { .. } => &self.account,
+            Trigger::Cron { .. } | Trigger::EpochFilteredCron { .. } => &self.cron,
+            Trigger::Epoch { .. } => &self.epoch,
+            Trigger::Immediate => &self.immediate,
+            Trigger::Slot { .. } | Trigger::EpochFilteredSlot { .. } => &self.slot,
+            Trigger::Timestamp { .. } => &self.timestamp,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
 }
 
 #[derive(Debug)]
 pub struct TransactionMetadata {
     pub slot_sent: u64,
     pub signature: Signature,
+    /// The hash of the transaction's message with its blockhash zeroed and the automation's exec
+    /// context it was built against. It is the same for a retry under a newer blockhash.
+    pub retry_hash: Hash,
+    /// The index of the first exec instruction in the transaction.
+    pub exec_offset: usize,
+    /// The compute units the transaction consumed in its last simulation.
+    pub compute_units: Option<u64>,
+    /// The automation's own simulation failure threshold when the transaction was sent.
+    pub max_simulation_failures: Option<u32>,
 }
 
 impl TxExecutor {
-    pub fn new(config: PluginConfig) -> Self {
+    pub fn new(config: PluginConfig, event_stream: Arc<EventStream>) -> Self {
         Self {
             config: config.clone(),
             executable_automations: RwLock::new(HashMap::new()),
             transaction_history: RwLock::new(HashMap::new()),
             dropped_automations: AtomicU64::new(0),
+            simulation_successes: AtomicU64::new(0),
+            simulation_failures: AtomicU64::new(0),
+            rate_limited_execs: AtomicU64::new(0),
+            trigger_metrics: TriggerMetrics::default(),
+            compute_unit_estimates: RwLock::new(HashMap::new()),
+            compute_unit_usage: RwLock::new(HashMap::new()),
+            cached_blockhash: RwLock::new(None),
+            leader_schedule: RwLock::new(None),
+            pool_positions: RwLock::new(vec![]),
+            tpu_client: OnceCell::new(),
+            event_stream,
+            balance_guard: BalanceGuard::default(),
+            underfunded_automations: RwLock::new(HashSet::new()),
+            shutting_down: AtomicBool::new(false),
             keypair: read_or_new_keypair(config.keypath),
         }
     }
 
+    /// Reload the state persisted before a restart.
+    /// Transactions sent more than a confirmation period ago are dropped rather than tracked again.
+    pub async fn restore(&self, state: TxState, slot: u64) {
+        let mut transaction_history = state.transaction_history();
+        let persisted_transactions = transaction_history.len();
+        transaction_history
+            .retain(|_, metadata| slot <= metadata.slot_sent + TRANSACTION_CONFIRMATION_PERIOD);
+        let executable_automations = state.executable_automations();
+        info!(
+            "tx_state: slot: {} persisted_slot: {} status: restored executable_automations: {} transactions: {} dropped_transactions: {}",
+            slot,
+            state.slot,
+            executable_automations.len(),
+            transaction_history.len(),
+            persisted_transactions - transaction_history.len()
+        );
+        self.executable_automations
+            .write()
+            .await
+            .extend(executable_automations);
+        self.transaction_history
+            .write()
+            .await
+            .extend(transaction_history);
+    }
+
     pub async fn execute_txs(
         self: Arc<Self>,
         client: Arc<RpcClient>,
@@ -86,6 +317,15 @@ impl TxExecutor {
         slot: u64,
         runtime: Arc<Runtime>,
     ) -> PluginResult<()> {
+        // Accept no more work while draining for shutdown.
+        if self.shutting_down.load(Ordering::Relaxed) {
+            info!(
+                "slot: {} worker_id: {} status: shutting_down",
+                slot, self.config.worker_id
+            );
+            return Ok(());
+        }
+
         // Index the provided automations as executable.
         let mut w_executable_automations = self.executable_automations.write().await;
         automation_pubkeys.iter().for_each(|pubkey| {
@@ -94,61 +334,223 @@ impl TxExecutor {
                 ExecutableAutomationMetadata {
                     due_slot: slot,
                     simulation_failures: 0,
+                    instruction_limit: None,
+                    last_simulation_failure_slot: None,
+                    max_simulation_failures: None,
+                    rate_limited_slot: None,
                 },
             );
         });
 
         // Drop automations that cross the simulation failure threshold.
-        w_executable_automations.retain(|_automation_pubkey, metadata| {
-            if metadata.simulation_failures > MAX_AUTOMATION_SIMULATION_FAILURES {
+        w_executable_automations.retain(|automation_pubkey, metadata| {
+            if metadata.exceeds_simulation_failures() {
                 self.dropped_automations.fetch_add(1, Ordering::Relaxed);
+                self.event_stream.emit(ExecEvent::Dropped {
+                    slot,
+                    automation: automation_pubkey.to_string(),
+                });
                 false
             } else {
                 true
             }
         });
         info!(
-            "dropped_automations: {:?} executable_automations: {:?}",
+            "dropped_automations: {:?} executed_automations: {{ account: {:?} cron: {:?} epoch: {:?} immediate: {:?} slot: {:?} timestamp: {:?} }} executable_automations: {:?}",
             self.dropped_automations.load(Ordering::Relaxed),
+            self.trigger_metrics.account.load(Ordering::Relaxed),
+            self.trigger_metrics.cron.load(Ordering::Relaxed),
+            self.trigger_metrics.epoch.load(Ordering::Relaxed),
+            self.trigger_metrics.immediate.load(Ordering::Relaxed),
+            self.trigger_metrics.slot.load(Ordering::Relaxed),
+            self.trigger_metrics.timestamp.load(Ordering::Relaxed),
             *w_executable_automations
         );
         drop(w_executable_automations);
 
+        // Periodically report the automations that consume the most compute.
+        if slot % COMPUTE_UNIT_REPORT_PERIOD == 0 {
+            self.report_compute_unit_usage(slot).await;
+        }
+
         // Process retries.
         self.clone()
             .process_retries(client.clone(), slot)
             .await
             .ok();
 
-        // Get self worker's position in the delegate pool.
+        // Hold off on submitting new transactions during a maintenance window.
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|duration| duration.as_secs() as i64)
+            .unwrap_or_default();
+        if let Some(window) = active_window(&self.config.maintenance_windows, now) {
+            info!(
+                "slot: {} worker_id: {} status: maintenance schedule: {:?} duration: {}",
+                slot, self.config.worker_id, window.schedule, window.duration
+            );
+            return Ok(());
+        }
+
+        // Hold off on submitting new transactions while the keypair cannot pay for them.
+        if self.config.min_keypair_balance > 0 {
+            self.check_keypair_balance(client.clone(), slot).await;
+            if self.balance_guard.is_paused() {
+                info!(
+                    "slot: {} worker_id: {} status: low_balance",
+                    slot, self.config.worker_id
+                );
+                return Ok(());
+            }
+        }
+
+        // Get self worker's position in each of its delegate pools.
         let worker_pubkey = Worker::pubkey(self.config.worker_id);
-        if let Ok(pool_position) = client.get::<Pool>(&Pool::pubkey(0)).await.map(|pool| {
-            let workers = &mut pool.workers.clone();
-            PoolPosition {
-                current_position: pool
-                    .workers
-                    .iter()
-                    .position(|k| k.eq(&worker_pubkey))
-                    .map(|i| i as u64),
-                workers: workers.make_contiguous().to_vec().clone(),
+        let mut pool_positions = vec![];
+        for pool_id in self.config.pool_ids() {
+            if let Ok(pool_position) = client
+                .get::<Pool>(&Pool::pubkey(pool_id))
+                .await
+                .map(|pool| PoolPosition::new(pool_id, worker_pubkey, pool.workers.into()))
+            {
+                info!(
+                    "slot: {} worker_id: {} pool_id: {} current_position: {:?} pool_size: {}",
+                    slot,
+                    self.config.worker_id,
+                    pool_id,
+                    pool_position.current_position,
+                    pool_position.workers.len()
+                );
+                pool_positions.push(pool_position);
             }
-        }) {
-            // Rotate into the worker pool.
-            if pool_position.current_position.is_none() {
+        }
+        *self.pool_positions.write().await = pool_positions.clone();
+        let exec_pool_position = match exec_pool_position(&pool_positions) {
+            None => return Ok(()),
+            Some(pool_position) => pool_position.clone(),
+        };
+
+        // Rotate into each worker pool.
+        // If bundling is enabled, the rotation into the pool automations are executed through is deferred
+        // to the front of the first exec transaction.
+        let mut rotation_ix = None;
+        for pool_position in pool_positions {
+            if pool_position.current_position.is_some() {
+                continue;
+            }
+            if self.config.bundle_pool_rotation
+                && pool_position.pool_id == exec_pool_position.pool_id
+            {
+                rotation_ix = self
+                    .clone()
+                    .build_pool_rotate_ix(client.clone(), slot, pool_position)
+                    .await;
+            } else {
                 self.clone()
-                    .execute_pool_rotate_txs(client.clone(), slot, pool_position.clone())
+                    .execute_pool_rotate_txs(client.clone(), slot, pool_position)
                     .await
                     .ok();
             }
+        }
 
-            // Execute automation transactions.
-            self.clone()
-                .execute_automation_exec_txs(client.clone(), slot, pool_position, runtime.clone())
+        // Execute automation transactions.
+        self.clone()
+            .execute_automation_exec_txs(
+                client.clone(),
+                slot,
+                exec_pool_position,
+                runtime.clone(),
+                rotation_ix,
+            )
+            .await
+            .ok();
+
+        Ok(())
+    }
+
+    /// Stops accepting executable automations and waits up to the timeout for the sent transactions to land.
+    /// Transactions that land are dropped from the history, so only the unresolved ones are persisted for the next
+    /// instance to check rather than resubmit.
+    pub async fn shutdown(&self, client: Arc<RpcClient>, timeout: Duration) {
+        self.shutting_down.store(true, Ordering::Relaxed);
+        let deadline = Instant::now() + timeout;
+        loop {
+            let signatures = self
+                .transaction_history
+                .read()
                 .await
-                .ok();
+                .iter()
+                .map(|(pubkey, metadata)| (*pubkey, metadata.signature))
+                .collect::<Vec<(Pubkey, Signature)>>();
+            if signatures.is_empty() {
+                break;
+            }
+            for (automation_pubkey, signature) in signatures {
+                if let Ok(Some(_status)) = client
+                    .get_signature_status_with_commitment(&signature, CommitmentConfig::confirmed())
+                    .await
+                {
+                    self.transaction_history
+                        .write()
+                        .await
+                        .remove(&automation_pubkey);
+                }
+            }
+            if Instant::now() >= deadline {
+                break;
+            }
+            tokio::time::sleep(SHUTDOWN_POLL_INTERVAL).await;
         }
+        info!(
+            "shutdown: worker_id: {} status: drained unresolved_transactions: {}",
+            self.config.worker_id,
+            self.transaction_history.read().await.len()
+        );
+    }
 
-        Ok(())
+    /// Fetches the keypair's balance and pauses or resumes submissions as it crosses the configured minimum.
+    /// If the balance cannot be fetched, the previous state is kept.
+    async fn check_keypair_balance(&self, client: Arc<RpcClient>, slot: u64) {
+        let keypair_pubkey = self.keypair.pubkey();
+        let balance = match client.get_balance(&keypair_pubkey).await {
+            Err(_err) => return,
+            Ok(balance) => balance,
+        };
+        match self
+            .balance_guard
+            .observe(balance, self.config.min_keypair_balance)
+        {
+            BalanceTransition::Paused => error!(
+                "keypair: {} slot: {} balance: {} min_keypair_balance: {} status: paused_low_balance",
+                keypair_pubkey, slot, balance, self.config.min_keypair_balance
+            ),
+            BalanceTransition::Resumed => info!(
+                "keypair: {} slot: {} balance: {} status: resumed",
+                keypair_pubkey, slot, balance
+            ),
+            BalanceTransition::Unchanged => {}
+        }
+    }
+
+    /// Log the automations with the highest compute unit usage, most expensive first.
+    async fn report_compute_unit_usage(&self, slot: u64) {
+        if self.config.compute_unit_report_size == 0 {
+            return;
+        }
+        let mut usage = self
+            .compute_unit_usage
+            .read()
+            .await
+            .iter()
+            .map(|(pubkey, units)| (*pubkey, *units))
+            .collect::<Vec<(Pubkey, u64)>>();
+        let tracked_automations = usage.len();
+        usage.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        usage.truncate(self.config.compute_unit_report_size);
+        info!(
+            "compute_units: slot: {} tracked_automations: {} top_automations: {:?}",
+            slot, tracked_automations, usage
+        );
     }
 
     async fn process_retries(
@@ -156,10 +558,32 @@ impl TxExecutor {
         client: Arc<RpcClient>,
         slot: u64,
     ) -> PluginResult<()> {
+        // Evict transactions that were never resolved within the TTL, and requeue their automations below.
+        // Each retriable automation maps to its instruction limit and simulation failure threshold.
+        let mut retriable_automations: HashMap<Pubkey, (Option<usize>, Option<u32>)> =
+            HashMap::new();
+        let mut w_transaction_history = self.transaction_history.write().await;
+        for (pubkey, metadata) in evict_expired_transactions(
+            &mut w_transaction_history,
+            slot,
+            self.config.transaction_history_ttl,
+        ) {
+            info!(
+                "slot: {} automation: {} status: transaction_expired",
+                slot, pubkey
+            );
+            self.event_stream
+                .emit(ExecEvent::failed(slot, pubkey, "transaction_expired"));
+            retriable_automations.insert(pubkey, (None, metadata.max_simulation_failures));
+        }
+        drop(w_transaction_history);
+
         // Get transaction signatures and corresponding automations to check.
         struct CheckableTransaction {
             automation_pubkey: Pubkey,
             signature: Signature,
+            exec_offset: usize,
+            max_simulation_failures: Option<u32>,
         }
         let r_transaction_history = self.transaction_history.read().await;
         let checkable_transactions = r_transaction_history
@@ -168,12 +592,15 @@ impl TxExecutor {
             .map(|(pubkey, metadata)| CheckableTransaction {
                 automation_pubkey: *pubkey,
                 signature: metadata.signature,
+                exec_offset: metadata.exec_offset,
+                max_simulation_failures: metadata.max_simulation_failures,
             })
             .collect::<Vec<CheckableTransaction>>();
         drop(r_transaction_history);
 
         // Lookup transaction statuses and track which automations are successful / retriable.
-        let mut retriable_automations: HashSet<Pubkey> = HashSet::new();
+        // For transactions that failed partway through a multi-step exec, track how many steps succeeded
+        // so the retry lands those steps and resumes from the failing step via the on-chain exec context.
         let mut successful_automations: HashSet<Pubkey> = HashSet::new();
         for data in checkable_transactions {
             match client
@@ -186,13 +613,37 @@ impl TxExecutor {
                 Err(_err) => {}
                 Ok(status) => match status {
                     None => {
-                        retriable_automations.insert(data.automation_pubkey);
+                        self.event_stream.emit(ExecEvent::failed(
+                            slot,
+                            data.automation_pubkey,
+                            "not_confirmed",
+                        ));
+                        retriable_automations.insert(
+                            data.automation_pubkey,
+                            (None, data.max_simulation_failures),
+                        );
                     }
                     Some(status) => match status {
-                        Err(_err) => {
-                            retriable_automations.insert(data.automation_pubkey);
+                        Err(err) => {
+                            self.event_stream.emit(ExecEvent::failed(
+                                slot,
+                                data.automation_pubkey,
+                                &err,
+                            ));
+                            retriable_automations.insert(
+                                data.automation_pubkey,
+                                (
+                                    successful_exec_steps(&err, data.exec_offset),
+                                    data.max_simulation_failures,
+                                ),
+                            );
                         }
                         Ok(()) => {
+                            self.event_stream.emit(ExecEvent::Succeeded {
+                                slot,
+                                automation: data.automation_pubkey.to_string(),
+                                signature: data.signature.to_string(),
+                            });
                             successful_automations.insert(data.automation_pubkey);
                         }
                     },
@@ -206,14 +657,15 @@ impl TxExecutor {
         for pubkey in successful_automations {
             w_transaction_history.remove(&pubkey);
         }
-        for pubkey in retriable_automations {
+        for (pubkey, (instruction_limit, max_simulation_failures)) in retriable_automations {
             w_transaction_history.remove(&pubkey);
             w_executable_automations.insert(
                 pubkey,
-                ExecutableAutomationMetadata {
-                    due_slot: slot,
-                    simulation_failures: 0,
-                },
+                ExecutableAutomationMetadata::requeued(
+                    slot,
+                    instruction_limit,
+                    max_simulation_failures,
+                ),
             );
         }
         info!("transaction_history: {:?}", *w_transaction_history);
@@ -225,30 +677,104 @@ impl TxExecutor {
     async fn execute_pool_rotate_txs(
         self: Arc<Self>,
         client: Arc<RpcClient>,
-        _slot: u64,
+        slot: u64,
         pool_position: PoolPosition,
     ) -> PluginResult<()> {
-        let registry = client.get::<Registry>(&Registry::pubkey()).await.unwrap();
-        let snapshot_pubkey = Snapshot::pubkey(registry.current_epoch);
+        if let Some(rotation_ix) = self
+            .clone()
+            .build_pool_rotate_ix(client.clone(), slot, pool_position)
+            .await
+        {
+            self.clone()
+                .submit_pool_rotate_ix(client.clone(), rotation_ix)
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn build_pool_rotate_ix(
+        self: Arc<Self>,
+        client: Arc<RpcClient>,
+        slot: u64,
+        pool_position: PoolPosition,
+    ) -> Option<Instruction> {
+        let registry = match client.get::<Registry>(&Registry::pubkey()).await {
+            Err(_err) => {
+                info!(
+                    "pool_rotation: slot: {} worker_id: {} status: skipped reason: RegistryNotFound",
+                    slot, self.config.worker_id
+                );
+                self.event_stream.emit(ExecEvent::Rotation {
+                    slot,
+                    status: "skipped".into(),
+                    reason: Some("RegistryNotFound".into()),
+                });
+                return None;
+            }
+            Ok(registry) => registry,
+        };
+        let epoch = registry.current_epoch;
+        let pool_id = pool_position.pool_id;
+        let current_position = pool_position.current_position;
+        let snapshot_pubkey = Snapshot::pubkey(epoch);
         let snapshot_frame_pubkey = SnapshotFrame::pubkey(snapshot_pubkey, self.config.worker_id);
-        if let Ok(snapshot) = client.get::<Snapshot>(&snapshot_pubkey).await {
-            if let Ok(snapshot_frame) = client.get::<SnapshotFrame>(&snapshot_frame_pubkey).await {
-                if let Some(tx) = crate::builders::build_pool_rotation_tx(
-                    client.clone(),
-                    &self.keypair,
+        let result = match client.get::<Snapshot>(&snapshot_pubkey).await {
+            Err(_err) => Err(PoolRotationSkipReason::NoSnapshot),
+            Ok(snapshot) => match client.get::<SnapshotFrame>(&snapshot_frame_pubkey).await {
+                Err(_err) => Err(PoolRotationSkipReason::NoSnapshotFrame),
+                Ok(snapshot_frame) => crate::builders::build_pool_rotation_ix(
+                    self.keypair.pubkey(),
                     pool_position,
                     registry,
                     snapshot,
                     snapshot_frame,
+                    pool_id,
                     self.config.worker_id,
-                )
-                .await
-                {
-                    self.clone().simulate_tx(&tx).await?;
-                    self.clone().submit_tx(&tx).await?;
-                }
+                ),
+            },
+        };
+        match result {
+            Err(reason) => {
+                info!(
+                    "pool_rotation: slot: {} epoch: {} worker_id: {} pool_id: {} current_position: {:?} status: skipped reason: {:?}",
+                    slot, epoch, self.config.worker_id, pool_id, current_position, reason
+                );
+                self.event_stream.emit(ExecEvent::Rotation {
+                    slot,
+                    status: "skipped".into(),
+                    reason: Some(format!("{:?}", reason)),
+                });
+                None
+            }
+            Ok(rotation_ix) => {
+                info!(
+                    "pool_rotation: slot: {} epoch: {} worker_id: {} pool_id: {} current_position: {:?} status: attempted",
+                    slot, epoch, self.config.worker_id, pool_id, current_position
+                );
+                self.event_stream.emit(ExecEvent::Rotation {
+                    slot,
+                    status: "attempted".into(),
+                    reason: None,
+                });
+                Some(rotation_ix)
             }
         }
+    }
+
+    async fn submit_pool_rotate_ix(
+        self: Arc<Self>,
+        client: Arc<RpcClient>,
+        rotation_ix: Instruction,
+    ) -> PluginResult<()> {
+        let mut tx = Transaction::new_with_payer(&[rotation_ix], Some(&self.keypair.pubkey()));
+        tx.sign(
+            &[&self.keypair],
+            client.get_latest_blockhash().await.map_err(|err| {
+                GeyserPluginError::Custom(format!("Failed to get blockhash: {}", err).into())
+            })?,
+        );
+        self.clone().simulate_tx(&tx).await?;
+        self.clone().submit_tx(&tx).await?;
         Ok(())
     }
 
@@ -260,32 +786,42 @@ impl TxExecutor {
         // Get the set of automation pubkeys that are executable.
         // Note we parallelize using rayon because this work is CPU heavy.
         let r_executable_automations = self.executable_automations.read().await;
+        let rate_limited_automations = r_executable_automations
+            .values()
+            .filter(|metadata| metadata.rate_limited_slot == Some(slot))
+            .count();
         let automation_pubkeys =
-            if pool_position.current_position.is_none() && !pool_position.workers.is_empty() {
+            if pool_position.defers_to_pool() {
                 // This worker is not in the pool. Get pubkeys of automations that are beyond the timeout window.
                 r_executable_automations
                     .iter()
                     .filter(|(_pubkey, metadata)| slot > metadata.due_slot + AUTOMATION_TIMEOUT_WINDOW)
-                    .filter(|(_pubkey, metadata)| {
-                        slot >= metadata.due_slot
-                            + EXPONENTIAL_BACKOFF_CONSTANT.pow(metadata.simulation_failures) as u64
-                            - 1
+                    .filter(|(_pubkey, metadata)| !metadata.exceeds_simulation_failures())
+                    .filter(|(_pubkey, metadata)| metadata.rate_limited_slot != Some(slot))
+                    .filter(|(pubkey, metadata)| {
+                        slot >= metadata.retry_slot(pubkey, &self.config)
                     })
                     .map(|(pubkey, _metadata)| *pubkey)
                     .collect::<Vec<Pubkey>>()
             } else {
-                // This worker is in the pool. Get pubkeys executable automations.
+                // This worker is in the pool, or the pool is empty. Get pubkeys executable automations.
                 r_executable_automations
                     .iter()
-                    .filter(|(_pubkey, metadata)| {
-                        slot >= metadata.due_slot
-                            + EXPONENTIAL_BACKOFF_CONSTANT.pow(metadata.simulation_failures) as u64
-                            - 1
+                    .filter(|(_pubkey, metadata)| !metadata.exceeds_simulation_failures())
+                    .filter(|(_pubkey, metadata)| metadata.rate_limited_slot != Some(slot))
+                    .filter(|(pubkey, metadata)| {
+                        slot >= metadata.retry_slot(pubkey, &self.config)
                     })
                     .map(|(pubkey, _metadata)| *pubkey)
                     .collect::<Vec<Pubkey>>()
             };
         drop(r_executable_automations);
+        if rate_limited_automations > 0 {
+            info!(
+                "slot: {} rate_limited_automations: {} status: deferred",
+                slot, rate_limited_automations
+            );
+        }
         Ok(automation_pubkeys)
     }
 
@@ -295,124 +831,445 @@ impl TxExecutor {
         slot: u64,
         pool_position: PoolPosition,
         runtime: Arc<Runtime>,
+        rotation_ix: Option<Instruction>,
     ) -> PluginResult<()> {
+        let pool_id = pool_position.pool_id;
         let executable_automations = self
             .clone()
             .get_executable_automations(pool_position, slot)
             .await?;
         if executable_automations.is_empty() {
+            // There is nothing to bundle the rotation with, so submit it on its own.
+            if let Some(rotation_ix) = rotation_ix {
+                self.clone()
+                    .submit_pool_rotate_ix(client.clone(), rotation_ix)
+                    .await?;
+            }
             return Ok(());
         }
 
-        // Build transactions in parallel.
+        // Fetch the blockhash once for the whole batch.
+        let blockhash = self.clone().get_blockhash(client.clone(), slot).await?;
+
+        // Price the batch's compute units once for the whole batch.
+        let compute_unit_price = self.config.priority_fee.compute_unit_price(&client).await;
+
+        // Look up the leaders the batch will be submitted to.
+        let upcoming_leaders = self.clone().upcoming_leaders(client.clone(), slot).await;
+        info!("slot: {} upcoming_leaders: {:?}", slot, upcoming_leaders);
+
+        // Build transactions in parallel and submit each one as soon as it is ready.
         // Note we parallelize using tokio because this work is IO heavy (RPC simulation calls).
-        let tasks: Vec<_> = executable_automations
-            .iter()
-            .map(|automation_pubkey| {
-                runtime.spawn(self.clone().try_build_automation_exec_tx(
-                    client.clone(),
-                    slot,
-                    *automation_pubkey,
-                ))
-            })
-            .collect();
-        let mut executed_automations: HashMap<Pubkey, Signature> = HashMap::new();
+        // The pool rotation, if any, is bundled at the front of the first exec transaction. If it cannot be
+        // bundled, the first exec is built on its own and the rotation is submitted separately.
+        let mut tasks = spawn_in_completion_order(
+            &runtime,
+            executable_automations
+                .iter()
+                .enumerate()
+                .map(|(i, automation_pubkey)| {
+                    let prefix_ixs = match (i, rotation_ix.clone()) {
+                        (0, Some(rotation_ix)) => vec![rotation_ix],
+                        _ => vec![],
+                    };
+                    self.clone().try_build_automation_exec_tx(
+                        client.clone(),
+                        slot,
+                        pool_id,
+                        *automation_pubkey,
+                        prefix_ixs,
+                        compute_unit_price,
+                        blockhash,
+                    )
+                }),
+        );
+        while let Some((i, result)) = tasks.next().await {
+            let carries_rotation = i == 0 && rotation_ix.is_some();
+            match result {
+                Ok(Some((pubkey, tx, trigger, compute_units, bundled, retry_hash))) => {
+                    if let (true, false, Some(rotation_ix)) =
+                        (carries_rotation, bundled, rotation_ix.clone())
+                    {
+                        self.clone()
+                            .submit_pool_rotate_ix(client.clone(), rotation_ix)
+                            .await
+                            .ok();
+                    }
 
-        // Serialize to wire transactions.
-        let wire_txs = futures::future::join_all(tasks)
-            .await
-            .iter()
-            .filter_map(|res| match res {
-                Err(_err) => None,
-                Ok(res) => match res {
-                    None => None,
-                    Some((pubkey, tx)) => {
-                        executed_automations.insert(*pubkey, tx.signatures[0]);
-                        Some(tx)
+                    // The compute budget instructions, and the rotation if bundled, precede the exec instructions.
+                    let exec_offset = 1 + compute_unit_price.is_some() as usize + bundled as usize;
+                    self.clone()
+                        .submit_automation_exec_tx(
+                            slot,
+                            pubkey,
+                            tx,
+                            trigger,
+                            exec_offset,
+                            compute_units,
+                            retry_hash,
+                        )
+                        .await;
+                }
+                _ => {
+                    // Fall back to submitting the rotation separately if it could not be bundled.
+                    if let (true, Some(rotation_ix)) = (carries_rotation, rotation_ix.clone()) {
+                        self.clone()
+                            .submit_pool_rotate_ix(client.clone(), rotation_ix)
+                            .await
+                            .ok();
                     }
-                },
-            })
-            .map(|tx| serialize(tx).unwrap())
-            .collect::<Vec<Vec<u8>>>();
+                }
+            }
+        }
 
-        // Batch submit transactions to the leader.
+        Ok(())
+    }
+
+    async fn submit_automation_exec_tx(
+        self: Arc<Self>,
+        slot: u64,
+        automation_pubkey: Pubkey,
+        tx: Transaction,
+        trigger: Trigger,
+        exec_offset: usize,
+        compute_units: Option<u64>,
+        retry_hash: Hash,
+    ) {
         // TODO Explore rewriting the TPU client for optimized performance.
         //      This currently is by far the most expensive part of processing automations.
         //      Submitting transactions takes 8x longer (>200ms) than simulating and building transactions.
-        match TPU_CLIENT
-            .get()
-            .await
-            .try_send_wire_transaction_batch(wire_txs)
-            .await
-        {
+        let wire_tx = serialize(&tx).unwrap();
+        match self.tpu_client().await.try_send_wire_transaction(wire_tx).await {
             Err(err) => {
-                info!("Failed to sent transaction batch: {:?}", err);
+                info!(
+                    "Failed to send transaction for automation {}: {:?}",
+                    automation_pubkey, err
+                );
+                self.event_stream
+                    .emit(ExecEvent::failed(slot, automation_pubkey, format!("{:?}", err)));
             }
             Ok(()) => {
+                self.event_stream.emit(ExecEvent::Attempted {
+                    slot,
+                    automation: automation_pubkey.to_string(),
+                    signature: tx.signatures[0].to_string(),
+                });
+                self.trigger_metrics.record(&trigger);
                 let mut w_executable_automations = self.executable_automations.write().await;
                 let mut w_transaction_history = self.transaction_history.write().await;
-                for (pubkey, signature) in executed_automations {
-                    w_executable_automations.remove(&pubkey);
-                    w_transaction_history.insert(
-                        pubkey,
-                        TransactionMetadata {
-                            slot_sent: slot,
-                            signature,
-                        },
-                    );
-                }
+                let max_simulation_failures = w_executable_automations
+                    .remove(&automation_pubkey)
+                    .and_then(|metadata| metadata.max_simulation_failures);
+                w_transaction_history.insert(
+                    automation_pubkey,
+                    TransactionMetadata {
+                        slot_sent: slot,
+                        signature: tx.signatures[0],
+                        retry_hash,
+                        exec_offset,
+                        compute_units,
+                        max_simulation_failures,
+                    },
+                );
                 drop(w_executable_automations);
                 drop(w_transaction_history);
+                if let Some(compute_units) = compute_units {
+                    self.compute_unit_usage
+                        .write()
+                        .await
+                        .insert(automation_pubkey, compute_units);
+                }
             }
         }
-
-        Ok(())
     }
 
     pub async fn try_build_automation_exec_tx(
         self: Arc<Self>,
         client: Arc<RpcClient>,
         slot: u64,
+        pool_id: u64,
         automation_pubkey: Pubkey,
-    ) -> Option<(Pubkey, Transaction)> {
+        prefix_ixs: Vec<Instruction>,
+        compute_unit_price: Option<u64>,
+        blockhash: Hash,
+    ) -> Option<(Pubkey, Transaction, Trigger, Option<u64>, bool, Hash)> {
         let automation = match client.clone().get::<Automation>(&automation_pubkey).await {
-            Err(_err) => {
-                self.increment_simulation_failure(automation_pubkey).await;
+            Err(err) => {
+                self.handle_automation_fetch_error(&err, automation_pubkey, slot)
+                    .await;
                 return None;
             }
             Ok(automation) => automation,
         };
 
-        if let Some(tx) = crate::builders::build_automation_exec_tx(
-            client.clone(),
-            &self.keypair,
-            automation.clone(),
-            automation_pubkey,
-            self.config.worker_id,
-        )
-        .await
+        // Defer an automation that already reached its rate limit in this slot. Its exec would fail simulation.
+        if let Some(exec_context) = automation.exec_context {
+            if exec_context.is_rate_limited(slot, automation.rate_limit) {
+                self.record_rate_limit(automation_pubkey, slot, &automation, true)
+                    .await;
+                return None;
+            }
+        }
+
+        // Record the automation's failure threshold, and limit the number of steps if a previous multi-step
+        // transaction failed partway through.
+        let instruction_limit = self
+            .executable_automations
+            .write()
+            .await
+            .get_mut(&automation_pubkey)
+            .and_then(|metadata| {
+                metadata.max_simulation_failures = automation.max_simulation_failures;
+                metadata.instruction_limit
+            });
+
+        // Skip simulation for allowlisted automations, once their compute units have been estimated.
+        let skip_simulation = self
+            .config
+            .skip_simulation_automations
+            .contains(&automation_pubkey.to_string());
+        let compute_unit_estimate = match skip_simulation {
+            true => self
+                .compute_unit_estimates
+                .read()
+                .await
+                .get(&automation_pubkey)
+                .cloned(),
+            false => None,
+        };
+
+        if let Some(((tx, units_consumed, rate_limited), bundled)) =
+            crate::builders::build_bundled_automation_exec_tx(
+                client.clone(),
+                &self.keypair,
+                automation.clone(),
+                automation_pubkey,
+                pool_id,
+                self.config.worker_id,
+                instruction_limit,
+                prefix_ixs,
+                compute_unit_estimate,
+                compute_unit_price,
+                blockhash,
+            )
+            .await
         {
+            self.simulation_successes.fetch_add(1, Ordering::Relaxed);
+            if rate_limited {
+                self.record_rate_limit(automation_pubkey, slot, &automation, false)
+                    .await;
+            }
+            if self
+                .underfunded_automations
+                .write()
+                .await
+                .remove(&automation_pubkey)
+            {
+                info!("automation: {} status: funded", automation_pubkey);
+            }
+
+            if let (true, Some(units_consumed)) = (skip_simulation, units_consumed) {
+                self.compute_unit_estimates
+                    .write()
+                    .await
+                    .insert(automation_pubkey, units_consumed);
+            }
+
+            let retry_hash = retry_hash(&tx.message, automation.exec_context);
             if self
                 .clone()
-                .dedupe_tx(slot, automation_pubkey, &tx)
+                .dedupe_tx(slot, automation_pubkey, &tx, retry_hash)
                 .await
                 .is_ok()
             {
-                Some((automation_pubkey, tx))
+                Some((
+                    automation_pubkey,
+                    tx,
+                    automation.trigger,
+                    units_consumed,
+                    bundled,
+                    retry_hash,
+                ))
             } else {
                 None
             }
         } else {
-            self.increment_simulation_failure(automation_pubkey).await;
+            // Hold an automation that has run out of lamports until it is topped up, rather than dropping it.
+            if is_underfunded(&client, automation_pubkey, &automation).await {
+                self.hold_underfunded_automation(automation_pubkey, slot)
+                    .await;
+            } else {
+                self.increment_simulation_failure(automation_pubkey, slot).await;
+            }
             None
         }
     }
 
-    pub async fn increment_simulation_failure(self: Arc<Self>, automation_pubkey: Pubkey) {
+    /// The leaders of the next few slots, from the cached leader schedule. They are reported with each batch and
+    /// in the state dump, since the TPU client targets leaders internally. The schedule is fetched once per epoch
+    /// rather than on every submission.
+    async fn upcoming_leaders(self: Arc<Self>, client: Arc<RpcClient>, slot: u64) -> Vec<Pubkey> {
+        let mut w_leader_schedule = self.leader_schedule.write().await;
+        if let Some(leader_schedule) = cached_schedule(&mut w_leader_schedule, slot) {
+            return leader_schedule.upcoming_leaders(slot, LEADER_LOOKAHEAD_SLOTS);
+        }
+        drop(w_leader_schedule);
+
+        // The cached schedule is missing or stale. Fetch the schedule for the current epoch.
+        let epoch_info = match client.get_epoch_info().await {
+            Err(_err) => return vec![],
+            Ok(epoch_info) => epoch_info,
+        };
+        let rpc_schedule = match client.get_leader_schedule(Some(slot)).await {
+            Ok(Some(rpc_schedule)) => rpc_schedule,
+            _ => return vec![],
+        };
+        let leader_schedule = LeaderSchedule::new(
+            epoch_info.epoch,
+            epoch_info.absolute_slot - epoch_info.slot_index,
+            epoch_info.slots_in_epoch,
+            rpc_schedule,
+        );
+        info!(
+            "leader_schedule: epoch: {} first_slot: {} status: refreshed",
+            leader_schedule.epoch, leader_schedule.first_slot
+        );
+        let upcoming_leaders = leader_schedule.upcoming_leaders(slot, LEADER_LOOKAHEAD_SLOTS);
+        let mut w_leader_schedule = self.leader_schedule.write().await;
+        *w_leader_schedule = Some(leader_schedule);
+        drop(w_leader_schedule);
+        upcoming_leaders
+    }
+
+    /// Fetch the latest blockhash, falling back to the last known good blockhash if the fetch fails and it is still valid.
+    async fn get_blockhash(self: Arc<Self>, client: Arc<RpcClient>, slot: u64) -> PluginResult<Hash> {
+        match client.get_latest_blockhash().await {
+            Ok(blockhash) => {
+                let mut w_cached_blockhash = self.cached_blockhash.write().await;
+                *w_cached_blockhash = Some((blockhash, slot));
+                drop(w_cached_blockhash);
+                Ok(blockhash)
+            }
+            Err(err) => match *self.cached_blockhash.read().await {
+                Some((blockhash, cached_slot))
+                    if slot.saturating_sub(cached_slot) < BLOCKHASH_CACHE_VALIDITY =>
+                {
+                    info!(
+                        "slot: {} blockhash: {} cached_slot: {} status: using_cached_blockhash error: {}",
+                        slot, blockhash, cached_slot, err
+                    );
+                    Ok(blockhash)
+                }
+                _ => Err(GeyserPluginError::Custom(
+                    format!("Failed to get blockhash: {}", err).into(),
+                )),
+            },
+        }
+    }
+
+    /// Records that the automation's remaining execs were deferred to a later slot by its rate limit.
+    /// If `defer` is set, the automation is also held back from simulation for the rest of the slot.
+    async fn record_rate_limit(
+        &self,
+        automation_pubkey: Pubkey,
+        slot: u64,
+        automation: &Automation,
+        defer: bool,
+    ) {
+        self.rate_limited_execs.fetch_add(1, Ordering::Relaxed);
+        info!(
+            "automation: {} slot: {} rate_limit: {} execs_since_slot: {} status: rate_limited",
+            automation_pubkey,
+            slot,
+            automation.rate_limit,
+            automation
+                .exec_context
+                .map(|exec_context| exec_context.execs_since_slot)
+                .unwrap_or_default()
+        );
+        self.event_stream.emit(ExecEvent::RateLimited {
+            slot,
+            automation: automation_pubkey.to_string(),
+            rate_limit: automation.rate_limit,
+        });
+        if defer {
+            if let Some(metadata) = self
+                .executable_automations
+                .write()
+                .await
+                .get_mut(&automation_pubkey)
+            {
+                metadata.rate_limited_slot = Some(slot);
+            }
+        }
+    }
+
+    /// Stops tracking an automation that was deleted, or counts a simulation failure if the
+    /// automation could not be fetched for any other reason.
+    pub async fn handle_automation_fetch_error(
+        self: Arc<Self>,
+        err: &ClientError,
+        automation_pubkey: Pubkey,
+        slot: u64,
+    ) {
+        if is_account_not_found(err) {
+            self.purge_automation(automation_pubkey).await;
+        } else {
+            self.increment_simulation_failure(automation_pubkey, slot).await;
+        }
+    }
+
+    pub async fn purge_automation(self: Arc<Self>, automation_pubkey: Pubkey) {
+        info!("automation: {} status: not_found", automation_pubkey);
+        let mut w_executable_automations = self.executable_automations.write().await;
+        let mut w_transaction_history = self.transaction_history.write().await;
+        remove_automation(
+            &mut w_executable_automations,
+            &mut w_transaction_history,
+            automation_pubkey,
+        );
+        drop(w_executable_automations);
+        drop(w_transaction_history);
+        self.compute_unit_usage
+            .write()
+            .await
+            .remove(&automation_pubkey);
+    }
+
+    /// Stops retrying an automation that cannot pay for its exec, without counting a simulation failure.
+    /// Topping up the automation writes its account, so the observer indexes it again.
+    pub async fn hold_underfunded_automation(self: Arc<Self>, automation_pubkey: Pubkey, slot: u64) {
+        info!(
+            "slot: {} automation: {} status: underfunded",
+            slot, automation_pubkey
+        );
+        self.event_stream
+            .emit(ExecEvent::failed(slot, automation_pubkey, "underfunded"));
+        self.executable_automations
+            .write()
+            .await
+            .remove(&automation_pubkey);
+        self.underfunded_automations
+            .write()
+            .await
+            .insert(automation_pubkey);
+    }
+
+    pub async fn increment_simulation_failure(
+        self: Arc<Self>,
+        automation_pubkey: Pubkey,
+        slot: u64,
+    ) {
+        self.simulation_failures.fetch_add(1, Ordering::Relaxed);
+        self.event_stream
+            .emit(ExecEvent::failed(slot, automation_pubkey, "simulation_failed"));
         let mut w_executable_automations = self.executable_automations.write().await;
         w_executable_automations
             .entry(automation_pubkey)
-            .and_modify(|metadata| metadata.simulation_failures += 1);
+            .and_modify(|metadata| {
+                metadata.simulation_failures += 1;
+                metadata.last_simulation_failure_slot = Some(slot);
+            });
         drop(w_executable_automations);
     }
 
@@ -421,20 +1278,38 @@ impl TxExecutor {
         slot: u64,
         automation_pubkey: Pubkey,
         tx: &Transaction,
+        retry_hash: Hash,
     ) -> PluginResult<()> {
         let r_transaction_history = self.transaction_history.read().await;
         if let Some(metadata) = r_transaction_history.get(&automation_pubkey) {
-            if metadata.signature.eq(&tx.signatures[0]) && metadata.slot_sent.le(&slot) {
-                return Err(GeyserPluginError::Custom(format!("Transaction signature is a duplicate of a previously submitted transaction").into()));
+            if is_duplicate_tx(metadata, slot, tx, retry_hash) {
+                return Err(GeyserPluginError::Custom(format!("Transaction is a duplicate of a previously submitted transaction").into()));
             }
         }
         drop(r_transaction_history);
         Ok(())
     }
 
+    async fn tpu_client(&self) -> &TpuClient {
+        self.tpu_client
+            .get_or_init(|| async {
+                let rpc_client = Arc::new(RpcClient::new_with_commitment(
+                    self.config.rpc_url.clone(),
+                    CommitmentConfig::processed(),
+                ));
+                TpuClient::new(
+                    rpc_client,
+                    &self.config.websocket_url,
+                    TpuClientConfig::default(),
+                )
+                .await
+                .unwrap()
+            })
+            .await
+    }
+
     async fn simulate_tx(self: Arc<Self>, tx: &Transaction) -> PluginResult<Transaction> {
-        TPU_CLIENT
-            .get()
+        self.tpu_client()
             .await
             .rpc_client()
             .simulate_transaction_with_config(
@@ -462,7 +1337,7 @@ impl TxExecutor {
     }
 
     async fn submit_tx(self: Arc<Self>, tx: &Transaction) -> PluginResult<Transaction> {
-        if !TPU_CLIENT.get().await.send_transaction(tx).await {
+        if !self.tpu_client().await.send_transaction(tx).await {
             return Err(GeyserPluginError::Custom(
                 "Failed to send transaction".into(),
             ));
@@ -477,8 +1352,108 @@ impl Debug for TxExecutor {
     }
 }
 
-/// BlockhashAgnosticHash
-trait BlockhashAgnosticHash {
+/// Returns true if the automation's balance cannot cover a worker's fee and reimbursement on top of its rent.
+/// If the balance cannot be fetched, the automation is assumed to be funded.
+async fn is_underfunded(client: &RpcClient, automation_pubkey: Pubkey, automation: &Automation) -> bool {
+    let account = match client.get_account(&automation_pubkey).await {
+        Err(_err) => return false,
+        Ok(account) => account,
+    };
+    match client
+        .get_minimum_balance_for_rent_exemption(account.data.len())
+        .await
+    {
+        Err(_err) => false,
+        Ok(rent_exempt_minimum) => {
+            account.lamports < minimum_exec_balance(automation.fee, rent_exempt_minimum)
+        }
+    }
+}
+
+/// Returns the number of exec steps that succeeded before a multi-step transaction failed.
+/// The exec instructions of a transaction start at `exec_offset` (after the compute budget and any
+/// bundled instructions), so a failure at instruction index `i` means the `i - exec_offset` exec steps
+/// before it would have succeeded on their own.
+fn successful_exec_steps(err: &TransactionError, exec_offset: usize) -> Option<usize> {
+    match err {
+        TransactionError::InstructionError(index, _) if *index as usize > exec_offset => {
+            Some(*index as usize - exec_offset)
+        }
+        _ => None,
+    }
+}
+
+/// Removes the transactions sent more than `ttl` slots ago from the history and returns them.
+fn evict_expired_transactions(
+    transaction_history: &mut HashMap<Pubkey, TransactionMetadata>,
+    slot: u64,
+    ttl: u64,
+) -> Vec<(Pubkey, TransactionMetadata)> {
+    let expired_automations = transaction_history
+        .iter()
+        .filter(|(_, metadata)| slot > metadata.slot_sent.saturating_add(ttl))
+        .map(|(pubkey, _)| *pubkey)
+        .collect::<Vec<Pubkey>>();
+    expired_automations
+        .into_iter()
+        .filter_map(|pubkey| Some((pubkey, transaction_history.remove(&pubkey)?)))
+        .collect()
+}
+
+/// Spawns the tasks on the runtime and yields each one's output with its index, in the order the
+/// tasks finish rather than the order they were spawned in.
+fn spawn_in_completion_order<T, F>(
+    runtime: &Runtime,
+    tasks: impl IntoIterator<Item = F>,
+) -> FuturesUnordered<impl Future<Output = (usize, Result<T, JoinError>)>>
+where
+    T: Send + 'static,
+    F: Future<Output = T> + Send + 'static,
+{
+    tasks
+        .into_iter()
+        .enumerate()
+        .map(|(i, task)| {
+            let task = runtime.spawn(task);
+            async move { (i, task.await) }
+        })
+        .collect()
+}
+
+/// Removes an automation from the executable automations and the transaction history.
+fn remove_automation(
+    executable_automations: &mut HashMap<Pubkey, ExecutableAutomationMetadata>,
+    transaction_history: &mut HashMap<Pubkey, TransactionMetadata>,
+    automation_pubkey: Pubkey,
+) {
+    executable_automations.remove(&automation_pubkey);
+    transaction_history.remove(&automation_pubkey);
+}
+
+/// Returns true if the transaction repeats one already in flight: the same signature, or a retry
+/// of it signed under a different blockhash.
+fn is_duplicate_tx(
+    metadata: &TransactionMetadata,
+    slot: u64,
+    tx: &Transaction,
+    retry_hash: Hash,
+) -> bool {
+    metadata.slot_sent.le(&slot)
+        && (metadata.signature.eq(&tx.signatures[0]) || metadata.retry_hash.eq(&retry_hash))
+}
+
+/// The hash of a message without its recent blockhash, together with the exec context of the
+/// automation it was built against. Once an exec lands, the automation's exec context advances,
+/// so a later transaction with the same instructions (such as a repeat kickoff) is not a retry.
+fn retry_hash(message: &Message, exec_context: Option<ExecContext>) -> Hash {
+    hashv(&[
+        message.blockhash_agnostic_hash().as_ref(),
+        &exec_context.try_to_vec().unwrap(),
+    ])
+}
+
+/// The hash of a message without its recent blockhash.
+pub trait BlockhashAgnosticHash {
     fn blockhash_agnostic_hash(&self) -> Hash;
 }
 
@@ -494,22 +1469,373 @@ impl BlockhashAgnosticHash for Message {
     }
 }
 
-static LOCAL_RPC_URL: &str = "http://127.0.0.1:8899";
-static LOCAL_WEBSOCKET_URL: &str = "ws://127.0.0.1:8900";
+#[cfg(test)]
+mod tests {
+    use clockwork_client::automation::state::{EpochFilter, TriggerContext};
 
-lazy_static! {
-    static ref TPU_CLIENT: AsyncOnce<TpuClient> = AsyncOnce::new(async {
-        let rpc_client = Arc::new(RpcClient::new_with_commitment(
-            LOCAL_RPC_URL.into(),
-            CommitmentConfig::processed(),
+    use super::*;
+
+    fn transaction_metadata(slot_sent: u64) -> TransactionMetadata {
+        TransactionMetadata {
+            slot_sent,
+            signature: Signature::new_unique(),
+            retry_hash: Hash::default(),
+            exec_offset: 1,
+            compute_units: None,
+            max_simulation_failures: None,
+        }
+    }
+
+    fn exec_context(last_exec_at: u64, data_hash: u64) -> Option<ExecContext> {
+        Some(ExecContext {
+            exec_index: 0,
+            execs_since_reimbursement: 0,
+            execs_since_slot: 0,
+            last_exec_at,
+            trigger_context: TriggerContext::Account { data_hash },
+        })
+    }
+
+    #[test]
+    fn test_dedupe_survives_blockhash_change() {
+        let payer = Keypair::new();
+        let ix = Instruction::new_with_bytes(Pubkey::new_unique(), &[1, 2, 3], vec![]);
+        let sent_tx = Transaction::new_signed_with_payer(
+            &[ix.clone()],
+            Some(&payer.pubkey()),
+            &[&payer],
+            Hash::new_unique(),
+        );
+        let sent_retry_hash = retry_hash(&sent_tx.message, exec_context(90, 1));
+        let metadata = TransactionMetadata {
+            slot_sent: 100,
+            signature: sent_tx.signatures[0],
+            retry_hash: sent_retry_hash,
+            exec_offset: 0,
+            compute_units: None,
+            max_simulation_failures: None,
+        };
+        assert!(is_duplicate_tx(&metadata, 100, &sent_tx, sent_retry_hash));
+
+        // The same instruction signed under a rotated blockhash, before the first transaction
+        // lands, has a new signature but is still a duplicate.
+        let resigned_tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&payer.pubkey()),
+            &[&payer],
+            Hash::new_unique(),
+        );
+        assert_ne!(resigned_tx.signatures[0], sent_tx.signatures[0]);
+        let resigned_retry_hash = retry_hash(&resigned_tx.message, exec_context(90, 1));
+        assert!(is_duplicate_tx(&metadata, 101, &resigned_tx, resigned_retry_hash));
+
+        // A different instruction is not.
+        let other_ix = Instruction::new_with_bytes(Pubkey::new_unique(), &[4], vec![]);
+        let other_tx = Transaction::new_signed_with_payer(
+            &[other_ix],
+            Some(&payer.pubkey()),
+            &[&payer],
+            Hash::new_unique(),
+        );
+        let other_retry_hash = retry_hash(&other_tx.message, exec_context(90, 1));
+        assert!(!is_duplicate_tx(&metadata, 101, &other_tx, other_retry_hash));
+    }
+
+    #[test]
+    fn test_dedupe_allows_repeat_kickoff() {
+        let payer = Keypair::new();
+        let ix = Instruction::new_with_bytes(Pubkey::new_unique(), &[1, 2, 3], vec![]);
+        let sent_tx = Transaction::new_signed_with_payer(
+            &[ix.clone()],
+            Some(&payer.pubkey()),
+            &[&payer],
+            Hash::new_unique(),
+        );
+        let sent_retry_hash = retry_hash(&sent_tx.message, exec_context(90, 1));
+        let metadata = TransactionMetadata {
+            slot_sent: 100,
+            signature: sent_tx.signatures[0],
+            retry_hash: sent_retry_hash,
+            exec_offset: 0,
+            compute_units: None,
+            max_simulation_failures: None,
+        };
+
+        // Once the first kickoff lands, the account changes again. The next kickoff has the same
+        // instructions, but is built against the new exec context, so it is not a retry.
+        let repeat_tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&payer.pubkey()),
+            &[&payer],
+            Hash::new_unique(),
+        );
+        let repeat_retry_hash = retry_hash(&repeat_tx.message, exec_context(101, 2));
+        assert_eq!(
+            repeat_tx.message.blockhash_agnostic_hash(),
+            sent_tx.message.blockhash_agnostic_hash()
+        );
+        assert!(!is_duplicate_tx(&metadata, 102, &repeat_tx, repeat_retry_hash));
+    }
+
+    #[test]
+    fn test_stale_transaction_evicted_after_ttl() {
+        let stale_automation = Pubkey::new_unique();
+        let recent_automation = Pubkey::new_unique();
+        let mut transaction_history = HashMap::from([
+            (stale_automation, transaction_metadata(1_000)),
+            (recent_automation, transaction_metadata(1_250)),
+        ]);
+
+        // Nothing is evicted until the TTL has passed.
+        assert!(evict_expired_transactions(&mut transaction_history, 1_300, 300).is_empty());
+        assert_eq!(transaction_history.len(), 2);
+
+        let expired_automations = evict_expired_transactions(&mut transaction_history, 1_301, 300)
+            .into_iter()
+            .map(|(pubkey, _)| pubkey)
+            .collect::<Vec<Pubkey>>();
+        assert_eq!(expired_automations, vec![stale_automation]);
+        assert!(!transaction_history.contains_key(&stale_automation));
+        assert!(transaction_history.contains_key(&recent_automation));
+    }
+
+    #[test]
+    fn test_early_build_submitted_before_slow_build() {
+        let runtime = Runtime::new().unwrap();
+        let builds = [200, 10].map(|millis| async move {
+            tokio::time::sleep(Duration::from_millis(millis)).await;
+            millis
+        });
+        let mut tasks = spawn_in_completion_order(&runtime, builds);
+
+        // The second build finishes first, so it is yielded for submission without waiting on the
+        // first.
+        let completion_order = runtime.block_on(async {
+            let mut completion_order = vec![];
+            while let Some((i, result)) = tasks.next().await {
+                completion_order.push((i, result.unwrap()));
+            }
+            completion_order
+        });
+        assert_eq!(completion_order, vec![(1, 10), (0, 200)]);
+    }
+
+    #[test]
+    fn test_blockhash_falls_back_to_cache() {
+        let tx = Arc::new(TxExecutor::new(
+            PluginConfig::default(),
+            Arc::new(EventStream::default()),
         ));
-        let tpu_client = TpuClient::new(
-            rpc_client,
-            LOCAL_WEBSOCKET_URL.into(),
-            TpuClientConfig::default(),
-        )
-        .await
-        .unwrap();
-        tpu_client
-    });
+        let failing_client = Arc::new(RpcClient::new_mock("fails".into()));
+        let cached_blockhash = Hash::new_unique();
+        Runtime::new().unwrap().block_on(async {
+            // Without a cached blockhash, a failed fetch is an error.
+            assert!(tx.clone().get_blockhash(failing_client.clone(), 100).await.is_err());
+
+            // A failed fetch reuses the cached blockhash while it is still valid.
+            *tx.cached_blockhash.write().await = Some((cached_blockhash, 100));
+            let last_valid_slot = 100 + BLOCKHASH_CACHE_VALIDITY - 1;
+            assert_eq!(
+                tx.clone()
+                    .get_blockhash(failing_client.clone(), last_valid_slot)
+                    .await
+                    .unwrap(),
+                cached_blockhash
+            );
+
+            // Once the cached blockhash has expired, a failed fetch is an error again.
+            assert!(tx
+                .clone()
+                .get_blockhash(failing_client.clone(), last_valid_slot + 1)
+                .await
+                .is_err());
+
+            // A successful fetch replaces the cache.
+            let client = Arc::new(RpcClient::new_mock("succeeds".into()));
+            let blockhash = tx.clone().get_blockhash(client, 200).await.unwrap();
+            assert_eq!(*tx.cached_blockhash.read().await, Some((blockhash, 200)));
+        });
+    }
+
+    #[test]
+    fn test_trigger_metrics_record() {
+        let trigger_metrics = TriggerMetrics::default();
+        let address = Pubkey::new_unique();
+        for trigger in [
+            Trigger::Account {
+                address,
+                offset: 0,
+                size: 8,
+            },
+            Trigger::AccountValue {
+                address,
+                offset: 0,
+                size: 8,
+                equals: Some(vec![1]),
+                changed_from: None,
+            },
+            Trigger::Cron {
+                schedule: "* * * * * *".into(),
+                skippable: true,
+            },
+            Trigger::EpochFilteredCron {
+                schedule: "* * * * * *".into(),
+                skippable: true,
+                epoch_filter: EpochFilter::Even,
+            },
+            Trigger::Epoch { skippable: true },
+            Trigger::Immediate,
+            Trigger::Slot { target_slot: 100 },
+            Trigger::EpochFilteredSlot {
+                target_slot: 100,
+                epoch_filter: EpochFilter::Odd,
+            },
+            Trigger::Slot { target_slot: 200 },
+            Trigger::Timestamp { unix_ts: 1_700_000_000 },
+        ] {
+            trigger_metrics.record(&trigger);
+        }
+        assert_eq!(trigger_metrics.account.load(Ordering::Relaxed), 2);
+        assert_eq!(trigger_metrics.cron.load(Ordering::Relaxed), 2);
+        assert_eq!(trigger_metrics.epoch.load(Ordering::Relaxed), 1);
+        assert_eq!(trigger_metrics.immediate.load(Ordering::Relaxed), 1);
+        assert_eq!(trigger_metrics.slot.load(Ordering::Relaxed), 3);
+        assert_eq!(trigger_metrics.timestamp.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_deleted_automation_removed_from_tracking() {
+        let deleted_automation = Pubkey::new_unique();
+        let other_automation = Pubkey::new_unique();
+        let mut executable_automations = HashMap::from([
+            (deleted_automation, ExecutableAutomationMetadata::requeued(100, None, None)),
+            (other_automation, ExecutableAutomationMetadata::requeued(100, None, None)),
+        ]);
+        let mut transaction_history = HashMap::from([
+            (deleted_automation, transaction_metadata(90)),
+            (other_automation, transaction_metadata(90)),
+        ]);
+
+        remove_automation(
+            &mut executable_automations,
+            &mut transaction_history,
+            deleted_automation,
+        );
+        assert!(!executable_automations.contains_key(&deleted_automation));
+        assert!(!transaction_history.contains_key(&deleted_automation));
+        assert!(executable_automations.contains_key(&other_automation));
+        assert!(transaction_history.contains_key(&other_automation));
+    }
+
+    #[test]
+    fn test_automation_overrides_simulation_failure_threshold() {
+        let mut metadata = ExecutableAutomationMetadata {
+            due_slot: 0,
+            simulation_failures: MAX_AUTOMATION_SIMULATION_FAILURES + 1,
+            instruction_limit: None,
+            last_simulation_failure_slot: None,
+            max_simulation_failures: None,
+            rate_limited_slot: None,
+        };
+        assert!(metadata.exceeds_simulation_failures());
+
+        metadata.max_simulation_failures = Some(20);
+        assert!(!metadata.exceeds_simulation_failures());
+
+        // A threshold of zero drops the automation on its first failure.
+        metadata.max_simulation_failures = Some(0);
+        metadata.simulation_failures = 0;
+        assert!(!metadata.exceeds_simulation_failures());
+        metadata.simulation_failures = 1;
+        assert!(metadata.exceeds_simulation_failures());
+    }
+
+    #[test]
+    fn test_requeued_automation_keeps_simulation_failure_threshold() {
+        let automation_pubkey = Pubkey::new_unique();
+        let mut transaction_history = HashMap::from([(
+            automation_pubkey,
+            TransactionMetadata {
+                max_simulation_failures: Some(20),
+                ..transaction_metadata(1_000)
+            },
+        )]);
+
+        // An expired transaction's automation is requeued with its own threshold, not the default.
+        let (pubkey, transaction) =
+            evict_expired_transactions(&mut transaction_history, 1_301, 300).remove(0);
+        assert_eq!(pubkey, automation_pubkey);
+        let mut metadata = ExecutableAutomationMetadata::requeued(
+            1_301,
+            None,
+            transaction.max_simulation_failures,
+        );
+        assert_eq!(metadata.due_slot, 1_301);
+        assert_eq!(metadata.simulation_failures, 0);
+        assert_eq!(metadata.max_simulation_failures, Some(20));
+
+        metadata.simulation_failures = MAX_AUTOMATION_SIMULATION_FAILURES + 1;
+        assert!(!metadata.exceeds_simulation_failures());
+        metadata.simulation_failures = 21;
+        assert!(metadata.exceeds_simulation_failures());
+    }
+
+    #[test]
+    fn test_cooldown_floor_after_single_failure() {
+        let automation_pubkey = Pubkey::new_unique();
+        let metadata = ExecutableAutomationMetadata {
+            due_slot: 100,
+            simulation_failures: 1,
+            instruction_limit: None,
+            last_simulation_failure_slot: Some(100),
+            max_simulation_failures: None,
+            rate_limited_slot: None,
+        };
+        let config = PluginConfig {
+            retry_jitter_window: 0,
+            simulation_failure_cooldown: 10,
+            ..PluginConfig::default()
+        };
+
+        // A single failure backs off by a single slot, so the cooldown sets the retry slot.
+        assert_eq!(metadata.retry_slot(&automation_pubkey, &config), 110);
+
+        // Without a cooldown, the exponential backoff applies on its own.
+        let config = PluginConfig {
+            simulation_failure_cooldown: 0,
+            ..config
+        };
+        assert_eq!(metadata.retry_slot(&automation_pubkey, &config), 101);
+    }
+
+    #[test]
+    fn test_backoff_is_capped() {
+        let automation_pubkey = Pubkey::new_unique();
+        let config = PluginConfig {
+            retry_jitter_window: 0,
+            simulation_failure_cooldown: 0,
+            ..PluginConfig::default()
+        };
+        let metadata = |simulation_failures| ExecutableAutomationMetadata {
+            due_slot: 100,
+            simulation_failures,
+            instruction_limit: None,
+            last_simulation_failure_slot: None,
+            max_simulation_failures: Some(u32::MAX),
+            rate_limited_slot: None,
+        };
+        assert_eq!(metadata(3).retry_slot(&automation_pubkey, &config), 107);
+        assert_eq!(
+            metadata(10).retry_slot(&automation_pubkey, &config),
+            100 + MAX_BACKOFF_SLOTS - 1
+        );
+
+        // Failure counts whose backoff would overflow wait the max backoff instead.
+        for simulation_failures in [32, 64, u32::MAX] {
+            assert_eq!(
+                metadata(simulation_failures).retry_slot(&automation_pubkey, &config),
+                100 + MAX_BACKOFF_SLOTS - 1
+            );
+        }
+    }
 }
@@ -1,55 +1,202 @@
 use {
-    crate::{config::PluginConfig, observers::webhook::HttpRequest},
+    crate::{
+        config::PluginConfig,
+        observers::{webhook::HttpRequest, Observers},
+    },
+    clockwork_client::webhook::state::HttpMethod,
+    log::info,
     solana_geyser_plugin_interface::geyser_plugin_interface::Result as PluginResult,
-    std::{fmt::Debug, sync::Arc},
+    solana_program::pubkey::Pubkey,
+    std::{
+        collections::{HashMap, HashSet},
+        fmt::Debug,
+        sync::Arc,
+        time::{SystemTime, UNIX_EPOCH},
+    },
+    tokio::{runtime::Runtime, sync::RwLock},
 };
 
+/// The delivery state of a request that has failed at least once.
+#[derive(Clone, Copy, Debug)]
+pub struct RequestRetry {
+    /// The number of failed attempts so far.
+    pub failures: u64,
+    /// The unix timestamp at which the request may be sent again.
+    pub retry_at: u64,
+}
+
 pub struct WebhookExecutor {
     pub config: PluginConfig,
+    pub client: reqwest::Client,
+    /// Requests currently being sent.
+    pub in_flight: RwLock<HashSet<Pubkey>>,
+    /// Requests waiting to be retried after a failure.
+    pub retries: RwLock<HashMap<Pubkey, RequestRetry>>,
+    /// Requests that failed after exhausting their retries. These are not sent again.
+    pub failed_requests: RwLock<HashSet<Pubkey>>,
 }
 
 impl WebhookExecutor {
     pub fn new(config: PluginConfig) -> Self {
         Self {
             config: config.clone(),
+            client: reqwest::Client::new(),
+            in_flight: RwLock::new(HashSet::new()),
+            retries: RwLock::new(HashMap::new()),
+            failed_requests: RwLock::new(HashSet::new()),
         }
     }
 
-    pub fn execute_requests(self: Arc<Self>) -> PluginResult<()> {
-        // for request in self.clone().observers.webhook.webhook_requests.iter() {
-        //     self.clone().execute_request(request.clone())?;
-        // }
+    /// Sends the observed requests that are not in flight, waiting out a retry backoff, or failed.
+    pub async fn execute_requests(
+        self: Arc<Self>,
+        observers: Arc<Observers>,
+        runtime: Arc<Runtime>,
+    ) -> PluginResult<()> {
+        let now = unix_timestamp();
+        let requests = observers
+            .webhook
+            .webhook_requests
+            .read()
+            .await
+            .iter()
+            .cloned()
+            .collect::<Vec<HttpRequest>>();
+        let mut w_in_flight = self.in_flight.write().await;
+        let r_retries = self.retries.read().await;
+        let r_failed_requests = self.failed_requests.read().await;
+        for http_request in requests {
+            let is_waiting = r_retries
+                .get(&http_request.pubkey)
+                .map_or(false, |retry| now < retry.retry_at);
+            if is_waiting
+                || r_failed_requests.contains(&http_request.pubkey)
+                || !w_in_flight.insert(http_request.pubkey)
+            {
+                continue;
+            }
+            runtime.spawn(
+                self.clone()
+                    .execute_request(observers.clone(), http_request),
+            );
+        }
         Ok(())
     }
 
-    fn _execute_request(self: Arc<Self>, _http_request: HttpRequest) -> PluginResult<()> {
-        // self.spawn(|this| async move {
-        //     let url = http_request.clone().request.url;
-        //     let res = match http_request.request.method {
-        //         HttpMethod::Get => this.client.get(url),
-        //         HttpMethod::Post => this.client.post(url),
-        //     }
-        //     .header("x-caller-id", http_request.request.caller.to_string())
-        //     .header("x-request-id", http_request.pubkey.to_string())
-        //     .header("x-worker-id", this.worker_id.to_string())
-        //     .send()
-        //     .await;
-        //     match res {
-        //         Ok(res) => info!("Webhook response: {:#?}", res),
-        //         Err(err) => info!("Webhook request failed with error: {}", err),
-        //     }
-        //     this.observers
-        //         .webhook
-        //         .webhook_requests
-        //         .remove(&http_request);
-        //     Ok(())
-        // })
-        Ok(())
+    async fn execute_request(self: Arc<Self>, observers: Arc<Observers>, http_request: HttpRequest) {
+        let request = &http_request.request;
+        let mut builder = match request.method {
+            HttpMethod::Get => self.client.get(request.url.clone()),
+            HttpMethod::Post => self.client.post(request.url.clone()),
+            HttpMethod::Put => self.client.put(request.url.clone()),
+        }
+        .header("x-caller-id", request.caller.to_string())
+        .header("x-request-id", http_request.pubkey.to_string())
+        .header("x-worker-id", self.config.worker_id.to_string());
+        for (key, value) in request.headers.iter() {
+            builder = builder.header(key, value);
+        }
+        if !request.body.is_empty() {
+            builder = builder.body(request.body.clone());
+        }
+        let result = match builder.send().await {
+            Err(err) => Err(err.to_string()),
+            Ok(res) if !res.status().is_success() => Err(res.status().to_string()),
+            Ok(res) => Ok(res.status()),
+        };
+
+        let mut w_retries = self.retries.write().await;
+        match result {
+            Ok(status) => {
+                info!(
+                    "webhook_request: {} status: delivered http_status: {}",
+                    http_request.pubkey, status
+                );
+                w_retries.remove(&http_request.pubkey);
+                observers
+                    .webhook
+                    .webhook_requests
+                    .write()
+                    .await
+                    .remove(&http_request);
+            }
+            Err(err) => {
+                let failures = w_retries
+                    .get(&http_request.pubkey)
+                    .map_or(0, |retry| retry.failures)
+                    + 1;
+                if failures > request.max_retries {
+                    // Give up on the request.
+                    info!(
+                        "webhook_request: {} status: failed attempts: {} error: {}",
+                        http_request.pubkey, failures, err
+                    );
+                    w_retries.remove(&http_request.pubkey);
+                    self.failed_requests
+                        .write()
+                        .await
+                        .insert(http_request.pubkey);
+                    observers
+                        .webhook
+                        .webhook_requests
+                        .write()
+                        .await
+                        .remove(&http_request);
+                } else {
+                    let delay = retry_delay(request.backoff_seconds, failures);
+                    info!(
+                        "webhook_request: {} status: retrying attempts: {} retry_in: {}s error: {}",
+                        http_request.pubkey, failures, delay, err
+                    );
+                    w_retries.insert(
+                        http_request.pubkey,
+                        RequestRetry {
+                            failures,
+                            retry_at: unix_timestamp().saturating_add(delay),
+                        },
+                    );
+                }
+            }
+        }
+        drop(w_retries);
+        self.in_flight.write().await.remove(&http_request.pubkey);
     }
 }
 
+/// The number of seconds to wait before retrying a request that has failed the given number of times.
+/// The delay starts at the request's backoff and doubles with each further failure.
+fn retry_delay(backoff_seconds: u64, failures: u64) -> u64 {
+    let exponent = failures.saturating_sub(1).min(u32::MAX as u64) as u32;
+    backoff_seconds.saturating_mul(2u64.saturating_pow(exponent))
+}
+
+fn unix_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
 impl Debug for WebhookExecutor {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "http-executor")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_delay_doubles() {
+        assert_eq!(retry_delay(5, 1), 5);
+        assert_eq!(retry_delay(5, 2), 10);
+        assert_eq!(retry_delay(5, 3), 20);
+    }
+
+    #[test]
+    fn test_retry_delay_saturates() {
+        assert_eq!(retry_delay(5, 100), u64::MAX);
+        assert_eq!(retry_delay(0, 3), 0);
+    }
+}
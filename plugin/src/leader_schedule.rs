@@ -0,0 +1,113 @@
+use {
+    solana_program::pubkey::Pubkey,
+    std::{collections::HashMap, fmt::Debug, str::FromStr},
+};
+
+/// The leader schedule of a single epoch.
+#[derive(Clone, Debug)]
+pub struct LeaderSchedule {
+    pub epoch: u64,
+    /// The absolute slot the epoch starts at.
+    pub first_slot: u64,
+    pub slots_in_epoch: u64,
+    /// Map from slot indices within the epoch to the identity of the leader for that slot.
+    pub leaders: HashMap<u64, Pubkey>,
+}
+
+impl LeaderSchedule {
+    /// Build a schedule from the RPC `getLeaderSchedule` response, which maps leader identities to slot indices.
+    pub fn new(
+        epoch: u64,
+        first_slot: u64,
+        slots_in_epoch: u64,
+        rpc_schedule: HashMap<String, Vec<usize>>,
+    ) -> Self {
+        let mut leaders = HashMap::new();
+        for (identity, slot_indices) in rpc_schedule {
+            if let Ok(identity) = Pubkey::from_str(&identity) {
+                for slot_index in slot_indices {
+                    leaders.insert(slot_index as u64, identity);
+                }
+            }
+        }
+        LeaderSchedule {
+            epoch,
+            first_slot,
+            slots_in_epoch,
+            leaders,
+        }
+    }
+
+    /// Returns true if the slot falls within this schedule's epoch.
+    pub fn covers(&self, slot: u64) -> bool {
+        slot >= self.first_slot && slot < self.first_slot + self.slots_in_epoch
+    }
+
+    /// The distinct leaders of the next `count` slots starting at the given slot, in order.
+    pub fn upcoming_leaders(&self, slot: u64, count: u64) -> Vec<Pubkey> {
+        let mut upcoming: Vec<Pubkey> = vec![];
+        for slot in slot..slot + count {
+            if !self.covers(slot) {
+                break;
+            }
+            if let Some(leader) = self.leaders.get(&(slot - self.first_slot)) {
+                if !upcoming.contains(leader) {
+                    upcoming.push(*leader);
+                }
+            }
+        }
+        upcoming
+    }
+}
+
+/// Returns the cached schedule if it covers the slot. A schedule that no longer covers the slot, such as the
+/// previous epoch's after a rollover, is evicted so the caller fetches the schedule of the slot's epoch.
+pub fn cached_schedule(cache: &mut Option<LeaderSchedule>, slot: u64) -> Option<&LeaderSchedule> {
+    if cache.as_ref().map_or(false, |schedule| !schedule.covers(slot)) {
+        *cache = None;
+    }
+    cache.as_ref()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schedule(epoch: u64, leaders: &[Pubkey]) -> LeaderSchedule {
+        // Each leader is scheduled for consecutive slots in order, repeating over a 10 slot epoch.
+        let mut rpc_schedule: HashMap<String, Vec<usize>> = HashMap::new();
+        for slot_index in 0..10 {
+            rpc_schedule
+                .entry(leaders[slot_index % leaders.len()].to_string())
+                .or_default()
+                .push(slot_index);
+        }
+        LeaderSchedule::new(epoch, epoch * 10, 10, rpc_schedule)
+    }
+
+    #[test]
+    fn test_upcoming_leaders_stop_at_epoch_end() {
+        let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let schedule = schedule(1, &[a, b]);
+        assert_eq!(schedule.upcoming_leaders(10, 4), vec![a, b]);
+
+        // The last slot of the epoch is led by b, and the next epoch's leaders are not known.
+        assert_eq!(schedule.upcoming_leaders(19, 4), vec![b]);
+        assert!(schedule.upcoming_leaders(20, 4).is_empty());
+    }
+
+    #[test]
+    fn test_cached_schedule_evicted_on_epoch_rollover() {
+        let mut cache = Some(schedule(1, &[Pubkey::new_unique()]));
+        assert_eq!(cached_schedule(&mut cache, 10).map(|s| s.epoch), Some(1));
+        assert_eq!(cached_schedule(&mut cache, 19).map(|s| s.epoch), Some(1));
+
+        // The first slot of the next epoch needs the next epoch's schedule.
+        assert!(cached_schedule(&mut cache, 20).is_none());
+        assert!(cache.is_none());
+
+        cache = Some(schedule(2, &[Pubkey::new_unique()]));
+        assert_eq!(cached_schedule(&mut cache, 20).map(|s| s.epoch), Some(2));
+        assert!(cached_schedule(&mut None, 20).is_none());
+    }
+}
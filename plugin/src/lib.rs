@@ -1,12 +1,21 @@
 use solana_geyser_plugin_interface::geyser_plugin_interface::GeyserPlugin;
 
+mod backlog;
+mod balance;
 mod builders;
 mod config;
+mod event_stream;
 mod events;
 mod executors;
+mod leader_schedule;
+mod maintenance;
+mod metrics;
 mod observers;
 mod plugin;
 mod pool_position;
+mod priority_fee;
+mod state_dump;
+mod tx_state;
 mod utils;
 
 pub use plugin::ClockworkPlugin;
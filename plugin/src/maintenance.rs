@@ -0,0 +1,84 @@
+use {
+    chrono::{DateTime, NaiveDateTime, Utc},
+    clockwork_cron::Schedule,
+    serde::{Deserialize, Serialize},
+    std::str::FromStr,
+};
+
+/// A recurring window of time during which the worker does not submit transactions.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct CronWindow {
+    /// The cron schedule of the window's start times, in UTC.
+    pub schedule: String,
+    /// The length of the window in seconds.
+    pub duration: u64,
+}
+
+impl CronWindow {
+    /// Returns true if the unix timestamp falls within an occurrence of the window.
+    /// An invalid schedule never matches.
+    pub fn contains(&self, unix_timestamp: i64) -> bool {
+        let schedule = match Schedule::from_str(&self.schedule) {
+            Err(_) => return false,
+            Ok(schedule) => schedule,
+        };
+
+        // The latest window to start after `unix_timestamp - duration` is the only one that may still be open.
+        let after = unix_timestamp.saturating_sub(self.duration as i64);
+        match schedule.next_after(&DateTime::<Utc>::from_utc(
+            NaiveDateTime::from_timestamp(after, 0),
+            Utc,
+        )) {
+            None => false,
+            Some(start) => start.timestamp() <= unix_timestamp,
+        }
+    }
+}
+
+/// Returns the first configured window the unix timestamp falls within, if any.
+pub fn active_window(windows: &[CronWindow], unix_timestamp: i64) -> Option<&CronWindow> {
+    windows
+        .iter()
+        .find(|window| window.contains(unix_timestamp))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2023-01-01T00:00:00Z
+    const MIDNIGHT: i64 = 1_672_531_200;
+
+    fn nightly_window() -> CronWindow {
+        // Every day at 02:00 UTC for one hour.
+        CronWindow {
+            schedule: "0 0 2 * * * *".into(),
+            duration: 3_600,
+        }
+    }
+
+    #[test]
+    fn test_suppressed_during_window() {
+        let windows = vec![nightly_window()];
+        assert!(active_window(&windows, MIDNIGHT + 2 * 3_600).is_some());
+        assert!(active_window(&windows, MIDNIGHT + 2 * 3_600 + 1_800).is_some());
+        assert!(active_window(&windows, MIDNIGHT + 3 * 3_600 - 1).is_some());
+    }
+
+    #[test]
+    fn test_resumes_after_window() {
+        let windows = vec![nightly_window()];
+        assert!(active_window(&windows, MIDNIGHT + 2 * 3_600 - 1).is_none());
+        assert!(active_window(&windows, MIDNIGHT + 3 * 3_600).is_none());
+        assert!(active_window(&windows, MIDNIGHT + 12 * 3_600).is_none());
+    }
+
+    #[test]
+    fn test_invalid_schedule_never_matches() {
+        let window = CronWindow {
+            schedule: "cheesecake".into(),
+            duration: 3_600,
+        };
+        assert!(!window.contains(MIDNIGHT));
+    }
+}
@@ -0,0 +1,209 @@
+use {
+    crate::executors::{
+        tx::{TriggerMetrics, TxExecutor},
+        Executors,
+    },
+    log::info,
+    prometheus::{Encoder, IntCounter, IntCounterVec, IntGauge, Opts, Registry, TextEncoder},
+    std::sync::{atomic::Ordering, Arc},
+    tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::{TcpListener, TcpStream},
+    },
+};
+
+/// Max number of bytes read from a scrape request. The request is not otherwise inspected.
+static MAX_REQUEST_SIZE: usize = 4096;
+
+/// The transaction executor's counters at the moment of a scrape.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct MetricsSnapshot {
+    pub dropped_automations: u64,
+    pub executable_automations: usize,
+    pub transaction_history: usize,
+    pub simulation_successes: u64,
+    pub simulation_failures: u64,
+    pub rate_limited_execs: u64,
+    pub executed_automations: TriggerCounts,
+}
+
+/// The number of executed automations of each trigger type at the moment of a scrape.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TriggerCounts {
+    pub account: u64,
+    pub cron: u64,
+    pub epoch: u64,
+    pub immediate: u64,
+    pub slot: u64,
+    pub timestamp: u64,
+}
+
+impl TriggerCounts {
+    pub fn new(trigger_metrics: &TriggerMetrics) -> Self {
+        TriggerCounts {
+            account: trigger_metrics.account.load(Ordering::Relaxed),
+            cron: trigger_metrics.cron.load(Ordering::Relaxed),
+            epoch: trigger_metrics.epoch.load(Ordering::Relaxed),
+            immediate: trigger_metrics.immediate.load(Ordering::Relaxed),
+            slot: trigger_metrics.slot.load(Ordering::Relaxed),
+            timestamp: trigger_metrics.timestamp.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl MetricsSnapshot {
+    pub async fn new(tx: &TxExecutor) -> Self {
+        MetricsSnapshot {
+            dropped_automations: tx.dropped_automations.load(Ordering::Relaxed),
+            executable_automations: tx.executable_automations.read().await.len(),
+            transaction_history: tx.transaction_history.read().await.len(),
+            simulation_successes: tx.simulation_successes.load(Ordering::Relaxed),
+            simulation_failures: tx.simulation_failures.load(Ordering::Relaxed),
+            rate_limited_execs: tx.rate_limited_execs.load(Ordering::Relaxed),
+            executed_automations: TriggerCounts::new(&tx.trigger_metrics),
+        }
+    }
+
+    /// Renders the snapshot in the Prometheus text exposition format.
+    /// Counters restart from zero whenever the watchdog reinitializes the transaction executor.
+    pub fn encode(&self) -> String {
+        let registry = Registry::new_custom(Some("clockwork_plugin".into()), None).unwrap();
+        let counter = |name: &str, help: &str, value: u64| {
+            let counter = IntCounter::with_opts(Opts::new(name, help)).unwrap();
+            counter.inc_by(value);
+            registry.register(Box::new(counter)).unwrap();
+        };
+        let gauge = |name: &str, help: &str, value: usize| {
+            let gauge = IntGauge::with_opts(Opts::new(name, help)).unwrap();
+            gauge.set(value as i64);
+            registry.register(Box::new(gauge)).unwrap();
+        };
+        counter(
+            "dropped_automations_total",
+            "Automations dropped after crossing their simulation failure threshold",
+            self.dropped_automations,
+        );
+        gauge(
+            "executable_automations",
+            "Automations waiting to be simulated and submitted",
+            self.executable_automations,
+        );
+        gauge(
+            "transaction_history",
+            "Sent transactions awaiting confirmation",
+            self.transaction_history,
+        );
+        counter(
+            "simulation_successes_total",
+            "Exec transactions that were built and simulated successfully",
+            self.simulation_successes,
+        );
+        counter(
+            "simulation_failures_total",
+            "Exec transactions that failed to build or simulate",
+            self.simulation_failures,
+        );
+        counter(
+            "rate_limited_execs_total",
+            "Times an automation's execs were deferred to a later slot by its rate limit",
+            self.rate_limited_execs,
+        );
+        let executed_automations = IntCounterVec::new(
+            Opts::new(
+                "executed_automations_total",
+                "Automations whose exec transactions were submitted, by trigger type",
+            ),
+            &["trigger"],
+        )
+        .unwrap();
+        for (trigger, value) in [
+            ("account", self.executed_automations.account),
+            ("cron", self.executed_automations.cron),
+            ("epoch", self.executed_automations.epoch),
+            ("immediate", self.executed_automations.immediate),
+            ("slot", self.executed_automations.slot),
+            ("timestamp", self.executed_automations.timestamp),
+        ] {
+            executed_automations
+                .with_label_values(&[trigger])
+                .inc_by(value);
+        }
+        registry
+            .register(Box::new(executed_automations))
+            .unwrap();
+
+        let mut buffer = vec![];
+        TextEncoder::new()
+            .encode(&registry.gather(), &mut buffer)
+            .unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+}
+
+/// Serves the transaction executor's metrics over HTTP on the port, on every interface, for Prometheus to scrape.
+/// Every request is answered with the metrics, whatever its path.
+pub async fn serve(executors: Arc<Executors>, port: u16) -> std::io::Result<()> {
+    let listener = TcpListener::bind(("0.0.0.0", port)).await?;
+    info!("metrics: port: {} status: listening", port);
+    loop {
+        let (stream, _addr) = listener.accept().await?;
+        let tx = executors.tx.read().unwrap().clone();
+        tokio::spawn(respond(stream, tx));
+    }
+}
+
+async fn respond(mut stream: TcpStream, tx: Arc<TxExecutor>) {
+    let mut request = vec![0; MAX_REQUEST_SIZE];
+    if stream.read(&mut request).await.is_err() {
+        return;
+    }
+    let body = MetricsSnapshot::new(&tx).await.encode();
+    let response = format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await.ok();
+    stream.shutdown().await.ok();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_metrics() {
+        let metrics = MetricsSnapshot {
+            dropped_automations: 3,
+            executable_automations: 12,
+            transaction_history: 4,
+            simulation_successes: 90,
+            simulation_failures: 10,
+            rate_limited_execs: 7,
+            executed_automations: TriggerCounts {
+                cron: 5,
+                immediate: 1,
+                ..TriggerCounts::default()
+            },
+        }
+        .encode();
+        assert!(metrics.contains("# TYPE clockwork_plugin_dropped_automations_total counter"));
+        assert!(metrics.contains("clockwork_plugin_dropped_automations_total 3\n"));
+        assert!(metrics.contains("# TYPE clockwork_plugin_executable_automations gauge"));
+        assert!(metrics.contains("clockwork_plugin_executable_automations 12\n"));
+        assert!(metrics.contains("clockwork_plugin_transaction_history 4\n"));
+        assert!(metrics.contains("clockwork_plugin_simulation_successes_total 90\n"));
+        assert!(metrics.contains("clockwork_plugin_simulation_failures_total 10\n"));
+        assert!(metrics.contains("clockwork_plugin_rate_limited_execs_total 7\n"));
+        assert!(metrics.contains("# TYPE clockwork_plugin_executed_automations_total counter"));
+        let executed = |trigger: &str, value: u64| {
+            format!(
+                "clockwork_plugin_executed_automations_total{{trigger=\"{}\"}} {}\n",
+                trigger, value
+            )
+        };
+        assert!(metrics.contains(&executed("cron", 5)));
+        assert!(metrics.contains(&executed("immediate", 1)));
+        assert!(metrics.contains(&executed("slot", 0)));
+    }
+}
@@ -6,8 +6,12 @@ use std::{
 };
 
 use chrono::{DateTime, NaiveDateTime, Utc};
-use clockwork_client::automation::state::{Automation, Trigger, TriggerContext};
+use clockwork_client::{
+    automation::state::{Automation, ExecMode, Trigger, TriggerContext},
+    network::state::ProgramStatus,
+};
 use clockwork_cron::Schedule;
+use clockwork_utils::automation::account_trigger_slice;
 use log::info;
 use solana_geyser_plugin_interface::geyser_plugin_interface::{
     GeyserPluginError, Result as PluginResult,
@@ -15,6 +19,8 @@ use solana_geyser_plugin_interface::geyser_plugin_interface::{
 use solana_program::{clock::Clock, pubkey::Pubkey};
 use tokio::sync::RwLock;
 
+use crate::backlog::{BacklogBudgets, StartupBacklogPolicy};
+
 pub struct AutomationObserver {
     // Map from slot numbers to the sysvar clock data for that slot.
     pub clocks: RwLock<HashMap<u64, Clock>>,
@@ -23,28 +29,123 @@ pub struct AutomationObserver {
     // Map from account pubkeys to the set of automations listening for an account update.
     pub account_automations: RwLock<HashMap<Pubkey, HashSet<Pubkey>>>,
 
+    // The value conditions of automations with an account trigger.
+    // Map from automation pubkeys to the conditions the account data must meet to kick the automation off.
+    pub account_conditions: RwLock<HashMap<Pubkey, AccountCondition>>,
+
+    // The last observed data of each watched account.
+    pub account_data: RwLock<HashMap<Pubkey, Vec<u8>>>,
+
     // The set of automations with a cront trigger.
     // Map from unix timestamps to the list of automations scheduled for that moment.
     pub cron_automations: RwLock<HashMap<i64, HashSet<Pubkey>>>,
 
+    // The set of automations with an epoch trigger.
+    // Map from epochs to the set of automations waiting for that epoch to begin.
+    pub epoch_automations: RwLock<HashMap<u64, HashSet<Pubkey>>>,
+
+    // The triggers of automations with an epoch filter.
+    // Map from automation pubkeys to their trigger, checked against the clock's epoch before the automation is executable.
+    pub epoch_filtered_triggers: RwLock<HashMap<Pubkey, Trigger>>,
+
     // The set of automations with an immediate trigger.
     pub immediate_automations: RwLock<HashSet<Pubkey>>,
 
+    // The set of automations with a slot trigger.
+    // Map from target slots to the set of automations waiting for that slot.
+    pub slot_automations: RwLock<HashMap<u64, HashSet<Pubkey>>>,
+
     // The set of accounts that have updated.
-    pub updated_accounts: RwLock<HashSet<Pubkey>>,
+    // Map from account pubkeys to the account's data before and after the updates since the last slot.
+    pub updated_accounts: RwLock<HashMap<Pubkey, AccountUpdate>>,
+
+    // The set of programs that have been paused networkwide.
+    pub paused_programs: RwLock<HashSet<Pubkey>>,
+
+    // The set of automations waiting on a paused program.
+    // Map from program ids to the set of automations whose next instruction targets that program.
+    pub paused_automations: RwLock<HashMap<Pubkey, HashSet<Pubkey>>>,
+
+    // The set of accounts watched by account triggers.
+    // Checked synchronously on every account update so irrelevant updates can be dropped before spawning a task.
+    pub account_filter: std::sync::RwLock<HashSet<Pubkey>>,
+
+    // The remaining replays of non-skippable cron automations found with missed moments at startup.
+    pub backlog_budgets: RwLock<BacklogBudgets>,
 }
 
 impl AutomationObserver {
-    pub fn new() -> Self {
+    pub fn new(startup_backlog_policy: StartupBacklogPolicy) -> Self {
         Self {
             clocks: RwLock::new(HashMap::new()),
             account_automations: RwLock::new(HashMap::new()),
+            account_conditions: RwLock::new(HashMap::new()),
+            account_data: RwLock::new(HashMap::new()),
             cron_automations: RwLock::new(HashMap::new()),
+            epoch_automations: RwLock::new(HashMap::new()),
+            epoch_filtered_triggers: RwLock::new(HashMap::new()),
             immediate_automations: RwLock::new(HashSet::new()),
-            updated_accounts: RwLock::new(HashSet::new()),
+            slot_automations: RwLock::new(HashMap::new()),
+            updated_accounts: RwLock::new(HashMap::new()),
+            paused_programs: RwLock::new(HashSet::new()),
+            paused_automations: RwLock::new(HashMap::new()),
+            account_filter: std::sync::RwLock::new(HashSet::new()),
+            backlog_budgets: RwLock::new(BacklogBudgets::new(startup_backlog_policy)),
+        }
+    }
+
+    /// The unix timestamp of the latest observed clock, falling back to the system time before any clock is observed.
+    async fn latest_timestamp(&self) -> i64 {
+        let r_clocks = self.clocks.read().await;
+        match r_clocks.values().max_by_key(|clock| clock.slot) {
+            Some(clock) => clock.unix_timestamp,
+            None => std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|duration| duration.as_secs() as i64)
+                .unwrap_or_default(),
         }
     }
 
+    /// Returns true if the account is watched by an account trigger.
+    pub fn is_watched(&self, account_pubkey: &Pubkey) -> bool {
+        self.account_filter.read().unwrap().contains(account_pubkey)
+    }
+
+    /// Rebuild the account filter from the automations currently listening for account updates.
+    /// Accounts are added to the filter as soon as an automation is indexed, so this only needs
+    /// to run periodically to prune accounts that are no longer watched.
+    pub async fn refresh_account_filter(self: Arc<Self>) {
+        let watched_accounts = self.watched_accounts().await;
+
+        // Drop the data and conditions of accounts and automations that are no longer watched.
+        let mut w_account_data = self.account_data.write().await;
+        w_account_data.retain(|account_pubkey, _data| watched_accounts.contains(account_pubkey));
+        drop(w_account_data);
+        let r_account_automations = self.account_automations.read().await;
+        let mut w_account_conditions = self.account_conditions.write().await;
+        w_account_conditions.retain(|automation_pubkey, _condition| {
+            r_account_automations
+                .values()
+                .any(|automation_pubkeys| automation_pubkeys.contains(automation_pubkey))
+        });
+        drop(w_account_conditions);
+        drop(r_account_automations);
+
+        let mut w_account_filter = self.account_filter.write().unwrap();
+        *w_account_filter = watched_accounts;
+        info!("account_filter: watched_accounts: {}", w_account_filter.len());
+    }
+
+    /// The set of accounts watched by active account triggers.
+    pub async fn watched_accounts(&self) -> HashSet<Pubkey> {
+        self.account_automations
+            .read()
+            .await
+            .keys()
+            .cloned()
+            .collect()
+    }
+
     pub async fn process_slot(self: Arc<Self>, slot: u64) -> PluginResult<HashSet<Pubkey>> {
         let mut executable_automations: HashSet<Pubkey> = HashSet::new();
 
@@ -55,32 +156,97 @@ impl AutomationObserver {
 
         // Get the set of automations that were triggered by the current clock.
         let r_clocks = self.clocks.read().await;
+        let r_epoch_filtered_triggers = self.epoch_filtered_triggers.read().await;
         if let Some(clock) = r_clocks.get(&slot) {
             let mut w_cron_automations = self.cron_automations.write().await;
+            let mut skipped_cron_automations = vec![];
             w_cron_automations.retain(|target_timestamp, automation_pubkeys| {
                 let is_due = clock.unix_timestamp >= *target_timestamp;
                 if is_due {
                     for pubkey in automation_pubkeys.iter() {
-                        executable_automations.insert(*pubkey);
+                        match r_epoch_filtered_triggers.get(pubkey) {
+                            Some(trigger) if !trigger.is_epoch_allowed(clock.epoch) => {
+                                skipped_cron_automations.push((*pubkey, trigger.clone()))
+                            }
+                            _ => {
+                                executable_automations.insert(*pubkey);
+                            }
+                        }
                     }
                 }
                 !is_due
             });
+
+            // Skip moments that fall in filtered out epochs by indexing the automation to its following moment.
+            for (pubkey, trigger) in skipped_cron_automations {
+                if let Trigger::EpochFilteredCron { schedule, .. } = trigger {
+                    if let Some(target_timestamp) = next_moment(clock.unix_timestamp, schedule) {
+                        w_cron_automations
+                            .entry(target_timestamp)
+                            .or_default()
+                            .insert(pubkey);
+                    }
+                }
+            }
             drop(w_cron_automations);
+
+            let mut w_epoch_automations = self.epoch_automations.write().await;
+            w_epoch_automations.retain(|target_epoch, automation_pubkeys| {
+                let is_due = clock.epoch >= *target_epoch;
+                if is_due {
+                    executable_automations.extend(automation_pubkeys.iter());
+                }
+                !is_due
+            });
+            drop(w_epoch_automations);
         }
 
+        // Get the set of automations whose target slot has been reached.
+        // Automations with an epoch filter keep waiting until the clock shows an allowed epoch.
+        let epoch = r_clocks.get(&slot).map(|clock| clock.epoch);
+        let mut w_slot_automations = self.slot_automations.write().await;
+        w_slot_automations.retain(|target_slot, automation_pubkeys| {
+            if slot < *target_slot {
+                return true;
+            }
+            automation_pubkeys.retain(|pubkey| {
+                let is_allowed = match r_epoch_filtered_triggers.get(pubkey) {
+                    None => true,
+                    Some(trigger) => epoch.map_or(false, |epoch| trigger.is_epoch_allowed(epoch)),
+                };
+                if is_allowed {
+                    executable_automations.insert(*pubkey);
+                }
+                !is_allowed
+            });
+            !automation_pubkeys.is_empty()
+        });
+        drop(w_slot_automations);
+        drop(r_epoch_filtered_triggers);
+
         // Get the set of automations were triggered by an account update.
+        // Automations whose value conditions are not met keep listening to the account.
         let mut w_account_automations = self.account_automations.write().await;
         let mut w_updated_accounts = self.updated_accounts.write().await;
-        w_updated_accounts.iter().for_each(|account_pubkey| {
-            if let Some(automation_pubkeys) = w_account_automations.get(&account_pubkey) {
-                automation_pubkeys.iter().for_each(|pubkey| {
-                    executable_automations.insert(*pubkey);
+        let r_account_conditions = self.account_conditions.read().await;
+        for (account_pubkey, account_update) in w_updated_accounts.drain() {
+            if let Some(automation_pubkeys) = w_account_automations.get_mut(&account_pubkey) {
+                automation_pubkeys.retain(|pubkey| {
+                    let is_active = !account_update.closed
+                        && r_account_conditions
+                            .get(pubkey)
+                            .map_or(true, |condition| condition.is_active(&account_update));
+                    if is_active {
+                        executable_automations.insert(*pubkey);
+                    }
+                    !is_active
                 });
-                w_account_automations.remove(&account_pubkey);
+                if automation_pubkeys.is_empty() {
+                    w_account_automations.remove(&account_pubkey);
+                }
             }
-        });
-        w_updated_accounts.clear();
+        }
+        drop(r_account_conditions);
         drop(w_account_automations);
         drop(w_updated_accounts);
 
@@ -102,64 +268,156 @@ impl AutomationObserver {
         Ok(())
     }
 
-    /// Move all automations listening to this account into the executable set.
+    /// Record an update to a watched account, to be checked against its automations' conditions on the next slot.
+    /// A closed account (zero lamports) never fires its automations. They keep listening, and the account's
+    /// last observed data is forgotten so a recreated account is compared against nothing.
     pub async fn observe_account(
         self: Arc<Self>,
         account_pubkey: Pubkey,
-        _slot: u64,
+        lamports: u64,
+        data: Vec<u8>,
+        slot: u64,
     ) -> PluginResult<()> {
+        let closed = lamports == 0;
+        let mut w_account_data = self.account_data.write().await;
+        let prior_data = if closed {
+            w_account_data.remove(&account_pubkey)
+        } else {
+            w_account_data.insert(account_pubkey, data.clone())
+        };
+        drop(w_account_data);
+
         let r_account_automations = self.account_automations.read().await;
-        if r_account_automations.contains_key(&account_pubkey) {
+        if let Some(automation_pubkeys) = r_account_automations.get(&account_pubkey) {
+            if closed {
+                info!(
+                    "account: {} slot: {} status: closed automations: {:?}",
+                    account_pubkey, slot, automation_pubkeys
+                );
+            }
             let mut w_updated_accounts = self.updated_accounts.write().await;
-            w_updated_accounts.insert(account_pubkey);
+            w_updated_accounts
+                .entry(account_pubkey)
+                .and_modify(|account_update| {
+                    account_update.data = data.clone();
+                    account_update.closed = closed;
+                })
+                .or_insert(AccountUpdate {
+                    prior_data,
+                    data,
+                    closed,
+                });
             drop(w_updated_accounts);
         }
         drop(r_account_automations);
         Ok(())
     }
 
+    /// Track paused programs, and release automations waiting on a program once it resumes.
+    pub async fn observe_program_status(
+        self: Arc<Self>,
+        program_status: ProgramStatus,
+    ) -> PluginResult<()> {
+        let program_id = program_status.program_id;
+        let mut w_paused_programs = self.paused_programs.write().await;
+        if program_status.paused {
+            info!("program: {} status: paused", program_id);
+            w_paused_programs.insert(program_id);
+        } else if w_paused_programs.remove(&program_id) {
+            info!("program: {} status: resumed", program_id);
+            let mut w_paused_automations = self.paused_automations.write().await;
+            if let Some(automation_pubkeys) = w_paused_automations.remove(&program_id) {
+                let mut w_immediate_automations = self.immediate_automations.write().await;
+                w_immediate_automations.extend(automation_pubkeys);
+                drop(w_immediate_automations);
+            }
+            drop(w_paused_automations);
+        }
+        drop(w_paused_programs);
+        Ok(())
+    }
+
     pub async fn observe_automation(
         self: Arc<Self>,
         automation: Automation,
         automation_pubkey: Pubkey,
         slot: u64,
+        is_startup: bool,
     ) -> PluginResult<()> {
         // If the automation is paused, just return without indexing
         if automation.paused {
             return Ok(());
         }
 
+        // If the automation has spent its lifetime budget, its execs would be refused.
+        if let Some(lifetime_spend_cap) = automation.lifetime_spend_cap {
+            if automation.lifetime_spent >= lifetime_spend_cap {
+                return Ok(());
+            }
+        }
+
+        // If the automation has completed its max number of executions, its kickoffs would be refused.
+        if let Some(max_executions) = automation.max_executions {
+            if automation.execution_count >= max_executions {
+                return Ok(());
+            }
+        }
+
+        // Relayer-mode automations are only submitted by their relayer.
+        if let ExecMode::Relayer { .. } = automation.exec_mode {
+            return Ok(());
+        }
+
         info!("indexing automation: {:?} slot: {}", automation_pubkey, slot);
-        if automation.next_instruction.is_some() {
-            // If the automation has a next instruction, index it as executable.
+        if let Some(next_instruction) = automation.next_instruction {
+            // If the next instruction targets a paused program, hold the automation until the program resumes.
+            let r_paused_programs = self.paused_programs.read().await;
+            if r_paused_programs.contains(&next_instruction.program_id) {
+                let mut w_paused_automations = self.paused_automations.write().await;
+                w_paused_automations
+                    .entry(next_instruction.program_id)
+                    .or_default()
+                    .insert(automation_pubkey);
+                drop(w_paused_automations);
+                return Ok(());
+            }
+            drop(r_paused_programs);
+
+            // Otherwise, index it as executable.
             let mut w_immediate_automations = self.immediate_automations.write().await;
             w_immediate_automations.insert(automation_pubkey);
             drop(w_immediate_automations);
         } else {
-            // Otherwise, index the automation according to its trigger type.
+            // Otherwise, track the trigger if it has an epoch filter.
+            let mut w_epoch_filtered_triggers = self.epoch_filtered_triggers.write().await;
+            match &automation.trigger {
+                Trigger::EpochFilteredCron { .. } | Trigger::EpochFilteredSlot { .. } => {
+                    w_epoch_filtered_triggers.insert(automation_pubkey, automation.trigger.clone());
+                }
+                _ => {
+                    w_epoch_filtered_triggers.remove(&automation_pubkey);
+                }
+            }
+            drop(w_epoch_filtered_triggers);
+
+            // Index the automation according to its trigger type.
             match automation.trigger {
                 Trigger::Account {
                     address,
                     offset: _,
                     size: _,
                 } => {
-                    // Index the automation by its trigger's account pubkey.
-                    let mut w_account_automations = self.account_automations.write().await;
-                    w_account_automations
-                        .entry(address)
-                        .and_modify(|v| {
-                            v.insert(automation_pubkey);
-                        })
-                        .or_insert_with(|| {
-                            let mut v = HashSet::new();
-                            v.insert(automation_pubkey);
-                            v
-                        });
-                    drop(w_account_automations);
+                    // Any update to the account kicks the automation off.
+                    self.index_account_automation(automation_pubkey, address, None).await;
                 }
                 Trigger::Cron {
                     schedule,
-                    skippable: _,
+                    skippable,
+                }
+                | Trigger::EpochFilteredCron {
+                    schedule,
+                    skippable,
+                    epoch_filter: _,
                 } => {
                     // Find a reference timestamp for calculating the automation's upcoming target time.
                     let reference_timestamp = match automation.exec_context {
@@ -174,8 +432,28 @@ impl AutomationObserver {
                         },
                     };
 
+                    // Non-skippable automations that missed moments replay them according to the startup backlog policy.
+                    // Deferred automations are indexed to the schedule's next upcoming moment instead.
+                    let mut target_timestamp = next_moment(reference_timestamp, schedule.clone());
+                    if let (false, Some(missed_timestamp)) = (skippable, target_timestamp) {
+                        let now = self.latest_timestamp().await;
+                        let is_backlogged = missed_timestamp < now;
+                        let should_replay = self.backlog_budgets.write().await.should_replay(
+                            &automation_pubkey,
+                            is_startup,
+                            is_backlogged,
+                        );
+                        if !should_replay {
+                            info!(
+                                "automation: {} missed_timestamp: {} status: backlog_deferred",
+                                automation_pubkey, missed_timestamp
+                            );
+                            target_timestamp = next_moment(now, schedule);
+                        }
+                    }
+
                     // Index the automation to its target timestamp
-                    match next_moment(reference_timestamp, schedule) {
+                    match target_timestamp {
                         None => {} // The automation does not have any upcoming scheduled target time
                         Some(target_timestamp) => {
                             let mut w_cron_automations = self.cron_automations.write().await;
@@ -193,16 +471,167 @@ impl AutomationObserver {
                         }
                     }
                 }
+                Trigger::Epoch { skippable: _ } => {
+                    // Find the epoch the automation last kicked off for.
+                    let reference_epoch = match automation.exec_context {
+                        None => automation.created_at.epoch,
+                        Some(exec_context) => match exec_context.trigger_context {
+                            TriggerContext::Epoch { started_at } => started_at,
+                            _ => {
+                                return Err(GeyserPluginError::Custom(
+                                    "Invalid exec context".into(),
+                                ))
+                            }
+                        },
+                    };
+
+                    // Index the automation to the start of the following epoch.
+                    // Non-skippable automations that fell behind are released on the next clock and catch up one epoch per kickoff.
+                    let mut w_epoch_automations = self.epoch_automations.write().await;
+                    w_epoch_automations
+                        .entry(reference_epoch + 1)
+                        .or_default()
+                        .insert(automation_pubkey);
+                    drop(w_epoch_automations);
+                }
+                Trigger::Timestamp { unix_ts } => {
+                    // Timestamp triggers fire once. Index the automation alongside the cron moments,
+                    // which are released by the first clock at or past their timestamp.
+                    if automation.exec_context.is_none() {
+                        let mut w_cron_automations = self.cron_automations.write().await;
+                        w_cron_automations
+                            .entry(unix_ts)
+                            .or_default()
+                            .insert(automation_pubkey);
+                        drop(w_cron_automations);
+                    }
+                }
                 Trigger::Immediate => {
                     let mut w_immediate_automations = self.immediate_automations.write().await;
                     w_immediate_automations.insert(automation_pubkey);
                     drop(w_immediate_automations);
                 }
+                Trigger::Slot { target_slot }
+                | Trigger::EpochFilteredSlot {
+                    target_slot,
+                    epoch_filter: _,
+                } => {
+                    // Slot triggers fire once. Only index the automation if it has not been kicked off yet.
+                    if automation.exec_context.is_none() {
+                        let mut w_slot_automations = self.slot_automations.write().await;
+                        w_slot_automations
+                            .entry(target_slot)
+                            .or_default()
+                            .insert(automation_pubkey);
+                        drop(w_slot_automations);
+                    }
+                }
+                Trigger::AccountValue {
+                    address,
+                    offset,
+                    size,
+                    equals,
+                    changed_from,
+                } => {
+                    // Only updates meeting the value conditions kick the automation off.
+                    let condition = AccountCondition {
+                        offset,
+                        size,
+                        equals,
+                        changed_from,
+                    };
+                    self.index_account_automation(automation_pubkey, address, Some(condition))
+                        .await;
+                }
             }
         }
 
         Ok(())
     }
+
+    /// Index an automation by the account its trigger watches, along with the value conditions
+    /// the account data must meet, if any.
+    async fn index_account_automation(
+        &self,
+        automation_pubkey: Pubkey,
+        address: Pubkey,
+        condition: Option<AccountCondition>,
+    ) {
+        // Record the value conditions the account data must meet.
+        let mut w_account_conditions = self.account_conditions.write().await;
+        match condition {
+            None => w_account_conditions.remove(&automation_pubkey),
+            Some(condition) => w_account_conditions.insert(automation_pubkey, condition),
+        };
+        drop(w_account_conditions);
+
+        // Index the automation by its trigger's account pubkey.
+        let mut w_account_automations = self.account_automations.write().await;
+        w_account_automations
+            .entry(address)
+            .and_modify(|v| {
+                v.insert(automation_pubkey);
+            })
+            .or_insert_with(|| {
+                let mut v = HashSet::new();
+                v.insert(automation_pubkey);
+                v
+            });
+        drop(w_account_automations);
+
+        // Let updates to the account through the filter right away.
+        self.account_filter.write().unwrap().insert(address);
+    }
+}
+
+/// The data of an account before and after its updates in a slot.
+pub struct AccountUpdate {
+    pub prior_data: Option<Vec<u8>>,
+    pub data: Vec<u8>,
+    /// Whether the account was closed by the last update in the slot.
+    pub closed: bool,
+}
+
+/// The value conditions of an account trigger.
+pub struct AccountCondition {
+    pub offset: u64,
+    pub size: u64,
+    pub equals: Option<Vec<u8>>,
+    pub changed_from: Option<Vec<u8>>,
+}
+
+impl AccountCondition {
+    /// Returns true if the account update meets the trigger's conditions.
+    /// Without conditions, any update activates the trigger.
+    pub fn is_active(&self, account_update: &AccountUpdate) -> bool {
+        // A closed account's zeroed data is not a change to the monitored value.
+        if account_update.closed {
+            return false;
+        }
+
+        let slice = account_trigger_slice(&account_update.data, self.offset, self.size);
+        let prior_slice = account_update
+            .prior_data
+            .as_ref()
+            .map(|prior_data| account_trigger_slice(prior_data, self.offset, self.size));
+
+        // Fire when the slice changes to the target value.
+        if let Some(equals) = &self.equals {
+            if slice.ne(equals.as_slice()) || prior_slice.eq(&Some(slice)) {
+                return false;
+            }
+        }
+
+        // Fire when the slice changes away from the source value.
+        // If the prior value is unknown, the transition cannot be confirmed.
+        if let Some(changed_from) = &self.changed_from {
+            if prior_slice.ne(&Some(changed_from.as_slice())) || slice.eq(changed_from.as_slice()) {
+                return false;
+            }
+        }
+
+        true
+    }
 }
 
 impl Debug for AutomationObserver {
@@ -223,3 +652,212 @@ fn next_moment(after: i64, schedule: String) -> Option<i64> {
             .map(|datetime| datetime.timestamp()),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        clockwork_client::automation::state::{ClockData, EpochFilter, ExecContext},
+        tokio::runtime::Runtime,
+    };
+
+    fn automation(trigger: Trigger, exec_context: Option<ExecContext>) -> Automation {
+        Automation {
+            authority: Pubkey::new_unique(),
+            bump: 255,
+            chain_depth: 0,
+            created_at: ClockData {
+                slot: 0,
+                epoch: 0,
+                unix_timestamp: 0,
+            },
+            description: String::new(),
+            exec_context,
+            exec_mode: ExecMode::Worker,
+            execution_count: 0,
+            fee: 1_000,
+            id: b"crank".to_vec(),
+            instructions: vec![],
+            lifetime_spend_cap: None,
+            lifetime_spent: 0,
+            max_chain_depth: None,
+            max_executions: None,
+            max_simulation_failures: None,
+            name: String::new(),
+            next_instruction: None,
+            paused: false,
+            rate_limit: u64::MAX,
+            trigger,
+        }
+    }
+
+    fn condition(equals: Option<Vec<u8>>, changed_from: Option<Vec<u8>>) -> AccountCondition {
+        AccountCondition {
+            offset: 0,
+            size: 4,
+            equals,
+            changed_from,
+        }
+    }
+
+    #[test]
+    fn test_closed_account_does_not_fire() {
+        let account_update = AccountUpdate {
+            prior_data: Some(vec![1, 2, 3, 4]),
+            data: vec![],
+            closed: true,
+        };
+        assert!(!condition(None, None).is_active(&account_update));
+        assert!(!condition(Some(vec![0, 0, 0, 0]), None).is_active(&account_update));
+        assert!(!condition(None, Some(vec![1, 2, 3, 4])).is_active(&account_update));
+    }
+
+    #[test]
+    fn test_open_account_fires() {
+        let account_update = AccountUpdate {
+            prior_data: Some(vec![1, 2, 3, 4]),
+            data: vec![0, 0, 0, 0],
+            closed: false,
+        };
+        assert!(condition(None, None).is_active(&account_update));
+        assert!(condition(Some(vec![0, 0, 0, 0]), None).is_active(&account_update));
+        assert!(condition(None, Some(vec![1, 2, 3, 4])).is_active(&account_update));
+    }
+
+    #[test]
+    fn test_slot_automation_fires_once_target_slot_is_reached() {
+        Runtime::new().unwrap().block_on(async {
+            let observer = Arc::new(AutomationObserver::new(StartupBacklogPolicy::default()));
+            let automation_pubkey = Pubkey::new_unique();
+            let trigger = Trigger::Slot { target_slot: 100 };
+            observer
+                .clone()
+                .observe_automation(automation(trigger, None), automation_pubkey, 90, false)
+                .await
+                .unwrap();
+
+            assert!(observer.clone().process_slot(99).await.unwrap().is_empty());
+
+            // A worker that skipped the target slot still fires the automation late, and only once.
+            assert_eq!(
+                observer.clone().process_slot(105).await.unwrap(),
+                HashSet::from([automation_pubkey])
+            );
+            assert!(observer.clone().process_slot(106).await.unwrap().is_empty());
+        });
+    }
+
+    #[test]
+    fn test_kicked_off_slot_automation_is_not_indexed() {
+        Runtime::new().unwrap().block_on(async {
+            let observer = Arc::new(AutomationObserver::new(StartupBacklogPolicy::default()));
+            let trigger = Trigger::Slot { target_slot: 100 };
+            let exec_context = ExecContext {
+                exec_index: 0,
+                execs_since_reimbursement: 0,
+                execs_since_slot: 0,
+                last_exec_at: 100,
+                trigger_context: TriggerContext::Slot { started_at: 100 },
+            };
+            observer
+                .clone()
+                .observe_automation(
+                    automation(trigger, Some(exec_context)),
+                    Pubkey::new_unique(),
+                    101,
+                    false,
+                )
+                .await
+                .unwrap();
+            assert!(observer.clone().process_slot(101).await.unwrap().is_empty());
+        });
+    }
+
+    #[test]
+    fn test_account_value_automation_waits_for_condition() {
+        Runtime::new().unwrap().block_on(async {
+            let observer = Arc::new(AutomationObserver::new(StartupBacklogPolicy::default()));
+            let address = Pubkey::new_unique();
+            let any_update_pubkey = Pubkey::new_unique();
+            let value_pubkey = Pubkey::new_unique();
+            let any_update = Trigger::Account {
+                address,
+                offset: 0,
+                size: 4,
+            };
+            let value = Trigger::AccountValue {
+                address,
+                offset: 0,
+                size: 4,
+                equals: Some(vec![1, 1, 1, 1]),
+                changed_from: None,
+            };
+            let automations = [(any_update, any_update_pubkey), (value, value_pubkey)];
+            for (trigger, automation_pubkey) in automations {
+                observer
+                    .clone()
+                    .observe_automation(automation(trigger, None), automation_pubkey, 90, false)
+                    .await
+                    .unwrap();
+            }
+            assert!(observer.is_watched(&address));
+
+            // Any update fires the plain account trigger, but not the value trigger.
+            observer
+                .clone()
+                .observe_account(address, 1, vec![0, 0, 0, 0], 91)
+                .await
+                .unwrap();
+            assert_eq!(
+                observer.clone().process_slot(91).await.unwrap(),
+                HashSet::from([any_update_pubkey])
+            );
+
+            // The value trigger fires once the monitored slice changes to its target value.
+            observer
+                .clone()
+                .observe_account(address, 1, vec![1, 1, 1, 1], 92)
+                .await
+                .unwrap();
+            assert_eq!(
+                observer.clone().process_slot(92).await.unwrap(),
+                HashSet::from([value_pubkey])
+            );
+        });
+    }
+
+    #[test]
+    fn test_epoch_filtered_slot_automation_waits_for_allowed_epoch() {
+        Runtime::new().unwrap().block_on(async {
+            let observer = Arc::new(AutomationObserver::new(StartupBacklogPolicy::default()));
+            let automation_pubkey = Pubkey::new_unique();
+            let trigger = Trigger::EpochFilteredSlot {
+                target_slot: 100,
+                epoch_filter: EpochFilter::Even,
+            };
+            observer
+                .clone()
+                .observe_automation(automation(trigger, None), automation_pubkey, 90, false)
+                .await
+                .unwrap();
+
+            // The target slot falls in an odd epoch, so the automation keeps waiting.
+            for (slot, epoch) in [(100, 3), (101, 4)] {
+                observer
+                    .clone()
+                    .observe_clock(Clock {
+                        slot,
+                        epoch,
+                        ..Clock::default()
+                    })
+                    .await
+                    .unwrap();
+            }
+            assert!(observer.clone().process_slot(100).await.unwrap().is_empty());
+            assert_eq!(
+                observer.clone().process_slot(101).await.unwrap(),
+                HashSet::from([automation_pubkey])
+            );
+        });
+    }
+}
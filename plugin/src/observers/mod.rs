@@ -6,15 +6,17 @@ use std::{fmt::Debug, sync::Arc};
 use automation::AutomationObserver;
 use webhook::WebhookObserver;
 
+use crate::config::PluginConfig;
+
 pub struct Observers {
     pub automation: Arc<AutomationObserver>,
     pub webhook: Arc<WebhookObserver>,
 }
 
 impl Observers {
-    pub fn new() -> Self {
+    pub fn new(config: PluginConfig) -> Self {
         Observers {
-            automation: Arc::new(AutomationObserver::new()),
+            automation: Arc::new(AutomationObserver::new(config.startup_backlog_policy)),
             webhook: Arc::new(WebhookObserver::new()),
         }
     }
@@ -5,7 +5,7 @@ use solana_geyser_plugin_interface::geyser_plugin_interface::{
     GeyserPlugin, ReplicaAccountInfo, ReplicaAccountInfoVersions, Result as PluginResult,
     SlotStatus,
 };
-use solana_program::pubkey::Pubkey;
+use solana_program::{pubkey::Pubkey, sysvar};
 use tokio::runtime::{Builder, Runtime};
 
 use crate::{
@@ -15,6 +15,9 @@ use crate::{
     observers::{webhook::HttpRequest, Observers},
 };
 
+/// Number of slots between refreshes of the account filter.
+static ACCOUNT_FILTER_REFRESH_PERIOD: u64 = 10;
+
 pub struct ClockworkPlugin {
     pub inner: Arc<Inner>,
 }
@@ -58,7 +61,13 @@ impl GeyserPlugin for ClockworkPlugin {
         Ok(())
     }
 
-    fn on_unload(&mut self) {}
+    fn on_unload(&mut self) {
+        // Let the sent transactions land, then persist the rest so they are not resubmitted after a restart.
+        let executors = self.inner.executors.clone();
+        self.inner
+            .runtime
+            .block_on(async move { executors.shutdown().await });
+    }
 
     fn update_account(
         &mut self,
@@ -88,19 +97,33 @@ impl GeyserPlugin for ClockworkPlugin {
             },
         };
         let account_pubkey = Pubkey::new(account_info.pubkey);
+
+        // Drop updates to accounts the plugin does not care about before spawning a task.
+        if !self.is_relevant(&account_pubkey, account_info.owner) {
+            return Ok(());
+        }
+
+        // Copy the data of accounts watched by account triggers so it can be checked against their conditions.
+        let watched_data = if self.inner.observers.automation.is_watched(&account_pubkey) {
+            Some((account_info.lamports, account_info.data.to_vec()))
+        } else {
+            None
+        };
         let event = AccountUpdateEvent::try_from(account_info);
 
         // Process event on tokio task.
         self.inner.clone().spawn(|inner| async move {
-            // Send all account updates to the automation observer for account listeners.
+            // Send watched account updates to the automation observer for account listeners.
             // Only process account updates if we're past the startup phase.
             if !is_startup {
-                inner
-                    .observers
-                    .automation
-                    .clone()
-                    .observe_account(account_pubkey, slot)
-                    .await?;
+                if let Some((lamports, data)) = watched_data {
+                    inner
+                        .observers
+                        .automation
+                        .clone()
+                        .observe_account(account_pubkey, lamports, data, slot)
+                        .await?;
+                }
             }
 
             // Parse and process specific update events.
@@ -132,7 +155,16 @@ impl GeyserPlugin for ClockworkPlugin {
                             .observers
                             .automation
                             .clone()
-                            .observe_automation(automation, account_pubkey, slot)
+                            .observe_automation(automation, account_pubkey, slot, is_startup)
+                            .await
+                            .ok();
+                    }
+                    AccountUpdateEvent::ProgramStatus { program_status } => {
+                        inner
+                            .observers
+                            .automation
+                            .clone()
+                            .observe_program_status(program_status)
                             .await
                             .ok();
                     }
@@ -157,6 +189,14 @@ impl GeyserPlugin for ClockworkPlugin {
         self.inner.clone().spawn(|inner| async move {
             match status {
                 SlotStatus::Processed => {
+                    if slot % ACCOUNT_FILTER_REFRESH_PERIOD == 0 {
+                        inner
+                            .observers
+                            .automation
+                            .clone()
+                            .refresh_account_filter()
+                            .await;
+                    }
                     inner
                         .executors
                         .clone()
@@ -197,8 +237,24 @@ impl GeyserPlugin for ClockworkPlugin {
 impl ClockworkPlugin {
     fn new_from_config(config: PluginConfig) -> Self {
         let runtime = build_runtime(config.clone());
-        let observers = Arc::new(Observers::new());
+        let observers = Arc::new(Observers::new(config.clone()));
         let executors = Arc::new(Executors::new(config.clone()));
+        if let Some(event_stream_path) = config.event_stream_path.clone() {
+            let event_stream = executors.event_stream.clone();
+            runtime.spawn(async move {
+                if let Err(err) = event_stream.serve(event_stream_path).await {
+                    info!("event_stream: status: stopped error: {}", err);
+                }
+            });
+        }
+        if let Some(metrics_port) = config.metrics_port {
+            let executors = executors.clone();
+            runtime.spawn(async move {
+                if let Err(err) = crate::metrics::serve(executors, metrics_port).await {
+                    info!("metrics: status: stopped error: {}", err);
+                }
+            });
+        }
         Self {
             inner: Arc::new(Inner {
                 config,
@@ -208,6 +264,15 @@ impl ClockworkPlugin {
             }),
         }
     }
+
+    /// Returns true if the account update may be an event the plugin handles or may fire an account trigger.
+    fn is_relevant(&self, account_pubkey: &Pubkey, owner: &[u8]) -> bool {
+        account_pubkey.eq(&sysvar::clock::ID)
+            || owner.eq(clockwork_client::automation::ID.as_ref())
+            || owner.eq(clockwork_client::network::ID.as_ref())
+            || owner.eq(clockwork_client::webhook::ID.as_ref())
+            || self.inner.observers.automation.is_watched(account_pubkey)
+    }
 }
 
 impl Default for ClockworkPlugin {
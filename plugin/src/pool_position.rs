@@ -2,15 +2,73 @@ use {solana_program::pubkey::Pubkey, std::fmt::Debug};
 
 #[derive(Clone, Debug)]
 pub struct PoolPosition {
+    pub pool_id: u64,
     pub current_position: Option<u64>,
     pub workers: Vec<Pubkey>,
 }
 
+impl PoolPosition {
+    pub fn new(pool_id: u64, worker_pubkey: Pubkey, workers: Vec<Pubkey>) -> Self {
+        PoolPosition {
+            pool_id,
+            current_position: workers
+                .iter()
+                .position(|k| k.eq(&worker_pubkey))
+                .map(|i| i as u64),
+            workers,
+        }
+    }
+
+    /// Returns true if this worker should give the pool's workers a chance to execute first.
+    /// An empty pool has no worker to defer to, so execs proceed immediately.
+    pub fn defers_to_pool(&self) -> bool {
+        self.current_position.is_none() && !self.workers.is_empty()
+    }
+}
+
 impl Default for PoolPosition {
     fn default() -> Self {
         PoolPosition {
+            pool_id: 0,
             current_position: None,
             workers: vec![],
         }
     }
 }
+
+/// Returns the position in the pool to execute automations through: the first pool, in config order, whose
+/// workers this worker need not defer to, or else the first pool.
+/// Each automation is executed through one pool per slot, so it is never submitted once per pool.
+pub fn exec_pool_position(pool_positions: &[PoolPosition]) -> Option<&PoolPosition> {
+    pool_positions
+        .iter()
+        .find(|pool_position| !pool_position.defers_to_pool())
+        .or_else(|| pool_positions.first())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exec_pool_prefers_pool_worker_is_in() {
+        let worker = Pubkey::new_unique();
+        let pool_positions = vec![
+            PoolPosition::new(0, worker, vec![Pubkey::new_unique()]),
+            PoolPosition::new(1, worker, vec![Pubkey::new_unique(), worker]),
+            PoolPosition::new(2, worker, vec![worker]),
+        ];
+        assert_eq!(exec_pool_position(&pool_positions).unwrap().pool_id, 1);
+    }
+
+    #[test]
+    fn test_exec_pool_defaults_to_first_pool() {
+        let worker = Pubkey::new_unique();
+        let pool_positions = vec![
+            PoolPosition::new(3, worker, vec![Pubkey::new_unique()]),
+            PoolPosition::new(4, worker, vec![Pubkey::new_unique()]),
+        ];
+        assert_eq!(exec_pool_position(&pool_positions).unwrap().pool_id, 3);
+        assert!(exec_pool_position(&[]).is_none());
+    }
+}
@@ -0,0 +1,74 @@
+use {
+    serde::{Deserialize, Serialize},
+    serde_json::json,
+    solana_client::{nonblocking::rpc_client::RpcClient, rpc_request::RpcRequest},
+};
+
+/// How the worker prices the compute units of its exec transactions, in micro-lamports per compute unit.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum PriorityFee {
+    /// Submit transactions without a compute unit price.
+    None,
+    /// Pay a fixed compute unit price.
+    Fixed { micro_lamports: u64 },
+    /// Pay a percentile of the prioritization fees recently paid on the cluster, up to a cap.
+    Dynamic {
+        percentile: u8,
+        max_micro_lamports: u64,
+    },
+}
+
+/// A recent prioritization fee, as returned by `getRecentPrioritizationFees`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RpcPrioritizationFee {
+    prioritization_fee: u64,
+}
+
+impl Default for PriorityFee {
+    fn default() -> Self {
+        PriorityFee::None
+    }
+}
+
+impl PriorityFee {
+    /// The compute unit price to attach to the next batch of transactions, if any.
+    /// In dynamic mode, a failed fee lookup falls back to submitting without a price.
+    pub async fn compute_unit_price(&self, client: &RpcClient) -> Option<u64> {
+        match self {
+            PriorityFee::None => None,
+            PriorityFee::Fixed { micro_lamports } => Some(*micro_lamports),
+            PriorityFee::Dynamic {
+                percentile,
+                max_micro_lamports,
+            } => {
+                let fees = client
+                    .send::<Vec<RpcPrioritizationFee>>(
+                        RpcRequest::Custom {
+                            method: "getRecentPrioritizationFees",
+                        },
+                        json!([]),
+                    )
+                    .await
+                    .ok()?;
+                let mut fees = fees
+                    .into_iter()
+                    .map(|fee| fee.prioritization_fee)
+                    .collect::<Vec<u64>>();
+                percentile_of(&mut fees, *percentile)
+                    .map(|micro_lamports| micro_lamports.min(*max_micro_lamports))
+            }
+        }
+    }
+}
+
+/// The value at the given percentile (0-100) of the values, using the nearest-rank method.
+fn percentile_of(values: &mut [u64], percentile: u8) -> Option<u64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_unstable();
+    let rank = (values.len() * percentile.min(100) as usize + 99) / 100;
+    Some(values[rank.saturating_sub(1)])
+}
@@ -0,0 +1,146 @@
+use {
+    crate::executors::tx::{ExecutableAutomationMetadata, TransactionMetadata, TxExecutor},
+    serde::{Deserialize, Serialize},
+    solana_program::{hash::Hash, pubkey::Pubkey},
+    solana_sdk::signature::Signature,
+    std::{collections::HashMap, fs::File, path::Path, str::FromStr},
+};
+
+/// The transaction executor state that must survive a plugin restart to avoid resubmitting in-flight automations.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct TxState {
+    /// The slot the state was written at.
+    pub slot: u64,
+    pub executable_automations: HashMap<String, PersistedExecutableAutomation>,
+    pub transaction_history: HashMap<String, PersistedTransaction>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PersistedExecutableAutomation {
+    pub due_slot: u64,
+    pub simulation_failures: u32,
+    pub instruction_limit: Option<usize>,
+    #[serde(default)]
+    pub last_simulation_failure_slot: Option<u64>,
+    #[serde(default)]
+    pub max_simulation_failures: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PersistedTransaction {
+    pub slot_sent: u64,
+    pub signature: String,
+    /// The hash that identifies retries of the transaction. Missing from state written by older
+    /// plugins.
+    #[serde(default)]
+    pub retry_hash: Option<String>,
+    pub exec_offset: usize,
+    #[serde(default)]
+    pub compute_units: Option<u64>,
+    #[serde(default)]
+    pub max_simulation_failures: Option<u32>,
+}
+
+impl TxState {
+    pub async fn new(tx: &TxExecutor, slot: u64) -> Self {
+        TxState {
+            slot,
+            executable_automations: tx
+                .executable_automations
+                .read()
+                .await
+                .iter()
+                .map(|(pubkey, metadata)| {
+                    (
+                        pubkey.to_string(),
+                        PersistedExecutableAutomation {
+                            due_slot: metadata.due_slot,
+                            simulation_failures: metadata.simulation_failures,
+                            instruction_limit: metadata.instruction_limit,
+                            last_simulation_failure_slot: metadata.last_simulation_failure_slot,
+                            max_simulation_failures: metadata.max_simulation_failures,
+                        },
+                    )
+                })
+                .collect(),
+            transaction_history: tx
+                .transaction_history
+                .read()
+                .await
+                .iter()
+                .map(|(pubkey, metadata)| {
+                    (
+                        pubkey.to_string(),
+                        PersistedTransaction {
+                            slot_sent: metadata.slot_sent,
+                            signature: metadata.signature.to_string(),
+                            retry_hash: Some(metadata.retry_hash.to_string()),
+                            exec_offset: metadata.exec_offset,
+                            compute_units: metadata.compute_units,
+                            max_simulation_failures: metadata.max_simulation_failures,
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    /// Read the state from a JSON file.
+    pub fn read_from<P: AsRef<Path>>(path: P) -> std::io::Result<Self> {
+        let file = File::open(path)?;
+        Ok(serde_json::from_reader(file)?)
+    }
+
+    /// Write the state as JSON. The file is written next to the destination and then renamed over it,
+    /// so a crash mid-write leaves the previous state intact.
+    pub fn write_to<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        let tmp_path = format!("{}.tmp", path.as_ref().display());
+        let file = File::create(&tmp_path)?;
+        serde_json::to_writer(file, self)?;
+        std::fs::rename(tmp_path, path)
+    }
+
+    /// The executable automations, skipping any entry that cannot be parsed.
+    pub fn executable_automations(&self) -> HashMap<Pubkey, ExecutableAutomationMetadata> {
+        self.executable_automations
+            .iter()
+            .filter_map(|(pubkey, metadata)| {
+                Some((
+                    Pubkey::from_str(pubkey).ok()?,
+                    ExecutableAutomationMetadata {
+                        due_slot: metadata.due_slot,
+                        simulation_failures: metadata.simulation_failures,
+                        instruction_limit: metadata.instruction_limit,
+                        last_simulation_failure_slot: metadata.last_simulation_failure_slot,
+                        max_simulation_failures: metadata.max_simulation_failures,
+                        rate_limited_slot: None,
+                    },
+                ))
+            })
+            .collect()
+    }
+
+    /// The transaction history, skipping any entry that cannot be parsed.
+    pub fn transaction_history(&self) -> HashMap<Pubkey, TransactionMetadata> {
+        self.transaction_history
+            .iter()
+            .filter_map(|(pubkey, metadata)| {
+                Some((
+                    Pubkey::from_str(pubkey).ok()?,
+                    TransactionMetadata {
+                        slot_sent: metadata.slot_sent,
+                        signature: Signature::from_str(&metadata.signature).ok()?,
+                        retry_hash: metadata
+                            .retry_hash
+                            .as_ref()
+                            .and_then(|retry_hash| Hash::from_str(retry_hash).ok())
+                            .unwrap_or_default(),
+                        exec_offset: metadata.exec_offset,
+                        compute_units: metadata.compute_units,
+                        max_simulation_failures: metadata.max_simulation_failures,
+                    },
+                ))
+            })
+            .collect()
+    }
+}
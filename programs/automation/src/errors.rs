@@ -43,4 +43,64 @@ pub enum ClockworkError {
     /// Thrown if the user attempts to withdraw SOL that would put a automation below it's minimum rent threshold.
     #[msg("Withdrawing this amount would leave the automation with less than the minimum required SOL for rent exemption")]
     WithdrawalTooLarge,
+
+    /// Thrown if the payer cannot afford the network's automation creation fee.
+    #[msg("The payer has insufficient lamports to cover the automation creation fee")]
+    InsufficientCreationFee,
+
+    /// Thrown if an automation's description is longer than the max allowed length.
+    #[msg("The description must be at most 256 bytes")]
+    DescriptionTooLong,
+
+    /// Thrown if an account trigger monitors zero bytes or more than the max allowed size.
+    #[msg("Account triggers must monitor between 1 and 1024 bytes")]
+    InvalidTriggerSize,
+
+    /// Thrown if an exec would be processed after the automation has spent its lifetime budget.
+    #[msg("The automation has reached its lifetime spend cap")]
+    LifetimeSpendCapReached,
+
+    /// Thrown if the next instruction targets a program that has been paused networkwide.
+    #[msg("The target program has been paused by the network")]
+    ProgramPaused,
+
+    /// Thrown if an account trigger's monitored account has been closed.
+    #[msg("The account monitored by the trigger has been closed")]
+    TriggerAccountClosed,
+
+    /// Thrown if a kickoff would start an automation that has completed its max number of executions.
+    #[msg("The automation has reached its max number of executions")]
+    MaxExecutionsReached,
+
+    /// Thrown if an automation response carries more event data than the max allowed size.
+    #[msg("The automation response's event data must be at most 512 bytes")]
+    EventDataTooLarge,
+
+    /// Thrown if a relayer-mode automation is kicked off or executed without its relayer's signature.
+    #[msg("The automation may only be kicked off or executed by its relayer")]
+    RelayerSignatureRequired,
+
+    /// Thrown if a cron trigger's schedule cannot be parsed.
+    #[msg("The cron schedule could not be parsed")]
+    InvalidCronSchedule,
+
+    /// Thrown if a cron trigger's schedule has no firings left, so the automation would never run.
+    #[msg("The cron schedule will never fire again")]
+    CronScheduleExhausted,
+
+    /// Thrown if a batch creates no automations, more than the max allowed, or not one per account given.
+    #[msg("A batch must create between 1 and 16 automations, one for each automation account")]
+    InvalidBatchSize,
+
+    /// Thrown if an automation account given to a batch is not at the automation's address or is already in use.
+    #[msg("The account is not a free automation address")]
+    InvalidAutomationAddress,
+
+    /// Thrown if an exec would chain more dynamic next instructions than the automation's max depth.
+    #[msg("The automation has chained more next instructions than its max chain depth")]
+    ChainDepthExceeded,
+
+    /// Thrown if an automation to be migrated already decodes in the current layout.
+    #[msg("The automation is already in the current layout")]
+    AutomationAlreadyMigrated,
 }
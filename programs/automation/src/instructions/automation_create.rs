@@ -1,13 +1,16 @@
-use std::mem::size_of;
+use std::{mem::size_of, str::FromStr};
 
 use anchor_lang::{
     prelude::*,
     solana_program::system_program,
     system_program::{transfer, Transfer}
 };
+use chrono::{DateTime, NaiveDateTime, Utc};
+use clockwork_cron::Schedule;
+use clockwork_network_program::state::Config;
 use clockwork_utils::automation::{Trigger, InstructionData};
 
-use crate::state::*;
+use crate::{errors::ClockworkError, state::*};
 
 /// The minimum exec fee that may be set on a automation.
 const MINIMUM_FEE: u64 = 1000;
@@ -20,6 +23,10 @@ pub struct AutomationCreate<'info> {
     #[account()]
     pub authority: Signer<'info>,
 
+    /// The network config.
+    #[account(address = Config::pubkey())]
+    pub config: Box<Account<'info, Config>>,
+
     /// The payer for account initializations. 
     #[account(mut)]
     pub payer: Signer<'info>,
@@ -28,6 +35,10 @@ pub struct AutomationCreate<'info> {
     #[account(address = system_program::ID)]
     pub system_program: Program<'info, System>,
 
+    /// The network treasury to collect the creation fee.
+    #[account(mut, address = config.treasury)]
+    pub treasury: SystemAccount<'info>,
+
     /// The automation to be created.
     #[account(
         init,
@@ -52,24 +63,25 @@ pub struct AutomationCreate<'info> {
 pub fn handler(ctx: Context<AutomationCreate>, amount: u64, id: Vec<u8>, instructions: Vec<InstructionData>, trigger: Trigger) -> Result<()> {
     // Get accounts
     let authority = &ctx.accounts.authority;
+    let config = &ctx.accounts.config;
     let payer = &ctx.accounts.payer;
     let system_program = &ctx.accounts.system_program;
+    let treasury = &ctx.accounts.treasury;
     let automation = &mut ctx.accounts.automation;
 
+    // Verify the trigger is within its allowed bounds.
+    require!(trigger.is_valid(), ClockworkError::InvalidTriggerSize);
+
+    // Verify a cron schedule parses and will fire after the automation is created.
+    let clock = Clock::get().unwrap();
+    verify_cron_schedule(&trigger, clock.unix_timestamp)?;
+
+    // Verify the payer can cover the creation fee and the initial funding amount.
+    creation_charge(config.creation_fee, amount, payer.lamports())?;
+
     // Initialize the automation
     let bump = *ctx.bumps.get("automation").unwrap();
-    automation.authority = authority.key();
-    automation.bump = bump;
-    automation.created_at = Clock::get().unwrap().into();
-    automation.exec_context = None;
-    automation.fee = MINIMUM_FEE;
-    automation.id = id;
-    automation.instructions = instructions;
-    automation.name = String::new();
-    automation.next_instruction = None;
-    automation.paused = false;
-    automation.rate_limit = u64::MAX;
-    automation.trigger = trigger;
+    automation.set_inner(new_automation(authority.key(), bump, clock, id, instructions, trigger));
 
     // Transfer SOL from payer to the automation.
     transfer(
@@ -83,5 +95,139 @@ pub fn handler(ctx: Context<AutomationCreate>, amount: u64, id: Vec<u8>, instruc
         amount
     )?;
 
+    // Collect the creation fee into the network treasury.
+    if config.creation_fee.gt(&0) {
+        transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                Transfer {
+                    from: payer.to_account_info(),
+                    to: treasury.to_account_info(),
+                },
+            ),
+            config.creation_fee,
+        )?;
+    }
+
     Ok(())
 }
+
+/// Returns the initial state of a newly created automation.
+pub fn new_automation(
+    authority: Pubkey,
+    bump: u8,
+    clock: Clock,
+    id: Vec<u8>,
+    instructions: Vec<InstructionData>,
+    trigger: Trigger,
+) -> Automation {
+    Automation {
+        authority,
+        bump,
+        created_at: clock.into(),
+        exec_context: None,
+        fee: MINIMUM_FEE,
+        id,
+        instructions,
+        name: String::new(),
+        next_instruction: None,
+        paused: false,
+        rate_limit: u64::MAX,
+        trigger,
+        lifetime_spend_cap: None,
+        lifetime_spent: 0,
+        description: String::new(),
+        execution_count: 0,
+        max_executions: None,
+        exec_mode: ExecMode::Worker,
+        max_simulation_failures: None,
+        chain_depth: 0,
+        max_chain_depth: None,
+    }
+}
+
+/// Returns the lamports the payer is charged to create an automation funded with the amount, the network's
+/// creation fee included. Fails if the payer cannot cover the charge.
+pub fn creation_charge(creation_fee: u64, amount: u64, payer_lamports: u64) -> Result<u64> {
+    let charge = creation_fee
+        .checked_add(amount)
+        .ok_or(error!(ClockworkError::InsufficientCreationFee))?;
+    require!(payer_lamports.ge(&charge), ClockworkError::InsufficientCreationFee);
+    Ok(charge)
+}
+
+/// Verifies a cron trigger's schedule can be parsed and still has a firing after the reference timestamp,
+/// which is the timestamp the kickoff instruction measures the automation's next firing from.
+pub fn verify_cron_schedule(trigger: &Trigger, reference_timestamp: i64) -> Result<()> {
+    if let Trigger::Cron { schedule, .. } | Trigger::EpochFilteredCron { schedule, .. } = trigger {
+        let schedule =
+            Schedule::from_str(schedule).map_err(|_| error!(ClockworkError::InvalidCronSchedule))?;
+        require!(
+            schedule
+                .next_after(&DateTime::<Utc>::from_utc(
+                    NaiveDateTime::from_timestamp(reference_timestamp, 0),
+                    Utc,
+                ))
+                .is_some(),
+            ClockworkError::CronScheduleExhausted
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cron(schedule: &str) -> Trigger {
+        Trigger::Cron {
+            schedule: schedule.into(),
+            skippable: true,
+        }
+    }
+
+    // 2023-01-01 00:00:00 UTC
+    const NOW: i64 = 1_672_531_200;
+
+    #[test]
+    fn test_creation_charge() {
+        assert_eq!(creation_charge(5_000, 1_000_000, 1_005_000).unwrap(), 1_005_000);
+        assert_eq!(creation_charge(0, 1_000_000, 2_000_000).unwrap(), 1_000_000);
+        assert_eq!(creation_charge(5_000, 0, 5_000).unwrap(), 5_000);
+    }
+
+    #[test]
+    fn test_creation_charge_rejects_insufficient_funds() {
+        assert_eq!(
+            creation_charge(5_000, 1_000_000, 1_004_999).unwrap_err(),
+            error!(ClockworkError::InsufficientCreationFee)
+        );
+        assert_eq!(
+            creation_charge(5_000, u64::MAX, u64::MAX).unwrap_err(),
+            error!(ClockworkError::InsufficientCreationFee)
+        );
+    }
+
+    #[test]
+    fn test_verify_cron_schedule() {
+        assert!(verify_cron_schedule(&cron("0 * * * * * *"), NOW).is_ok());
+        assert!(verify_cron_schedule(&cron("0 0 0 1 1 * 2030"), NOW).is_ok());
+        assert!(verify_cron_schedule(&Trigger::Immediate, NOW).is_ok());
+    }
+
+    #[test]
+    fn test_verify_cron_schedule_rejects_unparseable() {
+        assert_eq!(
+            verify_cron_schedule(&cron("every tuesday"), NOW).unwrap_err(),
+            error!(ClockworkError::InvalidCronSchedule)
+        );
+    }
+
+    #[test]
+    fn test_verify_cron_schedule_rejects_past_one_shot() {
+        assert_eq!(
+            verify_cron_schedule(&cron("0 0 0 1 1 * 2020"), NOW).unwrap_err(),
+            error!(ClockworkError::CronScheduleExhausted)
+        );
+    }
+}
@@ -0,0 +1,212 @@
+use std::mem::size_of;
+
+use anchor_lang::{
+    prelude::*,
+    solana_program::system_program,
+    system_program::{
+        allocate, assign, create_account, transfer, Allocate, Assign, CreateAccount, Transfer,
+    },
+};
+use clockwork_network_program::state::Config;
+
+use crate::{
+    errors::ClockworkError,
+    instructions::automation_create::{new_automation, verify_cron_schedule},
+    state::*,
+};
+
+/// Accounts required by the `automation_create_batch` instruction.
+/// The automations to be created are passed as remaining accounts, in the same order as their params.
+#[derive(Accounts)]
+pub struct AutomationCreateBatch<'info> {
+    /// The authority (owner) of the automations.
+    #[account()]
+    pub authority: Signer<'info>,
+
+    /// The network config.
+    #[account(address = Config::pubkey())]
+    pub config: Box<Account<'info, Config>>,
+
+    /// The payer for account initializations.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The Solana system program.
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+
+    /// The network treasury to collect the creation fees.
+    #[account(mut, address = config.treasury)]
+    pub treasury: SystemAccount<'info>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, AutomationCreateBatch<'info>>,
+    automations: Vec<AutomationCreateParams>,
+) -> Result<()> {
+    // Get accounts
+    let authority = &ctx.accounts.authority;
+    let config = &ctx.accounts.config;
+    let payer = &ctx.accounts.payer;
+    let system_program = &ctx.accounts.system_program;
+    let treasury = &ctx.accounts.treasury;
+
+    // Verify there is one automation account for each set of params.
+    require!(
+        !automations.is_empty() && automations.len() <= MAX_AUTOMATION_BATCH_SIZE,
+        ClockworkError::InvalidBatchSize
+    );
+    require!(
+        ctx.remaining_accounts.len() == automations.len(),
+        ClockworkError::InvalidBatchSize
+    );
+
+    // Verify the payer can cover the creation fees and the initial funding amounts.
+    let total_cost = automations.iter().try_fold(0u64, |total, params| {
+        total
+            .checked_add(config.creation_fee)?
+            .checked_add(params.amount)
+    });
+    require!(
+        total_cost.map_or(false, |total_cost| payer.lamports() >= total_cost),
+        ClockworkError::InsufficientCreationFee
+    );
+
+    let clock = Clock::get().unwrap();
+    for (params, automation_info) in automations.into_iter().zip(ctx.remaining_accounts.iter()) {
+        // Verify the trigger is within its allowed bounds.
+        require!(params.trigger.is_valid(), ClockworkError::InvalidTriggerSize);
+        verify_cron_schedule(&params.trigger, clock.unix_timestamp)?;
+
+        // Verify the account is the automation's address.
+        let (automation_pubkey, bump) = Pubkey::find_program_address(
+            &[SEED_AUTOMATION, authority.key().as_ref(), params.id.as_slice()],
+            &crate::ID,
+        );
+        require!(
+            automation_info.key().eq(&automation_pubkey),
+            ClockworkError::InvalidAutomationAddress
+        );
+
+        // Create the automation account.
+        let space = vec![
+            8,
+            size_of::<Automation>(),
+            params.id.len(),
+            params.instructions.try_to_vec()?.len(),
+            params.trigger.try_to_vec()?.len(),
+        ]
+        .iter()
+        .sum();
+        create_automation_account(
+            automation_info,
+            payer.to_account_info(),
+            system_program.to_account_info(),
+            &[SEED_AUTOMATION, authority.key().as_ref(), params.id.as_slice(), &[bump]],
+            space,
+        )?;
+
+        // Initialize the automation.
+        let automation = new_automation(
+            authority.key(),
+            bump,
+            clock.clone(),
+            params.id,
+            params.instructions,
+            params.trigger,
+        );
+        automation.try_serialize(&mut &mut automation_info.try_borrow_mut_data()?[..])?;
+
+        // Transfer SOL from payer to the automation.
+        transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                Transfer {
+                    from: payer.to_account_info(),
+                    to: automation_info.clone(),
+                },
+            ),
+            params.amount,
+        )?;
+
+        // Collect the creation fee into the network treasury.
+        if config.creation_fee.gt(&0) {
+            transfer(
+                CpiContext::new(
+                    system_program.to_account_info(),
+                    Transfer {
+                        from: payer.to_account_info(),
+                        to: treasury.to_account_info(),
+                    },
+                ),
+                config.creation_fee,
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Creates a program-owned account at the automation's address, like anchor's `init` constraint.
+/// An address that was already sent lamports is topped up to rent exemption, allocated, and assigned instead.
+fn create_automation_account<'info>(
+    automation_info: &AccountInfo<'info>,
+    payer: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    seeds: &[&[u8]],
+    space: usize,
+) -> Result<()> {
+    require!(
+        automation_info.owner.eq(&system_program::ID) && automation_info.data_is_empty(),
+        ClockworkError::InvalidAutomationAddress
+    );
+    let rent_exempt_minimum = Rent::get()?.minimum_balance(space);
+    if automation_info.lamports() == 0 {
+        return create_account(
+            CpiContext::new_with_signer(
+                system_program,
+                CreateAccount {
+                    from: payer,
+                    to: automation_info.clone(),
+                },
+                &[seeds],
+            ),
+            rent_exempt_minimum,
+            space as u64,
+            &crate::ID,
+        );
+    }
+    let top_up = rent_exempt_minimum.saturating_sub(automation_info.lamports());
+    if top_up.gt(&0) {
+        transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer {
+                    from: payer,
+                    to: automation_info.clone(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+    allocate(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            Allocate {
+                account_to_allocate: automation_info.clone(),
+            },
+            &[seeds],
+        ),
+        space as u64,
+    )?;
+    assign(
+        CpiContext::new_with_signer(
+            system_program,
+            Assign {
+                account_to_assign: automation_info.clone(),
+            },
+            &[seeds],
+        ),
+        &crate::ID,
+    )
+}
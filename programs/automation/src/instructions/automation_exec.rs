@@ -6,14 +6,11 @@ use anchor_lang::{
     },
     AnchorDeserialize,
 };
-use clockwork_network_program::state::{Fee, Pool, Worker, WorkerAccount};
-use clockwork_utils::automation::{InstructionData, AutomationResponse, PAYER_PUBKEY};
+use clockwork_network_program::state::{Fee, Pool, ProgramStatus, Worker, WorkerAccount};
+use clockwork_utils::automation::{InstructionData, AutomationResponse};
 
 use crate::{errors::ClockworkError, state::*};
 
-/// The ID of the pool workers must be a member of to collect fees.
-const POOL_ID: u64 = 0;
-
 /// The number of lamports to reimburse the worker with after they've submitted a transaction's worth of exec instructions.
 const TRANSACTION_BASE_FEE_REIMBURSEMENT: u64 = 5_000;
 
@@ -33,8 +30,7 @@ pub struct AutomationExec<'info> {
     )]
     pub fee: Account<'info, Fee>,
 
-    /// The active worker pool.
-    #[account(address = Pool::pubkey(POOL_ID))]
+    /// The worker pool the worker collects fees through. Any pool of the network program is accepted.
     pub pool: Box<Account<'info, Pool>>,
 
     /// The signatory.
@@ -52,10 +48,17 @@ pub struct AutomationExec<'info> {
         bump = automation.bump,
         constraint = !automation.paused @ ClockworkError::AutomationPaused,
         constraint = automation.next_instruction.is_some(),
+        constraint = automation.exec_mode.authorizes(signatory.key) @ ClockworkError::RelayerSignatureRequired,
         constraint = automation.exec_context.is_some()
     )]
     pub automation: Box<Account<'info, Automation>>,
 
+    /// CHECK: The networkwide status of the program targeted by the next instruction. This account may not exist.
+    #[account(
+        address = ProgramStatus::pubkey(automation.next_instruction.as_ref().unwrap().program_id)
+    )]
+    pub program_status: UncheckedAccount<'info>,
+
     /// The worker.
     #[account(address = worker.pubkey())]
     pub worker: Account<'info, Worker>,
@@ -67,11 +70,27 @@ pub fn handler(ctx: Context<AutomationExec>) -> Result<()> {
     let pool = &ctx.accounts.pool;
     let signatory = &mut ctx.accounts.signatory;
     let automation = &mut ctx.accounts.automation;
+    let program_status = &ctx.accounts.program_status;
     let worker = &ctx.accounts.worker;
 
+    // Refuse to invoke a program that has been paused networkwide.
+    if program_status.owner.eq(&clockwork_network_program::ID) {
+        let data = program_status.try_borrow_data()?;
+        let status = ProgramStatus::try_deserialize(&mut &data[..])?;
+        require!(!status.paused, ClockworkError::ProgramPaused);
+    }
+
+    // If the automation has spent its lifetime budget, refuse to exec.
+    require!(
+        !automation.is_spend_capped(),
+        ClockworkError::LifetimeSpendCapReached
+    );
+
     // If the rate limit has been met, exit early.
-    if automation.exec_context.unwrap().last_exec_at == Clock::get().unwrap().slot
-        && automation.exec_context.unwrap().execs_since_slot >= automation.rate_limit
+    if automation
+        .exec_context
+        .unwrap()
+        .is_rate_limited(Clock::get().unwrap().slot, automation.rate_limit)
     {
         return Err(ClockworkError::RateLimitExeceeded.into());
     }
@@ -85,26 +104,15 @@ pub fn handler(ctx: Context<AutomationExec>) -> Result<()> {
     let instruction = next_instruction.as_ref().unwrap();
 
     // Inject the signatory's pubkey for the Clockwork payer ID.
-    let normalized_accounts: &mut Vec<AccountMeta> = &mut vec![];
-    instruction.accounts.iter().for_each(|acc| {
-        let acc_pubkey = if acc.pubkey == PAYER_PUBKEY {
-            signatory.key()
-        } else {
-            acc.pubkey
-        };
-        normalized_accounts.push(AccountMeta {
-            pubkey: acc_pubkey,
-            is_signer: acc.is_signer,
-            is_writable: acc.is_writable,
-        });
-    });
+    let normalized_accounts = instruction.exec_accounts(signatory.key());
 
     // Invoke the provided instruction.
+    // The automation PDA signs the inner instruction, so target programs may require it as a signer authority.
     invoke_signed(
         &Instruction {
             program_id: instruction.program_id,
             data: instruction.data.clone(),
-            accounts: normalized_accounts.to_vec(),
+            accounts: normalized_accounts,
         },
         ctx.remaining_accounts,
         &[&[
@@ -122,25 +130,36 @@ pub fn handler(ctx: Context<AutomationExec>) -> Result<()> {
     let automation_response: Option<AutomationResponse> = match get_return_data() {
         None => None,
         Some((program_id, return_data)) => {
-            require!(
-                program_id.eq(&instruction.program_id),
-                ClockworkError::InvalidAutomationResponse
-            );
-            AutomationResponse::try_from_slice(return_data.as_slice()).ok()
+            parse_automation_response(instruction.program_id, program_id, &return_data)?
         }
     };
 
     // Grab the next instruction from the automation response.
     let mut next_instruction = None;
     if let Some(automation_response) = automation_response {
+        // Re-emit the response's custom payload, if any.
+        if let Some(event) = executed_event(automation.key(), &automation_response)? {
+            emit!(event);
+        }
+
         next_instruction = automation_response.next_instruction;
 
+        // Count the dynamic next instruction against the automation's max chain depth.
+        if next_instruction.is_some() {
+            automation.chain_depth = automation.chain_depth.saturating_add(1);
+            require!(
+                !automation.exceeds_chain_depth(),
+                ClockworkError::ChainDepthExceeded
+            );
+        }
+
         // Update the trigger.
         if let Some(trigger) = automation_response.trigger {
             require!(
-                std::mem::discriminant(&automation.trigger) == std::mem::discriminant(&trigger),
+                automation.trigger.is_same_kind(&trigger),
                 ClockworkError::InvalidTriggerVariant
             );
+            require!(trigger.is_valid(), ClockworkError::InvalidTriggerSize);
             automation.trigger = trigger;
         }
     }
@@ -157,29 +176,27 @@ pub fn handler(ctx: Context<AutomationExec>) -> Result<()> {
     // Update the next instruction.
     automation.next_instruction = next_instruction;
 
+    if automation.next_instruction.is_none() {
+        // Count the completed execution, and deactivate the trigger once the max has been reached.
+        automation.execution_count = automation.execution_count.saturating_add(1);
+        if automation.is_execution_capped() {
+            automation.paused = true;
+        }
+
+        // A timestamp trigger has fired for good once its instructions are done, so pause the automation.
+        if let Trigger::Timestamp { .. } = automation.trigger {
+            automation.paused = true;
+        }
+    }
+
     // Update the exec context.
     let current_slot = Clock::get().unwrap().slot;
-    automation.exec_context = Some(ExecContext {
-        exec_index,
-        execs_since_reimbursement: automation
+    automation.exec_context = Some(
+        automation
             .exec_context
             .unwrap()
-            .execs_since_reimbursement
-            .checked_add(1)
-            .unwrap(),
-        execs_since_slot: if current_slot == automation.exec_context.unwrap().last_exec_at {
-            automation
-                .exec_context
-                .unwrap()
-                .execs_since_slot
-                .checked_add(1)
-                .unwrap()
-        } else {
-            1
-        },
-        last_exec_at: current_slot,
-        ..automation.exec_context.unwrap()
-    });
+            .advance(current_slot, exec_index),
+    );
 
     // Realloc memory for the automation account.
     automation.realloc()?;
@@ -187,6 +204,7 @@ pub fn handler(ctx: Context<AutomationExec>) -> Result<()> {
     // Reimbursement signatory for lamports paid during inner ix.
     let signatory_lamports_post = signatory.lamports();
     let signatory_reimbursement = signatory_lamports_pre.saturating_sub(signatory_lamports_post);
+    let mut lamports_spent = signatory_reimbursement;
     if signatory_reimbursement.gt(&0) {
         **automation.to_account_info().try_borrow_mut_lamports()? = automation
             .to_account_info()
@@ -212,6 +230,7 @@ pub fn handler(ctx: Context<AutomationExec>) -> Result<()> {
             .lamports()
             .checked_add(automation.fee)
             .unwrap();
+        lamports_spent = lamports_spent.checked_add(automation.fee).unwrap();
     }
 
     // If the automation has no more work or the number of execs since the last payout has reached the rate limit,
@@ -230,6 +249,9 @@ pub fn handler(ctx: Context<AutomationExec>) -> Result<()> {
             .lamports()
             .checked_add(TRANSACTION_BASE_FEE_REIMBURSEMENT)
             .unwrap();
+        lamports_spent = lamports_spent
+            .checked_add(TRANSACTION_BASE_FEE_REIMBURSEMENT)
+            .unwrap();
 
         // Update the exec context to mark that a reimbursement happened this slot.
         automation.exec_context = Some(ExecContext {
@@ -238,5 +260,104 @@ pub fn handler(ctx: Context<AutomationExec>) -> Result<()> {
         });
     }
 
+    // Record the lamports this exec cost the automation.
+    automation.record_spend(lamports_spent);
+
     Ok(())
 }
+
+/// Parses the return data of the executed instruction into an automation response.
+/// Return data that does not decode is ignored, as if the instruction returned no response.
+fn parse_automation_response(
+    instruction_program_id: Pubkey,
+    program_id: Pubkey,
+    return_data: &[u8],
+) -> Result<Option<AutomationResponse>> {
+    require!(
+        program_id.eq(&instruction_program_id),
+        ClockworkError::InvalidAutomationResponse
+    );
+    Ok(AutomationResponse::try_from_slice(return_data).ok())
+}
+
+/// Builds the event re-emitting the custom payload of an automation response, if it has one.
+fn executed_event(
+    automation: Pubkey,
+    automation_response: &AutomationResponse,
+) -> Result<Option<AutomationExecuted>> {
+    match &automation_response.event_data {
+        None => Ok(None),
+        Some(data) => {
+            require!(
+                data.len() <= AUTOMATION_RESPONSE_EVENT_DATA_MAX_SIZE,
+                ClockworkError::EventDataTooLarge
+            );
+            Ok(Some(AutomationExecuted {
+                automation,
+                data: data.clone(),
+            }))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::{Discriminator, Event};
+
+    #[test]
+    fn test_executed_event_carries_payload() {
+        let automation = Pubkey::new_unique();
+        let automation_response = AutomationResponse {
+            event_data: Some(b"swap:42".to_vec()),
+            ..AutomationResponse::default()
+        };
+        let event = executed_event(automation, &automation_response)
+            .unwrap()
+            .unwrap();
+        assert_eq!(event.automation, automation);
+        assert_eq!(event.data, b"swap:42".to_vec());
+
+        // Indexers decode the event from its discriminator-prefixed log data.
+        let log_data = event.data();
+        assert_eq!(&log_data[..8], &AutomationExecuted::discriminator());
+        let decoded = AutomationExecuted::try_from_slice(&log_data[8..]).unwrap();
+        assert_eq!(decoded.data, b"swap:42".to_vec());
+    }
+
+    #[test]
+    fn test_executed_event_bounds_payload() {
+        let automation_response = AutomationResponse {
+            event_data: Some(vec![0; AUTOMATION_RESPONSE_EVENT_DATA_MAX_SIZE + 1]),
+            ..AutomationResponse::default()
+        };
+        assert!(executed_event(Pubkey::new_unique(), &automation_response).is_err());
+        assert!(executed_event(Pubkey::new_unique(), &AutomationResponse::default())
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_parse_automation_response() {
+        let program_id = Pubkey::new_unique();
+        let return_data = AutomationResponse::stop().try_to_vec().unwrap();
+        let automation_response =
+            parse_automation_response(program_id, program_id, &return_data).unwrap().unwrap();
+        assert_eq!(automation_response.trigger, AutomationResponse::stop().trigger);
+
+        // Responses from another program are rejected.
+        assert_eq!(
+            parse_automation_response(program_id, Pubkey::new_unique(), &return_data)
+                .unwrap_err(),
+            error!(ClockworkError::InvalidAutomationResponse)
+        );
+    }
+
+    #[test]
+    fn test_parse_automation_response_ignores_undecodable_data() {
+        let program_id = Pubkey::new_unique();
+        assert!(parse_automation_response(program_id, program_id, &[2, 0xff])
+            .unwrap()
+            .is_none());
+    }
+}
@@ -8,7 +8,7 @@ use anchor_lang::prelude::*;
 use chrono::{DateTime, NaiveDateTime, Utc};
 use clockwork_cron::Schedule;
 use clockwork_network_program::state::{Worker, WorkerAccount};
-use clockwork_utils::automation::Trigger;
+use clockwork_utils::automation::{account_trigger_slice, Trigger};
 
 use crate::{errors::*, state::*};
 
@@ -30,6 +30,7 @@ pub struct AutomationKickoff<'info> {
         bump = automation.bump,
         constraint = !automation.paused @ ClockworkError::AutomationPaused,
         constraint = automation.next_instruction.is_none() @ ClockworkError::AutomationBusy,
+        constraint = automation.exec_mode.authorizes(signatory.key) @ ClockworkError::RelayerSignatureRequired,
     )]
     pub automation: Box<Account<'info, Automation>>,
 
@@ -43,6 +44,18 @@ pub fn handler(ctx: Context<AutomationKickoff>) -> Result<()> {
     let automation = &mut ctx.accounts.automation;
     let clock = Clock::get().unwrap();
 
+    // Verify the automation has not completed its max number of executions.
+    require!(
+        !automation.is_execution_capped(),
+        ClockworkError::MaxExecutionsReached
+    );
+
+    // Verify the trigger may fire in the current epoch.
+    require!(
+        automation.trigger.is_epoch_allowed(clock.epoch),
+        ClockworkError::TriggerNotActive
+    );
+
     match automation.trigger.clone() {
         Trigger::Account {
             address,
@@ -53,53 +66,26 @@ pub fn handler(ctx: Context<AutomationKickoff>) -> Result<()> {
             match ctx.remaining_accounts.first() {
                 None => {}
                 Some(account_info) => {
-                    // Verify the remaining account is the account this automation is listening for.
-                    require!(
-                        address.eq(account_info.key),
-                        ClockworkError::TriggerNotActive
-                    );
-
-                    // Begin computing the data hash of this account.
-                    let mut hasher = DefaultHasher::new();
-                    let data = &account_info.try_borrow_data().unwrap();
-                    let offset = offset as usize;
-                    let range_end = offset.checked_add(size as usize).unwrap() as usize;
-                    if data.len().gt(&range_end) {
-                        data[offset..range_end].hash(&mut hasher);
-                    } else {
-                        data[offset..].hash(&mut hasher)
-                    }
-                    let data_hash = hasher.finish();
-
-                    // Verify the data hash is different than the prior data hash.
-                    if let Some(exec_context) = automation.exec_context {
-                        match exec_context.trigger_context {
-                            TriggerContext::Account {
-                                data_hash: prior_data_hash,
-                            } => {
-                                require!(
-                                    data_hash.ne(&prior_data_hash),
-                                    ClockworkError::TriggerNotActive
-                                )
-                            }
-                            _ => return Err(ClockworkError::InvalidAutomationState.into()),
-                        }
-                    }
+                    verify_trigger_account(account_info, address)?;
 
                     // Set a new exec context with the new data hash and slot number.
-                    automation.exec_context = Some(ExecContext {
-                        exec_index: 0,
-                        execs_since_reimbursement: 0,
-                        execs_since_slot: 0,
-                        last_exec_at: clock.slot,
-                        trigger_context: TriggerContext::Account { data_hash },
-                    })
+                    let data = &account_info.try_borrow_data().unwrap();
+                    automation.exec_context = Some(account_kickoff(
+                        automation.exec_context,
+                        account_trigger_slice(data, offset, size),
+                        clock.slot,
+                    )?);
                 }
             }
         }
         Trigger::Cron {
             schedule,
             skippable,
+        }
+        | Trigger::EpochFilteredCron {
+            schedule,
+            skippable,
+            ..
         } => {
             // Get the reference timestamp for calculating the automation's scheduled target timestamp.
             let reference_timestamp = match automation.exec_context.clone() {
@@ -149,12 +135,101 @@ pub fn handler(ctx: Context<AutomationKickoff>) -> Result<()> {
                 trigger_context: TriggerContext::Immediate,
             });
         }
+        Trigger::Slot { target_slot } | Trigger::EpochFilteredSlot { target_slot, .. } => {
+            // Set the exec context.
+            automation.exec_context = Some(slot_kickoff(
+                automation.exec_context,
+                target_slot,
+                clock.slot,
+            )?);
+        }
+        Trigger::Epoch { skippable } => {
+            // Get the epoch the automation last kicked off for.
+            let reference_epoch = match automation.exec_context.clone() {
+                None => automation.created_at.epoch,
+                Some(exec_context) => match exec_context.trigger_context {
+                    TriggerContext::Epoch { started_at } => started_at,
+                    _ => return Err(ClockworkError::InvalidAutomationState.into()),
+                },
+            };
+
+            // Verify a new epoch has begun since the reference epoch.
+            let threshold_epoch = reference_epoch.checked_add(1).unwrap();
+            require!(
+                clock.epoch.ge(&threshold_epoch),
+                ClockworkError::TriggerNotActive
+            );
+
+            // If the trigger is marked as skippable, jump ahead to the current epoch.
+            // Otherwise, the exec context must iterate through each missed epoch.
+            let started_at = if skippable {
+                clock.epoch
+            } else {
+                threshold_epoch
+            };
+
+            // Set the exec context.
+            automation.exec_context = Some(ExecContext {
+                exec_index: 0,
+                execs_since_reimbursement: 0,
+                execs_since_slot: 0,
+                last_exec_at: clock.slot,
+                trigger_context: TriggerContext::Epoch { started_at },
+            });
+        }
+        Trigger::Timestamp { unix_ts } => {
+            // Timestamp triggers fire exactly once. Late firings still execute once the moment has passed.
+            require!(
+                automation.exec_context.is_none(),
+                ClockworkError::InvalidAutomationState
+            );
+            require!(
+                clock.unix_timestamp.ge(&unix_ts),
+                ClockworkError::TriggerNotActive
+            );
+
+            // Set the exec context.
+            automation.exec_context = Some(ExecContext {
+                exec_index: 0,
+                execs_since_reimbursement: 0,
+                execs_since_slot: 0,
+                last_exec_at: clock.slot,
+                trigger_context: TriggerContext::Timestamp {
+                    started_at: clock.unix_timestamp,
+                },
+            });
+        }
+        Trigger::AccountValue {
+            address,
+            offset,
+            size,
+            equals,
+            changed_from,
+        } => {
+            // Value conditions cannot be checked without the monitored account.
+            let account_info = ctx
+                .remaining_accounts
+                .first()
+                .ok_or(ClockworkError::TriggerNotActive)?;
+            verify_trigger_account(account_info, address)?;
+
+            // Verify the monitored slice meets the trigger's value conditions.
+            let data = &account_info.try_borrow_data().unwrap();
+            let slice = account_trigger_slice(data, offset, size);
+            verify_account_value(slice, equals, changed_from)?;
+
+            // Set a new exec context with the new data hash and slot number.
+            automation.exec_context =
+                Some(account_kickoff(automation.exec_context, slice, clock.slot)?);
+        }
     }
 
     // If we make it here, the trigger is active. Update the next instruction and be done.
+    // The new cycle starts with an empty chain of dynamic next instructions.
     if let Some(kickoff_instruction) = automation.instructions.first() {
         automation.next_instruction = Some(kickoff_instruction.clone());
     }
+    automation.chain_depth = 0;
 
     // Realloc the automation account
     automation.realloc()?;
@@ -172,3 +247,154 @@ fn next_timestamp(after: i64, schedule: String) -> Option<i64> {
         .take()
         .map(|datetime| datetime.timestamp())
 }
+
+/// Verifies the remaining account is the open account an account trigger is listening for.
+fn verify_trigger_account(account_info: &AccountInfo, address: Pubkey) -> Result<()> {
+    require!(
+        address.eq(account_info.key),
+        ClockworkError::TriggerNotActive
+    );
+
+    // A closed account's zeroed data is not a change to the monitored value.
+    require!(
+        account_info.lamports() > 0,
+        ClockworkError::TriggerAccountClosed
+    );
+    Ok(())
+}
+
+/// Returns the exec context an account trigger starts when the monitored slice has changed
+/// since the prior kickoff.
+fn account_kickoff(
+    exec_context: Option<ExecContext>,
+    slice: &[u8],
+    slot: u64,
+) -> Result<ExecContext> {
+    let mut hasher = DefaultHasher::new();
+    slice.hash(&mut hasher);
+    let data_hash = hasher.finish();
+
+    // Verify the data hash is different than the prior data hash.
+    if let Some(exec_context) = exec_context {
+        match exec_context.trigger_context {
+            TriggerContext::Account {
+                data_hash: prior_data_hash,
+            } => {
+                require!(
+                    data_hash.ne(&prior_data_hash),
+                    ClockworkError::TriggerNotActive
+                )
+            }
+            _ => return Err(ClockworkError::InvalidAutomationState.into()),
+        }
+    }
+
+    Ok(ExecContext {
+        exec_index: 0,
+        execs_since_reimbursement: 0,
+        execs_since_slot: 0,
+        last_exec_at: slot,
+        trigger_context: TriggerContext::Account { data_hash },
+    })
+}
+
+/// Verifies the monitored slice meets an account value trigger's conditions.
+/// Only the current value can be checked here. Workers detect the transition itself.
+fn verify_account_value(
+    slice: &[u8],
+    equals: Option<Vec<u8>>,
+    changed_from: Option<Vec<u8>>,
+) -> Result<()> {
+    if let Some(equals) = equals {
+        require!(slice.eq(equals.as_slice()), ClockworkError::TriggerNotActive);
+    }
+    if let Some(changed_from) = changed_from {
+        require!(
+            slice.ne(changed_from.as_slice()),
+            ClockworkError::TriggerNotActive
+        );
+    }
+    Ok(())
+}
+
+/// Returns the exec context a slot trigger starts at the current slot. Slot triggers fire exactly once,
+/// and late firings still execute since slots are monotonic.
+fn slot_kickoff(
+    exec_context: Option<ExecContext>,
+    target_slot: u64,
+    slot: u64,
+) -> Result<ExecContext> {
+    require!(exec_context.is_none(), ClockworkError::InvalidAutomationState);
+    require!(slot.ge(&target_slot), ClockworkError::TriggerNotActive);
+    Ok(ExecContext {
+        exec_index: 0,
+        execs_since_reimbursement: 0,
+        execs_since_slot: 0,
+        last_exec_at: slot,
+        trigger_context: TriggerContext::Slot { started_at: slot },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slot_kickoff_fires_at_target_slot() {
+        let exec_context = slot_kickoff(None, 1_000, 1_000).unwrap();
+        assert_eq!(
+            exec_context.trigger_context,
+            TriggerContext::Slot { started_at: 1_000 }
+        );
+        assert_eq!(exec_context.last_exec_at, 1_000);
+    }
+
+    #[test]
+    fn test_slot_kickoff_fires_late() {
+        let exec_context = slot_kickoff(None, 1_000, 1_250).unwrap();
+        assert_eq!(
+            exec_context.trigger_context,
+            TriggerContext::Slot { started_at: 1_250 }
+        );
+    }
+
+    #[test]
+    fn test_slot_kickoff_rejects_early_and_repeat_firings() {
+        assert_eq!(
+            slot_kickoff(None, 1_000, 999).unwrap_err(),
+            error!(ClockworkError::TriggerNotActive)
+        );
+        let exec_context = slot_kickoff(None, 1_000, 1_000).unwrap();
+        assert_eq!(
+            slot_kickoff(Some(exec_context), 1_000, 1_001).unwrap_err(),
+            error!(ClockworkError::InvalidAutomationState)
+        );
+    }
+
+    #[test]
+    fn test_account_kickoff_requires_changed_data() {
+        let exec_context = account_kickoff(None, &[1, 2], 100).unwrap();
+        assert_eq!(exec_context.last_exec_at, 100);
+        assert_eq!(
+            account_kickoff(Some(exec_context), &[1, 2], 101).unwrap_err(),
+            error!(ClockworkError::TriggerNotActive)
+        );
+        let exec_context = account_kickoff(Some(exec_context), &[1, 3], 101).unwrap();
+        assert_eq!(exec_context.last_exec_at, 101);
+    }
+
+    #[test]
+    fn test_verify_account_value() {
+        assert!(verify_account_value(&[1, 2], None, None).is_ok());
+        assert!(verify_account_value(&[1, 2], Some(vec![1, 2]), None).is_ok());
+        assert_eq!(
+            verify_account_value(&[1, 3], Some(vec![1, 2]), None).unwrap_err(),
+            error!(ClockworkError::TriggerNotActive)
+        );
+        assert!(verify_account_value(&[1, 2], None, Some(vec![0, 0])).is_ok());
+        assert_eq!(
+            verify_account_value(&[0, 0], None, Some(vec![0, 0])).unwrap_err(),
+            error!(ClockworkError::TriggerNotActive)
+        );
+    }
+}
@@ -0,0 +1,73 @@
+use anchor_lang::{
+    error::ErrorCode,
+    prelude::*,
+    solana_program::system_program,
+    system_program::{transfer, Transfer},
+};
+
+use crate::{errors::ClockworkError, state::*};
+
+/// Accounts required by the `automation_migrate` instruction.
+#[derive(Accounts)]
+pub struct AutomationMigrate<'info> {
+    /// The payer for the rent of the larger account.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The Solana system program.
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+
+    /// The automation to be migrated.
+    /// CHECK: A legacy automation cannot be loaded as an `Automation`, so the handler decodes it.
+    #[account(mut, owner = crate::ID)]
+    pub automation: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<AutomationMigrate>) -> Result<()> {
+    // Get accounts
+    let automation = &ctx.accounts.automation;
+    let payer = &ctx.accounts.payer;
+    let system_program = &ctx.accounts.system_program;
+
+    // Verify the automation is in the legacy layout and at its address.
+    let legacy = {
+        let data = automation.try_borrow_data()?;
+        require!(
+            Automation::try_deserialize(&mut &data[..]).is_err(),
+            ClockworkError::AutomationAlreadyMigrated
+        );
+        LegacyAutomation::try_from_account_data(&data)?
+    };
+    require_keys_eq!(
+        Automation::pubkey(legacy.authority, legacy.id.clone()),
+        automation.key(),
+        ErrorCode::ConstraintSeeds
+    );
+
+    // Top up the rent for the larger account and grow it.
+    let migrated = legacy.migrate();
+    let data_len = 8 + migrated.try_to_vec()?.len();
+    let rent_due = Rent::get()?
+        .minimum_balance(data_len)
+        .saturating_sub(automation.lamports());
+    if rent_due.gt(&0) {
+        transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                Transfer {
+                    from: payer.to_account_info(),
+                    to: automation.to_account_info(),
+                },
+            ),
+            rent_due,
+        )?;
+    }
+    automation.to_account_info().realloc(data_len, false)?;
+
+    // Rewrite the automation in the current layout.
+    let mut data = automation.try_borrow_mut_data()?;
+    migrated.try_serialize(&mut &mut data[..])?;
+
+    Ok(())
+}
@@ -25,8 +25,9 @@ pub fn handler(ctx: Context<AutomationReset>) -> Result<()> {
     // Get accounts
     let automation = &mut ctx.accounts.automation;
 
-    // Reset the next instruction.
+    // Reset the next instruction and the chain that led to it.
     automation.next_instruction = None;
+    automation.chain_depth = 0;
 
     Ok(())
 }
@@ -48,6 +48,21 @@ pub fn handler(ctx: Context<AutomationResume>) -> Result<()> {
                 TriggerContext::Immediate => {
                     // Nothing to do
                 }
+                TriggerContext::Slot { started_at: _ } => {
+                    // Nothing to do
+                }
+                TriggerContext::Timestamp { started_at: _ } => {
+                    // Nothing to do
+                }
+                TriggerContext::Epoch { started_at: _ } => {
+                    // Jump ahead to the current epoch
+                    automation.exec_context = Some(ExecContext {
+                        trigger_context: TriggerContext::Epoch {
+                            started_at: Clock::get().unwrap().epoch,
+                        },
+                        ..exec_context
+                    });
+                }
             }
         }
     }
@@ -1,4 +1,6 @@
-use crate::{errors::ClockworkError, state::*};
+use crate::{
+    errors::ClockworkError, instructions::automation_create::verify_cron_schedule, state::*,
+};
 
 use anchor_lang::{
     prelude::*,
@@ -38,6 +40,16 @@ pub fn handler(ctx: Context<AutomationUpdate>, settings: AutomationSettings) ->
     let automation = &mut ctx.accounts.automation;
     let system_program = &ctx.accounts.system_program;
 
+    // If provided, update the description.
+    if let Some(description) = settings.description {
+        automation.set_description(description)?;
+    }
+
+    // If provided, update who may submit the automation's transactions.
+    if let Some(exec_mode) = settings.exec_mode {
+        automation.exec_mode = exec_mode;
+    }
+
     // Update the automation.
     if let Some(fee) = settings.fee {
         automation.fee = fee;
@@ -48,6 +60,26 @@ pub fn handler(ctx: Context<AutomationUpdate>, settings: AutomationSettings) ->
         automation.instructions = instructions;
     }
 
+    // If provided, update or remove the lifetime spend cap.
+    if let Some(lifetime_spend_cap) = settings.lifetime_spend_cap {
+        automation.lifetime_spend_cap = lifetime_spend_cap;
+    }
+
+    // If provided, update the max number of dynamic next instructions chained per kickoff.
+    if let Some(max_chain_depth) = settings.max_chain_depth {
+        automation.max_chain_depth = Some(max_chain_depth);
+    }
+
+    // If provided, update or remove the max number of executions.
+    if let Some(max_executions) = settings.max_executions {
+        automation.max_executions = max_executions;
+    }
+
+    // If provided, update the number of failed simulations workers tolerate, up to the max.
+    if let Some(max_simulation_failures) = settings.max_simulation_failures {
+        automation.set_max_simulation_failures(max_simulation_failures);
+    }
+
     // If provided, update the rate limit.
     if let Some(rate_limit) = settings.rate_limit {
         automation.rate_limit = rate_limit;
@@ -57,9 +89,20 @@ pub fn handler(ctx: Context<AutomationUpdate>, settings: AutomationSettings) ->
     if let Some(trigger) = settings.trigger {
         // Require the automation is not in the middle of processing.
         require!(
-            std::mem::discriminant(&automation.trigger) == std::mem::discriminant(&trigger),
+            automation.trigger.is_same_kind(&trigger),
             ClockworkError::InvalidTriggerVariant
         );
+        require!(trigger.is_valid(), ClockworkError::InvalidTriggerSize);
+
+        // Verify a new cron schedule will fire after the moment the kickoff measures from.
+        let reference_timestamp = match automation.exec_context {
+            Some(ExecContext {
+                trigger_context: TriggerContext::Cron { started_at },
+                ..
+            }) => started_at,
+            _ => automation.created_at.unix_timestamp,
+        };
+        verify_cron_schedule(&trigger, reference_timestamp)?;
         automation.trigger = trigger;
     }
 
@@ -1,7 +1,9 @@
 pub mod automation_create;
+pub mod automation_create_batch;
 pub mod automation_delete;
 pub mod automation_exec;
 pub mod automation_kickoff;
+pub mod automation_migrate;
 pub mod automation_pause;
 pub mod automation_reset;
 pub mod automation_resume;
@@ -10,9 +12,11 @@ pub mod automation_withdraw;
 pub mod get_crate_info;
 
 pub use automation_create::*;
+pub use automation_create_batch::*;
 pub use automation_delete::*;
 pub use automation_exec::*;
 pub use automation_kickoff::*;
+pub use automation_migrate::*;
 pub use automation_pause::*;
 pub use automation_reset::*;
 pub use automation_resume::*;
@@ -45,6 +45,14 @@ pub mod automation_program {
         automation_create::handler(ctx, amount, id, instructions, trigger)
     }
 
+    /// Creates a batch of transaction automations. The automation accounts are passed as remaining accounts.
+    pub fn automation_create_batch<'info>(
+        ctx: Context<'_, '_, '_, 'info, AutomationCreateBatch<'info>>,
+        automations: Vec<AutomationCreateParams>,
+    ) -> Result<()> {
+        automation_create_batch::handler(ctx, automations)
+    }
+
     /// Closes an existing automation account and returns the lamports to the owner.
     pub fn automation_delete(ctx: Context<AutomationDelete>) -> Result<()> {
         automation_delete::handler(ctx)
@@ -55,6 +63,11 @@ pub mod automation_program {
         automation_kickoff::handler(ctx)
     }
 
+    /// Migrates an automation created before the fields following its trigger were added.
+    pub fn automation_migrate(ctx: Context<AutomationMigrate>) -> Result<()> {
+        automation_migrate::handler(ctx)
+    }
+
     /// Pauses an active automation.
     pub fn automation_pause(ctx: Context<AutomationPause>) -> Result<()> {
         automation_pause::handler(ctx)
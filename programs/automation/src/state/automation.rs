@@ -1,9 +1,20 @@
-use anchor_lang::{prelude::*, AnchorDeserialize, AnchorSerialize};
+use anchor_lang::{error::ErrorCode, prelude::*, AnchorDeserialize, AnchorSerialize, Discriminator};
 use clockwork_macros::TryFromData;
-use clockwork_utils::automation::{ClockData, InstructionData, Trigger};
+use clockwork_utils::automation::{ClockData, ExecContext, InstructionData, Trigger};
+
+use crate::errors::ClockworkError;
 
 pub const SEED_AUTOMATION: &[u8] = b"automation";
 
+/// The maximum length of an automation's description, in bytes.
+pub const MAX_DESCRIPTION_LEN: usize = 256;
+
+/// The maximum number of failed simulations an automation may ask workers to tolerate.
+pub const MAX_SIMULATION_FAILURES: u32 = 64;
+
+/// The maximum number of automations that may be created by one `automation_create_batch` instruction.
+pub const MAX_AUTOMATION_BATCH_SIZE: usize = 16;
+
 /// Tracks the current state of a transaction automation on Solana.
 #[account]
 #[derive(Debug, TryFromData)]
@@ -32,6 +43,24 @@ pub struct Automation {
     pub rate_limit: u64,
     /// The triggering event to kickoff a automation.
     pub trigger: Trigger,
+    /// The maximum number of lamports the automation may spend over its lifetime, if any.
+    pub lifetime_spend_cap: Option<u64>,
+    /// The number of lamports the automation has spent on fees and reimbursements over its lifetime.
+    pub lifetime_spent: u64,
+    /// A free-text description of what the automation does, given by the authority.
+    pub description: String,
+    /// The number of times the automation has run its instructions to completion.
+    pub execution_count: u64,
+    /// The maximum number of times the automation may run its instructions to completion, if any.
+    pub max_executions: Option<u64>,
+    /// Who may submit the automation's kickoffs and execs.
+    pub exec_mode: ExecMode,
+    /// The number of failed simulations after which workers stop retrying the automation, if not the workers' default.
+    pub max_simulation_failures: Option<u32>,
+    /// The number of dynamic next instructions chained since the automation was last kicked off.
+    pub chain_depth: u64,
+    /// The maximum number of dynamic next instructions the automation may chain per kickoff, if any.
+    pub max_chain_depth: Option<u64>,
 }
 
 impl Automation {
@@ -43,6 +72,36 @@ impl Automation {
         )
         .0
     }
+
+    /// Returns true if the automation has spent its entire lifetime budget.
+    pub fn is_spend_capped(&self) -> bool {
+        match self.lifetime_spend_cap {
+            None => false,
+            Some(lifetime_spend_cap) => self.lifetime_spent >= lifetime_spend_cap,
+        }
+    }
+
+    /// Records the lamports an exec cost the automation.
+    /// The cap is checked before each exec, so the final exec may overshoot it by at most its own cost.
+    pub fn record_spend(&mut self, lamports: u64) {
+        self.lifetime_spent = self.lifetime_spent.saturating_add(lamports);
+    }
+
+    /// Sets the number of failed simulations after which workers stop retrying the automation,
+    /// clamped to `MAX_SIMULATION_FAILURES`.
+    pub fn set_max_simulation_failures(&mut self, max_simulation_failures: u32) {
+        self.max_simulation_failures = Some(max_simulation_failures.min(MAX_SIMULATION_FAILURES));
+    }
+
+    /// Sets the automation's description, rejecting descriptions longer than `MAX_DESCRIPTION_LEN`.
+    pub fn set_description(&mut self, description: String) -> Result<()> {
+        require!(
+            description.len() <= MAX_DESCRIPTION_LEN,
+            ClockworkError::DescriptionTooLong
+        );
+        self.description = description;
+        Ok(())
+    }
 }
 
 impl PartialEq for Automation {
@@ -53,11 +112,73 @@ impl PartialEq for Automation {
 
 impl Eq for Automation {}
 
+/// The layout of automations created before the fields following `trigger` were added.
+#[derive(AnchorDeserialize, Debug)]
+pub struct LegacyAutomation {
+    pub authority: Pubkey,
+    pub bump: u8,
+    pub created_at: ClockData,
+    pub exec_context: Option<ExecContext>,
+    pub fee: u64,
+    pub id: Vec<u8>,
+    pub instructions: Vec<InstructionData>,
+    pub name: String,
+    pub next_instruction: Option<InstructionData>,
+    pub paused: bool,
+    pub rate_limit: u64,
+    pub trigger: Trigger,
+}
+
+impl LegacyAutomation {
+    /// Decodes the legacy fields that prefix the data of every automation account.
+    pub fn try_from_account_data(data: &[u8]) -> Result<Self> {
+        require!(
+            data.len().ge(&8) && data[..8].eq(&Automation::discriminator()),
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        Self::deserialize(&mut &data[8..]).map_err(|_| error!(ErrorCode::AccountDidNotDeserialize))
+    }
+
+    /// Returns the automation in the current layout, with the fields added since set to their
+    /// defaults.
+    pub fn migrate(self) -> Automation {
+        Automation {
+            authority: self.authority,
+            bump: self.bump,
+            created_at: self.created_at,
+            exec_context: self.exec_context,
+            fee: self.fee,
+            id: self.id,
+            instructions: self.instructions,
+            name: self.name,
+            next_instruction: self.next_instruction,
+            paused: self.paused,
+            rate_limit: self.rate_limit,
+            trigger: self.trigger,
+            lifetime_spend_cap: None,
+            lifetime_spent: 0,
+            description: String::new(),
+            execution_count: 0,
+            max_executions: None,
+            exec_mode: ExecMode::Worker,
+            max_simulation_failures: None,
+            chain_depth: 0,
+            max_chain_depth: None,
+        }
+    }
+}
+
 /// Trait for reading and writing to a automation account.
 pub trait AutomationAccount {
     /// Get the pubkey of the automation account.
     fn pubkey(&self) -> Pubkey;
 
+    /// Returns true if the automation has completed its maximum number of executions.
+    fn is_execution_capped(&self) -> bool;
+
+    /// Returns true if the automation has chained more dynamic next instructions than its max depth.
+    fn exceeds_chain_depth(&self) -> bool;
+
     /// Allocate more memory for the account.
     fn realloc(&mut self) -> Result<()>;
 }
@@ -67,6 +188,20 @@ impl AutomationAccount for Account<'_, Automation> {
         Automation::pubkey(self.authority, self.id.clone())
     }
 
+    fn is_execution_capped(&self) -> bool {
+        match self.max_executions {
+            None => false,
+            Some(max_executions) => self.execution_count >= max_executions,
+        }
+    }
+
+    fn exceeds_chain_depth(&self) -> bool {
+        match self.max_chain_depth {
+            None => false,
+            Some(max_chain_depth) => self.chain_depth > max_chain_depth,
+        }
+    }
+
     fn realloc(&mut self) -> Result<()> {
         // Realloc memory for the automation account
         let data_len = 8 + self.try_to_vec()?.len();
@@ -75,50 +210,287 @@ impl AutomationAccount for Account<'_, Automation> {
     }
 }
 
-/// The execution context of a particular transaction automation.
-#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug, PartialEq, Eq)]
-pub struct ExecContext {
-    /// Index of the next instruction to be executed.
-    pub exec_index: u64,
-
-    /// Number of execs since the last tx reimbursement.
-    pub execs_since_reimbursement: u64,
-
-    /// Number of execs in this slot.
-    pub execs_since_slot: u64,
-
-    /// Slot of the last exec
-    pub last_exec_at: u64,
-
-    /// Context for the triggering condition
-    pub trigger_context: TriggerContext,
-}
-
-/// The event which allowed a particular transaction automation to be triggered.
-#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug, PartialEq, Eq)]
-pub enum TriggerContext {
-    /// A running hash of the observed account data.
-    Account {
-        /// The account's data hash.
-        data_hash: u64,
-    },
-
-    /// A cron execution context.
-    Cron {
-        /// The threshold moment the schedule was waiting for.
-        started_at: i64,
-    },
-
-    /// The immediate trigger context.
-    Immediate,
+/// The properties of an automation to create in a batch.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct AutomationCreateParams {
+    /// The lamports to fund the automation with.
+    pub amount: u64,
+    pub id: Vec<u8>,
+    pub instructions: Vec<InstructionData>,
+    pub trigger: Trigger,
 }
 
 /// The properties of automations which are updatable.
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct AutomationSettings {
+    pub description: Option<String>,
+    pub exec_mode: Option<ExecMode>,
     pub fee: Option<u64>,
     pub instructions: Option<Vec<InstructionData>>,
+    /// If provided, replaces the lifetime spend cap. `Some(None)` removes the cap.
+    pub lifetime_spend_cap: Option<Option<u64>>,
+    pub max_chain_depth: Option<u64>,
+    /// If provided, replaces the max number of executions. `Some(None)` removes the cap.
+    pub max_executions: Option<Option<u64>>,
+    pub max_simulation_failures: Option<u32>,
     pub name: Option<String>,
     pub rate_limit: Option<u64>,
     pub trigger: Option<Trigger>,
 }
+
+/// Who may submit an automation's kickoffs and execs.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExecMode {
+    /// Any worker may submit them, and workers in the pool collect the automation's fee.
+    Worker,
+    /// Only the relayer may submit them, signing each transaction as the signatory.
+    /// Workers ignore automations in this mode.
+    Relayer { relayer_authority: Pubkey },
+}
+
+impl ExecMode {
+    /// Returns true if a transaction signed by the signatory may kickoff or exec an automation in this mode.
+    pub fn authorizes(&self, signatory: &Pubkey) -> bool {
+        match self {
+            ExecMode::Worker => true,
+            ExecMode::Relayer { relayer_authority } => relayer_authority.eq(signatory),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clockwork_utils::automation::TriggerContext;
+
+    use super::*;
+
+    fn automation(lifetime_spend_cap: Option<u64>) -> Automation {
+        Automation {
+            authority: Pubkey::new_unique(),
+            bump: 255,
+            created_at: ClockData {
+                slot: 0,
+                epoch: 0,
+                unix_timestamp: 0,
+            },
+            exec_context: None,
+            fee: 1_000,
+            id: b"crank".to_vec(),
+            instructions: vec![],
+            name: String::new(),
+            next_instruction: None,
+            paused: false,
+            rate_limit: u64::MAX,
+            trigger: Trigger::Immediate,
+            lifetime_spend_cap,
+            lifetime_spent: 0,
+            description: String::new(),
+            execution_count: 0,
+            max_executions: None,
+            exec_mode: ExecMode::Worker,
+            max_simulation_failures: None,
+            chain_depth: 0,
+            max_chain_depth: None,
+        }
+    }
+
+    /// Returns the data of an automation account created before the fields following `trigger` were
+    /// added.
+    fn legacy_account_data(automation: &Automation) -> Vec<u8> {
+        let mut data = Automation::discriminator().to_vec();
+        data.extend(
+            (
+                automation.authority,
+                automation.bump,
+                automation.created_at.clone(),
+                automation.exec_context,
+                automation.fee,
+                automation.id.clone(),
+                automation.instructions.clone(),
+                automation.name.clone(),
+                automation.next_instruction.clone(),
+                automation.paused,
+                automation.rate_limit,
+                automation.trigger.clone(),
+            )
+                .try_to_vec()
+                .unwrap(),
+        );
+        data
+    }
+
+    #[test]
+    fn test_legacy_automation_migrate() {
+        let mut automation = automation(None);
+        automation.name = "crank".into();
+        automation.exec_context = Some(ExecContext {
+            exec_index: 1,
+            execs_since_reimbursement: 2,
+            execs_since_slot: 3,
+            last_exec_at: 4,
+            trigger_context: TriggerContext::Immediate,
+        });
+        let data = legacy_account_data(&automation);
+
+        // The legacy data ends before the fields added since, so it does not decode as is.
+        assert!(Automation::try_deserialize(&mut &data[..]).is_err());
+
+        let migrated = LegacyAutomation::try_from_account_data(&data)
+            .unwrap()
+            .migrate();
+        assert_eq!(migrated.authority, automation.authority);
+        assert_eq!(migrated.bump, automation.bump);
+        assert_eq!(migrated.created_at, automation.created_at);
+        assert_eq!(migrated.exec_context, automation.exec_context);
+        assert_eq!(migrated.fee, automation.fee);
+        assert_eq!(migrated.id, automation.id);
+        assert_eq!(migrated.name, automation.name);
+        assert_eq!(migrated.rate_limit, automation.rate_limit);
+        assert_eq!(migrated.trigger, automation.trigger);
+        assert_eq!(migrated.lifetime_spend_cap, None);
+        assert_eq!(migrated.lifetime_spent, 0);
+        assert_eq!(migrated.description, "");
+        assert_eq!(migrated.execution_count, 0);
+        assert_eq!(migrated.max_executions, None);
+        assert_eq!(migrated.exec_mode, ExecMode::Worker);
+        assert_eq!(migrated.max_simulation_failures, None);
+        assert_eq!(migrated.chain_depth, 0);
+        assert_eq!(migrated.max_chain_depth, None);
+
+        // The migrated automation keeps the legacy data as its prefix and decodes in the current
+        // layout.
+        let mut migrated_data = vec![];
+        migrated.try_serialize(&mut migrated_data).unwrap();
+        assert_eq!(migrated_data[..data.len()], data[..]);
+        let decoded = Automation::try_deserialize(&mut &migrated_data[..]).unwrap();
+        assert_eq!(decoded, automation);
+
+        // Other accounts are rejected.
+        let mut other_data = data.clone();
+        other_data[0] ^= 1;
+        assert!(LegacyAutomation::try_from_account_data(&other_data).is_err());
+    }
+
+    #[test]
+    fn test_migrated_automation_description() {
+        let data = legacy_account_data(&automation(None));
+        let mut migrated = LegacyAutomation::try_from_account_data(&data)
+            .unwrap()
+            .migrate();
+        assert_eq!(migrated.description, "");
+
+        // A description set after the migration follows the legacy fields in the account data.
+        migrated.set_description("Cranks the orderbook".into()).unwrap();
+        let mut migrated_data = vec![];
+        migrated.try_serialize(&mut migrated_data).unwrap();
+        assert_eq!(migrated_data[..data.len()], data[..]);
+        let decoded = Automation::try_deserialize(&mut &migrated_data[..]).unwrap();
+        assert_eq!(decoded.description, "Cranks the orderbook");
+    }
+
+    #[test]
+    fn test_migrated_automation_exec_mode_and_chain_depth() {
+        let data = legacy_account_data(&automation(None));
+        let mut migrated = LegacyAutomation::try_from_account_data(&data)
+            .unwrap()
+            .migrate();
+
+        // Legacy automations are executed by workers and have no chain depth limit.
+        assert_eq!(migrated.exec_mode, ExecMode::Worker);
+        assert!(migrated.exec_mode.authorizes(&Pubkey::new_unique()));
+        assert_eq!(migrated.chain_depth, 0);
+        assert_eq!(migrated.max_chain_depth, None);
+
+        // The fields round-trip once set on the migrated automation.
+        let relayer_authority = Pubkey::new_unique();
+        migrated.exec_mode = ExecMode::Relayer { relayer_authority };
+        migrated.chain_depth = 2;
+        migrated.max_chain_depth = Some(4);
+        let mut migrated_data = vec![];
+        migrated.try_serialize(&mut migrated_data).unwrap();
+        assert_eq!(migrated_data[..data.len()], data[..]);
+        let decoded = Automation::try_deserialize(&mut &migrated_data[..]).unwrap();
+        assert_eq!(decoded.exec_mode, ExecMode::Relayer { relayer_authority });
+        assert_eq!(decoded.chain_depth, 2);
+        assert_eq!(decoded.max_chain_depth, Some(4));
+    }
+
+    #[test]
+    fn test_automation_stops_at_lifetime_spend_cap() {
+        let mut automation = automation(Some(3_000));
+        let mut execs = 0;
+        while !automation.is_spend_capped() {
+            automation.record_spend(1_250);
+            execs += 1;
+        }
+        // The last exec is allowed to overshoot the cap by its own cost, then execs stop.
+        assert_eq!(execs, 3);
+        assert_eq!(automation.lifetime_spent, 3_750);
+        assert!(automation.is_spend_capped());
+    }
+
+    #[test]
+    fn test_automation_without_cap_is_never_spend_capped() {
+        let mut automation = automation(None);
+        automation.record_spend(u64::MAX);
+        automation.record_spend(1);
+        assert_eq!(automation.lifetime_spent, u64::MAX);
+        assert!(!automation.is_spend_capped());
+    }
+
+    #[test]
+    fn test_set_max_simulation_failures_is_clamped() {
+        let mut automation = automation(None);
+        automation.set_max_simulation_failures(3);
+        assert_eq!(automation.max_simulation_failures, Some(3));
+        automation.set_max_simulation_failures(u32::MAX);
+        assert_eq!(automation.max_simulation_failures, Some(MAX_SIMULATION_FAILURES));
+    }
+
+    #[test]
+    fn test_set_description() {
+        let mut automation = automation(None);
+        automation.set_description("Cranks the orderbook".into()).unwrap();
+        assert_eq!(automation.description, "Cranks the orderbook");
+
+        // The description round-trips through the account data.
+        let data = automation.try_to_vec().unwrap();
+        let decoded = Automation::try_from_slice(&data).unwrap();
+        assert_eq!(decoded.description, "Cranks the orderbook");
+
+        // A description at the limit is accepted.
+        let max = "a".repeat(MAX_DESCRIPTION_LEN);
+        automation.set_description(max.clone()).unwrap();
+        assert_eq!(automation.description, max);
+    }
+
+    #[test]
+    fn test_set_description_rejects_long_description() {
+        let mut automation = automation(None);
+        automation.set_description("Cranks the orderbook".into()).unwrap();
+        assert_eq!(
+            automation
+                .set_description("a".repeat(MAX_DESCRIPTION_LEN + 1))
+                .unwrap_err(),
+            error!(ClockworkError::DescriptionTooLong)
+        );
+        assert_eq!(automation.description, "Cranks the orderbook");
+    }
+
+    #[test]
+    fn test_relayer_signed_exec_is_authorized() {
+        let relayer_authority = Pubkey::new_unique();
+        let exec_mode = ExecMode::Relayer { relayer_authority };
+        assert!(exec_mode.authorizes(&relayer_authority));
+        assert!(ExecMode::Worker.authorizes(&Pubkey::new_unique()));
+    }
+
+    #[test]
+    fn test_exec_without_relayer_signature_is_rejected() {
+        let exec_mode = ExecMode::Relayer {
+            relayer_authority: Pubkey::new_unique(),
+        };
+        let worker_signatory = Pubkey::new_unique();
+        assert!(!exec_mode.authorizes(&worker_signatory));
+    }
+}
@@ -0,0 +1,8 @@
+use anchor_lang::prelude::*;
+
+/// Emitted when an automation's instruction returns event data, re-emitting the payload for indexers.
+#[event]
+pub struct AutomationExecuted {
+    pub automation: Pubkey,
+    pub data: Vec<u8>,
+}
@@ -1,6 +1,8 @@
 //! All objects needed to describe and manage the program's state.
 
 mod automation;
+mod events;
 
 pub use clockwork_utils::automation::*;
 pub use automation::*;
+pub use events::*;
@@ -22,4 +22,52 @@ pub enum ClockworkError {
 
     #[msg("The worker cannot rotate into the pool right now")]
     PoolFull,
+
+    #[msg("The signer is not the pending admin, or no admin transfer is pending")]
+    InvalidPendingAdmin,
+
+    #[msg("Commission must be paid out to the worker's commission recipient")]
+    InvalidCommissionRecipient,
+
+    #[msg("The signer is neither the worker's authority nor its commission recipient")]
+    InvalidCommissionSigner,
+
+    #[msg("Only the config admin or the program's upgrade authority may pause or resume a program")]
+    InvalidPauseAuthority,
+
+    #[msg("A delegation cannot be merged into itself")]
+    InvalidDelegationMerge,
+
+    #[msg("Delegations cannot be merged while unstakes are pending, try again after the next epoch")]
+    UnstakesPending,
+
+    #[msg("The delegation already has a withdrawal in progress")]
+    WithdrawalInProgress,
+
+    #[msg("The withdrawal amount exceeds the delegation's available balance")]
+    InvalidWithdrawalAmount,
+
+    #[msg("The delegation has no withdrawal that has finished unbonding")]
+    WithdrawalUnbonding,
+
+    #[msg("Delegations staking different kinds of assets cannot be merged")]
+    StakeKindMismatch,
+
+    #[msg("The config is already in the current layout")]
+    ConfigAlreadyMigrated,
+
+    #[msg("The delegation is already in the current layout")]
+    DelegationAlreadyMigrated,
+
+    #[msg("The worker is already in the current layout")]
+    WorkerAlreadyMigrated,
+
+    #[msg("The unstake is already in the current layout")]
+    UnstakeAlreadyMigrated,
+
+    #[msg("The fee account's lamports are below its rent-exempt minimum")]
+    FeeBalanceBelowRent,
+
+    #[msg("The worker's commission exceeds the fee account's usable balance")]
+    CommissionExceedsFeeBalance,
 }
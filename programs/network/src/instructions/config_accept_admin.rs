@@ -0,0 +1,23 @@
+use {crate::state::*, anchor_lang::prelude::*};
+
+#[derive(Accounts)]
+pub struct ConfigAcceptAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [SEED_CONFIG],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account()]
+    pub pending_admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ConfigAcceptAdmin>) -> Result<()> {
+    // Get accounts
+    let config = &mut ctx.accounts.config;
+    let pending_admin = &ctx.accounts.pending_admin;
+
+    // Transfer control of the config to the pending admin.
+    config.accept_admin(pending_admin.key())
+}
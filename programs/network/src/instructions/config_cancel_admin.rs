@@ -0,0 +1,23 @@
+use {crate::state::*, anchor_lang::prelude::*};
+
+#[derive(Accounts)]
+pub struct ConfigCancelAdmin<'info> {
+    #[account()]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_CONFIG],
+        bump,
+        has_one = admin,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+pub fn handler(ctx: Context<ConfigCancelAdmin>) -> Result<()> {
+    // Get accounts
+    let config = &mut ctx.accounts.config;
+
+    // Clear the pending admin.
+    config.cancel_admin()
+}
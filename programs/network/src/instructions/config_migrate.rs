@@ -0,0 +1,65 @@
+use {
+    crate::{errors::*, state::*},
+    anchor_lang::{
+        error::ErrorCode,
+        prelude::*,
+        solana_program::system_program,
+        system_program::{transfer, Transfer},
+    },
+    std::mem::size_of,
+};
+
+#[derive(Accounts)]
+pub struct ConfigMigrate<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// CHECK: A config in the legacy layout cannot be loaded as a `Config`, so the handler decodes it.
+    #[account(
+        mut,
+        seeds = [SEED_CONFIG],
+        bump,
+        owner = crate::ID,
+    )]
+    pub config: UncheckedAccount<'info>,
+
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ConfigMigrate>) -> Result<()> {
+    // Get accounts
+    let admin = &ctx.accounts.admin;
+    let config = &ctx.accounts.config;
+    let system_program = &ctx.accounts.system_program;
+
+    // Verify the config is in the legacy layout and the signer is its admin.
+    let space = 8 + size_of::<Config>();
+    require!(config.data_len().lt(&space), ClockworkError::ConfigAlreadyMigrated);
+    let legacy = LegacyConfig::try_from_account_data(&config.try_borrow_data()?)?;
+    require_keys_eq!(legacy.admin, admin.key(), ErrorCode::ConstraintHasOne);
+
+    // Top up the rent for the larger account and grow it.
+    let rent_due = Rent::get()?
+        .minimum_balance(space)
+        .saturating_sub(config.lamports());
+    if rent_due.gt(&0) {
+        transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                Transfer {
+                    from: admin.to_account_info(),
+                    to: config.to_account_info(),
+                },
+            ),
+            rent_due,
+        )?;
+    }
+    config.to_account_info().realloc(space, false)?;
+
+    // Rewrite the config in the current layout.
+    let mut data = config.try_borrow_mut_data()?;
+    legacy.migrate().try_serialize(&mut &mut data[..])?;
+
+    Ok(())
+}
@@ -0,0 +1,26 @@
+use {crate::state::*, anchor_lang::prelude::*};
+
+#[derive(Accounts)]
+#[instruction(pending_admin: Pubkey)]
+pub struct ConfigProposeAdmin<'info> {
+    #[account()]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_CONFIG],
+        bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, Config>,
+}
+
+pub fn handler(ctx: Context<ConfigProposeAdmin>, pending_admin: Pubkey) -> Result<()> {
+    // Get accounts
+    let config = &mut ctx.accounts.config;
+
+    // Record the pending admin. The transfer takes effect once the pending admin accepts it.
+    config.propose_admin(pending_admin);
+
+    Ok(())
+}
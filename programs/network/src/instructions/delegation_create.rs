@@ -5,13 +5,14 @@ use {
         solana_program::{system_program, sysvar},
     },
     anchor_spl::{
-        associated_token::AssociatedToken,
-        token::{Mint, Token, TokenAccount},
+        associated_token::{create, get_associated_token_address, AssociatedToken, Create},
+        token::{spl_token::native_mint, Mint, Token, TokenAccount},
     },
     std::mem::size_of,
 };
 
 #[derive(Accounts)]
+#[instruction(stake_kind: StakeKind)]
 pub struct DelegationCreate<'info> {
     #[account(address = anchor_spl::associated_token::ID)]
     pub associated_token_program: Program<'info, AssociatedToken>,
@@ -43,7 +44,7 @@ pub struct DelegationCreate<'info> {
     )]
     pub delegation_tokens: Account<'info, TokenAccount>,
 
-    #[account(address = config.mint)]
+    #[account(address = stake_kind.mint(&config))]
     pub mint: Account<'info, Mint>,
 
     #[account(address = sysvar::rent::ID)]
@@ -64,16 +65,50 @@ pub struct DelegationCreate<'info> {
         bump
     )]
     pub worker: Account<'info, Worker>,
+
+    /// CHECK: The worker's wrapped SOL stake account. It is created with the worker's first native delegation.
+    #[account(
+        mut,
+        address = get_associated_token_address(&worker.key(), &native_mint::ID)
+    )]
+    pub worker_native_stake: UncheckedAccount<'info>,
 }
 
-pub fn handler(ctx: Context<DelegationCreate>) -> Result<()> {
+pub fn handler(ctx: Context<DelegationCreate>, stake_kind: StakeKind) -> Result<()> {
     // Get accounts
+    let associated_token_program = &ctx.accounts.associated_token_program;
     let authority = &ctx.accounts.authority;
     let delegation = &mut ctx.accounts.delegation;
+    let mint = &ctx.accounts.mint;
+    let rent = &ctx.accounts.rent;
+    let system_program = &ctx.accounts.system_program;
+    let token_program = &ctx.accounts.token_program;
     let worker = &mut ctx.accounts.worker;
+    let worker_native_stake = &ctx.accounts.worker_native_stake;
 
     // Initialize the delegation account.
-    delegation.init(authority.key(), worker.total_delegations, worker.key())?;
+    delegation.init(
+        authority.key(),
+        worker.total_delegations,
+        stake_kind,
+        worker.key(),
+    )?;
+
+    // Create the worker's wrapped SOL stake account, if this is its first native delegation.
+    if stake_kind.eq(&StakeKind::Native) && worker_native_stake.data_is_empty() {
+        create(CpiContext::new(
+            associated_token_program.to_account_info(),
+            Create {
+                payer: authority.to_account_info(),
+                associated_token: worker_native_stake.to_account_info(),
+                authority: worker.to_account_info(),
+                mint: mint.to_account_info(),
+                system_program: system_program.to_account_info(),
+                token_program: token_program.to_account_info(),
+                rent: rent.to_account_info(),
+            },
+        ))?;
+    }
 
     // Increment the worker's total delegations counter.
     worker.total_delegations = worker.total_delegations.checked_add(1).unwrap();
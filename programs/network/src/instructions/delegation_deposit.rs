@@ -13,7 +13,7 @@ pub struct DelegationDeposit<'info> {
     #[account(
         mut,
         associated_token::authority = authority,
-        associated_token::mint = config.mint,
+        associated_token::mint = delegation.stake_kind.mint(&config),
     )]
     pub authority_tokens: Account<'info, TokenAccount>,
 
@@ -35,7 +35,7 @@ pub struct DelegationDeposit<'info> {
     #[account(
         mut,
         associated_token::authority = delegation,
-        associated_token::mint = config.mint,
+        associated_token::mint = delegation.stake_kind.mint(&config),
     )]
     pub delegation_tokens: Account<'info, TokenAccount>,
 
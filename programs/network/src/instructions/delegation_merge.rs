@@ -0,0 +1,150 @@
+use {
+    crate::{errors::*, state::*},
+    anchor_lang::prelude::*,
+    anchor_spl::token::{close_account, transfer, CloseAccount, Token, TokenAccount, Transfer},
+};
+
+#[derive(Accounts)]
+pub struct DelegationMerge<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(address = Config::pubkey())]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [
+            SEED_DELEGATION,
+            destination.worker.as_ref(),
+            destination.id.to_be_bytes().as_ref(),
+        ],
+        bump,
+        has_one = authority,
+        has_one = worker,
+    )]
+    pub destination: Account<'info, Delegation>,
+
+    #[account(
+        mut,
+        associated_token::authority = destination,
+        associated_token::mint = destination.stake_kind.mint(&config),
+    )]
+    pub destination_tokens: Account<'info, TokenAccount>,
+
+    #[account(
+        address = Registry::pubkey(),
+        constraint = !registry.is_locked() @ ClockworkError::RegistryLocked,
+        constraint = registry.total_unstakes.eq(&0) @ ClockworkError::UnstakesPending,
+    )]
+    pub registry: Account<'info, Registry>,
+
+    /// CHECK: The worker's frame in the current epoch's snapshot. This account may not exist.
+    #[account(
+        address = SnapshotFrame::pubkey(Snapshot::pubkey(registry.current_epoch), worker.id)
+    )]
+    pub snapshot_frame: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            SEED_DELEGATION,
+            source.worker.as_ref(),
+            source.id.to_be_bytes().as_ref(),
+        ],
+        bump,
+        has_one = authority,
+        has_one = worker,
+        constraint = source.id.ne(&destination.id) @ ClockworkError::InvalidDelegationMerge,
+        constraint = source.stake_kind.eq(&destination.stake_kind) @ ClockworkError::StakeKindMismatch,
+        constraint = !source.has_withdrawal() @ ClockworkError::WithdrawalInProgress,
+    )]
+    pub source: Account<'info, Delegation>,
+
+    #[account(
+        mut,
+        associated_token::authority = source,
+        associated_token::mint = source.stake_kind.mint(&config),
+    )]
+    pub source_tokens: Account<'info, TokenAccount>,
+
+    #[account(address = anchor_spl::token::ID)]
+    pub token_program: Program<'info, Token>,
+
+    #[account(mut, address = worker.pubkey())]
+    pub worker: Account<'info, Worker>,
+}
+
+pub fn handler(ctx: Context<DelegationMerge>) -> Result<()> {
+    // Get accounts.
+    let authority = &ctx.accounts.authority;
+    let destination = &mut ctx.accounts.destination;
+    let destination_tokens = &ctx.accounts.destination_tokens;
+    let snapshot_frame = &ctx.accounts.snapshot_frame;
+    let source = &mut ctx.accounts.source;
+    let source_tokens = &ctx.accounts.source_tokens;
+    let token_program = &ctx.accounts.token_program;
+    let worker = &mut ctx.accounts.worker;
+
+    // Transfer the source's unlocked tokens to the destination. They will be locked with the worker next epoch.
+    let bump = *ctx.bumps.get("source").unwrap();
+    let source_worker = source.worker;
+    let source_id = source.id.to_be_bytes();
+    let source_seeds: &[&[u8]] = &[
+        SEED_DELEGATION,
+        source_worker.as_ref(),
+        source_id.as_ref(),
+        &[bump],
+    ];
+    transfer(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            Transfer {
+                from: source_tokens.to_account_info(),
+                to: destination_tokens.to_account_info(),
+                authority: source.to_account_info(),
+            },
+            &[source_seeds],
+        ),
+        source_tokens.amount,
+    )?;
+
+    // Move the locked stake and claimable yield. Locked tokens are held by the worker, so only the books change.
+    let yield_amount = destination.absorb(source);
+    **source.to_account_info().try_borrow_mut_lamports()? = source
+        .to_account_info()
+        .lamports()
+        .checked_sub(yield_amount)
+        .unwrap();
+    **destination.to_account_info().try_borrow_mut_lamports()? = destination
+        .to_account_info()
+        .lamports()
+        .checked_add(yield_amount)
+        .unwrap();
+
+    // The epoch jobs walk a worker's delegations by contiguous id, and fee distribution visits every delegation
+    // in the current snapshot. So the source can only be closed if it is the worker's newest delegation and was
+    // created after the current snapshot was taken. Otherwise it is left open with empty balances.
+    let is_newest = source.id.checked_add(1).unwrap().eq(&worker.total_delegations);
+    let is_in_snapshot = match SnapshotFrame::try_from(snapshot_frame.try_borrow_data()?.to_vec()) {
+        Err(_err) => false,
+        Ok(frame) => source.id.lt(&frame.total_entries),
+    };
+    if is_newest && !is_in_snapshot {
+        close_account(CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            CloseAccount {
+                account: source_tokens.to_account_info(),
+                destination: authority.to_account_info(),
+                authority: source.to_account_info(),
+            },
+            &[source_seeds],
+        ))?;
+        source.close(authority.to_account_info())?;
+        worker.total_delegations = worker.total_delegations.checked_sub(1).unwrap();
+    } else {
+        msg!("The source delegation was emptied but cannot be closed");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use {
+    crate::{errors::*, state::*},
+    anchor_lang::{
+        error::ErrorCode,
+        prelude::*,
+        solana_program::system_program,
+        system_program::{transfer, Transfer},
+    },
+    std::mem::size_of,
+};
+
+#[derive(Accounts)]
+pub struct DelegationMigrate<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: A legacy delegation cannot be loaded as a `Delegation`, so the handler decodes it.
+    #[account(mut, owner = crate::ID)]
+    pub delegation: UncheckedAccount<'info>,
+
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<DelegationMigrate>) -> Result<()> {
+    // Get accounts
+    let delegation = &ctx.accounts.delegation;
+    let payer = &ctx.accounts.payer;
+    let system_program = &ctx.accounts.system_program;
+
+    // Verify the delegation is in the legacy layout and at its address.
+    let space = 8 + size_of::<Delegation>();
+    require!(
+        delegation.data_len().lt(&space),
+        ClockworkError::DelegationAlreadyMigrated
+    );
+    let legacy = LegacyDelegation::try_from_account_data(&delegation.try_borrow_data()?)?;
+    require_keys_eq!(
+        Delegation::pubkey(legacy.worker, legacy.id),
+        delegation.key(),
+        ErrorCode::ConstraintSeeds
+    );
+
+    // Top up the rent for the larger account and grow it.
+    let rent_due = Rent::get()?
+        .minimum_balance(space)
+        .saturating_sub(delegation.lamports());
+    if rent_due.gt(&0) {
+        transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                Transfer {
+                    from: payer.to_account_info(),
+                    to: delegation.to_account_info(),
+                },
+            ),
+            rent_due,
+        )?;
+    }
+    delegation.to_account_info().realloc(space, false)?;
+
+    // Rewrite the delegation in the current layout.
+    let mut data = delegation.try_borrow_mut_data()?;
+    legacy.migrate().try_serialize(&mut &mut data[..])?;
+
+    Ok(())
+}
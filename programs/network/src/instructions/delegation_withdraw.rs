@@ -1,5 +1,5 @@
 use {
-    crate::state::*,
+    crate::{errors::*, state::*},
     anchor_lang::prelude::*,
     anchor_spl::token::{transfer, Token, TokenAccount, Transfer},
 };
@@ -13,7 +13,7 @@ pub struct DelegationWithdraw<'info> {
     #[account(
         mut,
         associated_token::authority = authority,
-        associated_token::mint = config.mint,
+        associated_token::mint = delegation.stake_kind.mint(&config),
     )]
     pub authority_tokens: Account<'info, TokenAccount>,
 
@@ -35,7 +35,7 @@ pub struct DelegationWithdraw<'info> {
     #[account(
         mut,
         associated_token::authority = delegation,
-        associated_token::mint = config.mint,
+        associated_token::mint = delegation.stake_kind.mint(&config),
     )]
     pub delegation_tokens: Account<'info, TokenAccount>,
 
@@ -50,6 +50,12 @@ pub fn handler(ctx: Context<DelegationWithdraw>, amount: u64) -> Result<()> {
     let delegation_tokens = &ctx.accounts.delegation_tokens;
     let token_program = &ctx.accounts.token_program;
 
+    // Tokens that are unbonding may only leave through a withdrawal claim.
+    require!(
+        amount.le(&delegation_tokens.amount.saturating_sub(delegation.unbonding_amount)),
+        ClockworkError::InvalidWithdrawalAmount
+    );
+
     // Transfer tokens from authority tokens to delegation
     let bump = *ctx.bumps.get("delegation").unwrap();
     transfer(
@@ -0,0 +1,80 @@
+use {
+    crate::state::*,
+    anchor_lang::prelude::*,
+    anchor_spl::token::{transfer, Token, TokenAccount, Transfer},
+};
+
+#[derive(Accounts)]
+pub struct DelegationWithdrawClaim<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        associated_token::authority = authority,
+        associated_token::mint = delegation.stake_kind.mint(&config),
+    )]
+    pub authority_tokens: Account<'info, TokenAccount>,
+
+    #[account(address = Config::pubkey())]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [
+            SEED_DELEGATION,
+            delegation.worker.as_ref(),
+            delegation.id.to_be_bytes().as_ref(),
+        ],
+        bump,
+        has_one = authority,
+    )]
+    pub delegation: Account<'info, Delegation>,
+
+    #[account(
+        mut,
+        associated_token::authority = delegation,
+        associated_token::mint = delegation.stake_kind.mint(&config),
+    )]
+    pub delegation_tokens: Account<'info, TokenAccount>,
+
+    #[account(address = Registry::pubkey())]
+    pub registry: Account<'info, Registry>,
+
+    #[account(address = anchor_spl::token::ID)]
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<DelegationWithdrawClaim>) -> Result<()> {
+    // Get accounts.
+    let authority_tokens = &ctx.accounts.authority_tokens;
+    let delegation = &mut ctx.accounts.delegation;
+    let delegation_tokens = &ctx.accounts.delegation_tokens;
+    let registry = &ctx.accounts.registry;
+    let token_program = &ctx.accounts.token_program;
+
+    // Clear the withdrawal once it has finished unbonding.
+    let amount = delegation.claim_withdrawal(registry.current_epoch)?;
+
+    // Transfer the unbonded tokens from the delegation to the authority.
+    let bump = *ctx.bumps.get("delegation").unwrap();
+    transfer(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            Transfer {
+                from: delegation_tokens.to_account_info(),
+                to: authority_tokens.to_account_info(),
+                authority: delegation.to_account_info(),
+            },
+            &[&[
+                SEED_DELEGATION,
+                delegation.worker.as_ref(),
+                delegation.id.to_be_bytes().as_ref(),
+                &[bump],
+            ]],
+        ),
+        amount,
+    )?;
+
+    Ok(())
+}
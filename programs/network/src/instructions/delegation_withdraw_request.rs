@@ -0,0 +1,39 @@
+use {
+    crate::{errors::*, state::*},
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+#[instruction(amount: u64)]
+pub struct DelegationWithdrawRequest<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            SEED_DELEGATION,
+            delegation.worker.as_ref(),
+            delegation.id.to_be_bytes().as_ref(),
+        ],
+        bump,
+        has_one = authority,
+    )]
+    pub delegation: Account<'info, Delegation>,
+
+    #[account(
+        address = Registry::pubkey(),
+        constraint = !registry.is_locked() @ ClockworkError::RegistryLocked,
+    )]
+    pub registry: Account<'info, Registry>,
+}
+
+pub fn handler(ctx: Context<DelegationWithdrawRequest>, amount: u64) -> Result<()> {
+    // Get accounts.
+    let delegation = &mut ctx.accounts.delegation;
+    let registry = &ctx.accounts.registry;
+
+    // Mark the stake for withdrawal.
+    delegation.request_withdrawal(amount, registry.current_epoch)?;
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use {
+    crate::{errors::*, state::*},
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct FeeReconcile<'info> {
+    #[account()]
+    pub admin: Signer<'info>,
+
+    #[account(address = Config::pubkey(), has_one = admin)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [
+            SEED_FEE,
+            fee.worker.as_ref(),
+        ],
+        bump,
+        has_one = worker,
+    )]
+    pub fee: Account<'info, Fee>,
+
+    #[account(
+        address = Registry::pubkey(),
+        constraint = !registry.is_locked() @ ClockworkError::RegistryLocked
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(address = worker.pubkey())]
+    pub worker: Account<'info, Worker>,
+}
+
+pub fn handler(ctx: Context<FeeReconcile>) -> Result<()> {
+    // Get accounts
+    let fee = &mut ctx.accounts.fee;
+    let worker = &ctx.accounts.worker;
+
+    // Recompute the distributable balance from the fee account's actual lamports, net of rent and the worker's commission.
+    let fee_usable_balance = fee.usable_balance()?;
+    let prior_distributable_balance = fee.reconcile(fee_usable_balance, worker)?;
+
+    msg!(
+        "Fee reconciled! worker: {} distributable_balance: {} -> {}",
+        worker.key(),
+        prior_distributable_balance,
+        fee.distributable_balance
+    );
+
+    Ok(())
+}
@@ -1,33 +1,61 @@
+pub mod config_accept_admin;
+pub mod config_cancel_admin;
+pub mod config_migrate;
+pub mod config_propose_admin;
 pub mod config_update;
 pub mod delegation_claim;
 pub mod delegation_create;
 pub mod delegation_deposit;
+pub mod delegation_migrate;
+mod delegation_merge;
 pub mod delegation_withdraw;
+pub mod delegation_withdraw_claim;
+pub mod delegation_withdraw_request;
+pub mod fee_reconcile;
 pub mod initialize;
 pub mod penalty_claim;
 pub mod pool_create;
 pub mod pool_rotate;
 pub mod pool_update;
+pub mod program_pause;
+pub mod program_resume;
 pub mod registry_nonce_hash;
 pub mod registry_unlock;
 pub mod unstake_create;
+pub mod unstake_migrate;
 pub mod worker_claim;
+pub mod worker_commission_update;
 pub mod worker_create;
+pub mod worker_migrate;
 pub mod worker_update;
 
+pub use config_accept_admin::*;
+pub use config_cancel_admin::*;
+pub use config_migrate::*;
+pub use config_propose_admin::*;
 pub use config_update::*;
 pub use delegation_claim::*;
 pub use delegation_create::*;
 pub use delegation_deposit::*;
+pub use delegation_merge::*;
+pub use delegation_migrate::*;
 pub use delegation_withdraw::*;
+pub use delegation_withdraw_claim::*;
+pub use delegation_withdraw_request::*;
+pub use fee_reconcile::*;
 pub use initialize::*;
 pub use penalty_claim::*;
 pub use pool_create::*;
 pub use pool_rotate::*;
 pub use pool_update::*;
+pub use program_pause::*;
+pub use program_resume::*;
 pub use registry_nonce_hash::*;
 pub use registry_unlock::*;
 pub use unstake_create::*;
+pub use unstake_migrate::*;
 pub use worker_claim::*;
+pub use worker_commission_update::*;
 pub use worker_create::*;
+pub use worker_migrate::*;
 pub use worker_update::*;
@@ -73,6 +73,15 @@ pub fn handler(ctx: Context<PoolRotate>) -> Result<()> {
     // Rotate the worker into the pool.
     pool.rotate(worker.key())?;
 
+    // Announce the worker's slot, unless a pool of size zero drained it straight back out.
+    if let Some(position) = pool.workers.iter().position(|key| key.eq(&worker.key())) {
+        emit!(WorkerRotated {
+            pool: pool.key(),
+            worker: worker.key(),
+            position: position as u64,
+        });
+    }
+
     Ok(())
 }
 
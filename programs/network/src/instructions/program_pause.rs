@@ -0,0 +1,77 @@
+use {
+    crate::{errors::*, state::*},
+    anchor_lang::{
+        prelude::*,
+        solana_program::{bpf_loader_upgradeable, system_program},
+    },
+    std::mem::size_of,
+};
+
+#[derive(Accounts)]
+#[instruction(program_id: Pubkey)]
+pub struct ProgramPause<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(address = Config::pubkey())]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        seeds = [
+            SEED_PROGRAM_STATUS,
+            program_id.as_ref(),
+        ],
+        bump,
+        payer = authority,
+        space = 8 + size_of::<ProgramStatus>(),
+    )]
+    pub program_status: Account<'info, ProgramStatus>,
+
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ProgramPause>, program_id: Pubkey) -> Result<()> {
+    // Get accounts
+    let authority = &ctx.accounts.authority;
+    let config = &ctx.accounts.config;
+    let program_status = &mut ctx.accounts.program_status;
+
+    // Verify the signer may pause the program.
+    verify_pause_authority(authority, config, program_id, ctx.remaining_accounts)?;
+
+    // Pause the program.
+    program_status.paused = true;
+    program_status.program_id = program_id;
+
+    Ok(())
+}
+
+/// Verifies the authority is either the config admin or the upgrade authority of the program.
+/// To act as the upgrade authority, the program's data account must be the first remaining account.
+pub fn verify_pause_authority(
+    authority: &Signer,
+    config: &Config,
+    program_id: Pubkey,
+    remaining_accounts: &[AccountInfo],
+) -> Result<()> {
+    if authority.key().eq(&config.admin) {
+        return Ok(());
+    }
+    let program_data_pubkey =
+        Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::ID).0;
+    let program_data = remaining_accounts
+        .first()
+        .filter(|account_info| account_info.key().eq(&program_data_pubkey))
+        .ok_or(ClockworkError::InvalidPauseAuthority)?;
+    let program_data = Account::<ProgramData>::try_from(program_data)
+        .map_err(|_err| ClockworkError::InvalidPauseAuthority)?;
+    require!(
+        program_data
+            .upgrade_authority_address
+            .eq(&Some(authority.key())),
+        ClockworkError::InvalidPauseAuthority
+    );
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use {
+    super::program_pause::verify_pause_authority,
+    crate::state::*,
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+#[instruction(program_id: Pubkey)]
+pub struct ProgramResume<'info> {
+    #[account()]
+    pub authority: Signer<'info>,
+
+    #[account(address = Config::pubkey())]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [
+            SEED_PROGRAM_STATUS,
+            program_id.as_ref(),
+        ],
+        bump,
+    )]
+    pub program_status: Account<'info, ProgramStatus>,
+}
+
+pub fn handler(ctx: Context<ProgramResume>, program_id: Pubkey) -> Result<()> {
+    // Get accounts
+    let authority = &ctx.accounts.authority;
+    let config = &ctx.accounts.config;
+    let program_status = &mut ctx.accounts.program_status;
+
+    // Verify the signer may resume the program.
+    verify_pause_authority(authority, config, program_id, ctx.remaining_accounts)?;
+
+    // Resume the program.
+    program_status.paused = false;
+
+    Ok(())
+}
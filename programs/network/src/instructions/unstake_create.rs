@@ -26,7 +26,7 @@ pub struct UnstakeCreate<'info> {
         mut,
         seeds = [SEED_REGISTRY],
         bump, 
-        constraint = !registry.locked @ ClockworkError::RegistryLocked
+        constraint = !registry.is_locked() @ ClockworkError::RegistryLocked
     )]
     pub registry: Account<'info, Registry>,
 
@@ -58,10 +58,20 @@ pub fn handler(ctx: Context<UnstakeCreate>, amount: u64) -> Result<()> {
     let worker = &ctx.accounts.worker;
 
     // Validate the request is valid.
-    require!(amount.le(&delegation.stake_amount), ClockworkError::InvalidUnstakeAmount);
+    require!(
+        amount.le(&delegation.stake_amount.saturating_sub(delegation.pending_withdrawal)),
+        ClockworkError::InvalidUnstakeAmount
+    );
 
     // Initialize the unstake account.
-    unstake.init(amount, authority.key(), delegation.key(), registry.total_unstakes, worker.key())?;
+    unstake.init(
+        amount,
+        authority.key(),
+        delegation.key(),
+        registry.total_unstakes,
+        worker.key(),
+        delegation.stake_kind,
+    )?;
 
     // Increment the registry's unstake counter.
     registry.total_unstakes = registry.total_unstakes.checked_add(1).unwrap();
@@ -0,0 +1,67 @@
+use {
+    crate::{errors::*, state::*},
+    anchor_lang::{
+        error::ErrorCode,
+        prelude::*,
+        solana_program::system_program,
+        system_program::{transfer, Transfer},
+    },
+    std::mem::size_of,
+};
+
+#[derive(Accounts)]
+pub struct UnstakeMigrate<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: A legacy unstake cannot be loaded as an `Unstake`, so the handler decodes it.
+    #[account(mut, owner = crate::ID)]
+    pub unstake: UncheckedAccount<'info>,
+
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<UnstakeMigrate>) -> Result<()> {
+    // Get accounts
+    let unstake = &ctx.accounts.unstake;
+    let payer = &ctx.accounts.payer;
+    let system_program = &ctx.accounts.system_program;
+
+    // Verify the unstake is in the legacy layout and at its address.
+    let space = 8 + size_of::<Unstake>();
+    require!(
+        unstake.data_len().lt(&space),
+        ClockworkError::UnstakeAlreadyMigrated
+    );
+    let legacy = LegacyUnstake::try_from_account_data(&unstake.try_borrow_data()?)?;
+    require_keys_eq!(
+        Unstake::pubkey(legacy.id),
+        unstake.key(),
+        ErrorCode::ConstraintSeeds
+    );
+
+    // Top up the rent for the larger account and grow it.
+    let rent_due = Rent::get()?
+        .minimum_balance(space)
+        .saturating_sub(unstake.lamports());
+    if rent_due.gt(&0) {
+        transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                Transfer {
+                    from: payer.to_account_info(),
+                    to: unstake.to_account_info(),
+                },
+            ),
+            rent_due,
+        )?;
+    }
+    unstake.to_account_info().realloc(space, false)?;
+
+    // Rewrite the unstake in the current layout.
+    let mut data = unstake.try_borrow_mut_data()?;
+    legacy.migrate().try_serialize(&mut &mut data[..])?;
+
+    Ok(())
+}
@@ -1,12 +1,24 @@
-use {crate::state::*, anchor_lang::prelude::*};
+use {
+    crate::{errors::*, state::*},
+    anchor_lang::prelude::*,
+};
 
 #[derive(Accounts)]
 #[instruction(amount: u64)]
 pub struct WorkerClaim<'info> {
-    #[account()]
-    pub authority: Signer<'info>,
+    /// The worker's authority, or its commission recipient if one is set.
+    #[account(
+        constraint = worker.authority.eq(&signer.key())
+            || worker.commission_recipient.eq(&Some(signer.key()))
+            @ ClockworkError::InvalidCommissionSigner
+    )]
+    pub signer: Signer<'info>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = worker.commission_recipient.map_or(true, |recipient| recipient.eq(&pay_to.key()))
+            @ ClockworkError::InvalidCommissionRecipient
+    )]
     pub pay_to: SystemAccount<'info>,
 
     #[account(
@@ -16,7 +28,6 @@ pub struct WorkerClaim<'info> {
             worker.id.to_be_bytes().as_ref()
         ],
         bump,
-        has_one = authority
     )]
     pub worker: Account<'info, Worker>,
 }
@@ -0,0 +1,43 @@
+use {
+    crate::{errors::*, state::*},
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+#[instruction(commission_rate: u64)]
+pub struct WorkerCommissionUpdate<'info> {
+    #[account()]
+    pub authority: Signer<'info>,
+
+    #[account(address = Registry::pubkey())]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        mut,
+        seeds = [
+            SEED_WORKER,
+            worker.id.to_be_bytes().as_ref(),
+        ],
+        bump,
+        has_one = authority,
+    )]
+    pub worker: Account<'info, Worker>,
+}
+
+pub fn handler(ctx: Context<WorkerCommissionUpdate>, commission_rate: u64) -> Result<()> {
+    // Get accounts
+    let registry = &ctx.accounts.registry;
+    let worker = &mut ctx.accounts.worker;
+
+    // Validate the commission rate.
+    require!(
+        commission_rate.le(&100),
+        ClockworkError::InvalidCommissionRate
+    );
+
+    // Stage the new rate. Fees collected during the current epoch are still distributed at the current rate.
+    worker.pending_commission_rate = Some(commission_rate);
+    worker.pending_commission_epoch = registry.current_epoch.checked_add(1).unwrap();
+
+    Ok(())
+}
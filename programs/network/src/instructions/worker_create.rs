@@ -54,7 +54,7 @@ pub struct WorkerCreate<'info> {
         mut, 
         seeds = [SEED_REGISTRY],
         bump,
-        constraint = !registry.locked @ ClockworkError::RegistryLocked
+        constraint = !registry.is_locked() @ ClockworkError::RegistryLocked
     )]
     pub registry: Account<'info, Registry>,
 
@@ -82,14 +82,16 @@ pub struct WorkerCreate<'info> {
     )]
     pub worker: Account<'info, Worker>,
 
+    /// The worker's stake token account. The staking jobs require this account to exist, so it is
+    /// guaranteed at registration. Registration tolerates an account that was already created by
+    /// a third party, since anyone may create an associated token account for the worker address.
     #[account(
-        init,
+        init_if_needed,
         payer = authority,
         associated_token::authority = worker,
         associated_token::mint = mint,
     )]
     pub worker_tokens: Account<'info, TokenAccount>,
-
 }
 
 pub fn handler(ctx: Context<WorkerCreate>) -> Result<()> {
@@ -0,0 +1,70 @@
+use {
+    crate::{errors::*, state::*},
+    anchor_lang::{
+        error::ErrorCode,
+        prelude::*,
+        solana_program::system_program,
+        system_program::{transfer, Transfer},
+    },
+    std::mem::size_of,
+};
+
+#[derive(Accounts)]
+pub struct WorkerMigrate<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: A legacy worker cannot be loaded as a `Worker`, so the handler decodes it.
+    #[account(mut, owner = crate::ID)]
+    pub worker: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<WorkerMigrate>) -> Result<()> {
+    // Get accounts
+    let payer = &ctx.accounts.payer;
+    let system_program = &ctx.accounts.system_program;
+    let worker = &ctx.accounts.worker;
+
+    // Verify the worker is in the legacy layout and at its address.
+    let legacy = {
+        let data = worker.try_borrow_data()?;
+        require!(
+            Worker::try_deserialize(&mut &data[..]).is_err(),
+            ClockworkError::WorkerAlreadyMigrated
+        );
+        LegacyWorker::try_from_account_data(&data)?
+    };
+    require_keys_eq!(
+        Worker::pubkey(legacy.id),
+        worker.key(),
+        ErrorCode::ConstraintSeeds
+    );
+
+    // Top up the rent for the larger account and grow it.
+    let space = 8 + size_of::<Worker>();
+    let rent_due = Rent::get()?
+        .minimum_balance(space)
+        .saturating_sub(worker.lamports());
+    if rent_due.gt(&0) {
+        transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                Transfer {
+                    from: payer.to_account_info(),
+                    to: worker.to_account_info(),
+                },
+            ),
+            rent_due,
+        )?;
+    }
+    worker.to_account_info().realloc(space, false)?;
+
+    // Rewrite the worker in the current layout.
+    let mut data = worker.try_borrow_mut_data()?;
+    legacy.migrate().try_serialize(&mut &mut data[..])?;
+
+    Ok(())
+}
@@ -13,7 +13,7 @@ pub struct DeleteSnapshotJob<'info> {
 
     #[account(
         address = Registry::pubkey(),
-        constraint = !registry.locked
+        constraint = !registry.is_locked()
     )]
     pub registry: Account<'info, Registry>,
 
@@ -41,5 +41,6 @@ pub fn handler(ctx: Context<DeleteSnapshotJob>) -> Result<AutomationResponse> {
             data: anchor_sighash("delete_snapshot_process_snapshot").to_vec(),
         }),
         trigger: None,
+        event_data: None,
     })
 }
@@ -7,7 +7,7 @@ pub struct DeleteSnapshotProcessEntry<'info> {
 
     #[account(
         address = Registry::pubkey(),
-        constraint = !registry.locked
+        constraint = !registry.is_locked()
     )]
     pub registry: Account<'info, Registry>,
 
@@ -127,5 +127,5 @@ pub fn handler(ctx: Context<DeleteSnapshotProcessEntry>) -> Result<AutomationRes
         None
     };
 
-    Ok( AutomationResponse { next_instruction, trigger: None } )
+    Ok( AutomationResponse { next_instruction, trigger: None, event_data: None } )
 }
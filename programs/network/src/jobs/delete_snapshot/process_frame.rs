@@ -9,7 +9,7 @@ pub struct DeleteSnapshotProcessFrame<'info> {
 
     #[account(
         address = Registry::pubkey(),
-        constraint = !registry.locked
+        constraint = !registry.is_locked()
     )]
     pub registry: Account<'info, Registry>,
 
@@ -9,7 +9,7 @@ pub struct DeleteSnapshotProcessSnapshot<'info> {
 
     #[account(
         address = Registry::pubkey(),
-        constraint = !registry.locked
+        constraint = !registry.is_locked()
     )]
     pub registry: Account<'info, Registry>,
 
@@ -68,5 +68,5 @@ pub fn handler(ctx: Context<DeleteSnapshotProcessSnapshot>) -> Result<Automation
         None
     };
 
-    Ok(AutomationResponse { next_instruction, trigger: None })
+    Ok(AutomationResponse { next_instruction, trigger: None, event_data: None })
 }
@@ -28,7 +28,7 @@ pub fn handler(ctx: Context<DistributeFeesJob>) -> Result<AutomationResponse> {
     let automation = &ctx.accounts.automation;
 
     // Lock the registry.
-    registry.locked = true;
+    registry.lock_reason = RegistryLockReason::Distributing;
 
     // Process the snapshot.
     Ok(AutomationResponse {
@@ -43,5 +43,6 @@ pub fn handler(ctx: Context<DistributeFeesJob>) -> Result<AutomationResponse> {
             data: anchor_sighash("distribute_fees_process_snapshot").to_vec(),
         }),
         trigger: None,
+        event_data: None,
     })
 }
@@ -76,15 +76,8 @@ pub fn handler(ctx: Context<DistributeFeesProcessEntry>) -> Result<AutomationRes
     let worker = &ctx.accounts.worker;
 
     // Calculate the balance of this particular delegation, based on the weight of its stake with this worker.
-    let distribution_balance = if snapshot_frame.stake_amount.gt(&0) {
-        fee.distributable_balance
-            .checked_mul(snapshot_entry.stake_amount)
-            .unwrap()
-            .checked_div(snapshot_frame.stake_amount)
-            .unwrap()
-    } else {
-        0
-    };
+    let distribution_balance =
+        snapshot_frame.stake_share(fee.distributable_balance, snapshot_entry.stake_amount);
 
     // Transfer yield to the worker.
     **fee.to_account_info().try_borrow_mut_lamports()? = fee
@@ -103,6 +96,12 @@ pub fn handler(ctx: Context<DistributeFeesProcessEntry>) -> Result<AutomationRes
         .yield_balance
         .checked_add(distribution_balance)
         .unwrap();
+    emit!(YieldDistributed {
+        worker: worker.key(),
+        delegation: delegation.key(),
+        epoch: registry.current_epoch,
+        amount: distribution_balance,
+    });
 
     // Build the next instruction for the automation.
     let next_instruction = if snapshot_entry
@@ -163,5 +162,6 @@ pub fn handler(ctx: Context<DistributeFeesProcessEntry>) -> Result<AutomationRes
     Ok(AutomationResponse {
         next_instruction,
         trigger: None,
+        event_data: None,
     })
 }
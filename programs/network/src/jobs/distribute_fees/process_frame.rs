@@ -55,17 +55,13 @@ pub fn handler(ctx: Context<DistributeFeesProcessFrame>) -> Result<AutomationRes
     let worker = &mut ctx.accounts.worker;
 
     // Calculate the fee account's usuable balance.
-    let fee_lamport_balance = fee.to_account_info().lamports();
-    let fee_data_len = 8 + fee.try_to_vec()?.len();
-    let fee_rent_balance = Rent::get().unwrap().minimum_balance(fee_data_len);
-    let fee_usable_balance = fee_lamport_balance.checked_sub(fee_rent_balance).unwrap();
+    let fee_usable_balance = fee.usable_balance()?;
+
+    // Apply a commission rate change staged before this epoch.
+    worker.apply_pending_commission_rate(registry.current_epoch);
 
     // Calculate the commission to be retained by the worker.
-    let commission_balance = fee_usable_balance
-        .checked_mul(worker.commission_rate)
-        .unwrap()
-        .checked_div(100)
-        .unwrap();
+    let commission_balance = worker.commission(fee_usable_balance);
 
     // Transfer commission to the worker.
     **fee.to_account_info().try_borrow_mut_lamports()? = fee
@@ -86,7 +82,15 @@ pub fn handler(ctx: Context<DistributeFeesProcessFrame>) -> Result<AutomationRes
         .unwrap();
 
     // Record the balance that is distributable to delegations.
+    // Delegations' shares are rounded down. The few lamports of remainder stay in the fee account
+    // and are part of the usable balance, and so of the commission and shares, in the next epoch.
     fee.distributable_balance = fee_usable_balance.checked_sub(commission_balance).unwrap();
+    emit!(FeeDistributed {
+        worker: worker.key(),
+        epoch: registry.current_epoch,
+        commission_amount: commission_balance,
+        distributable_amount: fee.distributable_balance,
+    });
 
     // Build next instruction for the automation.
     let next_instruction = if snapshot_frame.total_entries.gt(&0) {
@@ -138,5 +142,6 @@ pub fn handler(ctx: Context<DistributeFeesProcessFrame>) -> Result<AutomationRes
     Ok(AutomationResponse {
         next_instruction,
         trigger: None,
+        event_data: None,
     })
 }
@@ -66,5 +66,6 @@ pub fn handler(ctx: Context<DistributeFeesProcessSnapshot>) -> Result<Automation
             None
         },
         trigger: None,
+        event_data: None,
     })
 }
@@ -22,9 +22,10 @@ pub struct EpochCutover<'info> {
 pub fn handler(ctx: Context<EpochCutover>) -> Result<AutomationResponse> {
     let registry = &mut ctx.accounts.registry;
     registry.current_epoch = registry.current_epoch.checked_add(1).unwrap();
-    registry.locked = false;
+    registry.lock_reason = RegistryLockReason::Unlocked;
     Ok(AutomationResponse {
         next_instruction: None,
         trigger: None,
+        event_data: None,
     })
 }
@@ -11,8 +11,9 @@ pub struct ProcessUnstakesJob<'info> {
     pub config: Account<'info, Config>,
 
     #[account(
+        mut,
         address = Registry::pubkey(),
-        constraint = registry.locked
+        constraint = registry.is_locked()
     )]
     pub registry: Account<'info, Registry>,
 
@@ -23,9 +24,12 @@ pub struct ProcessUnstakesJob<'info> {
 pub fn handler(ctx: Context<ProcessUnstakesJob>) -> Result<AutomationResponse> {
     // Get accounts.
     let config = &ctx.accounts.config;
-    let registry = &ctx.accounts.registry;
+    let registry = &mut ctx.accounts.registry;
     let automation = &ctx.accounts.automation;
 
+    // Record why the registry is locked.
+    registry.lock_reason = RegistryLockReason::Staking;
+
     // Return next instruction for automation.
     Ok(AutomationResponse {
         next_instruction: if registry.total_unstakes.gt(&0) {
@@ -43,5 +47,6 @@ pub fn handler(ctx: Context<ProcessUnstakesJob>) -> Result<AutomationResponse> {
             None
         },
         trigger: None,
+        event_data: None,
     })
 }
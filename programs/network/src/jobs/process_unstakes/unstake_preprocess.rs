@@ -13,7 +13,7 @@ pub struct UnstakePreprocess<'info> {
 
     #[account(
         address = Registry::pubkey(),
-        constraint = registry.locked
+        constraint = registry.is_locked()
     )]
     pub registry: Account<'info, Registry>,
 
@@ -30,6 +30,7 @@ pub fn handler(ctx: Context<UnstakePreprocess>) -> Result<AutomationResponse> {
     let registry = &ctx.accounts.registry;
     let automation = &ctx.accounts.automation;
     let unstake = &ctx.accounts.unstake;
+    let mint = unstake.stake_kind.mint(config);
 
     // Return next instruction for automation.
     Ok(AutomationResponse {
@@ -37,6 +38,10 @@ pub fn handler(ctx: Context<UnstakePreprocess>) -> Result<AutomationResponse> {
             program_id: crate::ID,
             accounts: vec![
                 AccountMetaData::new_readonly(unstake.authority, false),
+                AccountMetaData::new(
+                    get_associated_token_address(&unstake.authority, &mint),
+                    false,
+                ),
                 AccountMetaData::new_readonly(config.key(), false),
                 AccountMetaData::new(unstake.delegation, false),
                 AccountMetaData::new(registry.key(), false),
@@ -45,7 +50,7 @@ pub fn handler(ctx: Context<UnstakePreprocess>) -> Result<AutomationResponse> {
                 AccountMetaData::new(unstake.key(), false),
                 AccountMetaData::new_readonly(unstake.worker, false),
                 AccountMetaData::new(
-                    get_associated_token_address(&unstake.worker, &config.mint),
+                    get_associated_token_address(&unstake.worker, &mint),
                     false,
                 ),
             ],
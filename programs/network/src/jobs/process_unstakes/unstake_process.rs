@@ -14,7 +14,7 @@ pub struct UnstakeProcess<'info> {
     #[account(
         mut,
         associated_token::authority = delegation.authority,
-        associated_token::mint = config.mint,
+        associated_token::mint = delegation.stake_kind.mint(&config),
     )]
     pub authority_tokens: Box<Account<'info, TokenAccount>>,
 
@@ -65,7 +65,7 @@ pub struct UnstakeProcess<'info> {
     #[account(
         mut,
         associated_token::authority = worker,
-        associated_token::mint = config.mint,
+        associated_token::mint = delegation.stake_kind.mint(&config),
     )]
     pub worker_tokens: Box<Account<'info, TokenAccount>>,
 }
@@ -154,5 +154,6 @@ pub fn handler(ctx: Context<UnstakeProcess>) -> Result<AutomationResponse> {
     Ok(AutomationResponse {
         next_instruction,
         trigger: None,
+        event_data: None,
     })
 }
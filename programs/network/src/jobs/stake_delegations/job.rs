@@ -11,8 +11,9 @@ pub struct StakeDelegationsJob<'info> {
     pub config: Account<'info, Config>,
 
     #[account(
+        mut,
         address = Registry::pubkey(),
-        constraint = registry.locked
+        constraint = registry.is_locked()
     )]
     pub registry: Account<'info, Registry>,
 
@@ -22,9 +23,12 @@ pub struct StakeDelegationsJob<'info> {
 
 pub fn handler(ctx: Context<StakeDelegationsJob>) -> Result<AutomationResponse> {
     let config = &ctx.accounts.config;
-    let registry = &ctx.accounts.registry;
+    let registry = &mut ctx.accounts.registry;
     let automation = &ctx.accounts.automation;
 
+    // Record why the registry is locked.
+    registry.lock_reason = RegistryLockReason::Staking;
+
     Ok(AutomationResponse {
         next_instruction: if registry.total_workers.gt(&0) {
             Some(InstructionData {
@@ -41,5 +45,6 @@ pub fn handler(ctx: Context<StakeDelegationsJob>) -> Result<AutomationResponse>
             None
         },
         trigger: None,
+        event_data: None,
     })
 }
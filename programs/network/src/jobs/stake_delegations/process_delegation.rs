@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::get_associated_token_address,
-    token::{transfer, Token, TokenAccount, Transfer},
+    token::{spl_token::native_mint, transfer, Token, TokenAccount, Transfer},
 };
 use clockwork_utils::automation::{
     anchor_sighash, AccountMetaData, InstructionData, AutomationResponse,
@@ -26,15 +26,23 @@ pub struct StakeDelegationsProcessDelegation<'info> {
     )]
     pub delegation: Account<'info, Delegation>,
 
+    /// CHECK: The delegation's token stake account. Only used if the delegation stakes the network token.
     #[account(
-        associated_token::authority = delegation,
-        associated_token::mint = config.mint,
+        mut,
+        address = get_associated_token_address(&delegation.key(), &config.mint)
     )]
-    pub delegation_stake: Account<'info, TokenAccount>,
+    pub delegation_stake: UncheckedAccount<'info>,
+
+    /// CHECK: The delegation's wrapped SOL stake account. Only used if the delegation stakes native SOL.
+    #[account(
+        mut,
+        address = get_associated_token_address(&delegation.key(), &native_mint::ID)
+    )]
+    pub delegation_native_stake: UncheckedAccount<'info>,
 
     #[account(
         address = Registry::pubkey(),
-        constraint = registry.locked
+        constraint = registry.is_locked()
     )]
     pub registry: Account<'info, Registry>,
 
@@ -44,36 +52,84 @@ pub struct StakeDelegationsProcessDelegation<'info> {
     #[account(address = anchor_spl::token::ID)]
     pub token_program: Program<'info, Token>,
 
-    #[account(address = worker.pubkey())]
+    #[account(
+        seeds = [
+            SEED_WORKER,
+            worker.id.to_be_bytes().as_ref(),
+        ],
+        bump,
+    )]
     pub worker: Account<'info, Worker>,
 
     #[account(
+        mut,
         associated_token::authority = worker,
         associated_token::mint = config.mint,
     )]
     pub worker_stake: Account<'info, TokenAccount>,
+
+    /// CHECK: The worker's wrapped SOL stake account. This account does not exist until the worker's first
+    /// native delegation is created.
+    #[account(
+        mut,
+        address = get_associated_token_address(&worker.key(), &native_mint::ID)
+    )]
+    pub worker_native_stake: UncheckedAccount<'info>,
 }
 
 pub fn handler(ctx: Context<StakeDelegationsProcessDelegation>) -> Result<AutomationResponse> {
     // Get accounts.
     let config = &ctx.accounts.config;
     let delegation = &mut ctx.accounts.delegation;
-    let delegation_stake = &mut ctx.accounts.delegation_stake;
     let registry = &ctx.accounts.registry;
     let automation = &ctx.accounts.automation;
     let token_program = &ctx.accounts.token_program;
     let worker = &ctx.accounts.worker;
-    let worker_stake = &ctx.accounts.worker_stake;
 
-    // Transfer tokens from delegation to worker account.
-    let amount = delegation_stake.amount;
+    // Use the stake accounts for the kind of asset the delegation stakes.
+    let (delegation_stake, worker_stake) = match delegation.stake_kind {
+        StakeKind::Token => (
+            ctx.accounts.delegation_stake.to_account_info(),
+            ctx.accounts.worker_stake.to_account_info(),
+        ),
+        StakeKind::Native => (
+            ctx.accounts.delegation_native_stake.to_account_info(),
+            ctx.accounts.worker_native_stake.to_account_info(),
+        ),
+    };
+
+    // Unbond any stake requested for withdrawal, so it is excluded from the next snapshot.
+    let unbond_amount = delegation.unbond_pending_withdrawal();
+    if unbond_amount.gt(&0) {
+        let worker_bump = *ctx.bumps.get("worker").unwrap();
+        transfer(
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                Transfer {
+                    from: worker_stake.clone(),
+                    to: delegation_stake.clone(),
+                    authority: worker.to_account_info(),
+                },
+                &[&[SEED_WORKER, worker.id.to_be_bytes().as_ref(), &[worker_bump]]],
+            ),
+            unbond_amount,
+        )?;
+        emit!(DelegationUnbonded {
+            worker: worker.key(),
+            delegation: delegation.key(),
+            amount: unbond_amount,
+        });
+    }
+
+    // Transfer tokens from delegation to worker account, holding back any that are unbonding.
+    let amount = stake_balance(&delegation_stake)?.saturating_sub(delegation.unbonding_amount);
     let bump = *ctx.bumps.get("delegation").unwrap();
     transfer(
         CpiContext::new_with_signer(
             token_program.to_account_info(),
             Transfer {
-                from: delegation_stake.to_account_info(),
-                to: worker_stake.to_account_info(),
+                from: delegation_stake,
+                to: worker_stake,
                 authority: delegation.to_account_info(),
             },
             &[&[
@@ -88,6 +144,11 @@ pub fn handler(ctx: Context<StakeDelegationsProcessDelegation>) -> Result<Automa
 
     // Update the delegation's stake amount.
     delegation.stake_amount = delegation.stake_amount.checked_add(amount).unwrap();
+    emit!(DelegationStaked {
+        worker: worker.key(),
+        delegation: delegation.key(),
+        amount,
+    });
 
     // Build next instruction for the automation.
     let next_instruction = if delegation
@@ -108,11 +169,16 @@ pub fn handler(ctx: Context<StakeDelegationsProcessDelegation>) -> Result<Automa
                     get_associated_token_address(&next_delegation_pubkey, &config.mint),
                     false,
                 ),
+                AccountMetaData::new(
+                    get_associated_token_address(&next_delegation_pubkey, &native_mint::ID),
+                    false,
+                ),
                 AccountMetaData::new_readonly(registry.key(), false),
                 AccountMetaData::new_readonly(automation.key(), true),
                 AccountMetaData::new_readonly(token_program.key(), false),
                 AccountMetaData::new_readonly(worker.key(), false),
-                AccountMetaData::new(worker_stake.key(), false),
+                AccountMetaData::new(ctx.accounts.worker_stake.key(), false),
+                AccountMetaData::new(ctx.accounts.worker_native_stake.key(), false),
             ],
             data: anchor_sighash("stake_delegations_process_delegation").to_vec(),
         })
@@ -143,5 +209,6 @@ pub fn handler(ctx: Context<StakeDelegationsProcessDelegation>) -> Result<Automa
     Ok(AutomationResponse {
         next_instruction,
         trigger: None,
+        event_data: None,
     })
 }
@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::{associated_token::get_associated_token_address, token::spl_token::native_mint};
 use clockwork_utils::automation::{
     anchor_sighash, AccountMetaData, InstructionData, AutomationResponse,
 };
@@ -13,7 +13,7 @@ pub struct StakeDelegationsProcessWorker<'info> {
 
     #[account(
         address = Registry::pubkey(),
-        constraint = registry.locked
+        constraint = registry.is_locked()
     )]
     pub registry: Account<'info, Registry>,
 
@@ -44,6 +44,10 @@ pub fn handler(ctx: Context<StakeDelegationsProcessWorker>) -> Result<Automation
                     get_associated_token_address(&delegation_pubkey, &config.mint),
                     false,
                 ),
+                AccountMetaData::new(
+                    get_associated_token_address(&delegation_pubkey, &native_mint::ID),
+                    false,
+                ),
                 AccountMetaData::new_readonly(registry.key(), false),
                 AccountMetaData::new_readonly(automation.key(), true),
                 AccountMetaData::new_readonly(anchor_spl::token::ID, false),
@@ -52,6 +56,10 @@ pub fn handler(ctx: Context<StakeDelegationsProcessWorker>) -> Result<Automation
                     get_associated_token_address(&worker.key(), &config.mint),
                     false,
                 ),
+                AccountMetaData::new(
+                    get_associated_token_address(&worker.key(), &native_mint::ID),
+                    false,
+                ),
             ],
             data: anchor_sighash("stake_delegations_process_delegation").to_vec(),
         })
@@ -82,5 +90,6 @@ pub fn handler(ctx: Context<StakeDelegationsProcessWorker>) -> Result<Automation
     Ok(AutomationResponse {
         next_instruction,
         trigger: None,
+        event_data: None,
     })
 }
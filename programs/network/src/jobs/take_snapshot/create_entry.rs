@@ -1,5 +1,5 @@
 use anchor_lang::{prelude::*, solana_program::system_program};
-use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::{associated_token::get_associated_token_address, token::spl_token::native_mint};
 use clockwork_utils::automation::{
     anchor_sighash, AccountMetaData, InstructionData, AutomationResponse, PAYER_PUBKEY,
 };
@@ -24,7 +24,7 @@ pub struct TakeSnapshotCreateEntry<'info> {
 
     #[account(
         address = Registry::pubkey(),
-        constraint = registry.locked
+        constraint = registry.is_locked()
     )]
     pub registry: Box<Account<'info, Registry>>,
 
@@ -85,12 +85,12 @@ pub fn handler(ctx: Context<TakeSnapshotCreateEntry>) -> Result<AutomationRespon
     let automation = &ctx.accounts.automation;
     let worker = &ctx.accounts.worker;
 
-    // Initialize snapshot entry account.
+    // Initialize snapshot entry account, weighing the delegation's stake in lamports.
     snapshot_entry.init(
         delegation.key(),
         snapshot_frame.total_entries,
         snapshot_frame.key(),
-        delegation.stake_amount,
+        config.stake_weight(delegation.stake_kind, delegation.stake_amount),
     )?;
 
     // Update the snapshot frame.
@@ -141,6 +141,10 @@ pub fn handler(ctx: Context<TakeSnapshotCreateEntry>) -> Result<AutomationRespon
                     get_associated_token_address(&next_worker_pubkey, &config.mint),
                     false,
                 ),
+                AccountMetaData::new_readonly(
+                    get_associated_token_address(&next_worker_pubkey, &native_mint::ID),
+                    false,
+                ),
             ],
             data: anchor_sighash("take_snapshot_create_frame").to_vec(),
         })
@@ -1,5 +1,8 @@
 use anchor_lang::{prelude::*, solana_program::system_program};
-use anchor_spl::{associated_token::get_associated_token_address, token::TokenAccount};
+use anchor_spl::{
+    associated_token::get_associated_token_address,
+    token::{spl_token::native_mint, TokenAccount},
+};
 use clockwork_utils::automation::{
     anchor_sighash, AccountMetaData, InstructionData, AutomationResponse, PAYER_PUBKEY,
 };
@@ -17,7 +20,7 @@ pub struct TakeSnapshotCreateFrame<'info> {
 
     #[account(
         address = Registry::pubkey(),
-        constraint = registry.locked
+        constraint = registry.is_locked()
     )]
     pub registry: Account<'info, Registry>,
 
@@ -63,6 +66,10 @@ pub struct TakeSnapshotCreateFrame<'info> {
         associated_token::mint = config.mint,
     )]
     pub worker_stake: Account<'info, TokenAccount>,
+
+    /// CHECK: The worker's wrapped SOL stake account. This account may not exist.
+    #[account(address = get_associated_token_address(&worker.key(), &native_mint::ID))]
+    pub worker_native_stake: UncheckedAccount<'info>,
 }
 
 pub fn handler(ctx: Context<TakeSnapshotCreateFrame>) -> Result<AutomationResponse> {
@@ -75,21 +82,31 @@ pub fn handler(ctx: Context<TakeSnapshotCreateFrame>) -> Result<AutomationRespon
     let automation = &ctx.accounts.automation;
     let worker = &ctx.accounts.worker;
     let worker_stake = &ctx.accounts.worker_stake;
+    let worker_native_stake = &ctx.accounts.worker_native_stake;
+
+    // Weigh the worker's token and native stake together in lamports.
+    let stake_amount = config
+        .stake_weight(StakeKind::Token, worker_stake.amount)
+        .checked_add(stake_balance(&worker_native_stake.to_account_info())?)
+        .unwrap();
 
     // Initialize snapshot frame account.
     snapshot_frame.init(
         snapshot.total_frames,
         snapshot.key(),
-        worker_stake.amount,
+        stake_amount,
         snapshot.total_stake,
         worker.key(),
     )?;
+    emit!(SnapshotFrameCreated {
+        snapshot: snapshot.key(),
+        worker: worker.key(),
+        stake_amount,
+        stake_offset: snapshot.total_stake,
+    });
 
     // Update snapshot total workers.
-    snapshot.total_stake = snapshot
-        .total_stake
-        .checked_add(worker_stake.amount)
-        .unwrap();
+    snapshot.total_stake = snapshot.total_stake.checked_add(stake_amount).unwrap();
     snapshot.total_frames = snapshot.total_frames.checked_add(1).unwrap();
 
     // Build the next instruction for the automation.
@@ -133,6 +150,10 @@ pub fn handler(ctx: Context<TakeSnapshotCreateFrame>) -> Result<AutomationRespon
                     get_associated_token_address(&next_worker_pubkey, &config.mint),
                     false,
                 ),
+                AccountMetaData::new_readonly(
+                    get_associated_token_address(&next_worker_pubkey, &native_mint::ID),
+                    false,
+                ),
             ],
             data: anchor_sighash("take_snapshot_create_frame").to_vec(),
         })
@@ -143,5 +164,6 @@ pub fn handler(ctx: Context<TakeSnapshotCreateFrame>) -> Result<AutomationRespon
     Ok(AutomationResponse {
         next_instruction,
         trigger: None,
+        event_data: None,
     })
 }
@@ -1,5 +1,5 @@
 use anchor_lang::{prelude::*, solana_program::system_program};
-use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::{associated_token::get_associated_token_address, token::spl_token::native_mint};
 use clockwork_utils::automation::{
     anchor_sighash, AccountMetaData, InstructionData, AutomationResponse, PAYER_PUBKEY,
 };
@@ -17,7 +17,7 @@ pub struct TakeSnapshotCreateSnapshot<'info> {
 
     #[account(
         address = Registry::pubkey(),
-        constraint = registry.locked
+        constraint = registry.is_locked()
     )]
     pub registry: Account<'info, Registry>,
 
@@ -71,6 +71,10 @@ pub fn handler(ctx: Context<TakeSnapshotCreateSnapshot>) -> Result<AutomationRes
                         get_associated_token_address(&worker_pubkey, &config.mint),
                         false,
                     ),
+                    AccountMetaData::new_readonly(
+                        get_associated_token_address(&worker_pubkey, &native_mint::ID),
+                        false,
+                    ),
                 ],
                 data: anchor_sighash("take_snapshot_create_frame").to_vec(),
             })
@@ -78,5 +82,6 @@ pub fn handler(ctx: Context<TakeSnapshotCreateSnapshot>) -> Result<AutomationRes
             None
         },
         trigger: None,
+        event_data: None,
     })
 }
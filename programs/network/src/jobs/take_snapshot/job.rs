@@ -11,8 +11,9 @@ pub struct TakeSnapshotJob<'info> {
     pub config: Account<'info, Config>,
 
     #[account(
+        mut,
         address = Registry::pubkey(),
-        constraint = registry.locked
+        constraint = registry.is_locked()
     )]
     pub registry: Account<'info, Registry>,
 
@@ -23,9 +24,12 @@ pub struct TakeSnapshotJob<'info> {
 pub fn handler(ctx: Context<TakeSnapshotJob>) -> Result<AutomationResponse> {
     // Get accounts
     let config = &ctx.accounts.config;
-    let registry = &ctx.accounts.registry;
+    let registry = &mut ctx.accounts.registry;
     let automation = &ctx.accounts.automation;
 
+    // Record why the registry is locked.
+    registry.lock_reason = RegistryLockReason::Snapshotting;
+
     Ok(AutomationResponse {
         next_instruction: Some(InstructionData {
             program_id: crate::ID,
@@ -43,5 +47,6 @@ pub fn handler(ctx: Context<TakeSnapshotJob>) -> Result<AutomationResponse> {
             data: anchor_sighash("take_snapshot_create_snapshot").to_vec(),
         }),
         trigger: None,
+        event_data: None,
     })
 }
@@ -21,6 +21,22 @@ declare_id!("F8dKseqmBoAkHx3c58Lmb9TgJv5qeTf3BbtZZSEzYvUa");
 pub mod network_program {
     use super::*;
 
+    pub fn config_accept_admin(ctx: Context<ConfigAcceptAdmin>) -> Result<()> {
+        config_accept_admin::handler(ctx)
+    }
+
+    pub fn config_cancel_admin(ctx: Context<ConfigCancelAdmin>) -> Result<()> {
+        config_cancel_admin::handler(ctx)
+    }
+
+    pub fn config_migrate(ctx: Context<ConfigMigrate>) -> Result<()> {
+        config_migrate::handler(ctx)
+    }
+
+    pub fn config_propose_admin(ctx: Context<ConfigProposeAdmin>, pending_admin: Pubkey) -> Result<()> {
+        config_propose_admin::handler(ctx, pending_admin)
+    }
+
     pub fn config_update(ctx: Context<ConfigUpdate>, settings: ConfigSettings) -> Result<()> {
         config_update::handler(ctx, settings)
     }
@@ -29,18 +45,41 @@ pub mod network_program {
         delegation_claim::handler(ctx, amount)
     }
 
-    pub fn delegation_create(ctx: Context<DelegationCreate>) -> Result<()> {
-        delegation_create::handler(ctx)
+    pub fn delegation_create(ctx: Context<DelegationCreate>, stake_kind: StakeKind) -> Result<()> {
+        delegation_create::handler(ctx, stake_kind)
     }
 
     pub fn delegation_deposit(ctx: Context<DelegationDeposit>, amount: u64) -> Result<()> {
         delegation_deposit::handler(ctx, amount)
     }
 
+    pub fn delegation_merge(ctx: Context<DelegationMerge>) -> Result<()> {
+        delegation_merge::handler(ctx)
+    }
+
+    pub fn delegation_migrate(ctx: Context<DelegationMigrate>) -> Result<()> {
+        delegation_migrate::handler(ctx)
+    }
+
     pub fn delegation_withdraw(ctx: Context<DelegationWithdraw>, amount: u64) -> Result<()> {
         delegation_withdraw::handler(ctx, amount)
     }
 
+    pub fn delegation_withdraw_claim(ctx: Context<DelegationWithdrawClaim>) -> Result<()> {
+        delegation_withdraw_claim::handler(ctx)
+    }
+
+    pub fn delegation_withdraw_request(
+        ctx: Context<DelegationWithdrawRequest>,
+        amount: u64,
+    ) -> Result<()> {
+        delegation_withdraw_request::handler(ctx, amount)
+    }
+
+    pub fn fee_reconcile(ctx: Context<FeeReconcile>) -> Result<()> {
+        fee_reconcile::handler(ctx)
+    }
+
     pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
         initialize::handler(ctx)
     }
@@ -61,6 +100,14 @@ pub mod network_program {
         pool_update::handler(ctx, settings)
     }
 
+    pub fn program_pause(ctx: Context<ProgramPause>, program_id: Pubkey) -> Result<()> {
+        program_pause::handler(ctx, program_id)
+    }
+
+    pub fn program_resume(ctx: Context<ProgramResume>, program_id: Pubkey) -> Result<()> {
+        program_resume::handler(ctx, program_id)
+    }
+
     pub fn registry_nonce_hash(ctx: Context<RegistryNonceHash>) -> Result<AutomationResponse> {
         registry_nonce_hash::handler(ctx)
     }
@@ -73,14 +120,29 @@ pub mod network_program {
         unstake_create::handler(ctx, amount)
     }
 
+    pub fn unstake_migrate(ctx: Context<UnstakeMigrate>) -> Result<()> {
+        unstake_migrate::handler(ctx)
+    }
+
     pub fn worker_claim(ctx: Context<WorkerClaim>, amount: u64) -> Result<()> {
         worker_claim::handler(ctx, amount)
     }
 
+    pub fn worker_commission_update(
+        ctx: Context<WorkerCommissionUpdate>,
+        commission_rate: u64,
+    ) -> Result<()> {
+        worker_commission_update::handler(ctx, commission_rate)
+    }
+
     pub fn worker_create(ctx: Context<WorkerCreate>) -> Result<()> {
         worker_create::handler(ctx)
     }
 
+    pub fn worker_migrate(ctx: Context<WorkerMigrate>) -> Result<()> {
+        worker_migrate::handler(ctx)
+    }
+
     pub fn worker_update(ctx: Context<WorkerUpdate>, settings: WorkerSettings) -> Result<()> {
         worker_update::handler(ctx, settings)
     }
@@ -1,12 +1,16 @@
-use anchor_lang::{prelude::*, AnchorDeserialize};
+use anchor_lang::{error::ErrorCode, prelude::*, AnchorDeserialize, Discriminator};
 use clockwork_macros::TryFromData;
 
+use crate::{errors::ClockworkError, state::StakeKind};
+
 pub const SEED_CONFIG: &[u8] = b"config";
 
 /**
  * Config
  */
 
+// Fields added after the legacy layout are appended in the order they were introduced, so deployed configs
+// keep their layout and can be grown in place by `config_migrate`.
 #[account]
 #[derive(Debug, TryFromData)]
 pub struct Config {
@@ -14,12 +18,86 @@ pub struct Config {
     pub epoch_automation: Pubkey,
     pub hasher_automation: Pubkey,
     pub mint: Pubkey,
+    pub creation_fee: u64,
+    pub treasury: Pubkey,
+    pub pending_admin: Option<Pubkey>,
+    /// The lamports-equivalent weight of one base unit of the network token, for weighing it against native stake.
+    pub token_stake_rate: u64,
 }
 
 impl Config {
     pub fn pubkey() -> Pubkey {
         Pubkey::find_program_address(&[SEED_CONFIG], &crate::ID).0
     }
+
+    /// Proposes a transfer of the admin role. The transfer takes effect once the pending admin accepts it.
+    pub fn propose_admin(&mut self, pending_admin: Pubkey) {
+        self.pending_admin = Some(pending_admin);
+    }
+
+    /// Transfers the admin role to the signer, if it is the pending admin.
+    pub fn accept_admin(&mut self, signer: Pubkey) -> Result<()> {
+        require!(
+            self.pending_admin.eq(&Some(signer)),
+            ClockworkError::InvalidPendingAdmin
+        );
+        self.admin = signer;
+        self.pending_admin = None;
+        Ok(())
+    }
+
+    /// Cancels the pending transfer of the admin role.
+    pub fn cancel_admin(&mut self) -> Result<()> {
+        require!(self.pending_admin.is_some(), ClockworkError::InvalidPendingAdmin);
+        self.pending_admin = None;
+        Ok(())
+    }
+
+    /// Returns the lamports-equivalent weight of a stake amount of the given kind.
+    pub fn stake_weight(&self, stake_kind: StakeKind, amount: u64) -> u64 {
+        match stake_kind {
+            StakeKind::Token => amount.saturating_mul(self.token_stake_rate),
+            StakeKind::Native => amount,
+        }
+    }
+}
+
+/**
+ * LegacyConfig
+ */
+
+/// The layout of configs initialized before the creation fee was introduced.
+#[derive(AnchorDeserialize, Debug)]
+pub struct LegacyConfig {
+    pub admin: Pubkey,
+    pub epoch_automation: Pubkey,
+    pub hasher_automation: Pubkey,
+    pub mint: Pubkey,
+}
+
+impl LegacyConfig {
+    /// Decodes the legacy fields that prefix the data of every config account, in any layout.
+    pub fn try_from_account_data(data: &[u8]) -> Result<Self> {
+        require!(
+            data.len().ge(&8) && data[..8].eq(&Config::discriminator()),
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        Self::deserialize(&mut &data[8..]).map_err(|_| error!(ErrorCode::AccountDidNotDeserialize))
+    }
+
+    /// Returns the config in the current layout, with the fields added since set to their defaults.
+    pub fn migrate(self) -> Config {
+        Config {
+            admin: self.admin,
+            epoch_automation: self.epoch_automation,
+            hasher_automation: self.hasher_automation,
+            mint: self.mint,
+            creation_fee: 0,
+            treasury: self.admin,
+            pending_admin: None,
+            token_stake_rate: 1,
+        }
+    }
 }
 
 /**
@@ -28,10 +106,12 @@ impl Config {
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct ConfigSettings {
-    pub admin: Pubkey,
+    pub creation_fee: u64,
     pub epoch_automation: Pubkey,
     pub hasher_automation: Pubkey,
     pub mint: Pubkey,
+    pub token_stake_rate: u64,
+    pub treasury: Pubkey,
 }
 
 /**
@@ -47,15 +127,125 @@ pub trait ConfigAccount {
 impl ConfigAccount for Account<'_, Config> {
     fn init(&mut self, admin: Pubkey, mint: Pubkey) -> Result<()> {
         self.admin = admin;
+        self.creation_fee = 0;
         self.mint = mint;
+        self.pending_admin = None;
+        self.token_stake_rate = 1;
+        self.treasury = admin;
         Ok(())
     }
 
     fn update(&mut self, settings: ConfigSettings) -> Result<()> {
-        self.admin = settings.admin;
+        self.creation_fee = settings.creation_fee;
         self.epoch_automation = settings.epoch_automation;
         self.hasher_automation = settings.hasher_automation;
         self.mint = settings.mint;
+        self.token_stake_rate = settings.token_stake_rate;
+        self.treasury = settings.treasury;
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> Config {
+        Config {
+            admin: Pubkey::new_unique(),
+            epoch_automation: Pubkey::new_unique(),
+            hasher_automation: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            creation_fee: 5_000,
+            treasury: Pubkey::new_unique(),
+            pending_admin: Some(Pubkey::new_unique()),
+            token_stake_rate: 3,
+        }
+    }
+
+    #[test]
+    fn test_legacy_config_prefixes_current_layout() {
+        let config = config();
+        let mut data = vec![];
+        config.try_serialize(&mut data).unwrap();
+
+        let legacy = LegacyConfig::try_from_account_data(&data).unwrap();
+        assert_eq!(legacy.admin, config.admin);
+        assert_eq!(legacy.epoch_automation, config.epoch_automation);
+        assert_eq!(legacy.hasher_automation, config.hasher_automation);
+        assert_eq!(legacy.mint, config.mint);
+        assert!(LegacyConfig::try_from_account_data(&[0; 136]).is_err());
+    }
+
+    #[test]
+    fn test_legacy_config_migrate() {
+        let legacy = LegacyConfig {
+            admin: Pubkey::new_unique(),
+            epoch_automation: Pubkey::new_unique(),
+            hasher_automation: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+        };
+        let admin = legacy.admin;
+        let config = legacy.migrate();
+        assert_eq!(config.admin, admin);
+        assert_eq!(config.creation_fee, 0);
+        assert_eq!(config.treasury, admin);
+        assert_eq!(config.pending_admin, None);
+        assert_eq!(config.token_stake_rate, 1);
+    }
+
+    #[test]
+    fn test_accept_admin() {
+        let mut config = config();
+        let old_admin = config.admin;
+        let new_admin = Pubkey::new_unique();
+        config.propose_admin(new_admin);
+
+        // Only the pending admin may accept.
+        assert_eq!(
+            config.accept_admin(old_admin).unwrap_err(),
+            error!(ClockworkError::InvalidPendingAdmin)
+        );
+        assert_eq!(
+            config.accept_admin(Pubkey::new_unique()).unwrap_err(),
+            error!(ClockworkError::InvalidPendingAdmin)
+        );
+        assert_eq!(config.admin, old_admin);
+
+        // Once accepted, the old admin no longer controls the config and cannot reclaim it.
+        config.accept_admin(new_admin).unwrap();
+        assert_eq!(config.admin, new_admin);
+        assert_ne!(config.admin, old_admin);
+        assert_eq!(config.pending_admin, None);
+        assert!(config.accept_admin(old_admin).is_err());
+        assert!(config.accept_admin(new_admin).is_err());
+    }
+
+    #[test]
+    fn test_cancel_admin() {
+        let mut config = config();
+        let admin = config.admin;
+        let pending_admin = Pubkey::new_unique();
+        config.propose_admin(pending_admin);
+
+        config.cancel_admin().unwrap();
+        assert_eq!(config.pending_admin, None);
+        assert_eq!(config.admin, admin);
+
+        // A cancelled transfer can no longer be accepted, or cancelled again.
+        assert!(config.accept_admin(pending_admin).is_err());
+        assert_eq!(
+            config.cancel_admin().unwrap_err(),
+            error!(ClockworkError::InvalidPendingAdmin)
+        );
+    }
+
+    #[test]
+    fn test_stake_weight() {
+        let config = config();
+        assert_eq!(config.stake_weight(StakeKind::Token, 100), 300);
+        assert_eq!(config.stake_weight(StakeKind::Native, 100), 100);
+        assert_eq!(config.stake_weight(StakeKind::Token, u64::MAX), u64::MAX);
+        assert_eq!(StakeKind::Token.mint(&config), config.mint);
+    }
+}
@@ -1,8 +1,14 @@
-use anchor_lang::{prelude::*, AnchorDeserialize};
+use anchor_lang::{error::ErrorCode, prelude::*, AnchorDeserialize, Discriminator};
+use anchor_spl::token::{spl_token::native_mint, TokenAccount};
 use clockwork_macros::TryFromData;
 
+use crate::{errors::ClockworkError, state::Config};
+
 pub const SEED_DELEGATION: &[u8] = b"delegation";
 
+/// The number of epochs a withdrawal must unbond for, from the epoch it was requested in, before it can be claimed.
+pub const DELEGATION_UNBONDING_EPOCHS: u64 = 2;
+
 /// An account to manage a token holder's stake delegation with a particiular a worker.
 #[account]
 #[derive(Debug, TryFromData)]
@@ -21,6 +27,18 @@ pub struct Delegation {
 
     /// The number of lamports claimable as yield by the authority.
     pub yield_balance: u64,
+
+    /// The number of locked tokens requested for withdrawal, to be unstaked from the worker at the next epoch.
+    pub pending_withdrawal: u64,
+
+    /// The number of unstaked tokens held by the delegation while they unbond. These are never restaked.
+    pub unbonding_amount: u64,
+
+    /// The epoch the outstanding withdrawal was requested in.
+    pub withdrawal_epoch: u64,
+
+    /// The kind of asset this delegation stakes. Amounts on this account are denominated in it.
+    pub stake_kind: StakeKind,
 }
 
 impl Delegation {
@@ -31,13 +49,154 @@ impl Delegation {
         )
         .0
     }
+
+    /// Moves the source delegation's locked stake and claimable yield into this delegation.
+    /// Returns the yield moved, so the caller can move the matching lamports.
+    pub fn absorb(&mut self, source: &mut Delegation) -> u64 {
+        let yield_amount = source.yield_balance;
+        self.stake_amount = self.stake_amount.checked_add(source.stake_amount).unwrap();
+        self.yield_balance = self.yield_balance.checked_add(yield_amount).unwrap();
+        source.stake_amount = 0;
+        source.yield_balance = 0;
+        yield_amount
+    }
+
+    /// Returns true if a withdrawal has been requested and not yet claimed.
+    pub fn has_withdrawal(&self) -> bool {
+        self.pending_withdrawal.gt(&0) || self.unbonding_amount.gt(&0)
+    }
+
+    /// Moves the pending withdrawal out of the locked stake and into the unbonding balance.
+    /// Returns the number of tokens to move from the worker's stake back to the delegation.
+    pub fn unbond_pending_withdrawal(&mut self) -> u64 {
+        // An unstake processed earlier in the epoch may have already released some of the requested stake.
+        let amount = self.pending_withdrawal.min(self.stake_amount);
+        self.stake_amount = self.stake_amount.checked_sub(amount).unwrap();
+        self.unbonding_amount = self.unbonding_amount.checked_add(amount).unwrap();
+        self.pending_withdrawal = 0;
+        amount
+    }
+
+    /// Marks locked stake for withdrawal in the given epoch. It stays locked with the worker until
+    /// the next epoch's stake job unbonds it.
+    pub fn request_withdrawal(&mut self, amount: u64, epoch: u64) -> Result<()> {
+        require!(!self.has_withdrawal(), ClockworkError::WithdrawalInProgress);
+
+        // Only locked stake can be requested. Unstaked tokens may be withdrawn directly.
+        require!(
+            amount.gt(&0) && amount.le(&self.stake_amount),
+            ClockworkError::InvalidWithdrawalAmount
+        );
+        self.pending_withdrawal = amount;
+        self.withdrawal_epoch = epoch;
+        Ok(())
+    }
+
+    /// Clears the withdrawal once it has finished unbonding.
+    /// Returns the number of tokens to transfer from the delegation to its authority.
+    pub fn claim_withdrawal(&mut self, current_epoch: u64) -> Result<u64> {
+        require!(
+            self.is_withdrawal_claimable(current_epoch),
+            ClockworkError::WithdrawalUnbonding
+        );
+        let amount = self.unbonding_amount;
+        self.unbonding_amount = 0;
+        Ok(amount)
+    }
+
+    /// Returns true if the unbonded withdrawal may be claimed in the given epoch.
+    pub fn is_withdrawal_claimable(&self, current_epoch: u64) -> bool {
+        self.pending_withdrawal.eq(&0)
+            && self.unbonding_amount.gt(&0)
+            && current_epoch
+                >= self
+                    .withdrawal_epoch
+                    .saturating_add(DELEGATION_UNBONDING_EPOCHS)
+    }
+}
+
+/// The layouts of delegations created before withdrawals unbonded over a cooldown period, or
+/// before delegations could stake SOL.
+#[derive(Debug)]
+pub struct LegacyDelegation {
+    pub authority: Pubkey,
+    pub id: u64,
+    pub stake_amount: u64,
+    pub worker: Pubkey,
+    pub yield_balance: u64,
+    pub pending_withdrawal: u64,
+    pub unbonding_amount: u64,
+    pub withdrawal_epoch: u64,
+}
+
+// Delegations created before withdrawals unbonded have no withdrawal fields, so they are only read
+// if present.
+impl AnchorDeserialize for LegacyDelegation {
+    fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+        let authority = AnchorDeserialize::deserialize(buf)?;
+        let id = AnchorDeserialize::deserialize(buf)?;
+        let stake_amount = AnchorDeserialize::deserialize(buf)?;
+        let worker = AnchorDeserialize::deserialize(buf)?;
+        let yield_balance = AnchorDeserialize::deserialize(buf)?;
+        let (pending_withdrawal, unbonding_amount, withdrawal_epoch) = match buf.is_empty() {
+            true => (0, 0, 0),
+            false => (
+                AnchorDeserialize::deserialize(buf)?,
+                AnchorDeserialize::deserialize(buf)?,
+                AnchorDeserialize::deserialize(buf)?,
+            ),
+        };
+        Ok(Self {
+            authority,
+            id,
+            stake_amount,
+            worker,
+            yield_balance,
+            pending_withdrawal,
+            unbonding_amount,
+            withdrawal_epoch,
+        })
+    }
+}
+
+impl LegacyDelegation {
+    /// Decodes the data of a delegation account in either legacy layout.
+    pub fn try_from_account_data(data: &[u8]) -> Result<Self> {
+        require!(
+            data.len().ge(&8) && data[..8].eq(&Delegation::discriminator()),
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        Self::deserialize(&mut &data[8..]).map_err(|_| error!(ErrorCode::AccountDidNotDeserialize))
+    }
+
+    /// Returns the delegation in the current layout. Delegations that predate SOL staking staked
+    /// CLOCK tokens.
+    pub fn migrate(self) -> Delegation {
+        Delegation {
+            authority: self.authority,
+            id: self.id,
+            stake_amount: self.stake_amount,
+            worker: self.worker,
+            yield_balance: self.yield_balance,
+            pending_withdrawal: self.pending_withdrawal,
+            unbonding_amount: self.unbonding_amount,
+            withdrawal_epoch: self.withdrawal_epoch,
+            stake_kind: StakeKind::Token,
+        }
+    }
 }
 
 /// DelegationAccount
 pub trait DelegationAccount {
     fn pubkey(&self) -> Pubkey;
 
-    fn init(&mut self, authority: Pubkey, id: u64, worker: Pubkey) -> Result<()>;
+    fn init(
+        &mut self,
+        authority: Pubkey,
+        id: u64,
+        stake_kind: StakeKind,
+        worker: Pubkey,
+    ) -> Result<()>;
 }
 
 impl DelegationAccount for Account<'_, Delegation> {
@@ -45,12 +204,232 @@ impl DelegationAccount for Account<'_, Delegation> {
         Delegation::pubkey(self.worker, self.id)
     }
 
-    fn init(&mut self, authority: Pubkey, id: u64, worker: Pubkey) -> Result<()> {
+    fn init(
+        &mut self,
+        authority: Pubkey,
+        id: u64,
+        stake_kind: StakeKind,
+        worker: Pubkey,
+    ) -> Result<()> {
         self.authority = authority;
         self.id = id;
         self.stake_amount = 0;
         self.worker = worker;
         self.yield_balance = 0;
+        self.pending_withdrawal = 0;
+        self.unbonding_amount = 0;
+        self.withdrawal_epoch = 0;
+        self.stake_kind = stake_kind;
         Ok(())
     }
 }
+
+/// StakeKind
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StakeKind {
+    /// The network's SPL token, escrowed in associated token accounts for the config's mint.
+    Token,
+
+    /// Wrapped SOL, escrowed in associated token accounts for the native mint.
+    Native,
+}
+
+impl StakeKind {
+    /// Returns the mint of the token accounts holding stake of this kind.
+    pub fn mint(&self, config: &Config) -> Pubkey {
+        match self {
+            StakeKind::Token => config.mint,
+            StakeKind::Native => native_mint::ID,
+        }
+    }
+}
+
+/// Returns the balance of a stake token account, or zero if the account has not been created.
+pub fn stake_balance(stake: &AccountInfo) -> Result<u64> {
+    if stake.data_is_empty() {
+        return Ok(0);
+    }
+    Ok(TokenAccount::try_deserialize(&mut stake.try_borrow_data()?.as_ref())?.amount)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn delegation(id: u64, stake_amount: u64, yield_balance: u64) -> Delegation {
+        Delegation {
+            authority: Pubkey::default(),
+            id,
+            stake_amount,
+            worker: Pubkey::default(),
+            yield_balance,
+            pending_withdrawal: 0,
+            unbonding_amount: 0,
+            withdrawal_epoch: 0,
+            stake_kind: StakeKind::Token,
+        }
+    }
+
+    #[test]
+    fn test_absorb_consolidates_balances() {
+        let mut destination = delegation(0, 100, 7);
+        let mut source = delegation(1, 50, 3);
+        assert_eq!(destination.absorb(&mut source), 3);
+        assert_eq!(destination.stake_amount, 150);
+        assert_eq!(destination.yield_balance, 10);
+        assert_eq!(source.stake_amount, 0);
+        assert_eq!(source.yield_balance, 0);
+    }
+
+    #[test]
+    fn test_absorb_empty_source() {
+        let mut destination = delegation(0, 100, 7);
+        let mut source = delegation(1, 0, 0);
+        assert_eq!(destination.absorb(&mut source), 0);
+        assert_eq!(destination.stake_amount, 100);
+        assert_eq!(destination.yield_balance, 7);
+    }
+
+    #[test]
+    fn test_withdrawal_unbonds_at_next_epoch() {
+        let mut delegation = delegation(0, 100, 0);
+        delegation.pending_withdrawal = 40;
+        delegation.withdrawal_epoch = 5;
+        assert!(delegation.has_withdrawal());
+        assert!(!delegation.is_withdrawal_claimable(10));
+
+        assert_eq!(delegation.unbond_pending_withdrawal(), 40);
+        assert_eq!(delegation.stake_amount, 60);
+        assert_eq!(delegation.unbonding_amount, 40);
+        assert_eq!(delegation.pending_withdrawal, 0);
+    }
+
+    #[test]
+    fn test_unbond_clamps_to_locked_stake() {
+        let mut delegation = delegation(0, 25, 0);
+        delegation.pending_withdrawal = 40;
+        assert_eq!(delegation.unbond_pending_withdrawal(), 25);
+        assert_eq!(delegation.stake_amount, 0);
+        assert_eq!(delegation.unbonding_amount, 25);
+    }
+
+    #[test]
+    fn test_withdrawal_claimable_after_unbonding_period() {
+        let mut delegation = delegation(0, 60, 0);
+        delegation.unbonding_amount = 40;
+        delegation.withdrawal_epoch = 5;
+        assert!(!delegation.is_withdrawal_claimable(5));
+        assert!(!delegation.is_withdrawal_claimable(5 + DELEGATION_UNBONDING_EPOCHS - 1));
+        assert!(delegation.is_withdrawal_claimable(5 + DELEGATION_UNBONDING_EPOCHS));
+    }
+
+    #[test]
+    fn test_request_withdrawal() {
+        let mut delegation = delegation(0, 100, 0);
+        assert_eq!(
+            delegation.request_withdrawal(0, 5).unwrap_err(),
+            error!(ClockworkError::InvalidWithdrawalAmount)
+        );
+        assert_eq!(
+            delegation.request_withdrawal(101, 5).unwrap_err(),
+            error!(ClockworkError::InvalidWithdrawalAmount)
+        );
+
+        delegation.request_withdrawal(40, 5).unwrap();
+        assert_eq!(delegation.pending_withdrawal, 40);
+        assert_eq!(delegation.withdrawal_epoch, 5);
+
+        // Only one withdrawal may be in progress at a time.
+        assert_eq!(
+            delegation.request_withdrawal(10, 6).unwrap_err(),
+            error!(ClockworkError::WithdrawalInProgress)
+        );
+        delegation.unbond_pending_withdrawal();
+        assert_eq!(
+            delegation.request_withdrawal(10, 6).unwrap_err(),
+            error!(ClockworkError::WithdrawalInProgress)
+        );
+    }
+
+    #[test]
+    fn test_claim_withdrawal() {
+        let mut delegation = delegation(0, 100, 0);
+        delegation.request_withdrawal(40, 5).unwrap();
+
+        // The withdrawal cannot be claimed before it is unbonded, or while it is unbonding.
+        assert_eq!(
+            delegation.claim_withdrawal(5 + DELEGATION_UNBONDING_EPOCHS).unwrap_err(),
+            error!(ClockworkError::WithdrawalUnbonding)
+        );
+        delegation.unbond_pending_withdrawal();
+        assert_eq!(
+            delegation.claim_withdrawal(5 + DELEGATION_UNBONDING_EPOCHS - 1).unwrap_err(),
+            error!(ClockworkError::WithdrawalUnbonding)
+        );
+
+        assert_eq!(delegation.claim_withdrawal(5 + DELEGATION_UNBONDING_EPOCHS).unwrap(), 40);
+        assert!(!delegation.has_withdrawal());
+        assert_eq!(delegation.stake_amount, 60);
+
+        // A claimed withdrawal cannot be claimed again, and a new one may be requested.
+        assert_eq!(
+            delegation.claim_withdrawal(5 + DELEGATION_UNBONDING_EPOCHS).unwrap_err(),
+            error!(ClockworkError::WithdrawalUnbonding)
+        );
+        delegation.request_withdrawal(60, 8).unwrap();
+    }
+
+    #[test]
+    fn test_legacy_delegation_migrate() {
+        let mut data = Delegation::discriminator().to_vec();
+        let authority = Pubkey::new_unique();
+        let worker = Pubkey::new_unique();
+        data.extend_from_slice(authority.as_ref());
+        data.extend_from_slice(&3u64.to_le_bytes());
+        data.extend_from_slice(&100u64.to_le_bytes());
+        data.extend_from_slice(worker.as_ref());
+        data.extend_from_slice(&7u64.to_le_bytes());
+
+        let delegation = LegacyDelegation::try_from_account_data(&data).unwrap().migrate();
+        assert_eq!(delegation.authority, authority);
+        assert_eq!(delegation.id, 3);
+        assert_eq!(delegation.stake_amount, 100);
+        assert_eq!(delegation.worker, worker);
+        assert_eq!(delegation.yield_balance, 7);
+        assert!(!delegation.has_withdrawal());
+        assert_eq!(delegation.stake_kind, StakeKind::Token);
+
+        // Other accounts are rejected.
+        data[0] ^= 1;
+        assert!(LegacyDelegation::try_from_account_data(&data).is_err());
+    }
+
+    #[test]
+    fn test_legacy_delegation_with_withdrawal_migrate() {
+        // Delegations created before SOL staking hold the withdrawal fields but no stake kind.
+        let mut data = Delegation::discriminator().to_vec();
+        data.extend_from_slice(Pubkey::new_unique().as_ref());
+        data.extend_from_slice(&3u64.to_le_bytes());
+        data.extend_from_slice(&60u64.to_le_bytes());
+        data.extend_from_slice(Pubkey::new_unique().as_ref());
+        data.extend_from_slice(&7u64.to_le_bytes());
+        data.extend_from_slice(&0u64.to_le_bytes());
+        data.extend_from_slice(&40u64.to_le_bytes());
+        data.extend_from_slice(&5u64.to_le_bytes());
+
+        let delegation = LegacyDelegation::try_from_account_data(&data).unwrap().migrate();
+        assert_eq!(delegation.stake_amount, 60);
+        assert_eq!(delegation.pending_withdrawal, 0);
+        assert_eq!(delegation.unbonding_amount, 40);
+        assert_eq!(delegation.withdrawal_epoch, 5);
+        assert_eq!(delegation.stake_kind, StakeKind::Token);
+        assert!(delegation.is_withdrawal_claimable(5 + DELEGATION_UNBONDING_EPOCHS));
+
+        // The migrated delegation decodes as a token delegation.
+        let mut migrated = vec![];
+        delegation.try_serialize(&mut migrated).unwrap();
+        assert_eq!(migrated.len(), data.len() + 1);
+        assert_eq!(migrated[..data.len()], data[..]);
+        assert_eq!(migrated[data.len()], 0);
+    }
+}
@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+
+/// Emitted when a worker's fees for an epoch are split into its commission and the balance distributable to delegations.
+#[event]
+pub struct FeeDistributed {
+    pub worker: Pubkey,
+    pub epoch: u64,
+    /// The lamports retained by the worker as commission.
+    pub commission_amount: u64,
+    /// The lamports to be distributed to the worker's delegations.
+    pub distributable_amount: u64,
+}
+
+/// Emitted when a delegation is credited its share of a worker's distributable fees.
+#[event]
+pub struct YieldDistributed {
+    pub worker: Pubkey,
+    pub delegation: Pubkey,
+    pub epoch: u64,
+    pub amount: u64,
+}
+
+/// Emitted when a delegation's deposited tokens are locked with its worker.
+#[event]
+pub struct DelegationStaked {
+    pub worker: Pubkey,
+    pub delegation: Pubkey,
+    pub amount: u64,
+}
+
+/// Emitted when stake requested for withdrawal is returned from its worker to the delegation to unbond.
+#[event]
+pub struct DelegationUnbonded {
+    pub worker: Pubkey,
+    pub delegation: Pubkey,
+    pub amount: u64,
+}
+
+/// Emitted when a worker rotates into a pool, possibly displacing the pool's oldest workers.
+#[event]
+pub struct WorkerRotated {
+    pub pool: Pubkey,
+    pub worker: Pubkey,
+    /// The worker's index in the pool after the rotation.
+    pub position: u64,
+}
+
+/// Emitted when a worker's stake is recorded in a snapshot.
+#[event]
+pub struct SnapshotFrameCreated {
+    pub snapshot: Pubkey,
+    pub worker: Pubkey,
+    pub stake_amount: u64,
+    pub stake_offset: u64,
+}
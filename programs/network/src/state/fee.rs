@@ -1,6 +1,8 @@
 use anchor_lang::{prelude::*, AnchorDeserialize};
 use clockwork_macros::TryFromData;
 
+use crate::{errors::ClockworkError, state::Worker};
+
 pub const SEED_FEE: &[u8] = b"fee";
 
 /// Escrows the lamport balance owed to a particular worker.
@@ -18,6 +20,19 @@ impl Fee {
     pub fn pubkey(worker: Pubkey) -> Pubkey {
         Pubkey::find_program_address(&[SEED_FEE, worker.as_ref()], &crate::ID).0
     }
+
+    /// Recomputes the distributable balance from the account's usable balance, net of the worker's commission,
+    /// correcting any drift. Returns the distributable balance before reconciliation.
+    pub fn reconcile(&mut self, usable_balance: u64, worker: &Worker) -> Result<u64> {
+        let commission_balance = worker.commission(usable_balance);
+        let distributable_balance = usable_balance
+            .checked_sub(commission_balance)
+            .ok_or(ClockworkError::CommissionExceedsFeeBalance)?;
+        Ok(std::mem::replace(
+            &mut self.distributable_balance,
+            distributable_balance,
+        ))
+    }
 }
 
 /// Trait for reading and writing to a fee account.
@@ -27,6 +42,9 @@ pub trait FeeAccount {
 
     /// Initialize the account to hold fee object.
     fn init(&mut self, worker: Pubkey) -> Result<()>;
+
+    /// The account's lamport balance in excess of its rent-exempt minimum.
+    fn usable_balance(&self) -> Result<u64>;
 }
 
 impl FeeAccount for Account<'_, Fee> {
@@ -39,4 +57,74 @@ impl FeeAccount for Account<'_, Fee> {
         self.worker = worker;
         Ok(())
     }
+
+    fn usable_balance(&self) -> Result<u64> {
+        let lamport_balance = self.to_account_info().lamports();
+        let data_len = 8 + self.try_to_vec()?.len();
+        let rent_balance = Rent::get()?.minimum_balance(data_len);
+        lamport_balance
+            .checked_sub(rent_balance)
+            .ok_or(error!(ClockworkError::FeeBalanceBelowRent))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reconcile_corrects_drift() {
+        let worker = Worker {
+            authority: Pubkey::new_unique(),
+            commission_balance: 0,
+            commission_rate: 10,
+            id: 0,
+            signatory: Pubkey::new_unique(),
+            total_delegations: 0,
+            commission_recipient: None,
+            pending_commission_rate: None,
+            pending_commission_epoch: 0,
+        };
+        let mut fee = Fee {
+            distributable_balance: 0,
+            worker: Worker::pubkey(0),
+        };
+
+        // A partially applied distribution left the balance out of step with the account's lamports.
+        fee.reconcile(1_000_000, &worker).unwrap();
+        assert_eq!(fee.distributable_balance, 900_000);
+        fee.distributable_balance += 42;
+
+        assert_eq!(fee.reconcile(1_000_000, &worker).unwrap(), 900_042);
+        assert_eq!(fee.distributable_balance, 900_000);
+
+        // Reconciling a consistent balance is a no-op.
+        assert_eq!(fee.reconcile(1_000_000, &worker).unwrap(), 900_000);
+        assert_eq!(fee.distributable_balance, 900_000);
+    }
+
+    #[test]
+    fn test_reconcile_rejects_commission_above_balance() {
+        // A rate above 100 cannot be set through worker_update, but must not panic if encountered.
+        let worker = Worker {
+            authority: Pubkey::new_unique(),
+            commission_balance: 0,
+            commission_rate: 150,
+            id: 0,
+            signatory: Pubkey::new_unique(),
+            total_delegations: 0,
+            commission_recipient: None,
+            pending_commission_rate: None,
+            pending_commission_epoch: 0,
+        };
+        let mut fee = Fee {
+            distributable_balance: 7,
+            worker: Worker::pubkey(0),
+        };
+        assert_eq!(
+            fee.reconcile(1_000_000, &worker).unwrap_err(),
+            error!(ClockworkError::CommissionExceedsFeeBalance)
+        );
+        assert_eq!(fee.distributable_balance, 7);
+    }
 }
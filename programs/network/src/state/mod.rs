@@ -1,8 +1,10 @@
 mod config;
 mod delegation;
+mod events;
 mod fee;
 mod penalty;
 mod pool;
+mod program_status;
 mod registry;
 mod snapshot;
 mod snapshot_entry;
@@ -12,9 +14,11 @@ mod worker;
 
 pub use config::*;
 pub use delegation::*;
+pub use events::*;
 pub use fee::*;
 pub use penalty::*;
 pub use pool::*;
+pub use program_status::*;
 pub use registry::*;
 pub use snapshot::*;
 pub use snapshot_entry::*;
@@ -0,0 +1,21 @@
+use anchor_lang::{prelude::*, AnchorDeserialize};
+use clockwork_macros::TryFromData;
+
+pub const SEED_PROGRAM_STATUS: &[u8] = b"program_status";
+
+/// Tracks whether automations may invoke a particular program.
+#[account]
+#[derive(Debug, TryFromData)]
+pub struct ProgramStatus {
+    /// True if automations are forbidden from invoking the program networkwide.
+    pub paused: bool,
+    /// The program this status applies to.
+    pub program_id: Pubkey,
+}
+
+impl ProgramStatus {
+    /// Derive the pubkey of a program status account.
+    pub fn pubkey(program_id: Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[SEED_PROGRAM_STATUS, program_id.as_ref()], &crate::ID).0
+    }
+}
@@ -14,7 +14,7 @@ pub const SEED_REGISTRY: &[u8] = b"registry";
 #[derive(Debug, TryFromData)]
 pub struct Registry {
     pub current_epoch: u64,
-    pub locked: bool,
+    pub lock_reason: RegistryLockReason,
     pub nonce: u64,
     pub total_pools: u64,
     pub total_unstakes: u64,
@@ -25,6 +25,22 @@ impl Registry {
     pub fn pubkey() -> Pubkey {
         Pubkey::find_program_address(&[SEED_REGISTRY], &crate::ID).0
     }
+
+    pub fn is_locked(&self) -> bool {
+        self.lock_reason.ne(&RegistryLockReason::Unlocked)
+    }
+}
+
+/// The epoch job currently holding the registry lock.
+///
+/// This field replaces the old `locked: bool` and occupies the same byte, so existing accounts
+/// decode in place: `false` reads as `Unlocked` and `true` reads as `Snapshotting`.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RegistryLockReason {
+    Unlocked,
+    Snapshotting,
+    Distributing,
+    Staking,
 }
 
 /**
@@ -40,7 +56,7 @@ pub trait RegistryAccount {
 impl RegistryAccount for Account<'_, Registry> {
     fn init(&mut self) -> Result<()> {
         self.current_epoch = 0;
-        self.locked = false;
+        self.lock_reason = RegistryLockReason::Unlocked;
         self.total_workers = 0;
         Ok(())
     }
@@ -29,6 +29,22 @@ impl SnapshotFrame {
         )
         .0
     }
+
+    /// The portion of a balance owed to an entry of this frame, weighted by the entry's stake.
+    ///
+    /// Shares are rounded down, so the shares of a frame's entries may sum to fewer lamports than the balance,
+    /// by less than one lamport per entry. Fee distribution leaves that remainder in the worker's fee account,
+    /// where it is carried into the next epoch's distributable balance.
+    pub fn stake_share(&self, balance: u64, stake_amount: u64) -> u64 {
+        if self.stake_amount.eq(&0) {
+            return 0;
+        }
+        (balance as u128)
+            .checked_mul(stake_amount as u128)
+            .unwrap()
+            .checked_div(self.stake_amount as u128)
+            .unwrap() as u64
+    }
 }
 
 /**
@@ -70,3 +86,77 @@ impl SnapshotFrameAccount for Account<'_, SnapshotFrame> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(stakes: &[u64]) -> SnapshotFrame {
+        SnapshotFrame {
+            id: 0,
+            snapshot: Pubkey::default(),
+            stake_amount: stakes.iter().sum(),
+            stake_offset: 0,
+            total_entries: stakes.len() as u64,
+            worker: Pubkey::default(),
+        }
+    }
+
+    /// Distributes a fee balance the way the distribute fees job does, returning the commission,
+    /// the total of the entries' shares, and the remainder left in the fee account.
+    fn distribute(
+        frame: &SnapshotFrame,
+        stakes: &[u64],
+        balance: u64,
+        commission_rate: u64,
+    ) -> (u64, u64, u64) {
+        let commission = balance * commission_rate / 100;
+        let distributable = balance - commission;
+        let shares: u64 = stakes
+            .iter()
+            .map(|stake| frame.stake_share(distributable, *stake))
+            .sum();
+        (commission, shares, distributable - shares)
+    }
+
+    #[test]
+    fn test_stake_share_remainder_is_bounded() {
+        let stakes = [1, 3, 7, 11];
+        let frame = frame(&stakes);
+        let (commission, shares, remainder) = distribute(&frame, &stakes, 1_000_003, 10);
+        assert_eq!(commission + shares + remainder, 1_000_003);
+        assert!(remainder < stakes.len() as u64);
+    }
+
+    #[test]
+    fn test_remainder_carried_to_next_epoch() {
+        // Over many epochs, every collected lamport is paid out as commission or a share,
+        // except the remainder of the latest epoch, which is still waiting in the fee account.
+        let stakes = [2, 5, 9];
+        let frame = frame(&stakes);
+        let mut fee_balance = 0;
+        let mut collected = 0;
+        let mut paid = 0;
+        for epoch in 0..100 {
+            let fees = 99_991 + epoch;
+            collected += fees;
+            fee_balance += fees;
+            let (commission, shares, remainder) = distribute(&frame, &stakes, fee_balance, 7);
+            paid += commission + shares;
+            fee_balance = remainder;
+        }
+        assert_eq!(paid + fee_balance, collected);
+        assert!(fee_balance < stakes.len() as u64);
+    }
+
+    #[test]
+    fn test_stake_share_empty_frame() {
+        assert_eq!(frame(&[]).stake_share(1_000, 0), 0);
+    }
+
+    #[test]
+    fn test_stake_share_does_not_overflow() {
+        let stakes = [u64::MAX / 2, u64::MAX / 2];
+        assert_eq!(frame(&stakes).stake_share(u64::MAX / 2, stakes[0]), u64::MAX / 4);
+    }
+}
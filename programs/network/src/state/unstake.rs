@@ -1,6 +1,8 @@
-use anchor_lang::{prelude::*, AnchorDeserialize};
+use anchor_lang::{error::ErrorCode, prelude::*, AnchorDeserialize, Discriminator};
 use clockwork_macros::TryFromData;
 
+use crate::state::StakeKind;
+
 pub const SEED_UNSTAKE: &[u8] = b"unstake";
 
 /// Unstake
@@ -12,6 +14,7 @@ pub struct Unstake {
     pub delegation: Pubkey,
     pub id: u64,
     pub worker: Pubkey,
+    pub stake_kind: StakeKind,
 }
 
 impl Unstake {
@@ -20,6 +23,40 @@ impl Unstake {
     }
 }
 
+/// The layout of unstakes created before SOL staking was introduced.
+#[derive(AnchorDeserialize, Debug)]
+pub struct LegacyUnstake {
+    pub amount: u64,
+    pub authority: Pubkey,
+    pub delegation: Pubkey,
+    pub id: u64,
+    pub worker: Pubkey,
+}
+
+impl LegacyUnstake {
+    /// Decodes the data of an unstake account in the legacy layout.
+    pub fn try_from_account_data(data: &[u8]) -> Result<Self> {
+        require!(
+            data.len().ge(&8) && data[..8].eq(&Unstake::discriminator()),
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        Self::deserialize(&mut &data[8..]).map_err(|_| error!(ErrorCode::AccountDidNotDeserialize))
+    }
+
+    /// Returns the unstake in the current layout. Unstakes that predate SOL staking unstake CLOCK
+    /// tokens.
+    pub fn migrate(self) -> Unstake {
+        Unstake {
+            amount: self.amount,
+            authority: self.authority,
+            delegation: self.delegation,
+            id: self.id,
+            worker: self.worker,
+            stake_kind: StakeKind::Token,
+        }
+    }
+}
+
 /// UnstakeAccount
 pub trait UnstakeAccount {
     fn pubkey(&self) -> Pubkey;
@@ -31,6 +68,7 @@ pub trait UnstakeAccount {
         delegation: Pubkey,
         id: u64,
         worker: Pubkey,
+        stake_kind: StakeKind,
     ) -> Result<()>;
 }
 
@@ -46,12 +84,43 @@ impl UnstakeAccount for Account<'_, Unstake> {
         delegation: Pubkey,
         id: u64,
         worker: Pubkey,
+        stake_kind: StakeKind,
     ) -> Result<()> {
         self.amount = amount;
         self.authority = authority.key();
         self.delegation = delegation;
         self.id = id;
         self.worker = worker;
+        self.stake_kind = stake_kind;
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_legacy_unstake_migrate() {
+        let authority = Pubkey::new_unique();
+        let delegation = Pubkey::new_unique();
+        let worker = Pubkey::new_unique();
+        let mut data = Unstake::discriminator().to_vec();
+        data.extend_from_slice(&500u64.to_le_bytes());
+        data.extend_from_slice(authority.as_ref());
+        data.extend_from_slice(delegation.as_ref());
+        data.extend_from_slice(&4u64.to_le_bytes());
+        data.extend_from_slice(worker.as_ref());
+
+        // Unstakes created before SOL staking end before the stake kind.
+        assert!(Unstake::try_deserialize(&mut &data[..]).is_err());
+
+        let unstake = LegacyUnstake::try_from_account_data(&data).unwrap().migrate();
+        assert_eq!(unstake.amount, 500);
+        assert_eq!(unstake.authority, authority);
+        assert_eq!(unstake.delegation, delegation);
+        assert_eq!(unstake.id, 4);
+        assert_eq!(unstake.worker, worker);
+        assert_eq!(unstake.stake_kind, StakeKind::Token);
+    }
+}
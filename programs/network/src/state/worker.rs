@@ -1,4 +1,4 @@
-use anchor_lang::{prelude::*, AnchorDeserialize};
+use anchor_lang::{error::ErrorCode, prelude::*, AnchorDeserialize, Discriminator};
 use clockwork_macros::TryFromData;
 
 use crate::errors::*;
@@ -21,12 +21,101 @@ pub struct Worker {
     pub signatory: Pubkey,
     /// The number delegations allocated to this worker.
     pub total_delegations: u64,
+    /// The account commission is paid out to. If none, commission may be paid out anywhere by the authority.
+    pub commission_recipient: Option<Pubkey>,
+    /// A commission rate staged by the authority, applied from the fee distribution of `pending_commission_epoch`.
+    pub pending_commission_rate: Option<u64>,
+    /// The epoch whose fee distribution the pending commission rate first applies to.
+    pub pending_commission_epoch: u64,
 }
 
 impl Worker {
     pub fn pubkey(id: u64) -> Pubkey {
         Pubkey::find_program_address(&[SEED_WORKER, id.to_be_bytes().as_ref()], &crate::ID).0
     }
+
+    /// The portion of a fee balance the worker retains as commission.
+    pub fn commission(&self, fee_balance: u64) -> u64 {
+        fee_balance
+            .checked_mul(self.commission_rate)
+            .unwrap()
+            .checked_div(100)
+            .unwrap()
+    }
+
+    /// Promotes the pending commission rate once the fee distribution for its epoch is reached.
+    pub fn apply_pending_commission_rate(&mut self, epoch: u64) {
+        if let Some(pending_commission_rate) = self.pending_commission_rate {
+            if epoch.ge(&self.pending_commission_epoch) {
+                self.commission_rate = pending_commission_rate;
+                self.pending_commission_rate = None;
+            }
+        }
+    }
+}
+
+/// The layouts of workers created before commission recipients were introduced, or before
+/// commission rate changes were staged.
+#[derive(Debug)]
+pub struct LegacyWorker {
+    pub authority: Pubkey,
+    pub commission_balance: u64,
+    pub commission_rate: u64,
+    pub id: u64,
+    pub signatory: Pubkey,
+    pub total_delegations: u64,
+    pub commission_recipient: Option<Pubkey>,
+}
+
+// Workers created before commission recipients have no recipient, so it is only read if present.
+impl AnchorDeserialize for LegacyWorker {
+    fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+        let authority = AnchorDeserialize::deserialize(buf)?;
+        let commission_balance = AnchorDeserialize::deserialize(buf)?;
+        let commission_rate = AnchorDeserialize::deserialize(buf)?;
+        let id = AnchorDeserialize::deserialize(buf)?;
+        let signatory = AnchorDeserialize::deserialize(buf)?;
+        let total_delegations = AnchorDeserialize::deserialize(buf)?;
+        let commission_recipient = match buf.is_empty() {
+            true => None,
+            false => AnchorDeserialize::deserialize(buf)?,
+        };
+        Ok(Self {
+            authority,
+            commission_balance,
+            commission_rate,
+            id,
+            signatory,
+            total_delegations,
+            commission_recipient,
+        })
+    }
+}
+
+impl LegacyWorker {
+    /// Decodes the data of a worker account in either legacy layout.
+    pub fn try_from_account_data(data: &[u8]) -> Result<Self> {
+        require!(
+            data.len().ge(&8) && data[..8].eq(&Worker::discriminator()),
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        Self::deserialize(&mut &data[8..]).map_err(|_| error!(ErrorCode::AccountDidNotDeserialize))
+    }
+
+    /// Returns the worker in the current layout, with the fields added since set to their defaults.
+    pub fn migrate(self) -> Worker {
+        Worker {
+            authority: self.authority,
+            commission_balance: self.commission_balance,
+            commission_rate: self.commission_rate,
+            id: self.id,
+            signatory: self.signatory,
+            total_delegations: self.total_delegations,
+            commission_recipient: self.commission_recipient,
+            pending_commission_rate: None,
+            pending_commission_epoch: 0,
+        }
+    }
 }
 
 /// WorkerSettings
@@ -34,6 +123,7 @@ impl Worker {
 pub struct WorkerSettings {
     pub commission_rate: u64,
     pub signatory: Pubkey,
+    pub commission_recipient: Option<Pubkey>,
 }
 
 /// WorkerAccount
@@ -57,6 +147,9 @@ impl WorkerAccount for Account<'_, Worker> {
         self.id = id;
         self.signatory = signatory.key();
         self.total_delegations = 0;
+        self.commission_recipient = None;
+        self.pending_commission_rate = None;
+        self.pending_commission_epoch = 0;
         Ok(())
     }
 
@@ -66,6 +159,7 @@ impl WorkerAccount for Account<'_, Worker> {
             ClockworkError::InvalidCommissionRate
         );
         self.commission_rate = settings.commission_rate;
+        self.commission_recipient = settings.commission_recipient;
 
         require!(
             settings.signatory.ne(&self.authority),
@@ -75,3 +169,68 @@ impl WorkerAccount for Account<'_, Worker> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_legacy_worker_migrate() {
+        let authority = Pubkey::new_unique();
+        let signatory = Pubkey::new_unique();
+        let mut data = Worker::discriminator().to_vec();
+        data.extend_from_slice(authority.as_ref());
+        data.extend_from_slice(&1_000u64.to_le_bytes());
+        data.extend_from_slice(&10u64.to_le_bytes());
+        data.extend_from_slice(&3u64.to_le_bytes());
+        data.extend_from_slice(signatory.as_ref());
+        data.extend_from_slice(&2u64.to_le_bytes());
+
+        // Workers created before commission recipients end before the fields added since.
+        assert!(Worker::try_deserialize(&mut &data[..]).is_err());
+
+        let worker = LegacyWorker::try_from_account_data(&data).unwrap().migrate();
+        assert_eq!(worker.authority, authority);
+        assert_eq!(worker.commission_balance, 1_000);
+        assert_eq!(worker.commission_rate, 10);
+        assert_eq!(worker.id, 3);
+        assert_eq!(worker.signatory, signatory);
+        assert_eq!(worker.total_delegations, 2);
+        assert_eq!(worker.commission_recipient, None);
+        assert_eq!(worker.pending_commission_rate, None);
+        assert_eq!(worker.pending_commission_epoch, 0);
+
+        // The migrated worker keeps the legacy data as its prefix.
+        let mut migrated = vec![];
+        worker.try_serialize(&mut migrated).unwrap();
+        assert_eq!(migrated[..data.len()], data[..]);
+        assert!(Worker::try_deserialize(&mut &migrated[..]).is_ok());
+
+        // Other accounts are rejected.
+        data[0] ^= 1;
+        assert!(LegacyWorker::try_from_account_data(&data).is_err());
+    }
+
+    #[test]
+    fn test_legacy_worker_with_commission_recipient_migrate() {
+        // Workers created before staged commission rates hold a recipient, and were allocated
+        // with zeroed padding too short to hold the pending commission fields.
+        let commission_recipient = Pubkey::new_unique();
+        let mut data = Worker::discriminator().to_vec();
+        data.extend_from_slice(Pubkey::new_unique().as_ref());
+        data.extend_from_slice(&1_000u64.to_le_bytes());
+        data.extend_from_slice(&10u64.to_le_bytes());
+        data.extend_from_slice(&3u64.to_le_bytes());
+        data.extend_from_slice(Pubkey::new_unique().as_ref());
+        data.extend_from_slice(&2u64.to_le_bytes());
+        data.extend(Some(commission_recipient).try_to_vec().unwrap());
+        data.extend_from_slice(&[0; 7]);
+        assert!(Worker::try_deserialize(&mut &data[..]).is_err());
+
+        let worker = LegacyWorker::try_from_account_data(&data).unwrap().migrate();
+        assert_eq!(worker.commission_rate, 10);
+        assert_eq!(worker.commission_recipient, Some(commission_recipient));
+        assert_eq!(worker.pending_commission_rate, None);
+        assert_eq!(worker.pending_commission_epoch, 0);
+    }
+}
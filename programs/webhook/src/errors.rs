@@ -13,4 +13,13 @@ pub enum ClockworkError {
 
     #[msg("Invalid number of workers")]
     InvalidWorkers,
+
+    #[msg("The request headers and body exceed the maximum payload size")]
+    RequestPayloadTooLarge,
+
+    #[msg("The acknowledgement was not signed by the api's ack authority")]
+    InvalidAckSignature,
+
+    #[msg("The request is already in the current layout")]
+    RequestAlreadyMigrated,
 }
@@ -4,6 +4,8 @@ pub mod api_new;
 pub mod fee_claim;
 pub mod initialize;
 pub mod request_ack;
+pub mod request_ack_signed;
+pub mod request_migrate;
 pub mod request_new;
 
 pub use admin_config_update::*;
@@ -12,4 +14,6 @@ pub use api_new::*;
 pub use fee_claim::*;
 pub use initialize::*;
 pub use request_ack::*;
+pub use request_ack_signed::*;
+pub use request_migrate::*;
 pub use request_new::*;
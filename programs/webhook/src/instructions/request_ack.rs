@@ -1,5 +1,5 @@
 use {
-    crate::state::{Config, Fee, FeeAccount, Request, SEED_FEE, SEED_REQUEST},
+    crate::state::{Api, Config, Fee, FeeAccount, Request, SEED_FEE, SEED_REQUEST},
     anchor_lang::{prelude::*, system_program},
     std::mem::size_of,
 };
@@ -10,6 +10,12 @@ pub struct RequestAck<'info> {
     #[account(mut)]
     pub ack_authority: Signer<'info>,
 
+    #[account(
+        address = request.api,
+        constraint = ack_authority.key() == api.ack_authority
+    )]
+    pub api: Account<'info, Api>,
+
     #[account(mut)]
     pub caller: SystemAccount<'info>,
 
@@ -54,11 +60,21 @@ pub fn handler<'info>(ctx: Context<RequestAck>) -> Result<()> {
     let config = &ctx.accounts.config;
     let fee = &mut ctx.accounts.fee;
     let request = &mut ctx.accounts.request;
-    let worker = &mut ctx.accounts.worker;
+    let worker = &ctx.accounts.worker;
 
     // Payout request fee
+    pay_request_fee(config, fee, request, worker.key())
+}
+
+/// Pays the request's fee to the worker if it was authorized and acknowledged in time, or to the admin otherwise.
+pub fn pay_request_fee(
+    config: &Config,
+    fee: &mut Account<Fee>,
+    request: &mut Account<Request>,
+    worker: Pubkey,
+) -> Result<()> {
     let current_slot = Clock::get().unwrap().slot;
-    let is_authorized_worker = request.workers.contains(&worker.key());
+    let is_authorized_worker = request.workers.contains(&worker);
     let is_within_execution_window = current_slot
         < request
             .created_at
@@ -0,0 +1,187 @@
+use {
+    crate::{
+        errors::ClockworkError,
+        instructions::request_ack::pay_request_fee,
+        state::{Api, Config, Fee, Request, SEED_FEE, SEED_REQUEST},
+    },
+    anchor_lang::{
+        prelude::*,
+        solana_program::{
+            ed25519_program,
+            sysvar::{
+                self,
+                instructions::{load_current_index_checked, load_instruction_at_checked},
+            },
+        },
+        system_program,
+    },
+    std::mem::size_of,
+};
+
+/// The number of bytes of an ed25519 program instruction's header, before its signature offsets.
+const ED25519_HEADER_SIZE: usize = 2;
+
+/// The number of bytes of one signature's offsets in an ed25519 program instruction.
+const ED25519_OFFSETS_SIZE: usize = 14;
+
+#[derive(Accounts)]
+#[instruction(response_hash: [u8; 32])]
+pub struct RequestAckSigned<'info> {
+    #[account(address = request.api)]
+    pub api: Account<'info, Api>,
+
+    #[account(mut)]
+    pub caller: SystemAccount<'info>,
+
+    #[account(address = Config::pubkey())]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        seeds = [
+            SEED_FEE,
+            worker.key().as_ref(),
+        ],
+        bump,
+        space = 8 + size_of::<Fee>(),
+        payer = worker
+    )]
+    pub fee: Account<'info, Fee>,
+
+    /// CHECK: The instructions sysvar, used to find the ack authority's signature.
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            SEED_REQUEST,
+            request.api.as_ref(),
+            request.caller.as_ref(),
+            request.id.as_bytes(),
+        ],
+        bump,
+        close = caller,
+        has_one = caller
+    )]
+    pub request: Account<'info, Request>,
+
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+
+    #[account(mut)]
+    pub worker: Signer<'info>,
+}
+
+/// Acknowledges a request with the api's signature over the response, relayed by the worker.
+/// The instruction just before this one must be an ed25519 program instruction verifying a single signature
+/// by the api's ack authority over `Request::ack_message`.
+pub fn handler<'info>(ctx: Context<RequestAckSigned>, response_hash: [u8; 32]) -> Result<()> {
+    // Get accounts
+    let api = &ctx.accounts.api;
+    let config = &ctx.accounts.config;
+    let fee = &mut ctx.accounts.fee;
+    let instructions = &ctx.accounts.instructions;
+    let request = &mut ctx.accounts.request;
+    let worker = &ctx.accounts.worker;
+
+    // Find the signature verified by the preceding instruction.
+    let current_index = load_current_index_checked(&instructions.to_account_info())?;
+    let verify_index = current_index
+        .checked_sub(1)
+        .ok_or(ClockworkError::InvalidAckSignature)?;
+    let verify_ix = load_instruction_at_checked(verify_index as usize, &instructions.to_account_info())?;
+    require!(
+        verify_ix.program_id.eq(&ed25519_program::ID),
+        ClockworkError::InvalidAckSignature
+    );
+    let (signer, message) =
+        parse_ed25519_instruction(&verify_ix.data).ok_or(ClockworkError::InvalidAckSignature)?;
+
+    // Verify the ack authority signed the response to this request.
+    require!(
+        signer.eq(&api.ack_authority)
+            && message.eq(&Request::ack_message(request.key(), response_hash)),
+        ClockworkError::InvalidAckSignature
+    );
+
+    // Payout request fee
+    pay_request_fee(config, fee, request, worker.key())
+}
+
+/// Returns the public key and message of an ed25519 program instruction that verifies exactly one signature,
+/// with all of its data held in the instruction itself.
+fn parse_ed25519_instruction(data: &[u8]) -> Option<(Pubkey, Vec<u8>)> {
+    if data.len() < ED25519_HEADER_SIZE + ED25519_OFFSETS_SIZE || data[0] != 1 {
+        return None;
+    }
+    let read_u16 = |offset: usize| -> Option<u16> {
+        Some(u16::from_le_bytes(data.get(offset..offset + 2)?.try_into().ok()?))
+    };
+    let offsets = ED25519_HEADER_SIZE;
+    let signature_ix_index = read_u16(offsets + 2)?;
+    let public_key_offset = read_u16(offsets + 4)? as usize;
+    let public_key_ix_index = read_u16(offsets + 6)?;
+    let message_offset = read_u16(offsets + 8)? as usize;
+    let message_size = read_u16(offsets + 10)? as usize;
+    let message_ix_index = read_u16(offsets + 12)?;
+
+    // The signature, key, and message must come from this instruction, not one the worker controls elsewhere.
+    if [signature_ix_index, public_key_ix_index, message_ix_index]
+        .iter()
+        .any(|index| *index != u16::MAX)
+    {
+        return None;
+    }
+    let public_key = data.get(public_key_offset..public_key_offset + 32)?;
+    let message = data.get(message_offset..message_offset + message_size)?;
+    Some((Pubkey::new(public_key), message.to_vec()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ed25519_data(public_key: Pubkey, message: &[u8], ix_index: u16) -> Vec<u8> {
+        let public_key_offset = (ED25519_HEADER_SIZE + ED25519_OFFSETS_SIZE) as u16;
+        let signature_offset = public_key_offset + 32;
+        let message_offset = signature_offset + 64;
+        let mut data = vec![1, 0];
+        for value in [
+            signature_offset,
+            ix_index,
+            public_key_offset,
+            ix_index,
+            message_offset,
+            message.len() as u16,
+            ix_index,
+        ] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(public_key.as_ref());
+        data.extend_from_slice(&[0; 64]);
+        data.extend_from_slice(message);
+        data
+    }
+
+    #[test]
+    fn test_parse_ed25519_instruction() {
+        let ack_authority = Pubkey::new_unique();
+        let message = Request::ack_message(Pubkey::new_unique(), [7; 32]);
+        assert_eq!(message.len(), 88);
+        assert_eq!(
+            parse_ed25519_instruction(&ed25519_data(ack_authority, &message, u16::MAX)),
+            Some((ack_authority, message))
+        );
+    }
+
+    #[test]
+    fn test_parse_ed25519_instruction_rejects_external_data() {
+        let message = Request::ack_message(Pubkey::new_unique(), [7; 32]);
+        assert_eq!(
+            parse_ed25519_instruction(&ed25519_data(Pubkey::new_unique(), &message, 0)),
+            None
+        );
+        assert_eq!(parse_ed25519_instruction(&[1, 0, 0]), None);
+    }
+}
@@ -0,0 +1,70 @@
+use {
+    crate::{errors::ClockworkError, state::*},
+    anchor_lang::{
+        error::ErrorCode,
+        prelude::*,
+        solana_program::system_program,
+        system_program::{transfer, Transfer},
+    },
+};
+
+#[derive(Accounts)]
+pub struct RequestMigrate<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: A legacy request cannot be loaded as a `Request`, so the handler decodes it.
+    #[account(mut, owner = crate::ID)]
+    pub request: UncheckedAccount<'info>,
+
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler<'info>(ctx: Context<RequestMigrate>) -> Result<()> {
+    // Get accounts
+    let payer = &ctx.accounts.payer;
+    let request = &ctx.accounts.request;
+    let system_program = &ctx.accounts.system_program;
+
+    // Verify the request is in the legacy layout and at its address.
+    let legacy = {
+        let data = request.try_borrow_data()?;
+        require!(
+            Request::try_deserialize(&mut &data[..]).is_err(),
+            ClockworkError::RequestAlreadyMigrated
+        );
+        LegacyRequest::try_from_account_data(&data)?
+    };
+    require_keys_eq!(
+        Request::pubkey(legacy.api, legacy.caller, legacy.id.clone()),
+        request.key(),
+        ErrorCode::ConstraintSeeds
+    );
+
+    // Top up the rent for the larger account and grow it. The request's fee stays in escrow.
+    let migrated = legacy.migrate();
+    let space = 8 + migrated.try_to_vec()?.len();
+    let rent_due = Rent::get()?
+        .minimum_balance(space)
+        .saturating_sub(Rent::get()?.minimum_balance(request.data_len()));
+    if rent_due.gt(&0) {
+        transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                Transfer {
+                    from: payer.to_account_info(),
+                    to: request.to_account_info(),
+                },
+            ),
+            rent_due,
+        )?;
+    }
+    request.to_account_info().realloc(space, false)?;
+
+    // Rewrite the request in the current layout.
+    let mut data = request.try_borrow_mut_data()?;
+    migrated.try_serialize(&mut &mut data[..])?;
+
+    Ok(())
+}
@@ -1,6 +1,10 @@
 use {
-    crate::state::{
-        Api, ApiAccount, Config, HttpMethod, Request, RequestAccount, SEED_REQUEST,
+    crate::{
+        errors::ClockworkError,
+        state::{
+            Api, ApiAccount, Config, HttpMethod, Request, RequestAccount,
+            REQUEST_PAYLOAD_MAX_SIZE, SEED_REQUEST,
+        },
     },
     anchor_lang::{
         prelude::*,
@@ -8,14 +12,18 @@ use {
         system_program::{transfer, Transfer},
     },
     clockwork_network_program::state::Pool,
-    std::{collections::HashMap, mem::size_of},
+    std::mem::size_of,
 };
 
 #[derive(Accounts)]
 #[instruction(
     id: String, 
     method: HttpMethod, 
-    route: String
+    route: String,
+    max_retries: u64,
+    backoff_seconds: u64,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>
 )]
 pub struct RequestNew<'info> {
     #[account(address = api.pubkey())]
@@ -42,7 +50,7 @@ pub struct RequestNew<'info> {
             id.as_bytes(),
         ],
         bump,
-        space = 8 + size_of::<Request>(),
+        space = 8 + size_of::<Request>() + Request::payload_size(&headers, &body),
         payer = payer
     )]
     pub request: Account<'info, Request>,
@@ -56,6 +64,10 @@ pub fn handler<'info>(
     id: String,
     method: HttpMethod,
     route: String,
+    max_retries: u64,
+    backoff_seconds: u64,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
 ) -> Result<()> {
     // Fetch accounts
     let api = &ctx.accounts.api;
@@ -68,10 +80,15 @@ pub fn handler<'info>(
 
     // TODO Validate route is a relative path
 
+    // Validate the payload fits in the request account.
+    require!(
+        Request::payload_size(&headers, &body).le(&REQUEST_PAYLOAD_MAX_SIZE),
+        ClockworkError::RequestPayloadTooLarge
+    );
+
     // Initialize the request account
     let current_slot = Clock::get().unwrap().slot;
     let fee_amount = config.request_fee;
-    let headers = HashMap::new(); // TODO Get headers from ix data
     let workers = pool
         .clone()
         .into_inner()
@@ -81,11 +98,14 @@ pub fn handler<'info>(
         .collect::<Vec<Pubkey>>();
     request.init(
         api,
+        backoff_seconds,
+        body,
         caller.key(),
         current_slot,
         fee_amount,
         headers,
         id,
+        max_retries,
         method,
         route,
         workers,
@@ -40,12 +40,36 @@ pub mod webhook_program {
         request_ack::handler(ctx)
     }
 
+    pub fn request_ack_signed<'info>(
+        ctx: Context<RequestAckSigned>,
+        response_hash: [u8; 32],
+    ) -> Result<()> {
+        request_ack_signed::handler(ctx, response_hash)
+    }
+
+    pub fn request_migrate<'info>(ctx: Context<RequestMigrate>) -> Result<()> {
+        request_migrate::handler(ctx)
+    }
+
     pub fn request_new<'info>(
         ctx: Context<RequestNew>,
         id: String,
         method: HttpMethod,
         route: String,
+        max_retries: u64,
+        backoff_seconds: u64,
+        headers: Vec<(String, String)>,
+        body: Vec<u8>,
     ) -> Result<()> {
-        request_new::handler(ctx, id, method, route)
+        request_new::handler(
+            ctx,
+            id,
+            method,
+            route,
+            max_retries,
+            backoff_seconds,
+            headers,
+            body,
+        )
     }
 }
@@ -1,5 +1,4 @@
 use std::{
-    collections::HashMap,
     fmt::{Display, Formatter},
     str::FromStr,
 };
@@ -7,11 +6,17 @@ use std::{
 use super::Api;
 use crate::errors::ClockworkError;
 
-use anchor_lang::{prelude::*, AnchorDeserialize};
+use anchor_lang::{error::ErrorCode, prelude::*, AnchorDeserialize, Discriminator};
 use clockwork_macros::TryFromData;
 
 pub const SEED_REQUEST: &[u8] = b"request";
 
+/// The domain separator that begins every acknowledgement message.
+pub const ACK_MESSAGE_PREFIX: &[u8] = b"clockwork-webhook-ack-v1";
+
+/// The maximum number of serialized bytes a request's headers and body may occupy together.
+pub const REQUEST_PAYLOAD_MAX_SIZE: usize = 8_192;
+
 /**
  * Request
  */
@@ -23,12 +28,19 @@ pub struct Request {
     pub caller: Pubkey,
     pub created_at: u64,
     pub fee_amount: u64,
-    pub headers: HashMap<String, String>,
+    /// The headers to send with the request, in order.
+    pub headers: Vec<(String, String)>,
     pub id: String,
     pub method: HttpMethod,
     pub route: String,
     pub url: String,
     pub workers: Vec<Pubkey>,
+    /// The number of times workers may re-send the request after it fails, before giving up on it.
+    pub max_retries: u64,
+    /// The number of seconds to wait before the first retry of a failed request. Each later retry waits twice as long.
+    pub backoff_seconds: u64,
+    /// The body to send with the request.
+    pub body: Vec<u8>,
 }
 
 impl Request {
@@ -39,6 +51,76 @@ impl Request {
         )
         .0
     }
+
+    /// Returns the message an api's ack authority signs with ed25519 to acknowledge the response to a request.
+    ///
+    /// The message is 88 bytes, the concatenation of:
+    /// - the 24 ASCII bytes of `clockwork-webhook-ack-v1`,
+    /// - the 32 bytes of the request account's address,
+    /// - the 32-byte SHA-256 hash of the response body exactly as the api sent it.
+    pub fn ack_message(request: Pubkey, response_hash: [u8; 32]) -> Vec<u8> {
+        [ACK_MESSAGE_PREFIX, request.as_ref(), response_hash.as_ref()].concat()
+    }
+
+    /// Returns the number of bytes the headers and body occupy once serialized.
+    pub fn payload_size(headers: &[(String, String)], body: &[u8]) -> usize {
+        let headers_size = headers
+            .iter()
+            .map(|(key, value)| 4 + key.len() + 4 + value.len())
+            .sum::<usize>();
+        4 + headers_size + 4 + body.len()
+    }
+}
+
+/**
+ * LegacyRequest
+ */
+
+/// The layout of requests created before retry policies and bodies were introduced. Their headers
+/// were a map, which borsh encodes like the list of pairs that replaced it.
+#[derive(AnchorDeserialize, Debug)]
+pub struct LegacyRequest {
+    pub api: Pubkey,
+    pub caller: Pubkey,
+    pub created_at: u64,
+    pub fee_amount: u64,
+    pub headers: Vec<(String, String)>,
+    pub id: String,
+    pub method: HttpMethod,
+    pub route: String,
+    pub url: String,
+    pub workers: Vec<Pubkey>,
+}
+
+impl LegacyRequest {
+    /// Decodes the data of a request account in the legacy layout.
+    pub fn try_from_account_data(data: &[u8]) -> Result<Self> {
+        require!(
+            data.len().ge(&8) && data[..8].eq(&Request::discriminator()),
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        Self::deserialize(&mut &data[8..]).map_err(|_| error!(ErrorCode::AccountDidNotDeserialize))
+    }
+
+    /// Returns the request in the current layout. Requests that predate retry policies are not
+    /// retried, and have no body.
+    pub fn migrate(self) -> Request {
+        Request {
+            api: self.api,
+            caller: self.caller,
+            created_at: self.created_at,
+            fee_amount: self.fee_amount,
+            headers: self.headers,
+            id: self.id,
+            method: self.method,
+            route: self.route,
+            url: self.url,
+            workers: self.workers,
+            max_retries: 0,
+            backoff_seconds: 0,
+            body: vec![],
+        }
+    }
 }
 
 /**
@@ -51,11 +133,14 @@ pub trait RequestAccount {
     fn init(
         &mut self,
         api: &Account<Api>,
+        backoff_seconds: u64,
+        body: Vec<u8>,
         caller: Pubkey,
         created_at: u64,
         fee_amount: u64,
-        headers: HashMap<String, String>,
+        headers: Vec<(String, String)>,
         id: String,
+        max_retries: u64,
         method: HttpMethod,
         route: String,
         workers: Vec<Pubkey>,
@@ -70,11 +155,14 @@ impl RequestAccount for Account<'_, Request> {
     fn init(
         &mut self,
         api: &Account<Api>,
+        backoff_seconds: u64,
+        body: Vec<u8>,
         caller: Pubkey,
         created_at: u64,
         fee_amount: u64,
-        headers: HashMap<String, String>,
+        headers: Vec<(String, String)>,
         id: String,
+        max_retries: u64,
         method: HttpMethod,
         route: String,
         workers: Vec<Pubkey>,
@@ -89,6 +177,9 @@ impl RequestAccount for Account<'_, Request> {
         self.route = route.clone();
         self.url = api.clone().base_url.to_owned() + route.as_str();
         self.workers = workers;
+        self.max_retries = max_retries;
+        self.backoff_seconds = backoff_seconds;
+        self.body = body;
         Ok(())
     }
 }
@@ -101,6 +192,7 @@ impl RequestAccount for Account<'_, Request> {
 pub enum HttpMethod {
     Get,
     Post,
+    Put,
 }
 
 impl Display for HttpMethod {
@@ -108,6 +200,7 @@ impl Display for HttpMethod {
         match *self {
             HttpMethod::Get => write!(f, "GET"),
             HttpMethod::Post => write!(f, "POST"),
+            HttpMethod::Put => write!(f, "PUT"),
         }
     }
 }
@@ -119,7 +212,57 @@ impl FromStr for HttpMethod {
         match input.to_uppercase().as_str() {
             "GET" => Ok(HttpMethod::Get),
             "POST" => Ok(HttpMethod::Post),
+            "PUT" => Ok(HttpMethod::Put),
             _ => Err(ClockworkError::InvalidHttpMethod.into()),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn test_legacy_request_migrate() {
+        let api = Pubkey::new_unique();
+        let caller = Pubkey::new_unique();
+        let workers = vec![Pubkey::new_unique()];
+        let headers = HashMap::from([("Accept".to_string(), "*/*".to_string())]);
+        let mut data = Request::discriminator().to_vec();
+        data.extend(
+            (
+                api,
+                caller,
+                12u64,
+                1_000u64,
+                headers,
+                "id".to_string(),
+                HttpMethod::Post,
+                "/hook".to_string(),
+                "https://example.com/hook".to_string(),
+                workers.clone(),
+            )
+                .try_to_vec()
+                .unwrap(),
+        );
+
+        // Requests created before retry policies end before the fields added since.
+        assert!(Request::try_deserialize(&mut &data[..]).is_err());
+
+        let request = LegacyRequest::try_from_account_data(&data).unwrap().migrate();
+        assert_eq!(request.api, api);
+        assert_eq!(request.caller, caller);
+        assert_eq!(request.created_at, 12);
+        assert_eq!(request.fee_amount, 1_000);
+        assert_eq!(request.headers, vec![("Accept".to_string(), "*/*".to_string())]);
+        assert_eq!(request.id, "id");
+        assert_eq!(request.method, HttpMethod::Post);
+        assert_eq!(request.url, "https://example.com/hook");
+        assert_eq!(request.workers, workers);
+        assert_eq!(request.max_retries, 0);
+        assert_eq!(request.backoff_seconds, 0);
+        assert!(request.body.is_empty());
+    }
+}
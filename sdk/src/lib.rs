@@ -4,8 +4,10 @@ pub use clockwork_automation_program::ID;
 
 pub mod state {
     pub use clockwork_automation_program::state::{
-        AccountMetaData, Automation, AutomationAccount, AutomationResponse, AutomationSettings,
-        ClockData, ExecContext, InstructionData, Trigger, TriggerContext,
+        AccountMetaData, Automation, AutomationAccount, AutomationExecuted, AutomationResponse,
+        AutomationSettings, ClockData, ExecContext, ExecMode, InstructionData, Trigger,
+        TriggerContext,
+        AUTOMATION_RESPONSE_EVENT_DATA_MAX_SIZE,
     };
 }
 
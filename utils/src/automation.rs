@@ -1,4 +1,9 @@
-use std::{convert::TryFrom, fmt::Debug, hash::Hash};
+use std::{
+    convert::TryFrom,
+    fmt::{Debug, Display, Formatter},
+    hash::Hash,
+    str::FromStr,
+};
 
 use anchor_lang::{
     prelude::borsh::BorshSchema,
@@ -7,6 +12,8 @@ use anchor_lang::{
     solana_program::{self, instruction::Instruction},
     AnchorDeserialize,
 };
+use chrono::{Datelike, NaiveDateTime, Timelike};
+use clockwork_cron::{error::Error as CronError, Schedule};
 use static_pubkey::static_pubkey;
 
 /// The stand-in pubkey for delegating a payer address to a worker. All workers are re-imbursed by the user for lamports spent during this delegation.
@@ -44,16 +51,69 @@ impl From<Clock> for ClockData {
     }
 }
 
+impl ClockData {
+    /// Decodes a clock serialized with its borsh schema, as by `borsh::try_to_vec_with_schema`.
+    pub fn decode(data: &[u8]) -> std::result::Result<Self, ClockDataError> {
+        // Verify the data opens with the clock's schema, as far as it goes.
+        let schema = ClockData::schema_container()
+            .try_to_vec()
+            .map_err(|_err| ClockDataError::SchemaMismatch)?;
+        let prefix_len = schema.len().min(data.len());
+        if data[..prefix_len].ne(&schema[..prefix_len]) {
+            return Err(ClockDataError::SchemaMismatch);
+        }
+        if data.len().lt(&schema.len()) {
+            return Err(ClockDataError::TooShort);
+        }
+
+        // Read the clock and verify nothing follows it.
+        let mut rest = &data[schema.len()..];
+        let clock = ClockData::deserialize(&mut rest).map_err(|_err| ClockDataError::TooShort)?;
+        if !rest.is_empty() {
+            return Err(ClockDataError::TrailingBytes(rest.len()));
+        }
+        Ok(clock)
+    }
+}
+
 impl TryFrom<Vec<u8>> for ClockData {
     type Error = Error;
     fn try_from(data: Vec<u8>) -> std::result::Result<Self, Self::Error> {
-        Ok(
-            borsh::try_from_slice_with_schema::<ClockData>(data.as_slice())
-                .map_err(|_err| ErrorCode::AccountDidNotDeserialize)?,
-        )
+        Ok(ClockData::decode(data.as_slice())
+            .map_err(|_err| ErrorCode::AccountDidNotDeserialize)?)
     }
 }
 
+/// The reason raw data could not be decoded into a clock.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ClockDataError {
+    /// The data ends before the clock does.
+    TooShort,
+    /// The data does not open with the clock's borsh schema.
+    SchemaMismatch,
+    /// The data continues past the end of the clock by this many bytes.
+    TrailingBytes(usize),
+}
+
+impl Display for ClockDataError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClockDataError::TooShort => write!(f, "clock data is too short"),
+            ClockDataError::SchemaMismatch => {
+                write!(f, "clock data does not match the clock schema")
+            }
+            ClockDataError::TrailingBytes(len) => {
+                write!(f, "clock data has {} trailing bytes", len)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ClockDataError {}
+
+/// The max number of bytes an account trigger may monitor.
+pub const TRIGGER_ACCOUNT_MAX_SIZE: u64 = 1024;
+
 /// The triggering conditions of a automation.
 #[derive(AnchorDeserialize, AnchorSerialize, Debug, Clone, PartialEq)]
 pub enum Trigger {
@@ -63,7 +123,7 @@ pub enum Trigger {
         address: Pubkey,
         /// The byte offset of the account data to monitor.
         offset: u64,
-        /// The size of the byte slice to monitor (must be less than 1kb)
+        /// The size of the byte slice to monitor (must be between 1 byte and 1kb)
         size: u64,
     },
 
@@ -79,15 +139,251 @@ pub enum Trigger {
 
     /// Allows a automation to be kicked off as soon as it's created.
     Immediate,
+
+    /// Allows a automation to be kicked off once, as soon as the cluster reaches a specific slot.
+    Slot {
+        /// The slot at or after which the automation should be kicked off.
+        target_slot: u64,
+    },
+
+    /// Allows a automation to be kicked off once per epoch, as soon as a new epoch begins.
+    Epoch {
+        /// Boolean value indicating whether triggering moments may be skipped if they are missed (e.g. due to network downtime).
+        /// If false, any "missed" epochs will be executed one at a time until the automation catches up.
+        skippable: bool,
+    },
+
+    /// Allows a automation to be kicked off once, as soon as the cluster's unix timestamp reaches a specific moment.
+    /// The automation is paused once its instructions have run.
+    Timestamp {
+        /// The unix timestamp at or after which the automation should be kicked off.
+        unix_ts: i64,
+    },
+
+    /// Allows a automation to be kicked off whenever the monitored data of an account changes to meet
+    /// a value condition.
+    AccountValue {
+        /// The address of the account to monitor.
+        address: Pubkey,
+        /// The byte offset of the account data to monitor.
+        offset: u64,
+        /// The size of the byte slice to monitor (must be between 1 byte and 1kb)
+        size: u64,
+        /// If set, the automation is only kicked off when the monitored slice equals this value.
+        equals: Option<Vec<u8>>,
+        /// If set, the automation is only kicked off when the monitored slice changes away from this value.
+        changed_from: Option<Vec<u8>>,
+    },
+
+    /// Allows a automation to be kicked off according to a recurring schedule, only in epochs that pass a filter.
+    EpochFilteredCron {
+        /// The schedule in cron syntax. Value must be parsable by the `clockwork_cron` package.
+        schedule: String,
+
+        /// Boolean value indicating whether triggering moments may be skipped if they are missed (e.g. due to network downtime).
+        /// If false, any "missed" triggering moments will simply be executed as soon as the network comes back online.
+        skippable: bool,
+
+        /// Triggering moments only fire in epochs that pass the filter.
+        epoch_filter: EpochFilter,
+    },
+
+    /// Allows a automation to be kicked off once, at the first slot past a specific slot in an epoch that passes a filter.
+    EpochFilteredSlot {
+        /// The slot at or after which the automation should be kicked off.
+        target_slot: u64,
+
+        /// The automation is kicked off at the first slot past the target slot in an epoch that passes the filter.
+        epoch_filter: EpochFilter,
+    },
+}
+
+impl Trigger {
+    /// Returns true if the trigger's parameters are within their allowed bounds.
+    pub fn is_valid(&self) -> bool {
+        match self {
+            Trigger::Account { size, .. } => *size > 0 && *size <= TRIGGER_ACCOUNT_MAX_SIZE,
+            Trigger::AccountValue {
+                size,
+                equals,
+                changed_from,
+                ..
+            } => {
+                *size > 0
+                    && *size <= TRIGGER_ACCOUNT_MAX_SIZE
+                    && equals.iter().all(|v| v.len() as u64 == *size)
+                    && changed_from.iter().all(|v| v.len() as u64 == *size)
+            }
+            Trigger::EpochFilteredCron { epoch_filter, .. }
+            | Trigger::EpochFilteredSlot { epoch_filter, .. } => epoch_filter.is_valid(),
+            _ => true,
+        }
+    }
+
+    /// Returns true if the trigger may fire in the given epoch.
+    pub fn is_epoch_allowed(&self, epoch: u64) -> bool {
+        match self {
+            Trigger::EpochFilteredCron { epoch_filter, .. }
+            | Trigger::EpochFilteredSlot { epoch_filter, .. } => epoch_filter.matches(epoch),
+            _ => true,
+        }
+    }
+
+    /// Returns true if the trigger fires on the same kind of event as the other trigger.
+    /// Epoch filters and value conditions may be added or dropped, so a filtered cron trigger
+    /// can still be replaced by `AutomationResponse::stop`.
+    pub fn is_same_kind(&self, other: &Trigger) -> bool {
+        fn kind(trigger: &Trigger) -> u8 {
+            match trigger {
+                Trigger::Account { .. } | Trigger::AccountValue { .. } => 0,
+                Trigger::Cron { .. } | Trigger::EpochFilteredCron { .. } => 1,
+                Trigger::Immediate => 2,
+                Trigger::Slot { .. } | Trigger::EpochFilteredSlot { .. } => 3,
+                Trigger::Epoch { .. } => 4,
+                Trigger::Timestamp { .. } => 5,
+            }
+        }
+        kind(self) == kind(other)
+    }
+
+    /// Builds an account trigger for the program derived address of the given seeds.
+    pub fn account_from_seeds(program_id: &Pubkey, seeds: &[&[u8]], offset: u64, size: u64) -> Self {
+        Trigger::Account {
+            address: Pubkey::find_program_address(seeds, program_id).0,
+            offset,
+            size,
+        }
+    }
+}
+
+/// Restricts the epochs in which a trigger may fire.
+#[derive(AnchorDeserialize, AnchorSerialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EpochFilter {
+    /// Only fire in even epochs.
+    Even,
+    /// Only fire in odd epochs.
+    Odd,
+    /// Only fire in epochs divisible by the given number (must be greater than zero).
+    Every(u64),
+}
+
+impl EpochFilter {
+    /// Returns true if the filter's parameters are within their allowed bounds.
+    pub fn is_valid(&self) -> bool {
+        match self {
+            EpochFilter::Every(n) => *n > 0,
+            _ => true,
+        }
+    }
+
+    /// Returns true if the epoch passes the filter.
+    pub fn matches(&self, epoch: u64) -> bool {
+        match self {
+            EpochFilter::Even => epoch % 2 == 0,
+            EpochFilter::Odd => epoch % 2 == 1,
+            EpochFilter::Every(n) => *n > 0 && epoch % n == 0,
+        }
+    }
 }
 
+/// Returns the slice of account data monitored by an account trigger.
+/// The slice is truncated if the account data ends before `offset + size`.
+pub fn account_trigger_slice(data: &[u8], offset: u64, size: u64) -> &[u8] {
+    let start = (offset as usize).min(data.len());
+    let end = start.saturating_add(size as usize).min(data.len());
+    &data[start..end]
+}
+
+/// The execution context of a particular transaction automation.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ExecContext {
+    /// Index of the next instruction to be executed.
+    pub exec_index: u64,
+
+    /// Number of execs since the last tx reimbursement.
+    pub execs_since_reimbursement: u64,
+
+    /// Number of execs in this slot.
+    pub execs_since_slot: u64,
+
+    /// Slot of the last exec
+    pub last_exec_at: u64,
+
+    /// Context for the triggering condition
+    pub trigger_context: TriggerContext,
+}
+
+/// The event which allowed a particular transaction automation to be triggered.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TriggerContext {
+    /// A running hash of the observed account data.
+    Account {
+        /// The account's data hash.
+        data_hash: u64,
+    },
+
+    /// A cron execution context.
+    Cron {
+        /// The threshold moment the schedule was waiting for.
+        started_at: i64,
+    },
+
+    /// The immediate trigger context.
+    Immediate,
+
+    /// A slot execution context.
+    Slot {
+        /// The slot the automation was kicked off at.
+        started_at: u64,
+    },
+
+    /// An epoch execution context.
+    Epoch {
+        /// The epoch the automation last kicked off for.
+        started_at: u64,
+    },
+
+    /// A timestamp execution context.
+    Timestamp {
+        /// The unix timestamp the automation was kicked off at.
+        started_at: i64,
+    },
+}
+
+impl ExecContext {
+    /// Returns true if the automation has already reached its rate limit in the given slot.
+    pub fn is_rate_limited(&self, slot: u64, rate_limit: u64) -> bool {
+        self.last_exec_at == slot && self.execs_since_slot >= rate_limit
+    }
+
+    /// Returns the context after an exec at the given slot, moving the cursor to the given instruction index.
+    pub fn advance(&self, slot: u64, exec_index: u64) -> ExecContext {
+        ExecContext {
+            exec_index,
+            execs_since_reimbursement: self.execs_since_reimbursement.checked_add(1).unwrap(),
+            execs_since_slot: if slot == self.last_exec_at {
+                self.execs_since_slot.checked_add(1).unwrap()
+            } else {
+                1
+            },
+            last_exec_at: slot,
+            ..*self
+        }
+    }
+}
+
+/// The maximum size of the event data an automation response may carry, in bytes.
+pub const AUTOMATION_RESPONSE_EVENT_DATA_MAX_SIZE: usize = 512;
+
 /// A response value target programs can return to update the automation.
-#[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug)]
+#[derive(AnchorSerialize, Clone, Debug)]
 pub struct AutomationResponse {
     /// A dynamic instruction to execute next.
     pub next_instruction: Option<InstructionData>,
     /// Value to update the automation trigger to.
     pub trigger: Option<Trigger>,
+    /// A custom payload to be re-emitted in an `AutomationExecuted` event, for indexers to consume.
+    pub event_data: Option<Vec<u8>>,
 }
 
 impl Default for AutomationResponse {
@@ -95,7 +391,92 @@ impl Default for AutomationResponse {
         return Self {
             next_instruction: None,
             trigger: None,
+            event_data: None,
+        };
+    }
+}
+
+// Programs built before the event data was added return responses without it, so it is only read if present.
+impl AnchorDeserialize for AutomationResponse {
+    fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+        let next_instruction = AnchorDeserialize::deserialize(buf)?;
+        let trigger = AnchorDeserialize::deserialize(buf)?;
+        let event_data = match buf.is_empty() {
+            true => None,
+            false => AnchorDeserialize::deserialize(buf)?,
         };
+        Ok(Self {
+            next_instruction,
+            trigger,
+            event_data,
+        })
+    }
+}
+
+/// A cron schedule whose only moment is in the past, so it never fires again.
+const STOPPED_SCHEDULE: &str = "0 0 0 1 1 * 1970";
+
+/// Helpers for a cron-triggered automation to reschedule itself based on what its instruction observed.
+/// An automation's trigger can only be replaced by one of the same variant, so these only apply to cron triggers.
+///
+/// ```ignore
+/// let clock = ClockData::from(Clock::get()?);
+/// let response = if vault.balance < vault.threshold {
+///     // Check again soon.
+///     AutomationResponse::reschedule_in_seconds(&clock, 60)
+/// } else if vault.balance < vault.threshold * 2 {
+///     // Check again in an hour.
+///     AutomationResponse::reschedule_in_seconds(&clock, 3_600)
+/// } else {
+///     AutomationResponse::stop()
+/// };
+/// Ok(response)
+/// ```
+impl AutomationResponse {
+    /// Replaces the automation's schedule. Missed moments of the new schedule are skipped.
+    pub fn reschedule_cron(schedule: &str) -> std::result::Result<Self, CronError> {
+        Schedule::from_str(schedule)?;
+        Ok(Self {
+            next_instruction: None,
+            trigger: Some(Trigger::Cron {
+                schedule: schedule.to_string(),
+                skippable: true,
+            }),
+            event_data: None,
+        })
+    }
+
+    /// Schedules the automation to run once more, the given number of seconds after the clock.
+    pub fn reschedule_in_seconds(clock: &ClockData, seconds: i64) -> Self {
+        let moment = NaiveDateTime::from_timestamp(clock.unix_timestamp.saturating_add(seconds), 0);
+        Self {
+            next_instruction: None,
+            trigger: Some(Trigger::Cron {
+                schedule: format!(
+                    "{} {} {} {} {} * {}",
+                    moment.second(),
+                    moment.minute(),
+                    moment.hour(),
+                    moment.day(),
+                    moment.month(),
+                    moment.year()
+                ),
+                skippable: true,
+            }),
+            event_data: None,
+        }
+    }
+
+    /// Stops the automation from being kicked off again, by replacing its schedule with one that never fires.
+    pub fn stop() -> Self {
+        Self {
+            next_instruction: None,
+            trigger: Some(Trigger::Cron {
+                schedule: STOPPED_SCHEDULE.into(),
+                skippable: true,
+            }),
+            event_data: None,
+        }
     }
 }
 
@@ -146,6 +527,24 @@ impl From<&InstructionData> for Instruction {
     }
 }
 
+impl InstructionData {
+    /// The account metas of this instruction as executed by the given signatory, with the payer stand-in replaced by the signatory's pubkey.
+    pub fn exec_accounts(&self, signatory: Pubkey) -> Vec<AccountMeta> {
+        self.accounts
+            .iter()
+            .map(|acc| AccountMeta {
+                pubkey: if acc.pubkey == PAYER_PUBKEY {
+                    signatory
+                } else {
+                    acc.pubkey
+                },
+                is_signer: acc.is_signer,
+                is_writable: acc.is_writable,
+            })
+            .collect()
+    }
+}
+
 impl TryFrom<Vec<u8>> for InstructionData {
     type Error = Error;
     fn try_from(data: Vec<u8>) -> std::result::Result<Self, Self::Error> {
@@ -186,3 +585,232 @@ impl AccountMetaData {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clock_data_bytes() -> Vec<u8> {
+        borsh::try_to_vec_with_schema(&ClockData {
+            slot: 184_000_000,
+            epoch: 425,
+            unix_timestamp: 1_680_000_000,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_decode_clock_data() {
+        let data = clock_data_bytes();
+        let clock = ClockData::decode(&data).unwrap();
+        assert_eq!(clock.slot, 184_000_000);
+        assert_eq!(ClockData::try_from(data).unwrap(), clock);
+    }
+
+    #[test]
+    fn test_decode_clock_data_errors() {
+        let data = clock_data_bytes();
+        assert_eq!(
+            ClockData::decode(&data[..data.len() - 1]),
+            Err(ClockDataError::TooShort)
+        );
+        assert_eq!(ClockData::decode(&data[..4]), Err(ClockDataError::TooShort));
+        assert_eq!(ClockData::decode(&[]), Err(ClockDataError::TooShort));
+
+        let mut trailing = data.clone();
+        trailing.extend_from_slice(&[0, 0, 0]);
+        assert_eq!(
+            ClockData::decode(&trailing),
+            Err(ClockDataError::TrailingBytes(3))
+        );
+
+        let schemaless = ClockData::decode(&data).unwrap().try_to_vec().unwrap();
+        assert_eq!(
+            ClockData::decode(&schemaless),
+            Err(ClockDataError::SchemaMismatch)
+        );
+        assert!(ClockData::try_from(schemaless).is_err());
+    }
+
+    #[test]
+    fn test_clock_data_records_creation_clock() {
+        let clock = Clock {
+            slot: 184_000_000,
+            epoch: 425,
+            unix_timestamp: 1_680_000_000,
+            ..Clock::default()
+        };
+        let created_at = ClockData::from(clock);
+        assert_eq!(created_at.slot, 184_000_000);
+        assert_eq!(created_at.epoch, 425);
+        assert_eq!(created_at.unix_timestamp, 1_680_000_000);
+    }
+
+    fn next_moment(trigger: Option<Trigger>, after: i64) -> Option<i64> {
+        match trigger {
+            Some(Trigger::Cron { schedule, .. }) => Schedule::from_str(&schedule)
+                .unwrap()
+                .next_after(&chrono::DateTime::<chrono::Utc>::from_utc(
+                    NaiveDateTime::from_timestamp(after, 0),
+                    chrono::Utc,
+                ))
+                .map(|moment| moment.timestamp()),
+            _ => panic!("expected a cron trigger"),
+        }
+    }
+
+    #[test]
+    fn test_reschedule_cron() {
+        let response = AutomationResponse::reschedule_cron("0 */5 * * * * *").unwrap();
+        assert!(response.next_instruction.is_none());
+        assert_eq!(
+            response.trigger,
+            Some(Trigger::Cron {
+                schedule: "0 */5 * * * * *".into(),
+                skippable: true,
+            })
+        );
+        assert!(AutomationResponse::reschedule_cron("every five minutes").is_err());
+    }
+
+    #[test]
+    fn test_reschedule_in_seconds() {
+        let clock = ClockData {
+            slot: 184_000_000,
+            epoch: 425,
+            unix_timestamp: 1_680_000_000,
+        };
+        let response = AutomationResponse::reschedule_in_seconds(&clock, 90);
+        assert_eq!(
+            response.trigger,
+            Some(Trigger::Cron {
+                schedule: "30 41 10 28 3 * 2023".into(),
+                skippable: true,
+            })
+        );
+        assert_eq!(
+            next_moment(response.trigger.clone(), clock.unix_timestamp),
+            Some(1_680_000_090)
+        );
+        assert_eq!(next_moment(response.trigger, 1_680_000_090), None);
+    }
+
+    #[test]
+    fn test_stop() {
+        let response = AutomationResponse::stop();
+        assert!(response.next_instruction.is_none());
+        assert_eq!(next_moment(response.trigger, 0), None);
+    }
+
+    #[test]
+    fn test_automation_response_event_data_roundtrip() {
+        let response = AutomationResponse {
+            event_data: Some(vec![1, 2, 3]),
+            ..AutomationResponse::stop()
+        };
+        let decoded = AutomationResponse::try_from_slice(&response.try_to_vec().unwrap()).unwrap();
+        assert_eq!(decoded.trigger, response.trigger);
+        assert_eq!(decoded.event_data, Some(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn test_automation_response_without_event_data() {
+        // The return data of a program built before responses carried event data.
+        let mut legacy = None::<InstructionData>.try_to_vec().unwrap();
+        legacy.extend(AutomationResponse::stop().trigger.try_to_vec().unwrap());
+        let decoded = AutomationResponse::try_from_slice(&legacy).unwrap();
+        assert_eq!(decoded.trigger, AutomationResponse::stop().trigger);
+        assert_eq!(decoded.event_data, None);
+    }
+
+    #[test]
+    fn test_account_trigger_layout() {
+        // Account triggers keep the variant index and fields they were stored with.
+        let address = Pubkey::new_unique();
+        let data = Trigger::Account {
+            address,
+            offset: 8,
+            size: 32,
+        }
+        .try_to_vec()
+        .unwrap();
+        assert_eq!(data[0], 0);
+        assert_eq!(data.len(), 1 + 32 + 8 + 8);
+        assert_eq!(&data[1..33], address.as_ref());
+    }
+
+    #[test]
+    fn test_account_value_trigger_is_valid() {
+        let trigger = |equals: Option<Vec<u8>>, changed_from: Option<Vec<u8>>| {
+            Trigger::AccountValue {
+                address: Pubkey::new_unique(),
+                offset: 0,
+                size: 2,
+                equals,
+                changed_from,
+            }
+        };
+        assert!(trigger(Some(vec![1, 2]), None).is_valid());
+        assert!(trigger(None, Some(vec![0, 0])).is_valid());
+        assert!(!trigger(Some(vec![1]), None).is_valid());
+        assert!(!trigger(None, Some(vec![0, 0, 0])).is_valid());
+    }
+
+    #[test]
+    fn test_cron_and_slot_trigger_layout() {
+        // Cron and slot triggers keep the variant index and fields they were stored with.
+        let data = Trigger::Cron {
+            schedule: "* * * * * * *".into(),
+            skippable: true,
+        }
+        .try_to_vec()
+        .unwrap();
+        assert_eq!(data[0], 1);
+        assert_eq!(data.len(), 1 + 4 + 13 + 1);
+        let data = Trigger::Slot { target_slot: 7 }.try_to_vec().unwrap();
+        assert_eq!(data, vec![3, 7, 0, 0, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_epoch_filtered_triggers() {
+        let cron = Trigger::EpochFilteredCron {
+            schedule: "0 0 * * * * *".into(),
+            skippable: true,
+            epoch_filter: EpochFilter::Even,
+        };
+        assert!(cron.is_valid());
+        assert!(cron.is_epoch_allowed(424));
+        assert!(!cron.is_epoch_allowed(425));
+
+        let slot = Trigger::EpochFilteredSlot {
+            target_slot: 1_000,
+            epoch_filter: EpochFilter::Every(3),
+        };
+        assert!(slot.is_epoch_allowed(6));
+        assert!(!slot.is_epoch_allowed(7));
+        assert!(!Trigger::EpochFilteredSlot {
+            target_slot: 1_000,
+            epoch_filter: EpochFilter::Every(0),
+        }
+        .is_valid());
+
+        // Unfiltered triggers fire in every epoch.
+        assert!(Trigger::Slot { target_slot: 1_000 }.is_epoch_allowed(7));
+    }
+
+    #[test]
+    fn test_is_same_kind() {
+        let filtered_cron = Trigger::EpochFilteredCron {
+            schedule: "0 0 * * * * *".into(),
+            skippable: true,
+            epoch_filter: EpochFilter::Odd,
+        };
+        assert!(filtered_cron.is_same_kind(&AutomationResponse::stop().trigger.unwrap()));
+        assert!(Trigger::Slot { target_slot: 1 }.is_same_kind(&Trigger::EpochFilteredSlot {
+            target_slot: 2,
+            epoch_filter: EpochFilter::Even,
+        }));
+        assert!(!filtered_cron.is_same_kind(&Trigger::Slot { target_slot: 1 }));
+        assert!(!Trigger::Immediate.is_same_kind(&Trigger::Epoch { skippable: true }));
+    }
+}